@@ -1,24 +1,406 @@
-use bevy_app::App;
+use std::{fmt::Debug, hash::Hash};
 
-use crate::{Event, HandlerRegistry, IntoHandlerConfig};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{component::Component, entity::MapEntities, world::World};
+
+use crate::{
+    join::Join, owner::HandlerOwners, tick::TickAnchor, AudienceNormalization, DispatchStrategy,
+    Event, EventAlias, EventInfo, FromTargets, HandlerConfig, HandlerId, HandlerStorage,
+    IntoHandlerConfig, IntoHandlerSetConfig, Receive, Unicast, WorldEventBus,
+};
+
+#[cfg(feature = "journal")]
+mod backlog;
+mod plugin;
+mod shutdown;
+
+#[cfg(feature = "journal")]
+pub use backlog::*;
+pub use plugin::*;
+pub use shutdown::*;
 
 /// [`App`] extension trait for registering event handlers.
 pub trait AppEventBus {
     /// Adds an event handler for [`Event`] `E` to the app.
+    ///
+    /// Handlers that need to be removed again are added with
+    /// [`AppEventBus::add_handler_with_id`], which returns their [`HandlerId`].
     fn add_handler<E: Event, M>(&mut self, handler: impl IntoHandlerConfig<E, M>) -> &mut Self;
+
+    /// Adds an event handler for [`Event`] `E` to the app, returning its [`HandlerId`], e.g. to
+    /// remove it again with [`AppEventBus::remove_handler`].
+    fn add_handler_with_id<E: Event, M>(
+        &mut self,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> HandlerId<E>;
+
+    /// Adds a closure as an event handler for [`Event`] `E` to the app, see
+    /// [`WorldEventBus::add_handler_fn`].
+    fn add_handler_fn<E: Event>(
+        &mut self,
+        handler: impl FnMut(Receive<E>, &mut World) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Adds an event handler for [`Event`] `E` to the app, owned by the [`Plugin`] `P`.
+    ///
+    /// Handlers owned by a plugin are removed together with
+    /// [`AppEventBus::remove_plugin_handlers`].
+    fn add_handler_scoped_to_plugin<P: Plugin, E: Event, M>(
+        &mut self,
+        plugin: &P,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> &mut Self;
+
+    /// Adds a [`Plugin`] to the app, recording every event handler it registers during
+    /// [`Plugin::build`] as owned by it.
+    ///
+    /// Handlers owned by a plugin are removed together with
+    /// [`AppEventBus::remove_plugin_handlers`].
+    fn add_plugin_with_handlers<P: Plugin>(&mut self, plugin: P) -> &mut Self;
+
+    /// Removes all event handlers owned by the [`Plugin`] `P`, across all [`Event`] types.
+    fn remove_plugin_handlers<P: Plugin>(&mut self) -> &mut Self;
+
+    /// Adds an event handler for [`Event`] `E` to the app, which only runs for events posted to
+    /// the key, see [`WorldEventBus::add_keyed_handler`].
+    fn add_keyed_handler<E: Event, K: Eq + Hash + Send + Sync + 'static, M>(
+        &mut self,
+        key: K,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> &mut Self;
+
+    /// Sets the [`DispatchStrategy`] that posts of [`Event`] `E` run their handlers with.
+    fn set_dispatch_strategy<E: Event>(&mut self, strategy: impl DispatchStrategy<E>) -> &mut Self;
+
+    /// Moves the event handlers for [`Event`] `E` into a new [`HandlerStorage`] backend.
+    fn set_handler_storage<E: Event>(&mut self, storage: impl HandlerStorage<E>) -> &mut Self;
+
+    /// Removes an event handler for [`Event`] `E` from the app, see
+    /// [`WorldEventBus::remove_handler`].
+    ///
+    /// The [`HandlerId`] is returned by [`AppEventBus::add_handler_with_id`], or listed by
+    /// [`AppEventBus::handler_ids`] for handlers added otherwise, e.g. by another plugin.
+    fn remove_handler<E: Event>(&mut self, id: HandlerId<E>) -> &mut Self;
+
+    /// Reconfigures an already registered event handler for [`Event`] `E`, see
+    /// [`AppEventBus::remove_handler`] for where its [`HandlerId`] comes from.
+    fn configure_handler<E: Event>(
+        &mut self,
+        id: HandlerId<E>,
+        f: impl FnOnce(HandlerConfig<E>) -> HandlerConfig<E>,
+    ) -> &mut Self;
+
+    /// Returns the [`HandlerId`]s of all event handlers for [`Event`] `E`,
+    /// in the order they run.
+    fn handler_ids<E: Event>(&self) -> Vec<HandlerId<E>>;
+
+    /// Configures a [`HandlerSet`](crate::HandlerSet) for [`Event`] `E`, merging with any previous
+    /// configuration of the same set.
+    fn configure_handler_set<E: Event>(&mut self, config: impl IntoHandlerSetConfig) -> &mut Self;
+
+    /// Makes [`Event`] `Old` an alias of [`Event`] `New`, so that posting `Old` runs the handlers
+    /// registered for `New` through the [`EventAlias`] converter.
+    fn alias_event<Old, New>(&mut self, alias: EventAlias<Old, New>) -> &mut Self
+    where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event;
+
+    /// Deprecates [`Event`] `Old` in favor of [`Event`] `New`, forwarding its posts through the
+    /// converter, see [`WorldEventBus::register_deprecated_event`].
+    fn register_deprecated_event<Old, New>(
+        &mut self,
+        converter: impl Fn(&Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event;
+
+    /// Registers human-readable metadata of [`Event`] `E` into the
+    /// [`EventCatalog`](crate::EventCatalog), see [`WorldEventBus::register_bus_event`].
+    fn register_bus_event<E: Event>(&mut self, info: EventInfo) -> &mut Self;
+
+    /// Posts bevy's lifecycle triggers for component `T` as bus events, see
+    /// [`WorldEventBus::bridge_lifecycle`].
+    fn bridge_lifecycle<T: Component>(&mut self) -> &mut Self;
+
+    /// Starts retaining the last `capacity` posted events of type `E` in an
+    /// [`EventHistory`](crate::EventHistory).
+    fn enable_history<E>(&mut self, capacity: usize) -> &mut Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Catches the handlers of [`Event`] `E` up on the posts they missed while disabled or removed,
+    /// see [`WorldEventBus::track_delivery`].
+    fn track_delivery<E>(&mut self) -> &mut Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Remaps the entities of [`Event`] `E` itself when it re-enters the event bus from another
+    /// domain, see [`WorldEventBus::remap_event_entities`].
+    fn remap_event_entities<E: Event + MapEntities>(&mut self) -> &mut Self;
+
+    /// Limits how many posts of [`Event`] `E` each target entity receives per second, see
+    /// [`WorldEventBus::throttle_per_target`].
+    fn throttle_per_target<E: Event<Audience: Unicast>>(
+        &mut self,
+        max_per_target_per_second: u32,
+    ) -> &mut Self;
+
+    /// Normalizes the audiences of posts of [`Event`] `E` before any handler runs, see
+    /// [`WorldEventBus::normalize_audience`].
+    fn normalize_audience<E: Event<Audience: FromTargets>>(
+        &mut self,
+        policy: AudienceNormalization,
+    ) -> &mut Self;
+
+    /// Collects the posts of [`Event`] `E` into the [`EventInbox`](crate::EventInbox) of their
+    /// target entity, see [`WorldEventBus::collect_in_inboxes`].
+    fn collect_in_inboxes<E: Event<Audience: Unicast> + Clone + Send + Sync>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Includes the payload and audience of posts of [`Event`] `E` in the error logged when one of
+    /// its handlers panics, see [`WorldEventBus::dump_on_panic`].
+    fn dump_on_panic<E: Event<Audience: Debug> + Debug>(&mut self, max_len: usize) -> &mut Self;
+
+    /// Folds every post of [`Event`] `E` into the rolling hash of the
+    /// [`StreamHasher`](crate::StreamHasher), see [`WorldEventBus::hash_stream`].
+    fn hash_stream<E: Event<Audience: Hash> + Hash>(&mut self) -> &mut Self;
+
+    /// Delivers the posts of [`Event`] `E` targeting the same entity in the order they were made,
+    /// see [`WorldEventBus::sequence_by_target`].
+    fn sequence_by_target<E: Event<Audience: Unicast + Send + Sync> + Send + Sync>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Enables rollback for the queued posts of [`Event`] `E`, see
+    /// [`EventQueue::snapshot`](crate::EventQueue::snapshot).
+    fn enable_rollback<E>(&mut self) -> &mut Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Persists the queued posts of [`Event`] `E` under the key in the backlogs of the
+    /// [`EventQueue`](crate::EventQueue), see [`QueueBacklogPlugin`].
+    #[cfg(feature = "journal")]
+    fn persist_queued<E: crate::PersistentEvent>(
+        &mut self,
+        key: impl Into<std::borrow::Cow<'static, str>>,
+    ) -> &mut Self;
+
+    /// Registers the internal handlers of a [`Join`], which runs its callback once all of its
+    /// awaited events have been posted.
+    fn add_join<K: Eq + Hash + Send + 'static>(&mut self, join: Join<K>) -> &mut Self;
+
+    /// Runs the [`Tick`](crate::tick::Tick) handlers of a [`HandlerSet`](crate::HandlerSet) in a
+    /// bevy schedule, ordered relative to the engine's systems, see
+    /// [`WorldEventBus::anchor_tick_set`].
+    fn anchor_tick_set(&mut self, anchor: TickAnchor) -> &mut Self;
 }
 
 impl AppEventBus for App {
     fn add_handler<E: Event, M>(&mut self, handler: impl IntoHandlerConfig<E, M>) -> &mut Self {
-        let config = handler.into_config();
+        self.world_mut().add_handler(handler);
+        self
+    }
+
+    fn add_handler_with_id<E: Event, M>(
+        &mut self,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> HandlerId<E> {
+        self.world_mut().add_handler(handler)
+    }
+
+    fn add_handler_fn<E: Event>(
+        &mut self,
+        handler: impl FnMut(Receive<E>, &mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut().add_handler_fn(handler);
+        self
+    }
+
+    fn add_handler_scoped_to_plugin<P: Plugin, E: Event, M>(
+        &mut self,
+        _plugin: &P,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> &mut Self {
+        let world = self.world_mut();
+        HandlerOwners::enter_scope::<P>(world);
+        world.add_handler(handler);
+        HandlerOwners::exit_scope(world);
+        self
+    }
+
+    fn add_plugin_with_handlers<P: Plugin>(&mut self, plugin: P) -> &mut Self {
+        HandlerOwners::enter_scope::<P>(self.world_mut());
+        self.add_plugins(plugin);
+        HandlerOwners::exit_scope(self.world_mut());
+        self
+    }
+
+    fn remove_plugin_handlers<P: Plugin>(&mut self) -> &mut Self {
+        self.world_mut().remove_plugin_handlers::<P>();
+        self
+    }
+
+    fn add_keyed_handler<E: Event, K: Eq + Hash + Send + Sync + 'static, M>(
+        &mut self,
+        key: K,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> &mut Self {
+        self.world_mut().add_keyed_handler(key, handler);
+        self
+    }
+
+    fn set_dispatch_strategy<E: Event>(&mut self, strategy: impl DispatchStrategy<E>) -> &mut Self {
+        self.world_mut().set_dispatch_strategy(strategy);
+        self
+    }
+
+    fn set_handler_storage<E: Event>(&mut self, storage: impl HandlerStorage<E>) -> &mut Self {
+        self.world_mut().set_handler_storage(storage);
+        self
+    }
 
-        config.handler.lock_arc().initialize(self.world_mut());
+    fn remove_handler<E: Event>(&mut self, id: HandlerId<E>) -> &mut Self {
+        self.world_mut().remove_handler(id);
+        self
+    }
+
+    fn configure_handler<E: Event>(
+        &mut self,
+        id: HandlerId<E>,
+        f: impl FnOnce(HandlerConfig<E>) -> HandlerConfig<E>,
+    ) -> &mut Self {
+        self.world_mut().configure_handler(id, f);
+        self
+    }
+
+    fn handler_ids<E: Event>(&self) -> Vec<HandlerId<E>> {
+        self.world().handler_ids()
+    }
 
-        let mut registry = self
-            .world_mut()
-            .get_resource_or_insert_with(HandlerRegistry::<E>::default);
-        registry.insert(config);
+    fn configure_handler_set<E: Event>(&mut self, config: impl IntoHandlerSetConfig) -> &mut Self {
+        self.world_mut().configure_handler_set::<E>(config);
+        self
+    }
+
+    fn alias_event<Old, New>(&mut self, alias: EventAlias<Old, New>) -> &mut Self
+    where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event,
+    {
+        self.world_mut().alias_event(alias);
+        self
+    }
+
+    fn register_deprecated_event<Old, New>(
+        &mut self,
+        converter: impl Fn(&Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event,
+    {
+        self.world_mut().register_deprecated_event(converter);
+        self
+    }
+
+    fn register_bus_event<E: Event>(&mut self, info: EventInfo) -> &mut Self {
+        self.world_mut().register_bus_event::<E>(info);
+        self
+    }
+
+    fn bridge_lifecycle<T: Component>(&mut self) -> &mut Self {
+        self.world_mut().bridge_lifecycle::<T>();
+        self
+    }
+
+    fn enable_history<E>(&mut self, capacity: usize) -> &mut Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        self.world_mut().enable_history::<E>(capacity);
+        self
+    }
+
+    fn track_delivery<E>(&mut self) -> &mut Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        self.world_mut().track_delivery::<E>();
+        self
+    }
+
+    fn remap_event_entities<E: Event + MapEntities>(&mut self) -> &mut Self {
+        self.world_mut().remap_event_entities::<E>();
+        self
+    }
+
+    fn throttle_per_target<E: Event<Audience: Unicast>>(
+        &mut self,
+        max_per_target_per_second: u32,
+    ) -> &mut Self {
+        self.world_mut()
+            .throttle_per_target::<E>(max_per_target_per_second);
+        self
+    }
+
+    fn normalize_audience<E: Event<Audience: FromTargets>>(
+        &mut self,
+        policy: AudienceNormalization,
+    ) -> &mut Self {
+        self.world_mut().normalize_audience::<E>(policy);
+        self
+    }
+
+    fn collect_in_inboxes<E: Event<Audience: Unicast> + Clone + Send + Sync>(
+        &mut self,
+    ) -> &mut Self {
+        self.world_mut().collect_in_inboxes::<E>();
+        self
+    }
+
+    fn dump_on_panic<E: Event<Audience: Debug> + Debug>(&mut self, max_len: usize) -> &mut Self {
+        self.world_mut().dump_on_panic::<E>(max_len);
+        self
+    }
+
+    fn hash_stream<E: Event<Audience: Hash> + Hash>(&mut self) -> &mut Self {
+        self.world_mut().hash_stream::<E>();
+        self
+    }
+
+    fn sequence_by_target<E: Event<Audience: Unicast + Send + Sync> + Send + Sync>(
+        &mut self,
+    ) -> &mut Self {
+        self.world_mut().sequence_by_target::<E>();
+        self
+    }
+
+    fn enable_rollback<E>(&mut self) -> &mut Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        self.world_mut().enable_rollback::<E>();
+        self
+    }
+
+    #[cfg(feature = "journal")]
+    fn persist_queued<E: crate::PersistentEvent>(
+        &mut self,
+        key: impl Into<std::borrow::Cow<'static, str>>,
+    ) -> &mut Self {
+        self.world_mut().persist_queued::<E>(key);
+        self
+    }
+
+    fn add_join<K: Eq + Hash + Send + 'static>(&mut self, join: Join<K>) -> &mut Self {
+        self.world_mut().add_join(join);
+        self
+    }
 
+    fn anchor_tick_set(&mut self, anchor: TickAnchor) -> &mut Self {
+        self.world_mut().anchor_tick_set(anchor);
         self
     }
 }
@@ -0,0 +1,105 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy_app::{App, Plugin, PreStartup};
+use bevy_ecs::{system::Res, world::World};
+use bevy_utils::tracing::{info, warn};
+
+use crate::{AppEventBus, BacklogError, EventQueue, Receive, SavingState, WorldEventBus};
+
+/// [`Plugin`] which saves the pending posts of the [`EventQueue`] to a file when the app shuts
+/// down, and queues them again when it starts. Requires the `journal` feature.
+///
+/// Only the posts of event types registered with
+/// [`AppEventBus::persist_queued`](crate::AppEventBus::persist_queued) are saved. The backlog is
+/// written when [`SavingState`] is posted, so add the [`ShutdownPlugin`](crate::ShutdownPlugin)
+/// too. It is written to a temporary file first and then moved over the previous one, so that a
+/// crash while writing doesn't lose it.
+///
+/// The backlog is restored in [`PreStartup`] and then deleted, so that its posts aren't restored
+/// twice, see [`WorldEventBus::restore_queue_backlog`]. A backlog saved by a newer version of the
+/// app is moved aside to a file with its version appended, e.g. `.v3`, so that the newer version
+/// can still restore it. A backlog that is corrupted is moved aside to a file with the `.corrupt`
+/// extension appended. Either way, the app starts without it.
+pub struct QueueBacklogPlugin {
+    /// The file the backlog is saved to and restored from.
+    pub path: PathBuf,
+}
+
+impl QueueBacklogPlugin {
+    /// Creates a plugin which saves the backlog to the file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Plugin for QueueBacklogPlugin {
+    fn build(&self, app: &mut App) {
+        let path = self.path.clone();
+        app.add_systems(PreStartup, move |world: &mut World| {
+            restore_backlog(&path, world);
+        });
+        let path = self.path.clone();
+        app.add_handler(move |_: Receive<SavingState>, queue: Res<EventQueue>| {
+            if let Err(error) = save_backlog(&path, &queue) {
+                warn!(
+                    "Failed to save the event queue backlog to {}: {error}",
+                    path.display()
+                );
+            }
+        });
+    }
+}
+
+fn restore_backlog(path: &Path, world: &mut World) {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!(
+                "Failed to read the event queue backlog from {}: {error}",
+                path.display()
+            );
+            return;
+        }
+    };
+    match world.restore_queue_backlog(&bytes) {
+        Ok(restored) => {
+            info!("Restored {restored} queued posts from {}", path.display());
+            if let Err(error) = fs::remove_file(path) {
+                warn!(
+                    "Failed to remove the restored event queue backlog {}: {error}",
+                    path.display()
+                );
+            }
+        }
+        Err(error) => {
+            let mut aside = path.as_os_str().to_owned();
+            match error {
+                BacklogError::UnsupportedVersion(version) => aside.push(format!(".v{version}")),
+                _ => aside.push(".corrupt"),
+            }
+            warn!(
+                "Discarded the event queue backlog {}: {error}, moving it to {}",
+                path.display(),
+                aside.to_string_lossy()
+            );
+            if let Err(error) = fs::rename(path, &aside) {
+                warn!(
+                    "Failed to move the event queue backlog {}: {error}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
+
+fn save_backlog(path: &Path, queue: &EventQueue) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let backlog = queue.save_backlog().map_err(io::Error::other)?;
+    fs::write(&temporary, backlog)?;
+    fs::rename(&temporary, path)
+}
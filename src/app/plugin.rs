@@ -0,0 +1,68 @@
+use bevy_app::{App, Last, Plugin, PostStartup, Update};
+use bevy_core::update_frame_count;
+use bevy_ecs::schedule::{IntoSystemConfigs, SystemSet};
+
+use crate::{
+    advance_replays, finish_stream_hash, flush_event_queue, post_progress, post_tick,
+    report_tick_lag, resume_parked_events, EventBusWorldSetup,
+};
+
+/// [`Plugin`] which sets up the event bus' per-frame maintenance, see [`EventBusWorldSetup`] for
+/// worlds without an [`App`]:
+/// - Posts [`Tick`](crate::tick::Tick) every frame during [`Update`].
+/// - Advances the [`EventReplayer`](crate::EventReplayer) every frame during [`Update`], before
+///   [`Tick`](crate::tick::Tick) is posted.
+/// - Posts the progress reports buffered in the [`ProgressTracker`](crate::ProgressTracker) every
+///   frame during [`Update`], before [`Tick`](crate::tick::Tick) is posted.
+/// - Resumes the [`ParkedEvents`](crate::ParkedEvents) whose conditions return `true` every frame
+///   during [`Update`], before [`Tick`](crate::tick::Tick) is posted.
+/// - Flushes the [`EventQueue`](crate::EventQueue) at the end of every frame, see
+///   [`EventBusSettings::flush_budget`](crate::EventBusSettings::flush_budget).
+/// - Finishes the frame of the [`StreamHasher`](crate::StreamHasher) after flushing, if any.
+/// - Marks the thread that builds the app as the [`MainThread`](crate::MainThread), whose posts
+///   are the only ones that run main-thread-only handlers.
+/// - Reports one-frame-lag hazards of [`Tick`](crate::tick::Tick) handlers at startup, see
+///   [`TickLagReport`](crate::TickLagReport).
+#[derive(Default)]
+pub struct EventBusPlugin;
+
+impl Plugin for EventBusPlugin {
+    fn build(&self, app: &mut App) {
+        EventBusWorldSetup::init(app.world_mut());
+        app.add_systems(PostStartup, report_tick_lag)
+            .add_systems(
+                Update,
+                (
+                    advance_replays.in_set(EventBusSystems::Replay),
+                    post_progress.in_set(EventBusSystems::Progress),
+                    resume_parked_events.in_set(EventBusSystems::Resume),
+                    post_tick.in_set(EventBusSystems::Tick),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Last,
+                (flush_event_queue, finish_stream_hash)
+                    .chain()
+                    .in_set(EventBusSystems::Flush)
+                    .before(update_frame_count),
+            );
+    }
+}
+
+/// [`SystemSet`]s of the systems added by the [`EventBusPlugin`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventBusSystems {
+    /// Advances the [`EventReplayer`](crate::EventReplayer), in [`Update`].
+    Replay,
+    /// Posts the progress reports buffered in the [`ProgressTracker`](crate::ProgressTracker), in
+    /// [`Update`].
+    Progress,
+    /// Resumes the [`ParkedEvents`](crate::ParkedEvents) whose conditions return `true`, in
+    /// [`Update`].
+    Resume,
+    /// Posts [`Tick`](crate::tick::Tick), in [`Update`].
+    Tick,
+    /// Flushes the [`EventQueue`](crate::EventQueue), in [`Last`].
+    Flush,
+}
@@ -0,0 +1,220 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use bevy_app::{App, AppExit, Last, Plugin};
+use bevy_ecs::{
+    event::Events,
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::Resource,
+    world::World,
+};
+use bevy_utils::tracing::warn;
+
+use crate::{Event, EventBusSystems, Immutable, WorldEventBus};
+
+/// [`Event`] posted when the app is requested to exit, starting the shutdown sequence of the
+/// [`ShutdownPlugin`].
+#[derive(Debug, Clone, Copy)]
+pub struct ShutdownRequested;
+
+/// [`Event`] posted after [`ShutdownRequested`] finished, for saving state before the app exits.
+#[derive(Debug, Clone, Copy)]
+pub struct SavingState;
+
+/// [`Event`] posted after [`SavingState`] finished, on the last frame before the app exits.
+#[derive(Debug, Clone, Copy)]
+pub struct ShutdownComplete;
+
+impl Event for ShutdownRequested {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+impl Event for SavingState {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+impl Event for ShutdownComplete {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+/// [`Plugin`] which delays [`AppExit`] until the event bus has run an orderly shutdown sequence:
+/// [`ShutdownRequested`], then [`SavingState`], then [`ShutdownComplete`].
+///
+/// Handlers that need more than one frame to finish a step, e.g. flushing state to disk, can
+/// [`hold`](Shutdown::hold) the [`Shutdown`] until they [`release`](Shutdown::release) it. The next
+/// step is posted once nothing holds the shutdown anymore, or once the
+/// [`grace period`](Shutdown::grace_period) of the step elapsed.
+///
+/// The exit is intercepted in [`Last`], so an [`AppExit`] sent after the [`ShutdownSystems`] isn't
+/// delayed.
+pub struct ShutdownPlugin {
+    /// How long each step of the shutdown sequence waits for held handlers.
+    pub grace_period: Duration,
+}
+
+impl Default for ShutdownPlugin {
+    fn default() -> Self {
+        Self {
+            grace_period: Duration::from_secs(5),
+        }
+    }
+}
+
+/// [`SystemSet`] of the systems added by the [`ShutdownPlugin`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShutdownSystems;
+
+impl Plugin for ShutdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Shutdown::new(self.grace_period))
+            .add_systems(
+                Last,
+                drive_shutdown
+                    .in_set(ShutdownSystems)
+                    .after(EventBusSystems::Flush),
+            );
+    }
+}
+
+/// The steps of the shutdown sequence, see [`ShutdownPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownPhase {
+    /// [`ShutdownRequested`] was posted.
+    Requested,
+    /// [`SavingState`] was posted.
+    Saving,
+    /// [`ShutdownComplete`] was posted, and the app is exiting.
+    Complete,
+}
+
+/// [`Resource`] which tracks the shutdown sequence of the [`ShutdownPlugin`].
+#[derive(Resource, Debug)]
+pub struct Shutdown {
+    /// How long each step of the shutdown sequence waits for held handlers.
+    pub grace_period: Duration,
+    phase: Option<ShutdownPhase>,
+    exit: Option<AppExit>,
+    holds: HashSet<Cow<'static, str>>,
+    started: Option<Instant>,
+}
+
+impl Shutdown {
+    /// Creates a shutdown that hasn't been requested yet.
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            phase: None,
+            exit: None,
+            holds: HashSet::new(),
+            started: None,
+        }
+    }
+
+    /// Returns the current step of the shutdown sequence, or `None` if no shutdown was requested.
+    pub fn phase(&self) -> Option<ShutdownPhase> {
+        self.phase
+    }
+
+    /// Keeps the current step of the shutdown sequence from finishing until the hold is
+    /// [released](Shutdown::release), or the grace period elapses.
+    pub fn hold(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.holds.insert(name.into());
+    }
+
+    /// Releases a hold on the current step of the shutdown sequence.
+    pub fn release(&mut self, name: &str) {
+        self.holds.remove(name);
+    }
+
+    /// Returns `true` if anything holds the current step of the shutdown sequence.
+    pub fn is_held(&self) -> bool {
+        !self.holds.is_empty()
+    }
+
+    /// Starts the next step of the shutdown sequence, returning it.
+    fn advance(&mut self) -> ShutdownPhase {
+        let phase = match self.phase {
+            None => ShutdownPhase::Requested,
+            Some(ShutdownPhase::Requested) => ShutdownPhase::Saving,
+            Some(ShutdownPhase::Saving | ShutdownPhase::Complete) => ShutdownPhase::Complete,
+        };
+        self.phase = Some(phase);
+        self.holds.clear();
+        self.started = Some(Instant::now());
+        phase
+    }
+}
+
+/// Exclusive system that intercepts [`AppExit`] and drives the shutdown sequence of the
+/// [`ShutdownPlugin`], sending the [`AppExit`] again once it completes.
+pub fn drive_shutdown(world: &mut World) {
+    let Some(shutdown) = world.get_resource::<Shutdown>() else {
+        return;
+    };
+    if shutdown.phase == Some(ShutdownPhase::Complete) {
+        return;
+    }
+
+    let exit = world
+        .get_resource_mut::<Events<AppExit>>()
+        .and_then(|mut events| {
+            events
+                .drain()
+                .reduce(|exit, next| if exit.is_error() { exit } else { next })
+        });
+    let mut shutdown = world.resource_mut::<Shutdown>();
+    let phase = match shutdown.phase {
+        None => {
+            let Some(exit) = exit else {
+                return;
+            };
+            shutdown.exit = Some(exit);
+            shutdown.advance()
+        }
+        Some(_) => {
+            if let Some(exit) = exit.filter(AppExit::is_error) {
+                shutdown.exit = Some(exit);
+            }
+            let elapsed = shutdown
+                .started
+                .map_or(Duration::ZERO, |start| start.elapsed());
+            if shutdown.is_held() {
+                if elapsed < shutdown.grace_period {
+                    return;
+                }
+                warn!(
+                    "Shutdown grace period elapsed while held by {:?}",
+                    shutdown.holds
+                );
+            }
+            shutdown.advance()
+        }
+    };
+
+    match phase {
+        ShutdownPhase::Requested => {
+            world.post(ShutdownRequested);
+        }
+        ShutdownPhase::Saving => {
+            world.post(SavingState);
+        }
+        ShutdownPhase::Complete => {
+            world.post(ShutdownComplete);
+            let exit = world
+                .resource_mut::<Shutdown>()
+                .exit
+                .take()
+                .unwrap_or(AppExit::Success);
+            world.send_event(exit);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use bevy_ecs::system::Resource;
+
+/// Stand-in for bevy's [`FrameCount`](https://docs.rs/bevy/latest/bevy/core/struct.FrameCount.html)
+/// in worlds without the `bevy_app` feature, advanced by
+/// [`EventBusWorldSetup::update`](crate::EventBusWorldSetup::update).
+///
+/// Wraps around at [`u32::MAX`] like bevy's.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameCount(pub u32);
+
+/// Stand-in for bevy's [`Time`](https://docs.rs/bevy/latest/bevy/time/struct.Time.html) in worlds
+/// without the `bevy_app` feature, advanced by
+/// [`EventBusWorldSetup::update`](crate::EventBusWorldSetup::update).
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Time {
+    elapsed: Duration,
+    delta: Duration,
+}
+
+impl Time {
+    /// Returns how much time has advanced since the last update.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns how much time has advanced since the world was set up.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Advances the time by the delta.
+    pub fn advance_by(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+    }
+}
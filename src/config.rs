@@ -1,8 +1,31 @@
-use std::sync::Arc;
+use std::{any::type_name, borrow::Cow, sync::Arc};
 
+use bevy_ecs::{
+    schedule::{BoxedCondition, Condition, InternedSystemSet, IntoSystemSet, SystemSet},
+    system::{IntoSystem, Resource, SystemId},
+    world::World,
+};
+use bevy_utils::tracing::warn;
 use parking_lot::Mutex;
 
-use crate::{ArcHandlerSystem, Event, IntoHandlerSystem};
+use crate::{ArcHandlerSystem, Event, IntoHandlerSystem, ReadOnlyView, Receive};
+
+mod breaker;
+mod coroutine;
+mod flag;
+pub mod priority;
+mod set;
+#[cfg(feature = "bevy_reflect")]
+mod watch;
+
+pub(crate) use breaker::BreakerState;
+pub use breaker::*;
+pub use coroutine::*;
+pub use flag::*;
+pub use priority::*;
+pub use set::*;
+#[cfg(feature = "bevy_reflect")]
+pub(crate) use watch::FieldWatch;
 
 /// Configuration for an event handler.
 ///
@@ -12,23 +35,362 @@ use crate::{ArcHandlerSystem, Event, IntoHandlerSystem};
 /// Individual handlers can be assigned a priority using the [`HandlerConfig::priority`] method.
 ///
 /// Handlers with the same priority are ran in the order they were added.
+///
+/// # Run conditions
+///
+/// Handlers can be skipped with the same run conditions as bevy systems, e.g. `in_state`, using
+/// the [`HandlerConfig::run_if`] method. Unlike returning early from the handler, a skipped
+/// handler doesn't run at all.
+///
+/// # Sets
+///
+/// Handlers can join [`HandlerSet`]s using the [`HandlerConfig::in_set`] method, to be configured
+/// together with [`HandlerSetConfig`]. Handlers without a priority of their own use the priority of
+/// their set, or [`Normal`] otherwise.
+///
+/// Every handler is also in the system type set of its system, so that other handlers can be
+/// ordered relative to it by passing the system itself.
+///
+/// # Ordering
+///
+/// Handlers of the same priority can be ordered relative to other handlers or sets using the
+/// [`HandlerConfig::before`] and [`HandlerConfig::after`] methods, e.g. when multiple plugins add
+/// handlers at [`Normal`]. Cycles between the constraints are reported as an error, see
+/// [`HandlerRegistry::validate_order`](crate::HandlerRegistry::validate_order).
+///
+/// # Threads
+///
+/// Handlers that touch non-[`Send`] resources can be restricted to the main thread using the
+/// [`HandlerConfig::main_thread_only`] method, so that posts from any other thread skip them.
+/// Handlers with non-[`Send`] system parameters, such as [`NonSend`](bevy_ecs::system::NonSend),
+/// are restricted automatically when they are added.
+///
+/// # Rollback
+///
+/// Handlers that only cause side effects can be skipped while
+/// [`Resimulating`](crate::Resimulating) using the [`HandlerConfig::side_effect_only`] method.
+///
+/// # State
+///
+/// Handlers wrapped in [`Resettable`] can have their system state, such as
+/// [`Local`](bevy_ecs::system::Local)s, reset with
+/// [`HandlerRegistry::reset_handler_state`](crate::HandlerRegistry::reset_handler_state).
+///
+/// # Required resources
+///
+/// Handlers that only make sense while a resource exists can be bound to it using the
+/// [`HandlerConfig::while_resource_exists`] and [`HandlerConfig::until_resource_removed`] methods.
+///
+/// # Feature flags
+///
+/// Handlers can be gated behind [`FeatureFlags`] using the [`HandlerConfig::requires_flag`] method.
+///
+/// # One-shot handlers
+///
+/// Handlers that only need to react to the next event can remove themselves after their first
+/// run using the [`HandlerConfig::once`] method.
+///
+/// # Monitors
+///
+/// Handlers that need to observe every post, e.g. for logging or analytics, can run after all
+/// other handlers even if the event was cancelled using the [`HandlerConfig::always_run`] method.
+/// Handlers that need to react to cancelled posts in their usual place in the order, e.g. to undo
+/// a cancellation, can opt in using the [`HandlerConfig::receive_cancelled`] method.
+///
+/// # Circuit breakers
+///
+/// Handlers that keep failing can be disabled automatically using the
+/// [`HandlerConfig::circuit_breaker`] method.
 pub struct HandlerConfig<E: Event> {
-    pub(crate) priority: i32,
+    pub(crate) priority: Option<i32>,
+    pub(crate) sets: Arc<Vec<InternedSystemSet>>,
+    /// Ordering constraints relative to other handlers, shared with clones of the config.
+    pub(crate) ordering: Arc<Vec<HandlerOrder>>,
+    /// Run conditions of the handler itself, shared with the snapshots of the handler.
+    pub(crate) conditions: Arc<Vec<ArcCondition>>,
+    pub(crate) main_thread: bool,
+    pub(crate) side_effect: bool,
+    /// Whether the handler is removed after it first ran.
+    pub(crate) once: bool,
+    pub(crate) receive_cancelled: bool,
+    pub(crate) resources: Arc<Vec<RequiredResource>>,
+    /// The [`FeatureFlags`] that must be enabled for the handler to run, shared with the snapshots
+    /// of the handler.
+    pub(crate) flags: Arc<Vec<Cow<'static, str>>>,
+    /// Copies the final state of the post for the handler, if it always runs.
+    pub(crate) always_run: Option<MonitorView<E>>,
+    /// The state of the handler's [`CircuitBreaker`], if any, shared with the snapshots of the
+    /// handler.
+    pub(crate) breaker: Option<Arc<BreakerState>>,
+    /// The key that the [`DeliveryTracker`](crate::DeliveryTracker) remembers the handler by, if
+    /// it catches up after being removed and added again.
+    pub(crate) delivery_key: Option<Arc<Cow<'static, str>>>,
     pub(crate) handler: ArcHandlerSystem<E, ()>,
+    /// Builds a fresh instance of the handler, if it is [`Resettable`].
+    pub(crate) factory: Option<HandlerFactory<E>>,
+    /// Whether the crate that added the handler can't be told from its name, which exempts it
+    /// from [reserved priorities](Event::reserved_priorities).
+    pub(crate) anonymous: bool,
+    /// The fields of the event the handler watches, if any.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) watch: Option<Arc<FieldWatch<E>>>,
 }
 
+/// Copies the cancellation state of a post for a handler that
+/// [always runs](HandlerConfig::always_run), along with the event if the handler could change it.
+pub(crate) type MonitorView<E> =
+    fn(&<E as Event>::Cancellation, &E) -> (<E as Event>::Cancellation, Option<E>);
+
+fn monitor_view<E>(cancellation: &E::Cancellation, event: &E) -> (E::Cancellation, Option<E>)
+where
+    E: Event<Cancellation: Clone, Mutability: ReadOnlyView<E>>,
+{
+    (cancellation.clone(), E::Mutability::copy(event))
+}
+
+/// Builds a fresh, uninitialized instance of a [`Resettable`] handler.
+pub(crate) type HandlerFactory<E> = Arc<dyn Fn() -> ArcHandlerSystem<E, ()> + Send + Sync>;
+
 impl<E: Event> HandlerConfig<E> {
     /// Creates a new handler configuration.
     pub fn new(handler: ArcHandlerSystem<E, ()>) -> Self {
+        let sets = handler.lock().default_system_sets();
         Self {
-            priority: HandlerPriority::priority(&Normal),
+            priority: None,
+            sets: Arc::new(sets),
+            ordering: Arc::default(),
+            conditions: Arc::default(),
+            main_thread: false,
+            side_effect: false,
+            once: false,
+            receive_cancelled: false,
+            resources: Arc::default(),
+            flags: Arc::default(),
+            always_run: None,
+            breaker: None,
+            delivery_key: None,
             handler,
+            factory: None,
+            anonymous: false,
+            #[cfg(feature = "bevy_reflect")]
+            watch: None,
         }
     }
 
     /// Sets the priority of the handler.
     pub fn priority(mut self, priority: impl HandlerPriority) -> Self {
-        self.priority = HandlerPriority::priority(&priority);
+        self.priority = Some(HandlerPriority::priority(&priority));
+        self
+    }
+
+    /// Adds the handler to a [`HandlerSet`].
+    pub fn in_set(mut self, set: impl HandlerSet) -> Self {
+        Arc::make_mut(&mut self.sets).push(set.intern());
+        self
+    }
+
+    /// Runs the handler before the other handler, or the handlers in the [`HandlerSet`], if they
+    /// have the same priority.
+    pub fn before<M>(mut self, other: impl IntoSystemSet<M>) -> Self {
+        let other = other.into_system_set().intern();
+        Arc::make_mut(&mut self.ordering).push(HandlerOrder::Before(other));
+        self
+    }
+
+    /// Runs the handler after the other handler, or the handlers in the [`HandlerSet`], if they
+    /// have the same priority.
+    pub fn after<M>(mut self, other: impl IntoSystemSet<M>) -> Self {
+        let other = other.into_system_set().intern();
+        Arc::make_mut(&mut self.ordering).push(HandlerOrder::After(other));
+        self
+    }
+
+    /// Only runs the handler if the condition returns `true`, e.g. to only react to the event
+    /// while in a certain state.
+    ///
+    /// Multiple conditions can be added, in which case all of them must return `true`. The
+    /// conditions of the handler's sets are checked too, see [`HandlerSetConfig::run_if`].
+    pub fn run_if<M>(mut self, condition: impl Condition<M>) -> Self {
+        let condition: BoxedCondition = Box::new(IntoSystem::into_system(condition));
+        Arc::make_mut(&mut self.conditions).push(Arc::new(Mutex::new(condition)));
+        self
+    }
+
+    /// Only runs the handler for posts from the main thread, see [`MainThread`](crate::MainThread).
+    ///
+    /// Posts from any other thread skip the handler with a warning: the post isn't handed over to
+    /// the main thread, so the handler never sees it.
+    pub fn main_thread_only(mut self) -> Self {
+        self.main_thread = true;
+        self
+    }
+
+    /// Returns `true` if the handler's state can be reset, see [`Resettable`].
+    pub fn is_resettable(&self) -> bool {
+        self.factory.is_some()
+    }
+
+    /// Returns the priority assigned to the handler itself, if any. Handlers without one use the
+    /// priority of their set.
+    pub fn explicit_priority(&self) -> Option<i32> {
+        self.priority
+    }
+
+    /// Returns `true` if the handler is in the [`HandlerSet`].
+    pub fn is_in_set(&self, set: impl HandlerSet) -> bool {
+        self.sets.contains(&set.intern())
+    }
+
+    /// Returns the name of the handler.
+    pub fn name(&self) -> Cow<'static, str> {
+        self.handler.lock().name()
+    }
+
+    /// Returns `true` if the handler only runs for posts from the main thread.
+    pub fn is_main_thread_only(&self) -> bool {
+        self.main_thread
+    }
+
+    /// Marks the handler as only causing side effects, so that it is skipped while
+    /// [`Resimulating`](crate::Resimulating).
+    pub fn side_effect_only(mut self) -> Self {
+        self.side_effect = true;
+        self
+    }
+
+    /// Returns `true` if the handler only causes side effects.
+    pub fn is_side_effect_only(&self) -> bool {
+        self.side_effect
+    }
+
+    /// Removes the handler after it ran for the first time, e.g. to wait for the next time a
+    /// level is loaded.
+    ///
+    /// Posts for which the handler is skipped, e.g. by its run conditions or because an earlier
+    /// handler cancelled the event, don't count.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Returns `true` if the handler is removed after it first ran.
+    pub fn is_once(&self) -> bool {
+        self.once
+    }
+
+    /// Whether the handler still runs after an earlier handler cancelled the event, in its usual
+    /// place in the order. Such handlers see the cancellation state and can change it, e.g. to
+    /// veto the cancellation with [`Receive::uncancel`](crate::Receive::uncancel).
+    ///
+    /// How cancellation stops the other handlers depends on the
+    /// [`DispatchStrategy`](crate::DispatchStrategy): [`Sequential`](crate::Sequential) skips
+    /// them right away, while [`Phased`](crate::Phased) finishes the current phase first.
+    pub fn receive_cancelled(mut self, receive: bool) -> Self {
+        self.receive_cancelled = receive;
+        self
+    }
+
+    /// Returns `true` if the handler still runs after the event was cancelled.
+    pub fn receives_cancelled(&self) -> bool {
+        self.receive_cancelled
+    }
+
+    /// Runs the handler after all other handlers, even if the event was cancelled, e.g. to log or
+    /// count every post.
+    ///
+    /// The handler sees the final state of the post read-only: changes it makes to the
+    /// cancellation state, or to a copy of [`Mutable`](crate::Mutable) events, are discarded.
+    /// Handlers that always run are ordered among themselves like other handlers, and are still
+    /// skipped by their run conditions, required resources, and so on.
+    pub fn always_run(mut self) -> Self
+    where
+        E::Cancellation: Clone,
+        E::Mutability: ReadOnlyView<E>,
+    {
+        self.always_run = Some(monitor_view::<E>);
+        self
+    }
+
+    /// Returns `true` if the handler runs after all other handlers, even if the event was
+    /// cancelled.
+    pub fn is_always_run(&self) -> bool {
+        self.always_run.is_some()
+    }
+
+    /// Skips the handler while [`Resource`] `R` doesn't exist, without needing an
+    /// `Option<Res<R>>` parameter.
+    pub fn while_resource_exists<R: Resource>(mut self) -> Self {
+        self.require(RequiredResource {
+            exists: World::contains_resource::<R>,
+            remove: false,
+        });
+        self
+    }
+
+    /// Removes the handler the first time the event is dispatched while [`Resource`] `R` doesn't
+    /// exist.
+    pub fn until_resource_removed<R: Resource>(mut self) -> Self {
+        self.require(RequiredResource {
+            exists: World::contains_resource::<R>,
+            remove: true,
+        });
+        self
+    }
+
+    fn require(&mut self, resource: RequiredResource) {
+        Arc::make_mut(&mut self.resources).push(resource);
+    }
+
+    /// Skips the handler unless the feature flag is enabled in the [`FeatureFlags`], e.g.
+    /// `"pvp_enabled"`. Requiring multiple flags skips the handler unless all of them are enabled.
+    pub fn requires_flag(mut self, flag: impl Into<Cow<'static, str>>) -> Self {
+        Arc::make_mut(&mut self.flags).push(flag.into());
+        self
+    }
+
+    /// Disables the handler once it failed too many times in a row, see [`CircuitBreaker`].
+    pub fn circuit_breaker(mut self, policy: CircuitBreaker) -> Self {
+        self.breaker = Some(Arc::new(BreakerState::new::<E>(policy)));
+        self
+    }
+
+    /// Remembers the posts offered to the handler by the key in the
+    /// [`DeliveryTracker`](crate::DeliveryTracker), rather than by its
+    /// [`HandlerId`](crate::HandlerId), so that it catches up on the posts it missed after being
+    /// removed and added again with the same key.
+    pub fn delivery_key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.delivery_key = Some(Arc::new(key.into()));
+        self
+    }
+
+    /// Returns the [`CircuitBreaker`] policy of the handler, if any.
+    pub fn circuit_breaker_policy(&self) -> Option<CircuitBreaker> {
+        self.breaker.as_ref().map(|breaker| breaker.policy)
+    }
+
+    /// Returns `true` if the [`CircuitBreaker`] of the handler tripped, and was neither reset nor
+    /// closed by a run after its cool-down since.
+    pub fn is_tripped(&self) -> bool {
+        self.breaker
+            .as_ref()
+            .is_some_and(|breaker| breaker.is_tripped())
+    }
+
+    /// Skips the handler unless the handlers that ran before it in the same post modified the
+    /// field of the event, e.g. `"damage"`, compared with reflection.
+    ///
+    /// The field is a reflection path, so nested fields such as `"hit.damage"` can be watched.
+    /// Watching multiple fields runs the handler if any of them was modified. The event is cloned
+    /// through reflection when it is posted, if any of its handlers watches a field.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn watch_field(mut self, field: impl Into<Cow<'static, str>>) -> Self
+    where
+        E: Event<Mutability = crate::Mutable> + bevy_reflect::Reflect,
+    {
+        let watch = self
+            .watch
+            .get_or_insert_with(|| Arc::new(FieldWatch::new()));
+        Arc::make_mut(watch).fields.push(field.into());
         self
     }
 }
@@ -42,6 +404,107 @@ pub trait IntoHandlerConfig<E: Event, Marker>: Sized {
     fn priority(self, priority: impl HandlerPriority) -> HandlerConfig<E> {
         self.into_config().priority(priority)
     }
+
+    /// Adds the handler to a [`HandlerSet`].
+    fn in_set(self, set: impl HandlerSet) -> HandlerConfig<E> {
+        self.into_config().in_set(set)
+    }
+
+    /// Only runs the handler if the condition returns `true`.
+    fn run_if<M>(self, condition: impl Condition<M>) -> HandlerConfig<E> {
+        self.into_config().run_if(condition)
+    }
+
+    /// Runs the handler before the other handler, or the handlers in the [`HandlerSet`].
+    fn before<M>(self, other: impl IntoSystemSet<M>) -> HandlerConfig<E> {
+        self.into_config().before(other)
+    }
+
+    /// Runs the handler after the other handler, or the handlers in the [`HandlerSet`].
+    fn after<M>(self, other: impl IntoSystemSet<M>) -> HandlerConfig<E> {
+        self.into_config().after(other)
+    }
+
+    /// Only runs the handler for posts from the main thread.
+    fn main_thread_only(self) -> HandlerConfig<E> {
+        self.into_config().main_thread_only()
+    }
+
+    /// Marks the handler as only causing side effects.
+    fn side_effect_only(self) -> HandlerConfig<E> {
+        self.into_config().side_effect_only()
+    }
+
+    /// Removes the handler after it ran for the first time.
+    fn once(self) -> HandlerConfig<E> {
+        self.into_config().once()
+    }
+
+    /// Whether the handler still runs after an earlier handler cancelled the event.
+    fn receive_cancelled(self, receive: bool) -> HandlerConfig<E> {
+        self.into_config().receive_cancelled(receive)
+    }
+
+    /// Runs the handler after all other handlers, even if the event was cancelled.
+    fn always_run(self) -> HandlerConfig<E>
+    where
+        E::Cancellation: Clone,
+        E::Mutability: ReadOnlyView<E>,
+    {
+        self.into_config().always_run()
+    }
+
+    /// Skips the handler while [`Resource`] `R` doesn't exist.
+    fn while_resource_exists<R: Resource>(self) -> HandlerConfig<E> {
+        self.into_config().while_resource_exists::<R>()
+    }
+
+    /// Removes the handler once [`Resource`] `R` doesn't exist.
+    fn until_resource_removed<R: Resource>(self) -> HandlerConfig<E> {
+        self.into_config().until_resource_removed::<R>()
+    }
+
+    /// Skips the handler unless the feature flag is enabled.
+    fn requires_flag(self, flag: impl Into<Cow<'static, str>>) -> HandlerConfig<E> {
+        self.into_config().requires_flag(flag)
+    }
+
+    /// Disables the handler once it failed too many times in a row.
+    fn circuit_breaker(self, policy: CircuitBreaker) -> HandlerConfig<E> {
+        self.into_config().circuit_breaker(policy)
+    }
+
+    /// Remembers the posts offered to the handler by the key, rather than by its ID.
+    fn delivery_key(self, key: impl Into<Cow<'static, str>>) -> HandlerConfig<E> {
+        self.into_config().delivery_key(key)
+    }
+
+    /// Skips the handler unless earlier handlers modified the field of the event.
+    #[cfg(feature = "bevy_reflect")]
+    fn watch_field(self, field: impl Into<Cow<'static, str>>) -> HandlerConfig<E>
+    where
+        E: Event<Mutability = crate::Mutable> + bevy_reflect::Reflect,
+    {
+        self.into_config().watch_field(field)
+    }
+}
+
+/// An ordering constraint of a handler, see [`HandlerConfig::before`] and [`HandlerConfig::after`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HandlerOrder {
+    /// The handler runs before the handlers in the set.
+    Before(InternedSystemSet),
+    /// The handler runs after the handlers in the set.
+    After(InternedSystemSet),
+}
+
+/// A [`Resource`] that a handler requires to run, see [`HandlerConfig::while_resource_exists`].
+#[derive(Clone, Copy)]
+pub(crate) struct RequiredResource {
+    /// Returns `true` if the resource exists.
+    pub(crate) exists: fn(&World) -> bool,
+    /// Whether the handler is removed, rather than skipped, when the resource doesn't exist.
+    pub(crate) remove: bool,
 }
 
 /// [`HandlerConfig`]s can be converted into themselves.
@@ -73,78 +536,70 @@ impl<E: Event, Marker, S: IntoHandlerSystem<E, (), Marker>>
     }
 }
 
-/// Trait for types that can be converted into a priority value.
-pub trait HandlerPriority {
-    /// Higher priority handlers are ran first.
-    fn priority(&self) -> i32;
-}
-
-/// [`i32`] can be converted into a priority value.
-impl HandlerPriority for i32 {
-    fn priority(&self) -> i32 {
-        *self
-    }
-}
-
-/// [`HandlerPriority`] that runs with first priority.
-pub struct First;
-
-impl HandlerPriority for First {
-    fn priority(&self) -> i32 {
-        i32::MAX
-    }
-}
-
-/// [`HandlerPriority`] that runs with early priority.
-pub struct Early;
-
-impl HandlerPriority for Early {
-    fn priority(&self) -> i32 {
-        i32::MAX / 2
-    }
-}
-
-/// [`HandlerPriority`] that runs with pre priority.
-pub struct Pre;
-
-impl HandlerPriority for Pre {
-    fn priority(&self) -> i32 {
-        i32::MAX / 4
-    }
-}
-
-/// [`HandlerPriority`] that runs with normal priority.
-pub struct Normal;
-
-impl HandlerPriority for Normal {
-    fn priority(&self) -> i32 {
-        0
-    }
-}
-
-/// [`HandlerPriority`] that runs with post priority.
-pub struct Post;
-
-impl HandlerPriority for Post {
-    fn priority(&self) -> i32 {
-        i32::MIN / 4
-    }
-}
+/// Wrapper for handlers whose system state, such as [`Local`](bevy_ecs::system::Local)s and cached
+/// queries, can be reset without re-adding them, see
+/// [`HandlerRegistry::reset_handler_state`](crate::HandlerRegistry::reset_handler_state).
+///
+/// The handler must be [`Clone`], so that fresh instances of it can be built, which is the case
+/// for function items and closures that only capture [`Clone`] values.
+///
+/// ```rust
+/// # use bevy_ecs::{system::Local, world::World};
+/// # use bevy_eventbus::{prelude::*, Resettable};
+/// # struct MyEvent;
+/// # impl BusEvent for MyEvent {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// fn count(_event: Receive<MyEvent>, mut seen: Local<u32>) {
+///     *seen += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(Resettable(count).priority(priority::Early));
+/// ```
+#[derive(Clone)]
+pub struct Resettable<S>(pub S);
 
-/// [`HandlerPriority`] that runs with late priority.
-pub struct Late;
+#[doc(hidden)]
+pub struct ResettableMarker;
 
-impl HandlerPriority for Late {
-    fn priority(&self) -> i32 {
-        i32::MIN / 2
+impl<E, Marker, S> IntoHandlerConfig<E, (ResettableMarker, Marker)> for Resettable<S>
+where
+    E: Event,
+    S: IntoHandlerSystem<E, (), Marker> + Clone + Send + Sync + 'static,
+{
+    fn into_config(self) -> HandlerConfig<E> {
+        let factory: HandlerFactory<E> =
+            Arc::new(move || Arc::new(Mutex::new(IntoHandlerSystem::into_system(self.0.clone()))));
+        let mut config = HandlerConfig::new(factory());
+        config.factory = Some(factory);
+        config
     }
 }
 
-/// [`HandlerPriority`] that runs with last priority.
-pub struct Last;
+#[doc(hidden)]
+pub struct SystemIdMarker;
 
-impl HandlerPriority for Last {
-    fn priority(&self) -> i32 {
-        i32::MIN
+/// [`SystemId`]s of one-shot systems registered with [`World::register_system`] can be converted
+/// into [`HandlerConfig`]s, which run the registered system for every event.
+///
+/// Failing to run the system, e.g. because it was unregistered, is logged as a warning. As the
+/// crate that registered the system is unknown, these handlers are exempt from
+/// [reserved priorities](Event::reserved_priorities).
+impl<E: Event> IntoHandlerConfig<E, SystemIdMarker> for SystemId<Receive<'static, E>> {
+    fn into_config(self) -> HandlerConfig<E> {
+        let mut config = (move |event: Receive<E>, world: &mut World| {
+            if let Err(error) = world.run_system_with_input(self, event) {
+                warn!(
+                    "Failed to run one-shot handler {self:?} for {}: {error}",
+                    type_name::<E>()
+                );
+            }
+        })
+        .into_config();
+        config.anonymous = true;
+        config
     }
 }
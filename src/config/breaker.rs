@@ -0,0 +1,207 @@
+use std::{borrow::Cow, time::Duration};
+
+use bevy_ecs::world::World;
+use parking_lot::Mutex;
+
+use crate::{Event, HandlerId, HandlerRegistry, Immutable, Time, WorldEventBus};
+
+/// Posts [`HandlerTripped`] for the handler with the raw ID and name, for a known [`Event`] type.
+type PostTripped = fn(&mut World, u64, Cow<'static, str>, u32);
+
+/// Policy which disables a handler after it failed too many times in a row, set with
+/// [`HandlerConfig::circuit_breaker`](crate::HandlerConfig::circuit_breaker).
+///
+/// A run of the handler fails if it panics, or if it takes longer than the
+/// [`time_budget`](CircuitBreaker::time_budget), if any. Any run that doesn't fail resets the
+/// count. Once the handler failed [`max_failures`](CircuitBreaker::max_failures) times in a row,
+/// the breaker trips: the handler is skipped and [`HandlerTripped`] is posted. It runs again once
+/// the [`cool_down`](CircuitBreaker::cool_down) has passed, if any, or once it is reset with
+/// [`WorldEventBus::reset_circuit_breaker`](crate::WorldEventBus::reset_circuit_breaker).
+///
+/// The cool-down is measured in elapsed [`Time`], so worlds without it never close the breaker on
+/// their own, and a paused or resimulated world cools down at the pace of its simulation.
+///
+/// Panics of handlers with a circuit breaker are reported as usual, but don't propagate out of
+/// the post, so that the remaining handlers still run.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use bevy_ecs::{system::{ResMut, Resource}, world::World};
+/// # use bevy_eventbus::{prelude::*, CircuitBreaker};
+/// # struct Upload;
+/// # impl BusEvent for Upload {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// #[derive(Resource, Default)]
+/// struct Attempts(u32);
+///
+/// fn upload(_event: Receive<Upload>, mut attempts: ResMut<Attempts>) {
+///     attempts.0 += 1;
+///     panic!("The server is down");
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Attempts>();
+/// world.add_handler(
+///     upload.circuit_breaker(CircuitBreaker::new(3).cool_down(Duration::from_secs(60))),
+/// );
+/// for _ in 0..5 {
+///     world.post(Upload);
+/// }
+/// assert_eq!(world.resource::<Attempts>().0, 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    /// The number of failed runs in a row after which the breaker trips.
+    pub max_failures: u32,
+    /// The longest a run of the handler may take before it counts as failed.
+    pub time_budget: Option<Duration>,
+    /// How long the handler is skipped after the breaker tripped, or until it is reset if `None`.
+    pub cool_down: Option<Duration>,
+}
+
+impl CircuitBreaker {
+    /// Creates a policy which trips after `max_failures` failed runs in a row, until it is reset.
+    pub const fn new(max_failures: u32) -> Self {
+        Self {
+            max_failures,
+            time_budget: None,
+            cool_down: None,
+        }
+    }
+
+    /// Counts runs which take longer than the budget as failed.
+    pub const fn time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Runs the handler again once the cool-down has passed after the breaker tripped.
+    pub const fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = Some(cool_down);
+        self
+    }
+}
+
+/// The state of the [`CircuitBreaker`] of a handler, shared with the snapshots of the handler.
+pub(crate) struct BreakerState {
+    pub(crate) policy: CircuitBreaker,
+    status: Mutex<BreakerStatus>,
+    /// Erased, so that dispatching an event doesn't instantiate dispatching [`HandlerTripped`] for
+    /// it, and so on.
+    post_tripped: PostTripped,
+}
+
+#[derive(Default)]
+struct BreakerStatus {
+    /// The number of failed runs in a row.
+    failures: u32,
+    /// The elapsed [`Time`] when the breaker tripped, if it is tripped.
+    tripped: Option<Duration>,
+}
+
+impl BreakerState {
+    pub(crate) fn new<E: Event>(policy: CircuitBreaker) -> Self {
+        Self {
+            policy,
+            status: Mutex::default(),
+            post_tripped: |world, id, name, failures| {
+                if world.contains_resource::<HandlerRegistry<HandlerTripped<E>>>() {
+                    world.post(HandlerTripped {
+                        id: HandlerId::<E>::from_raw(id),
+                        name,
+                        failures,
+                    });
+                }
+            },
+        }
+    }
+
+    /// Returns the elapsed [`Time`] of the world, which breakers cool down by.
+    pub(crate) fn now(world: &World) -> Duration {
+        world
+            .get_resource::<Time>()
+            .map_or(Duration::ZERO, Time::elapsed)
+    }
+
+    /// Returns `true` if the handler is skipped at the elapsed time, closing the breaker if its
+    /// cool-down passed.
+    pub(crate) fn is_open(&self, now: Duration) -> bool {
+        let mut status = self.status.lock();
+        let Some(tripped) = status.tripped else {
+            return false;
+        };
+        if self
+            .policy
+            .cool_down
+            .is_some_and(|cool_down| now.saturating_sub(tripped) >= cool_down)
+        {
+            *status = BreakerStatus::default();
+            return false;
+        }
+        true
+    }
+
+    /// Records a run of the handler at the elapsed time, returning the number of failed runs in a
+    /// row if the breaker just tripped.
+    pub(crate) fn record(&self, panicked: bool, elapsed: Duration, now: Duration) -> Option<u32> {
+        let failed = panicked
+            || self
+                .policy
+                .time_budget
+                .is_some_and(|budget| elapsed > budget);
+        let mut status = self.status.lock();
+        if !failed {
+            status.failures = 0;
+            return None;
+        }
+        status.failures += 1;
+        if status.tripped.is_some() || status.failures < self.policy.max_failures {
+            return None;
+        }
+        status.tripped = Some(now);
+        Some(status.failures)
+    }
+
+    /// Posts [`HandlerTripped`] for the handler, after its breaker tripped.
+    pub(crate) fn post_tripped(
+        &self,
+        world: &mut World,
+        id: u64,
+        name: Cow<'static, str>,
+        failures: u32,
+    ) {
+        (self.post_tripped)(world, id, name, failures);
+    }
+
+    /// Returns `true` if the breaker is tripped, without closing it.
+    pub(crate) fn is_tripped(&self) -> bool {
+        self.status.lock().tripped.is_some()
+    }
+
+    /// Closes the breaker and forgets the failed runs, returning `true` if it was tripped.
+    pub(crate) fn reset(&self) -> bool {
+        std::mem::take(&mut *self.status.lock()).tripped.is_some()
+    }
+}
+
+/// [`Event`] posted after the [`CircuitBreaker`] of a handler for [`Event`] `E` tripped, e.g. to
+/// alert an operator.
+///
+/// Only posted if any handlers are registered for it.
+pub struct HandlerTripped<E: Event> {
+    /// The ID of the disabled handler.
+    pub id: HandlerId<E>,
+    /// The name of the disabled handler.
+    pub name: Cow<'static, str>,
+    /// The number of failed runs in a row that tripped the breaker.
+    pub failures: u32,
+}
+
+impl<E: Event> Event for HandlerTripped<E> {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
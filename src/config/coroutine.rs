@@ -0,0 +1,103 @@
+use bevy_ecs::world::World;
+
+use crate::{Event, HandlerConfig, HandlerSystem, IntoHandlerConfig, IntoHandlerSystem, Receive};
+
+/// How a [`Coroutine`] handler continues after one of its steps, returned by the step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Yield {
+    /// Runs the same step again for the next post.
+    Wait,
+    /// Runs the next step for the next post.
+    Next,
+    /// Runs the next step right away, for the same post.
+    Continue,
+    /// Finishes the coroutine, which starts over from its first step for the next post.
+    Finish,
+}
+
+/// Handler for [`Event`] `E` written as a sequence of steps, which processes the posts of `E`
+/// across multiple invocations, created with [`coroutine`].
+///
+/// Every post runs the current step, a handler system returning a [`Yield`] that decides which step
+/// runs next. The bus stores which step the coroutine is at along with the handler, so multi-step
+/// reactions such as cutscenes and tutorials don't need to track it themselves. Coroutines driven
+/// by the frame are written for [`Tick`](crate::tick::Tick):
+///
+/// ```rust
+/// # use bevy_ecs::system::{Res, Resource};
+/// # use bevy_eventbus::{coroutine, prelude::*, tick::Tick, Yield};
+/// # use bevy_ecs::world::World;
+/// #[derive(Resource)]
+/// struct Clicked(bool);
+///
+/// let mut world = World::new();
+/// world.add_handler(
+///     coroutine::<Tick>()
+///         .step(|_tick: Receive<Tick>| {
+///             println!("Welcome! Click anywhere to continue.");
+///             Yield::Next
+///         })
+///         .step(|_tick: Receive<Tick>, clicked: Res<Clicked>| {
+///             if clicked.0 { Yield::Continue } else { Yield::Wait }
+///         })
+///         .step(|_tick: Receive<Tick>| {
+///             println!("Tutorial complete.");
+///             Yield::Finish
+///         }),
+/// );
+/// ```
+///
+/// Passing the last step with [`Yield::Next`] or [`Yield::Continue`] finishes the coroutine like
+/// [`Yield::Finish`].
+pub struct Coroutine<E: Event> {
+    steps: Vec<Box<dyn HandlerSystem<E, Yield>>>,
+}
+
+/// Creates an empty [`Coroutine`] handler for [`Event`] `E`, to add steps to.
+pub fn coroutine<E: Event>() -> Coroutine<E> {
+    Coroutine { steps: Vec::new() }
+}
+
+impl<E: Event> Coroutine<E> {
+    /// Adds a step to the coroutine.
+    pub fn step<M>(mut self, step: impl IntoHandlerSystem<E, Yield, M>) -> Self {
+        self.steps
+            .push(Box::new(IntoHandlerSystem::into_system(step)));
+        self
+    }
+}
+
+#[doc(hidden)]
+pub struct CoroutineMarker;
+
+impl<E: Event> IntoHandlerConfig<E, CoroutineMarker> for Coroutine<E> {
+    fn into_config(self) -> HandlerConfig<E> {
+        let mut steps = self.steps;
+        let mut initialized = vec![false; steps.len()];
+        let mut current = 0;
+        (move |mut event: Receive<E>, world: &mut World| {
+            while let Some(step) = steps.get_mut(current) {
+                if !initialized[current] {
+                    step.initialize(world);
+                    initialized[current] = true;
+                }
+                if !step.validate_param(world) {
+                    return;
+                }
+                match step.run(event.reborrow(), world) {
+                    Yield::Wait => return,
+                    Yield::Next => {
+                        current = (current + 1) % steps.len();
+                        return;
+                    }
+                    Yield::Continue if current + 1 < steps.len() => current += 1,
+                    Yield::Continue | Yield::Finish => {
+                        current = 0;
+                        return;
+                    }
+                }
+            }
+        })
+        .into_config()
+    }
+}
@@ -0,0 +1,110 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{Event, Immutable};
+
+/// [`Resource`] which holds the feature flags that are enabled, flipped by posting [`SetFlag`].
+///
+/// Handlers that declare [`HandlerConfig::requires_flag`](crate::HandlerConfig::requires_flag)
+/// are skipped unless all of their flags are enabled. Flags are disabled unless enabled, so
+/// worlds without this resource skip all handlers that require flags.
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::{prelude::*, FeatureFlags, SetFlag};
+/// # struct Attack;
+/// # impl BusEvent for Attack {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// fn pvp(_event: Receive<Attack>) {
+///     // Damage the other player...
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(pvp.requires_flag("pvp_enabled"));
+/// world.post(SetFlag::enable("pvp_enabled"));
+/// assert!(world.resource::<FeatureFlags>().is_enabled("pvp_enabled"));
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FeatureFlags {
+    enabled: HashSet<Cow<'static, str>>,
+}
+
+impl FeatureFlags {
+    /// Returns `true` if the flag is enabled.
+    pub fn is_enabled(&self, flag: &str) -> bool {
+        self.enabled.contains(flag)
+    }
+
+    /// Enables or disables the flag, returning whether it was enabled before.
+    ///
+    /// Prefer posting [`SetFlag`], so that handlers can observe and veto the change.
+    pub fn set(&mut self, flag: impl Into<Cow<'static, str>>, enabled: bool) -> bool {
+        let flag = flag.into();
+        if enabled {
+            !self.enabled.insert(flag)
+        } else {
+            self.enabled.remove(&flag)
+        }
+    }
+
+    /// Returns an iterator over the enabled flags, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.enabled.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns `true` if all of the flags are enabled in the world.
+    pub(crate) fn all_enabled(world: &World, flags: &[Cow<'static, str>]) -> bool {
+        flags.is_empty()
+            || world
+                .get_resource::<Self>()
+                .is_some_and(|enabled| flags.iter().all(|flag| enabled.is_enabled(flag)))
+    }
+}
+
+/// [`Event`] which enables or disables a feature flag of the [`FeatureFlags`].
+///
+/// The flag is flipped after all handlers ran, unless one of them cancelled the event, e.g. to
+/// restrict who can change which flags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetFlag {
+    /// The name of the flag.
+    pub flag: Cow<'static, str>,
+    /// Whether the flag is enabled or disabled.
+    pub enabled: bool,
+}
+
+impl SetFlag {
+    /// Creates an event which enables the flag.
+    pub fn enable(flag: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            flag: flag.into(),
+            enabled: true,
+        }
+    }
+
+    /// Creates an event which disables the flag.
+    pub fn disable(flag: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            flag: flag.into(),
+            enabled: false,
+        }
+    }
+}
+
+impl Event for SetFlag {
+    type Mutability = Immutable;
+    type Cancellation = bool;
+    type Audience = ();
+
+    fn after_dispatch(&self, cancelled: &bool, world: &mut World) {
+        if !cancelled {
+            world
+                .get_resource_or_insert_with(FeatureFlags::default)
+                .set(self.flag.clone(), self.enabled);
+        }
+    }
+}
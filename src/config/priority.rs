@@ -0,0 +1,75 @@
+/// Trait for types that can be converted into a priority value.
+pub trait HandlerPriority {
+    /// Higher priority handlers are ran first.
+    fn priority(&self) -> i32;
+}
+
+/// [`i32`] can be converted into a priority value.
+impl HandlerPriority for i32 {
+    fn priority(&self) -> i32 {
+        *self
+    }
+}
+
+/// [`HandlerPriority`] that runs with first priority.
+pub struct First;
+
+impl HandlerPriority for First {
+    fn priority(&self) -> i32 {
+        i32::MAX
+    }
+}
+
+/// [`HandlerPriority`] that runs with early priority.
+pub struct Early;
+
+impl HandlerPriority for Early {
+    fn priority(&self) -> i32 {
+        i32::MAX / 2
+    }
+}
+
+/// [`HandlerPriority`] that runs with pre priority.
+pub struct Pre;
+
+impl HandlerPriority for Pre {
+    fn priority(&self) -> i32 {
+        i32::MAX / 4
+    }
+}
+
+/// [`HandlerPriority`] that runs with normal priority.
+pub struct Normal;
+
+impl HandlerPriority for Normal {
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
+/// [`HandlerPriority`] that runs with post priority.
+pub struct Post;
+
+impl HandlerPriority for Post {
+    fn priority(&self) -> i32 {
+        i32::MIN / 4
+    }
+}
+
+/// [`HandlerPriority`] that runs with late priority.
+pub struct Late;
+
+impl HandlerPriority for Late {
+    fn priority(&self) -> i32 {
+        i32::MIN / 2
+    }
+}
+
+/// [`HandlerPriority`] that runs with last priority.
+pub struct Last;
+
+impl HandlerPriority for Last {
+    fn priority(&self) -> i32 {
+        i32::MIN
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use bevy_ecs::{
+    schedule::{BoxedCondition, Condition, InternedSystemSet, SystemSet},
+    system::IntoSystem,
+};
+use parking_lot::Mutex;
+
+use crate::HandlerPriority;
+
+/// A shared, type-erased run condition.
+pub type ArcCondition = Arc<Mutex<BoxedCondition>>;
+
+/// Label for a group of handlers that can be configured together, analogous to bevy's
+/// [`SystemSet`].
+///
+/// Every [`SystemSet`] is a [`HandlerSet`], so handler sets are declared with
+/// `#[derive(SystemSet)]`. Handlers join a set with [`IntoHandlerConfig::in_set`], and the set
+/// itself is configured per [`Event`](crate::Event) type with
+/// [`WorldEventBus::configure_handler_set`](crate::WorldEventBus::configure_handler_set).
+///
+/// [`IntoHandlerConfig::in_set`]: crate::IntoHandlerConfig::in_set
+pub trait HandlerSet: SystemSet {}
+
+impl<S: SystemSet> HandlerSet for S {}
+
+/// Configuration for a [`HandlerSet`], applied to all handlers in the set.
+///
+/// ```rust
+/// # use bevy_app::App;
+/// # use bevy_ecs::{schedule::SystemSet, system::{Res, Resource}};
+/// # use bevy_eventbus::prelude::*;
+/// # struct Damage(u32);
+/// # impl BusEvent for Damage {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// # fn play_sound(_event: Receive<Damage>) {}
+/// # fn shake_camera(_event: Receive<Damage>) {}
+/// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Feedback;
+///
+/// #[derive(Resource)]
+/// struct Settings {
+///     effects: bool,
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_resource(Settings { effects: true })
+///     .add_handler(play_sound.in_set(Feedback))
+///     .add_handler(shake_camera.in_set(Feedback))
+///     .configure_handler_set::<Damage>(
+///         HandlerSetConfig::new(Feedback)
+///             .priority(priority::Late)
+///             .run_if(|settings: Res<Settings>| settings.effects),
+///     );
+/// ```
+///
+/// # Priority
+///
+/// A set's priority applies to each handler in the set that wasn't given a priority of its own.
+/// If a handler is in multiple sets with priorities, the first set it joined wins.
+///
+/// # Ordering
+///
+/// Ordering constraints between sets only apply to handlers of the same priority, and otherwise
+/// keep the order in which handlers were added.
+pub struct HandlerSetConfig {
+    pub(crate) set: InternedSystemSet,
+    pub(crate) priority: Option<i32>,
+    pub(crate) enabled: Option<bool>,
+    pub(crate) conditions: Vec<ArcCondition>,
+    pub(crate) before: Vec<InternedSystemSet>,
+    pub(crate) after: Vec<InternedSystemSet>,
+}
+
+impl HandlerSetConfig {
+    /// Creates a new, empty configuration for the [`HandlerSet`].
+    pub fn new(set: impl HandlerSet) -> Self {
+        Self {
+            set: set.intern(),
+            priority: None,
+            enabled: None,
+            conditions: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+
+    /// Sets the priority of the handlers in the set.
+    pub fn priority(mut self, priority: impl HandlerPriority) -> Self {
+        self.priority = Some(HandlerPriority::priority(&priority));
+        self
+    }
+
+    /// Sets whether the handlers in the set are run at all.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Only runs the handlers in the set if the condition returns `true`.
+    ///
+    /// Multiple conditions can be added, in which case all of them must return `true`.
+    pub fn run_if<M>(mut self, condition: impl Condition<M>) -> Self {
+        let condition: BoxedCondition = Box::new(IntoSystem::into_system(condition));
+        self.conditions.push(Arc::new(Mutex::new(condition)));
+        self
+    }
+
+    /// Runs the handlers in the set before the handlers in another set of the same priority.
+    pub fn before(mut self, set: impl HandlerSet) -> Self {
+        self.before.push(set.intern());
+        self
+    }
+
+    /// Runs the handlers in the set after the handlers in another set of the same priority.
+    pub fn after(mut self, set: impl HandlerSet) -> Self {
+        self.after.push(set.intern());
+        self
+    }
+
+    /// Merges another configuration of the same set into this one.
+    pub(crate) fn merge(&mut self, other: HandlerSetConfig) {
+        self.priority = other.priority.or(self.priority);
+        self.enabled = other.enabled.or(self.enabled);
+        self.conditions.extend(other.conditions);
+        self.before.extend(other.before);
+        self.after.extend(other.after);
+    }
+}
+
+/// Trait for types that can be converted into a [`HandlerSetConfig`].
+///
+/// Unlike [`IntoHandlerConfig`](crate::IntoHandlerConfig), this trait has no builder methods, as
+/// they would be ambiguous with bevy's own [`SystemSet`] configuration methods. Use
+/// [`HandlerSetConfig::new`] instead.
+pub trait IntoHandlerSetConfig: Sized {
+    /// Converts the type into a [`HandlerSetConfig`].
+    fn into_set_config(self) -> HandlerSetConfig;
+}
+
+/// [`HandlerSetConfig`]s can be converted into themselves.
+impl IntoHandlerSetConfig for HandlerSetConfig {
+    fn into_set_config(self) -> HandlerSetConfig {
+        self
+    }
+}
+
+/// [`HandlerSet`]s can be converted into an empty [`HandlerSetConfig`].
+impl<S: HandlerSet> IntoHandlerSetConfig for S {
+    fn into_set_config(self) -> HandlerSetConfig {
+        HandlerSetConfig::new(self)
+    }
+}
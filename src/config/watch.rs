@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+use bevy_reflect::{PartialReflect, Reflect, ReflectPath};
+
+use crate::Event;
+
+/// Fields of a [`Mutable`](crate::Mutable) [`Event`] that a handler watches, see
+/// [`HandlerConfig::watch_field`](crate::HandlerConfig::watch_field).
+pub(crate) struct FieldWatch<E: Event> {
+    /// Reflection paths of the watched fields.
+    pub(crate) fields: Vec<Cow<'static, str>>,
+    /// Takes a copy of the event to diff against, as it was posted.
+    pub(crate) baseline: fn(&E) -> Box<dyn PartialReflect>,
+    /// Returns `true` if the field of the event differs from the baseline.
+    pub(crate) changed: fn(&E, &dyn PartialReflect, &str) -> bool,
+}
+
+impl<E: Event> Clone for FieldWatch<E> {
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+            baseline: self.baseline,
+            changed: self.changed,
+        }
+    }
+}
+
+impl<E: Event + Reflect> FieldWatch<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            baseline: |event| event.clone_value(),
+            changed: |event, baseline, field| match (
+                field.reflect_element(event.as_partial_reflect()),
+                field.reflect_element(baseline),
+            ) {
+                (Ok(current), Ok(baseline)) => current.reflect_partial_eq(baseline) != Some(true),
+                _ => true,
+            },
+        }
+    }
+}
+
+impl<E: Event> FieldWatch<E> {
+    /// Returns `true` if any watched field of the event differs from the baseline.
+    pub(crate) fn any_changed(&self, event: &E, baseline: &dyn PartialReflect) -> bool {
+        self.fields
+            .iter()
+            .any(|field| (self.changed)(event, baseline, field))
+    }
+}
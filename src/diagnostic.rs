@@ -0,0 +1,25 @@
+mod access;
+mod budget;
+mod causality;
+mod harness;
+mod hash;
+#[cfg(feature = "bevy_app")]
+mod lag;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod snapshot;
+mod stats;
+mod trace;
+
+pub use access::*;
+pub use budget::*;
+pub use causality::*;
+pub use harness::*;
+pub use hash::*;
+#[cfg(feature = "bevy_app")]
+pub use lag::*;
+#[cfg(feature = "prometheus")]
+pub use prometheus::*;
+pub use snapshot::*;
+pub use stats::*;
+pub use trace::*;
@@ -0,0 +1,98 @@
+use std::{any::type_name, borrow::Cow};
+
+use bevy_ecs::{component::ComponentId, query::Access, world::World};
+use bevy_utils::tracing::warn;
+
+use crate::{dispatch::initialize_reset_handlers, Event, EventContext, HandlerId, HandlerRegistry};
+
+/// The merged access of all handlers for [`Event`] `E`, see
+/// [`WorldEventBus::event_access`](crate::WorldEventBus::event_access).
+///
+/// Useful for checking which handlers could run in parallel with each other or with scheduled
+/// systems, and for debugging surprising aliasing panics.
+pub struct EventAccess<E: Event> {
+    /// The merged component and resource access of all non-exclusive handlers.
+    pub access: Access<ComponentId>,
+    /// The exclusive handlers, which have access to the whole world.
+    pub exclusive: Vec<HandlerId<E>>,
+    /// The handlers whose access conflicts with the resources that the dispatch of `E` itself
+    /// writes to.
+    pub conflicts: Vec<AccessConflict<E>>,
+}
+
+/// A handler whose access conflicts with the dispatch of its [`Event`], see [`EventAccess`].
+pub struct AccessConflict<E: Event> {
+    /// The id of the handler.
+    pub id: HandlerId<E>,
+    /// The name of the handler.
+    pub name: Cow<'static, str>,
+    /// The name of the resource the handler writes to.
+    pub resource: String,
+}
+
+impl<E: Event> EventAccess<E> {
+    /// Computes the access of the handlers for [`Event`] `E`.
+    ///
+    /// The dispatch of `E` writes to its [`HandlerRegistry`] and to the [`EventContext`], so
+    /// handlers writing to them conflict with it: changes to them are either overwritten by the
+    /// dispatch, or corrupt the state it relies on.
+    pub fn compute(world: &mut World) -> Self {
+        initialize_reset_handlers::<E>(world);
+        let mut event_access = Self {
+            access: Access::default(),
+            exclusive: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        let Some(registry) = world.get_resource::<HandlerRegistry<E>>() else {
+            return event_access;
+        };
+
+        let components = world.components();
+        let reserved = [
+            components.resource_id::<HandlerRegistry<E>>(),
+            components.resource_id::<EventContext>(),
+        ];
+        for id in registry.ids() {
+            let Some(config) = registry.get(id) else {
+                continue;
+            };
+            let handler = config.handler.lock();
+            if handler.is_exclusive() {
+                event_access.exclusive.push(id);
+                continue;
+            }
+
+            let access = handler.component_access();
+            for resource in reserved.into_iter().flatten() {
+                if access.has_resource_write(resource) {
+                    event_access.conflicts.push(AccessConflict {
+                        id,
+                        name: handler.name(),
+                        resource: components
+                            .get_name(resource)
+                            .map_or_else(|| format!("{resource:?}"), ToString::to_string),
+                    });
+                }
+            }
+            event_access.access.extend(access);
+        }
+        event_access
+    }
+
+    /// Returns `true` if no handler conflicts with the dispatch of `E`.
+    pub fn is_valid(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Logs every conflict as a warning.
+    pub fn warn(&self) {
+        for conflict in &self.conflicts {
+            warn!(
+                "Handler `{}` for {} writes {}, which the dispatch of the event itself writes to",
+                conflict.name,
+                type_name::<E>(),
+                conflict.resource,
+            );
+        }
+    }
+}
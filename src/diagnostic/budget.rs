@@ -0,0 +1,98 @@
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+
+use bevy_ecs::{system::Resource, world::World};
+use parking_lot::Mutex;
+
+use crate::{Event, FrameCount};
+
+/// Guard which fails the test when more posts of an [`Event`] type are dispatched per frame than
+/// its budget allows, see
+/// [`WorldEventBus::assert_event_budget`](crate::WorldEventBus::assert_event_budget).
+///
+/// The budget is enforced until the guard is dropped. Posts are counted per [`FrameCount`], so
+/// worlds without it count all posts made while the guard is alive against a single frame.
+///
+/// Exceeding the budget panics within the post that exceeded it, so the backtrace points at the
+/// offending poster.
+#[must_use = "the budget is only enforced while the guard is alive"]
+pub struct EventBudgetGuard {
+    budget: Arc<Budget>,
+}
+
+impl EventBudgetGuard {
+    /// Returns the maximum number of posts per frame.
+    pub fn max_posts_per_frame(&self) -> usize {
+        self.budget.max
+    }
+
+    /// Returns the number of posts dispatched in the current frame.
+    pub fn posts(&self) -> usize {
+        self.budget.posts.lock().1
+    }
+}
+
+/// A budget of an [`EventBudgetGuard`].
+struct Budget {
+    max: usize,
+    /// The frame being counted, and the posts dispatched in it.
+    posts: Mutex<(u32, usize)>,
+}
+
+/// [`Resource`] which holds the budgets of the live [`EventBudgetGuard`]s, per [`Event`] type.
+#[derive(Resource, Default)]
+pub(crate) struct EventBudgets(HashMap<TypeId, Vec<Weak<Budget>>>);
+
+impl EventBudgets {
+    /// Starts enforcing a budget for [`Event`] `E`.
+    pub(crate) fn guard<E: Event>(world: &mut World, max: usize) -> EventBudgetGuard {
+        let frame = current_frame(world);
+        let budget = Arc::new(Budget {
+            max,
+            posts: Mutex::new((frame, 0)),
+        });
+        world
+            .get_resource_or_insert_with(Self::default)
+            .0
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(Arc::downgrade(&budget));
+        EventBudgetGuard { budget }
+    }
+
+    /// Counts a post of [`Event`] `E` against its budgets, panicking if any of them is exceeded.
+    pub(crate) fn count<E: Event>(world: &mut World) {
+        if !world.contains_resource::<Self>() {
+            return;
+        }
+        let frame = current_frame(world);
+        let mut budgets = world.resource_mut::<Self>();
+        let Some(budgets) = budgets.0.get_mut(&TypeId::of::<E>()) else {
+            return;
+        };
+        budgets.retain(|budget| budget.strong_count() > 0);
+        for budget in budgets.iter().filter_map(Weak::upgrade) {
+            let mut posts = budget.posts.lock();
+            if posts.0 != frame {
+                *posts = (frame, 0);
+            }
+            posts.1 += 1;
+            assert!(
+                posts.1 <= budget.max,
+                "{} was posted {} times in frame {frame}, exceeding its budget of {}",
+                type_name::<E>(),
+                posts.1,
+                budget.max,
+            );
+        }
+    }
+}
+
+fn current_frame(world: &World) -> u32 {
+    world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0)
+}
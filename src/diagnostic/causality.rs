@@ -0,0 +1,225 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Write,
+    ops::RangeBounds,
+    time::Duration,
+};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{EventMeta, FrameCount, PostId, Time};
+
+/// [`Resource`] which records which posts caused which other posts, for visualizing the causal
+/// graph of events.
+///
+/// Recording is opt-in: insert this resource into the world to start collecting posts. Only the
+/// most recent [`EventCausality::capacity`] posts are retained.
+///
+/// Every post becomes a node, with an edge from the post whose handler caused it, see
+/// [`EventMeta::parent`]. The graph for a time range can be exported with
+/// [`EventCausality::to_json`] and [`EventCausality::to_graphviz`].
+#[derive(Resource, Debug, Clone)]
+pub struct EventCausality {
+    capacity: usize,
+    nodes: VecDeque<CausalNode>,
+}
+
+/// A single post recorded in the [`EventCausality`].
+#[derive(Debug, Clone)]
+pub struct CausalNode {
+    /// The metadata of the post.
+    pub meta: EventMeta,
+    /// The frame the post was made in.
+    pub frame: u32,
+    /// The elapsed time when the post was made.
+    pub elapsed: Duration,
+}
+
+impl Default for EventCausality {
+    fn default() -> Self {
+        Self::new(4096)
+    }
+}
+
+impl EventCausality {
+    /// Creates an empty causal graph that retains up to `capacity` posts.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            nodes: VecDeque::new(),
+        }
+    }
+
+    /// Returns the maximum number of posts retained.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of posts retained.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if no posts are retained.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Removes all retained posts.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Returns an iterator over the retained posts, oldest first.
+    pub fn nodes(&self) -> impl Iterator<Item = &CausalNode> + '_ {
+        self.nodes.iter()
+    }
+
+    /// Returns an iterator over the retained posts made within the range of elapsed time, oldest
+    /// first.
+    pub fn nodes_in<'a>(
+        &'a self,
+        range: impl RangeBounds<Duration> + 'a,
+    ) -> impl Iterator<Item = &'a CausalNode> + 'a {
+        self.nodes
+            .iter()
+            .filter(move |node| range.contains(&node.elapsed))
+    }
+
+    /// Returns the retained post with the given ID, if any.
+    pub fn get(&self, id: PostId) -> Option<&CausalNode> {
+        self.nodes.iter().find(|node| node.meta.id == id)
+    }
+
+    /// Returns an iterator over the retained posts directly caused by the post.
+    pub fn children(&self, id: PostId) -> impl Iterator<Item = &CausalNode> + '_ {
+        self.nodes
+            .iter()
+            .filter(move |node| node.meta.parent == Some(id))
+    }
+
+    /// Returns all retained posts caused by the post, transitively, in the order they were made.
+    pub fn descendants(&self, id: PostId) -> Vec<&CausalNode> {
+        let mut ancestors = HashSet::from([id]);
+        self.nodes
+            .iter()
+            .filter(|node| {
+                let caused = node
+                    .meta
+                    .parent
+                    .is_some_and(|parent| ancestors.contains(&parent));
+                if caused {
+                    ancestors.insert(node.meta.id);
+                }
+                caused
+            })
+            .collect()
+    }
+
+    /// Exports the causal graph of the posts made within the range of elapsed time as JSON.
+    ///
+    /// The output is an object with a `nodes` array and an `edges` array of `{"from", "to"}` post
+    /// IDs. Only edges between posts within the range are included.
+    pub fn to_json(&self, range: impl RangeBounds<Duration>) -> String {
+        let nodes = self.nodes_in(range).collect::<Vec<_>>();
+        let ids = nodes
+            .iter()
+            .map(|node| node.meta.id)
+            .collect::<HashSet<_>>();
+
+        let mut json = String::from("{\"nodes\":[");
+        for (index, node) in nodes.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"id\":{},\"parent\":{},\"correlation\":\"{}\",\"event\":\"{}\",\"depth\":{},\
+                 \"frame\":{},\"elapsed\":{}}}",
+                node.meta.id,
+                node.meta
+                    .parent
+                    .map_or_else(|| "null".to_string(), |parent| parent.to_string()),
+                node.meta.correlation,
+                escape(node.meta.event),
+                node.meta.depth,
+                node.frame,
+                node.elapsed.as_secs_f64(),
+            );
+        }
+        json.push_str("],\"edges\":[");
+        for (index, (from, to)) in edges(&nodes, &ids).enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(json, "{{\"from\":{from},\"to\":{to}}}");
+        }
+        json.push_str("]}");
+        json
+    }
+
+    /// Exports the causal graph of the posts made within the range of elapsed time in the
+    /// graphviz DOT format.
+    pub fn to_graphviz(&self, range: impl RangeBounds<Duration>) -> String {
+        let nodes = self.nodes_in(range).collect::<Vec<_>>();
+        let ids = nodes
+            .iter()
+            .map(|node| node.meta.id)
+            .collect::<HashSet<_>>();
+
+        let mut dot = String::from("digraph causality {\n");
+        for node in &nodes {
+            let _ = writeln!(
+                dot,
+                "    {} [label=\"{}\\nframe {}\"];",
+                node.meta.id,
+                escape(node.meta.event),
+                node.frame,
+            );
+        }
+        for (from, to) in edges(&nodes, &ids) {
+            let _ = writeln!(dot, "    {from} -> {to};");
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Records a post, if the world has an [`EventCausality`].
+    pub(crate) fn record(world: &mut World, meta: EventMeta) {
+        if !world.contains_resource::<Self>() {
+            return;
+        }
+
+        let frame = world
+            .get_resource::<FrameCount>()
+            .map_or(0, |frame| frame.0);
+        let elapsed = world
+            .get_resource::<Time>()
+            .map_or(Duration::ZERO, Time::elapsed);
+        let mut causality = world.resource_mut::<Self>();
+        causality.nodes.push_back(CausalNode {
+            meta,
+            frame,
+            elapsed,
+        });
+        while causality.nodes.len() > causality.capacity {
+            causality.nodes.pop_front();
+        }
+    }
+}
+
+/// Returns the edges between the nodes whose posts are both in `ids`.
+fn edges<'a>(
+    nodes: &'a [&CausalNode],
+    ids: &'a HashSet<PostId>,
+) -> impl Iterator<Item = (PostId, PostId)> + 'a {
+    nodes.iter().filter_map(|node| {
+        let parent = node.meta.parent?;
+        ids.contains(&parent).then_some((parent, node.meta.id))
+    })
+}
+
+/// Escapes a string for use within double quotes in JSON and DOT.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
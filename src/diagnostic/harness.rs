@@ -0,0 +1,145 @@
+use std::{any::type_name, marker::PhantomData};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{Cancellation, Event, IntoHandlerConfig, Mutable, WorldEventBus};
+
+/// Runs handlers of [`Event`] `E` against a throwaway [`World`], for unit testing handler logic
+/// without assembling an [`App`](bevy_app::App).
+///
+/// The world starts out empty: only the resources injected with
+/// [`DispatchHarness::with_resource`] exist, along with the ones the event bus inserts itself.
+///
+/// ```rust
+/// # use bevy_ecs::system::{ResMut, Resource};
+/// # use bevy_eventbus::{prelude::*, DispatchHarness};
+/// struct Attack {
+///     damage: u32,
+/// }
+///
+/// impl BusEvent for Attack {
+///     type Mutability = Immutable;
+///     type Cancellation = bool;
+///     type Audience = ();
+/// }
+///
+/// #[derive(Resource)]
+/// struct Health(u32);
+///
+/// fn block_weak(mut event: Receive<Attack>) {
+///     if event.damage < 5 {
+///         event.cancel();
+///     }
+/// }
+///
+/// fn apply(event: Receive<Attack>, mut health: ResMut<Health>) {
+///     health.0 -= event.damage;
+/// }
+///
+/// let mut harness = DispatchHarness::new()
+///     .with_resource(Health(100))
+///     .with_handler(block_weak)
+///     .with_handler(apply.priority(priority::Late));
+/// harness.assert_cancelled(Attack { damage: 1 });
+/// harness.assert_not_cancelled(Attack { damage: 10 });
+/// assert_eq!(harness.resource::<Health>().0, 90);
+/// ```
+pub struct DispatchHarness<E: Event> {
+    world: World,
+    _marker: PhantomData<fn(E)>,
+}
+
+impl<E: Event> DispatchHarness<E> {
+    /// Creates a harness with an empty world and no handlers.
+    pub fn new() -> Self {
+        Self {
+            world: World::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Adds a handler, see [`WorldEventBus::add_handler`].
+    pub fn with_handler<M>(mut self, handler: impl IntoHandlerConfig<E, M>) -> Self {
+        self.world.add_handler(handler);
+        self
+    }
+
+    /// Inserts a resource that the handlers use.
+    pub fn with_resource<R: Resource>(mut self, resource: R) -> Self {
+        self.world.insert_resource(resource);
+        self
+    }
+
+    /// Returns the world the handlers run against.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Returns the world the handlers run against, mutably.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Returns a resource of the world, panicking if it doesn't exist.
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.world.resource::<R>()
+    }
+
+    /// Posts an event to the handlers.
+    pub fn post(&mut self, event: E) -> E::Cancellation
+    where
+        E: Event<Audience = ()>,
+    {
+        self.world.post(event)
+    }
+
+    /// Posts an event to the handlers with a specific [`Audience`](Event::Audience).
+    pub fn post_to(&mut self, event: E, audience: E::Audience) -> E::Cancellation {
+        self.world.post_to(event, audience)
+    }
+
+    /// Posts a mutable reference to an event to the handlers, so that their changes to it can be
+    /// inspected afterwards.
+    pub fn post_mut(&mut self, event: &mut E) -> E::Cancellation
+    where
+        E: Event<Audience = (), Mutability = Mutable>,
+    {
+        self.world.post_mut(event)
+    }
+
+    /// Posts an event to the handlers, panicking if they don't cancel it.
+    #[track_caller]
+    pub fn assert_cancelled(&mut self, event: E) -> E::Cancellation
+    where
+        E: Event<Audience = ()>,
+    {
+        let cancellation = self.post(event);
+        assert!(
+            cancellation.cancelled(),
+            "Expected {} to be cancelled",
+            type_name::<E>()
+        );
+        cancellation
+    }
+
+    /// Posts an event to the handlers, panicking if they cancel it.
+    #[track_caller]
+    pub fn assert_not_cancelled(&mut self, event: E) -> E::Cancellation
+    where
+        E: Event<Audience = ()>,
+    {
+        let cancellation = self.post(event);
+        assert!(
+            !cancellation.cancelled(),
+            "Expected {} not to be cancelled, but it was cancelled with {cancellation:?}",
+            type_name::<E>()
+        );
+        cancellation
+    }
+}
+
+impl<E: Event> Default for DispatchHarness<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
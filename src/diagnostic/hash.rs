@@ -0,0 +1,160 @@
+use std::{
+    any::type_name,
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{Event, FrameCount};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// [`Resource`] which folds every hashed post into a rolling hash per frame, so that lockstep
+/// clients can compare their simulations and detect desyncs.
+///
+/// Hashing is opt-in per event type with
+/// [`WorldEventBus::hash_stream`](crate::WorldEventBus::hash_stream), which requires the event and
+/// its audience to implement [`Hash`]. Each post contributes the type name of its event, the
+/// event, its audience, and its sequence number within the frame, so reordered posts change the
+/// hash too. The [`EventBusPlugin`](crate::EventBusPlugin) finishes the hash of every frame after
+/// flushing the [`EventQueue`](crate::EventQueue), and only the most recent
+/// [`StreamHasher::capacity`] frames are retained.
+///
+/// The hash is stable across runs and machines, as long as the [`Hash`] implementations of the
+/// events are, e.g. not hashing [`usize`]s or [`Entity`](bevy_ecs::entity::Entity) IDs that differ
+/// between clients.
+#[derive(Resource, Debug, Clone)]
+pub struct StreamHasher {
+    capacity: usize,
+    current: u64,
+    posts: u64,
+    frames: VecDeque<FrameHash>,
+}
+
+/// The hash of the posts of a single frame, retained in the [`StreamHasher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHash {
+    /// The frame the posts were made in.
+    pub frame: u32,
+    /// The rolling hash of the posts.
+    pub hash: u64,
+    /// The number of posts hashed.
+    pub posts: u64,
+}
+
+impl Default for StreamHasher {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl StreamHasher {
+    /// Creates a hasher that retains the hashes of up to `capacity` frames.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            current: FNV_OFFSET,
+            posts: 0,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Returns the maximum number of frames retained.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the rolling hash of the posts made since the last frame was finished.
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Returns the hash of the frame, if retained.
+    pub fn frame(&self, frame: u32) -> Option<u64> {
+        self.frames
+            .iter()
+            .find(|hash| hash.frame == frame)
+            .map(|hash| hash.hash)
+    }
+
+    /// Returns the most recently finished frame, if any.
+    pub fn latest(&self) -> Option<&FrameHash> {
+        self.frames.back()
+    }
+
+    /// Returns an iterator over the retained frames, oldest first.
+    pub fn frames(&self) -> impl Iterator<Item = &FrameHash> + '_ {
+        self.frames.iter()
+    }
+
+    /// Folds a post into the rolling hash.
+    pub fn fold<E: Event<Audience: Hash> + Hash>(&mut self, event: &E, audience: &E::Audience) {
+        let mut hasher = StableHasher::default();
+        type_name::<E>().hash(&mut hasher);
+        event.hash(&mut hasher);
+        audience.hash(&mut hasher);
+        self.posts.hash(&mut hasher);
+
+        self.current = (self.current ^ hasher.finish()).wrapping_mul(FNV_PRIME);
+        self.posts += 1;
+    }
+
+    /// Retains the rolling hash as the hash of the frame, and starts a new one. Returns the hash.
+    pub fn finish_frame(&mut self, frame: u32) -> u64 {
+        let hash = self.current;
+        self.frames.push_back(FrameHash {
+            frame,
+            hash,
+            posts: self.posts,
+        });
+        while self.frames.len() > self.capacity {
+            self.frames.pop_front();
+        }
+        self.current = FNV_OFFSET;
+        self.posts = 0;
+        hash
+    }
+
+    /// Folds a post into the [`StreamHasher`], if the world has one.
+    pub(crate) fn record<E>(world: &mut World, event: &E, audience: &E::Audience)
+    where
+        E: Event<Audience: Hash> + Hash,
+    {
+        if let Some(mut hasher) = world.get_resource_mut::<Self>() {
+            hasher.fold(event, audience);
+        }
+    }
+}
+
+/// FNV-1a [`Hasher`], which unlike the standard library's hashers is guaranteed to be stable.
+pub(crate) struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Exclusive system that finishes the current frame of the [`StreamHasher`], if any.
+pub fn finish_stream_hash(world: &mut World) {
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0);
+    if let Some(mut hasher) = world.get_resource_mut::<StreamHasher>() {
+        hasher.finish_frame(frame);
+    }
+}
@@ -0,0 +1,187 @@
+use std::borrow::Cow;
+
+use bevy_app::{MainScheduleOrder, Update};
+use bevy_ecs::{
+    component::ComponentId,
+    query::Access,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    system::Resource,
+    world::World,
+};
+use bevy_utils::tracing::warn;
+
+use crate::{
+    tick::{Tick, TickAnchors},
+    HandlerRegistry,
+};
+
+/// [`Resource`] which reports hidden one-frame-lag hazards between [`Tick`] handlers and the
+/// regular schedule.
+///
+/// A hazard is a [`Tick`] handler that writes data which a scheduled system running earlier in the
+/// same frame (or unordered with the tick dispatch) already processed. That system only observes
+/// the handler's writes on the next frame. Handlers with a [`TickAnchor`](crate::tick::TickAnchor)
+/// are ordered explicitly, so they aren't reported.
+///
+/// The [`EventBusPlugin`](crate::EventBusPlugin) computes this report at startup and logs every
+/// hazard as a warning.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TickLagReport {
+    /// The hazards found, in schedule order.
+    pub hazards: Vec<TickLagHazard>,
+}
+
+/// A single hazard in a [`TickLagReport`].
+#[derive(Debug, Clone)]
+pub struct TickLagHazard {
+    /// The name of the [`Tick`] handler.
+    pub handler: Cow<'static, str>,
+    /// The name of the scheduled system.
+    pub system: Cow<'static, str>,
+    /// The schedule the system belongs to.
+    pub schedule: InternedScheduleLabel,
+    /// How the system is ordered relative to the tick dispatch.
+    pub ordering: TickLagOrdering,
+    /// The names of the components and resources written by the handler and accessed by the system.
+    pub conflicts: Vec<String>,
+}
+
+/// How a scheduled system in a [`TickLagHazard`] is ordered relative to the tick dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickLagOrdering {
+    /// The system's schedule runs before [`Update`], where [`Tick`] is posted.
+    Before,
+    /// The system runs in [`Update`], and may run before or after [`Tick`] is posted.
+    Ambiguous,
+}
+
+impl TickLagReport {
+    /// Computes the report for all [`Tick`] handlers and the schedules in the [`MainScheduleOrder`]
+    /// up to and including [`Update`].
+    ///
+    /// Schedules are initialized as needed in order to inspect their systems.
+    pub fn analyze(world: &mut World) -> Self {
+        let Some(registry) = world.get_resource::<HandlerRegistry<Tick>>() else {
+            return Self::default();
+        };
+        // Anchored handlers are ordered relative to the schedule explicitly.
+        let handlers = registry
+            .ids()
+            .filter_map(|id| registry.get(id))
+            .filter(|config| {
+                !config
+                    .sets
+                    .iter()
+                    .any(|&set| TickAnchors::is_anchored(world, set))
+            })
+            .filter_map(|config| {
+                let handler = config.handler.lock();
+                (!handler.is_exclusive())
+                    .then(|| (handler.name(), handler.component_access().clone()))
+            })
+            .collect::<Vec<_>>();
+
+        // The order is taken out of the world while the main schedule runs, so fall back to the
+        // default order when analyzing from within a system.
+        let order = world.get_resource::<MainScheduleOrder>().map_or_else(
+            || MainScheduleOrder::default().labels,
+            |order| order.labels.clone(),
+        );
+        let mut labels = Vec::new();
+        for label in order {
+            if label == Update.intern() {
+                labels.push((label, TickLagOrdering::Ambiguous));
+                break;
+            }
+            labels.push((label, TickLagOrdering::Before));
+        }
+
+        let mut report = Self::default();
+        for (label, ordering) in labels {
+            let _ = world.try_schedule_scope(label, |world, schedule| {
+                if schedule.initialize(world).is_err() {
+                    return;
+                }
+                let Ok(systems) = schedule.systems() else {
+                    return;
+                };
+
+                for (_, system) in systems {
+                    if system.is_exclusive() {
+                        continue;
+                    }
+
+                    for (handler, access) in &handlers {
+                        let conflicts = written_and_accessed(access, system.component_access());
+                        if conflicts.is_empty() {
+                            continue;
+                        }
+
+                        report.hazards.push(TickLagHazard {
+                            handler: handler.clone(),
+                            system: system.name(),
+                            schedule: label,
+                            ordering,
+                            conflicts: conflicts
+                                .into_iter()
+                                .map(|id| {
+                                    world
+                                        .components()
+                                        .get_name(id)
+                                        .map_or_else(|| format!("{id:?}"), ToString::to_string)
+                                })
+                                .collect(),
+                        });
+                    }
+                }
+            });
+        }
+
+        report
+    }
+
+    /// Logs every hazard in the report as a warning.
+    pub fn warn(&self) {
+        for hazard in &self.hazards {
+            let when = match hazard.ordering {
+                TickLagOrdering::Before => "runs before",
+                TickLagOrdering::Ambiguous => "is unordered with",
+            };
+            warn!(
+                "Tick handler `{}` writes [{}], but system `{}` in {:?} {} the tick dispatch and \
+                 will only observe these writes on the next frame",
+                hazard.handler,
+                hazard.conflicts.join(", "),
+                hazard.system,
+                hazard.schedule,
+                when,
+            );
+        }
+    }
+}
+
+/// Returns the components and resources written by `writer` and read or written by `other`.
+fn written_and_accessed(
+    writer: &Access<ComponentId>,
+    other: &Access<ComponentId>,
+) -> Vec<ComponentId> {
+    let (components, inverted) = other.component_reads_and_writes();
+    let mut conflicts = Vec::new();
+    if !inverted {
+        conflicts.extend(components.filter(|&id| writer.has_component_write(id)));
+    }
+    conflicts.extend(
+        other
+            .resource_reads_and_writes()
+            .filter(|&id| writer.has_resource_write(id)),
+    );
+    conflicts
+}
+
+/// Startup system that computes the [`TickLagReport`], logs its hazards, and inserts it into the
+/// world.
+pub fn report_tick_lag(world: &mut World) {
+    let report = TickLagReport::analyze(world);
+    report.warn();
+    world.insert_resource(report);
+}
@@ -0,0 +1,142 @@
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::Resource,
+    world::World,
+};
+use bevy_utils::tracing::warn;
+use parking_lot::Mutex;
+
+use crate::{BusStats, EventBusStats, EventBusSystems, WorldEventBus};
+
+/// [`Plugin`] which serves the [`EventBusStats`] as Prometheus metrics over HTTP, with the event
+/// type as the `event` label, see [`BusStats::to_prometheus`].
+///
+/// The stats are scraped from the world every [`interval`](Self::interval) in [`Last`], and
+/// accumulated into cumulative counters. A background thread answers every request on
+/// [`address`](Self::address) with the latest metrics, for the rest of the process' lifetime.
+///
+/// Requires the `prometheus` feature. Metrics can be forwarded to OpenTelemetry collectors with
+/// their Prometheus receiver.
+pub struct PrometheusExporterPlugin {
+    /// The address to serve the metrics on.
+    pub address: SocketAddr,
+    /// How often the stats are scraped from the world.
+    pub interval: Duration,
+}
+
+impl Default for PrometheusExporterPlugin {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from(([0, 0, 0, 0], 9464)),
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// [`SystemSet`] of the systems added by the [`PrometheusExporterPlugin`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrometheusExporterSystems;
+
+impl Plugin for PrometheusExporterPlugin {
+    fn build(&self, app: &mut App) {
+        let listener = match TcpListener::bind(self.address) {
+            Ok(listener) => listener,
+            Err(error) => {
+                warn!(
+                    "Failed to serve event bus metrics on {}: {error}",
+                    self.address
+                );
+                return;
+            }
+        };
+        let exporter = PrometheusExporter {
+            address: listener.local_addr().unwrap_or(self.address),
+            interval: self.interval,
+            totals: BusStats::default(),
+            last_scrape: None,
+            metrics: Arc::default(),
+        };
+        let metrics = exporter.metrics.clone();
+        thread::spawn(move || serve(&listener, &metrics));
+
+        app.init_resource::<EventBusStats>()
+            .insert_resource(exporter)
+            .add_systems(
+                Last,
+                export_metrics
+                    .in_set(PrometheusExporterSystems)
+                    .after(EventBusSystems::Flush),
+            );
+    }
+}
+
+/// [`Resource`] which holds the cumulative metrics served by the [`PrometheusExporterPlugin`].
+#[derive(Resource)]
+pub struct PrometheusExporter {
+    address: SocketAddr,
+    interval: Duration,
+    totals: BusStats,
+    last_scrape: Option<Instant>,
+    metrics: Arc<Mutex<String>>,
+}
+
+impl PrometheusExporter {
+    /// Returns the address the metrics are served on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns the cumulative stats since the exporter started.
+    pub fn totals(&self) -> &BusStats {
+        &self.totals
+    }
+}
+
+/// Exclusive system that scrapes the [`EventBusStats`] for the [`PrometheusExporter`], once every
+/// interval.
+pub fn export_metrics(world: &mut World) {
+    let Some(exporter) = world.get_resource::<PrometheusExporter>() else {
+        return;
+    };
+    if exporter
+        .last_scrape
+        .is_some_and(|last| last.elapsed() < exporter.interval)
+    {
+        return;
+    }
+
+    let stats = world.event_bus_stats();
+    let mut exporter = world.resource_mut::<PrometheusExporter>();
+    exporter.last_scrape = Some(Instant::now());
+    exporter.totals.accumulate(stats);
+    *exporter.metrics.lock() = exporter.totals.to_prometheus();
+}
+
+/// Answers every request on the listener with the latest metrics.
+fn serve(listener: &TcpListener, metrics: &Mutex<String>) {
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+        // The request is ignored, but read so the client doesn't see a reset connection.
+        let _ = stream.set_read_timeout(Some(Duration::from_millis(100)));
+        let _ = stream.read(&mut [0; 1024]);
+
+        let body = metrics.lock().clone();
+        let _ = write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        );
+    }
+}
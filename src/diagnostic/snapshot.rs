@@ -0,0 +1,171 @@
+use std::{any::type_name, env, fmt::Write, fs, path::Path};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{Event, EventContext};
+
+/// [`Resource`] which records the ordered trace of dispatches, for comparing it against a
+/// checked-in snapshot in tests.
+///
+/// The trace lists every post by its event type, the handlers that ran for it by name, and whether
+/// it ended up cancelled. Posts made by a handler are nested below it:
+///
+/// ```text
+/// my_game::Attack
+///   my_game::validate_attack
+///   my_game::apply_damage
+///     my_game::Died
+///       my_game::drop_loot
+///   cancelled
+/// ```
+///
+/// Record a scenario with [`DispatchTrace::record`], then compare it with
+/// [`DispatchTrace::assert_snapshot`]:
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::{prelude::*, DispatchTrace};
+/// # struct Attack;
+/// # impl BusEvent for Attack {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = bool;
+/// #     type Audience = ();
+/// # }
+/// let mut world = World::new();
+/// world.add_handler(|mut event: Receive<Attack>| event.cancel());
+/// let trace = DispatchTrace::record(&mut world, |world| {
+///     world.post(Attack);
+/// });
+/// assert_eq!(trace.lines().len(), 3);
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DispatchTrace {
+    lines: Vec<String>,
+}
+
+/// Environment variable which makes [`DispatchTrace::assert_snapshot`] write snapshots instead of
+/// comparing against them, when set to anything but `0`.
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+impl DispatchTrace {
+    /// Records the trace of the dispatches made while running the scenario.
+    pub fn record(world: &mut World, scenario: impl FnOnce(&mut World)) -> Self {
+        world.insert_resource(Self::default());
+        scenario(world);
+        world.remove_resource::<Self>().unwrap_or_default()
+    }
+
+    /// Returns the lines of the trace, in order.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the trace serialized into the snapshot format, with a trailing newline.
+    pub fn to_snapshot(&self) -> String {
+        let mut snapshot = String::new();
+        for line in &self.lines {
+            let _ = writeln!(snapshot, "{line}");
+        }
+        snapshot
+    }
+
+    /// Compares the trace against the snapshot file, panicking with a line diff if they differ, or
+    /// if the file doesn't exist.
+    ///
+    /// The snapshot is written instead if the [`UPDATE_SNAPSHOTS`] environment variable is set, so
+    /// that a missing snapshot fails in CI rather than being silently created.
+    pub fn assert_snapshot(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.to_snapshot();
+        if env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| value != "0") {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            fs::write(path, actual)
+                .unwrap_or_else(|error| panic!("Failed to write {}: {error}", path.display()));
+            return;
+        }
+        let expected = fs::read_to_string(path).unwrap_or_else(|error| {
+            panic!(
+                "Failed to read the snapshot {}: {error}, set {UPDATE_SNAPSHOTS}=1 to create it",
+                path.display()
+            )
+        });
+        if expected != actual {
+            panic!(
+                "Dispatch trace doesn't match the snapshot {}, set {UPDATE_SNAPSHOTS}=1 to \
+                 update it:\n{}",
+                path.display(),
+                diff(&expected, &actual)
+            );
+        }
+    }
+
+    /// Records a post of [`Event`] `E` starting its dispatch, if the world records a trace.
+    pub(crate) fn post<E: Event>(world: &mut World) {
+        Self::push(world, 0, type_name::<E>());
+    }
+
+    /// Records a handler about to run, if the world records a trace.
+    pub(crate) fn handler(world: &mut World, name: &str) {
+        Self::push(world, 1, name);
+    }
+
+    /// Records a post ending up cancelled, if the world records a trace.
+    pub(crate) fn cancelled(world: &mut World) {
+        Self::push(world, 1, "cancelled");
+    }
+
+    fn push(world: &mut World, indent: usize, line: &str) {
+        if !world.contains_resource::<Self>() {
+            return;
+        }
+        let depth = world
+            .get_resource::<EventContext>()
+            .and_then(EventContext::current)
+            .map_or(0, |meta| meta.depth);
+        let indent = "  ".repeat(depth * 2 + indent);
+        world
+            .resource_mut::<Self>()
+            .lines
+            .push(format!("{indent}{line}"));
+    }
+}
+
+/// Returns a line diff between the expected and actual text, with removed lines prefixed by `-`
+/// and added lines prefixed by `+`.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    // Lengths of the longest common subsequences of the remaining lines.
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected[i] == actual[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            let _ = writeln!(diff, " {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if j < actual.len()
+            && (i == expected.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+        {
+            let _ = writeln!(diff, "+{}", actual[j]);
+            j += 1;
+        } else {
+            let _ = writeln!(diff, "-{}", expected[i]);
+            i += 1;
+        }
+    }
+    diff
+}
@@ -0,0 +1,278 @@
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{Event, EventQueue};
+
+/// [`Resource`] which counts posts, cancellations, and handler runs per [`Event`] type, for
+/// periodic scraping with [`WorldEventBus::event_bus_stats`](crate::WorldEventBus::event_bus_stats).
+///
+/// Collection is opt-in: insert this resource into the world, or take the first snapshot, to start
+/// counting. Unlike [`EventCausality`](crate::EventCausality), only counters are kept, so the
+/// overhead stays constant no matter how many events are posted.
+#[derive(Resource, Debug)]
+pub struct EventBusStats {
+    types: HashMap<TypeId, EventTypeStats>,
+    since: Instant,
+}
+
+impl Default for EventBusStats {
+    fn default() -> Self {
+        Self {
+            types: HashMap::new(),
+            since: Instant::now(),
+        }
+    }
+}
+
+/// Counters for a single [`Event`] type in a [`BusStats`] snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventTypeStats {
+    /// The type name of the event.
+    pub name: &'static str,
+    /// The number of posts dispatched.
+    pub posts: u64,
+    /// The number of posts that ended up cancelled.
+    pub cancellations: u64,
+    /// The number of posts dropped by a [`TargetThrottle`](crate::TargetThrottle).
+    pub throttled: u64,
+    /// The number of handler runs.
+    pub handler_runs: u64,
+    /// The total time spent running handlers.
+    pub handler_time: Duration,
+    /// The number of posts pending in the [`EventQueue`] when the snapshot was taken.
+    pub queue_depth: usize,
+}
+
+impl EventTypeStats {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            posts: 0,
+            cancellations: 0,
+            throttled: 0,
+            handler_runs: 0,
+            handler_time: Duration::ZERO,
+            queue_depth: 0,
+        }
+    }
+
+    /// Returns the average time a handler run took, or zero if no handlers ran.
+    pub fn avg_handler_time(&self) -> Duration {
+        u32::try_from(self.handler_runs)
+            .ok()
+            .and_then(|runs| self.handler_time.checked_div(runs))
+            .unwrap_or_default()
+    }
+}
+
+/// Snapshot of the [`EventBusStats`] counters since the previous snapshot, see
+/// [`WorldEventBus::event_bus_stats`](crate::WorldEventBus::event_bus_stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BusStats {
+    /// The time covered by the snapshot.
+    pub elapsed: Duration,
+    /// The counters of every event type that was posted or queued, sorted by name.
+    pub events: Vec<EventTypeStats>,
+}
+
+impl BusStats {
+    /// Returns the counters of [`Event`] `E`, if it was posted or queued.
+    pub fn get<E: Event>(&self) -> Option<&EventTypeStats> {
+        self.events
+            .iter()
+            .find(|stats| stats.name == type_name::<E>())
+    }
+
+    /// Exports the snapshot as JSON.
+    ///
+    /// The output is an object with the `elapsed` seconds and an `events` array with the counters
+    /// of every event type. Times are in seconds.
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"elapsed\":{},\"events\":[", self.elapsed.as_secs_f64());
+        for (index, stats) in self.events.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"event\":\"{}\",\"posts\":{},\"cancellations\":{},\"throttled\":{},\
+                 \"handler_runs\":{},\"handler_time\":{},\"avg_handler_time\":{},\
+                 \"queue_depth\":{}}}",
+                stats.name.replace('\\', "\\\\").replace('"', "\\\""),
+                stats.posts,
+                stats.cancellations,
+                stats.throttled,
+                stats.handler_runs,
+                stats.handler_time.as_secs_f64(),
+                stats.avg_handler_time().as_secs_f64(),
+                stats.queue_depth,
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl BusStats {
+    /// Exports the snapshot in the Prometheus text exposition format, with the event type as the
+    /// `event` label of every metric.
+    ///
+    /// The counters are exported as is, so for cumulative Prometheus counters the snapshots need to
+    /// be [accumulated](BusStats::accumulate) first.
+    pub fn to_prometheus(&self) -> String {
+        type Metric = (
+            &'static str,
+            &'static str,
+            &'static str,
+            fn(&EventTypeStats) -> f64,
+        );
+        const METRICS: [Metric; 6] = [
+            (
+                "eventbus_posts_total",
+                "counter",
+                "Posts dispatched.",
+                |stats| stats.posts as f64,
+            ),
+            (
+                "eventbus_cancellations_total",
+                "counter",
+                "Posts that ended up cancelled.",
+                |stats| stats.cancellations as f64,
+            ),
+            (
+                "eventbus_throttled_total",
+                "counter",
+                "Posts dropped by a throttle.",
+                |stats| stats.throttled as f64,
+            ),
+            (
+                "eventbus_handler_runs_total",
+                "counter",
+                "Handler runs.",
+                |stats| stats.handler_runs as f64,
+            ),
+            (
+                "eventbus_handler_seconds_total",
+                "counter",
+                "Time spent running handlers.",
+                |stats| stats.handler_time.as_secs_f64(),
+            ),
+            (
+                "eventbus_queue_depth",
+                "gauge",
+                "Posts pending in the event queue.",
+                |stats| stats.queue_depth as f64,
+            ),
+        ];
+
+        let mut text = String::new();
+        for (name, kind, help, value) in METRICS {
+            let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for stats in &self.events {
+                let event = stats.name.replace('\\', "\\\\").replace('"', "\\\"");
+                let _ = writeln!(text, "{name}{{event=\"{event}\"}} {}", value(stats));
+            }
+        }
+        text
+    }
+
+    /// Adds the counters of a later snapshot to this one, and takes over its queue depths.
+    pub fn accumulate(&mut self, later: BusStats) {
+        self.elapsed += later.elapsed;
+        for stats in &mut self.events {
+            stats.queue_depth = 0;
+        }
+        for later in later.events {
+            match self
+                .events
+                .iter_mut()
+                .find(|stats| stats.name == later.name)
+            {
+                Some(stats) => {
+                    stats.posts += later.posts;
+                    stats.cancellations += later.cancellations;
+                    stats.throttled += later.throttled;
+                    stats.handler_runs += later.handler_runs;
+                    stats.handler_time += later.handler_time;
+                    stats.queue_depth = later.queue_depth;
+                }
+                None => self.events.push(later),
+            }
+        }
+        self.events.sort_by_key(|stats| stats.name);
+    }
+}
+
+impl EventBusStats {
+    /// Returns the counters since the previous snapshot, and resets them.
+    pub(crate) fn snapshot(world: &mut World) -> BusStats {
+        let lanes = world
+            .get_resource::<EventQueue>()
+            .map(EventQueue::lane_stats)
+            .unwrap_or_default();
+        let mut stats = world.get_resource_or_insert_with(Self::default);
+        let elapsed = stats.since.elapsed();
+        stats.since = Instant::now();
+
+        let mut events = stats
+            .types
+            .drain()
+            .map(|(_, stats)| stats)
+            .collect::<Vec<_>>();
+        for lane in lanes.into_iter().filter(|lane| lane.pending > 0) {
+            match events.iter_mut().find(|stats| stats.name == lane.name) {
+                Some(stats) => stats.queue_depth = lane.pending,
+                None => events.push(EventTypeStats {
+                    queue_depth: lane.pending,
+                    ..EventTypeStats::new(lane.name)
+                }),
+            }
+        }
+        events.sort_by_key(|stats| stats.name);
+
+        BusStats { elapsed, events }
+    }
+
+    /// Returns `true` if the world collects stats.
+    pub(crate) fn is_enabled(world: &World) -> bool {
+        world.contains_resource::<Self>()
+    }
+
+    /// Counts a post of [`Event`] `E` along with its handler runs, if the world collects stats.
+    pub(crate) fn record<E: Event>(
+        world: &mut World,
+        cancelled: bool,
+        handler_runs: u64,
+        handler_time: Duration,
+    ) {
+        let Some(mut stats) = world.get_resource_mut::<Self>() else {
+            return;
+        };
+        let stats = stats
+            .types
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| EventTypeStats::new(type_name::<E>()));
+        stats.posts += 1;
+        stats.cancellations += u64::from(cancelled);
+        stats.handler_runs += handler_runs;
+        stats.handler_time += handler_time;
+    }
+
+    /// Counts a post of [`Event`] `E` dropped by its throttle, if the world collects stats.
+    pub(crate) fn record_throttled<E: Event>(world: &mut World) {
+        let Some(mut stats) = world.get_resource_mut::<Self>() else {
+            return;
+        };
+        stats
+            .types
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| EventTypeStats::new(type_name::<E>()))
+            .throttled += 1;
+    }
+}
@@ -0,0 +1,110 @@
+use std::{any::type_name, borrow::Cow, ops::RangeInclusive};
+
+use bevy_ecs::{entity::Entity, system::Resource, world::World};
+
+use crate::{Audience, Event, EventCatalog};
+
+/// [`Resource`] which narrows the trace-level logs of the event bus down to the posts and handlers
+/// under investigation, without recompiling.
+///
+/// While this resource exists, every post that passes its filters is logged at the trace level,
+/// along with every handler it runs whose priority is within [`BusTraceConfig::priorities`]. Empty
+/// filters don't restrict anything, so the default configuration traces everything:
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::BusTraceConfig;
+/// let mut world = World::new();
+/// let player = world.spawn_empty().id();
+/// world.insert_resource(BusTraceConfig {
+///     events: vec!["my_game::combat::*".into()],
+///     priorities: Some(0..=i32::MAX),
+///     entities: vec![player],
+///     ..Default::default()
+/// });
+/// ```
+///
+/// A post is traced if its type name matches any of the [`events`](BusTraceConfig::events)
+/// patterns, it has any of the [`tags`](BusTraceConfig::tags), and its audience includes any of the
+/// [`entities`](BusTraceConfig::entities).
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BusTraceConfig {
+    /// Patterns matched against the full type name of the event, where `*` matches any sequence of
+    /// characters and `?` matches a single character.
+    pub events: Vec<String>,
+    /// Tags of the event, as registered with [`EventInfo::tag`](crate::EventInfo::tag).
+    pub tags: Vec<Cow<'static, str>>,
+    /// The band of priorities of the handlers to trace, or `None` to trace all of them.
+    pub priorities: Option<RangeInclusive<i32>>,
+    /// Entities included in the audience of the event.
+    pub entities: Vec<Entity>,
+}
+
+impl BusTraceConfig {
+    /// Returns `true` if a post of [`Event`] `E` to the audience passes the filters.
+    pub fn traces<E: Event>(&self, catalog: Option<&EventCatalog>, audience: &E::Audience) -> bool {
+        let name = type_name::<E>();
+        if !self.events.is_empty() && !self.events.iter().any(|glob| glob_match(glob, name)) {
+            return false;
+        }
+        if !self.tags.is_empty() {
+            let tags = catalog
+                .and_then(EventCatalog::get::<E>)
+                .map_or(&[][..], |info| &info.tags[..]);
+            if !tags.iter().any(|tag| self.tags.contains(tag)) {
+                return false;
+            }
+        }
+        self.entities.is_empty()
+            || self
+                .entities
+                .iter()
+                .any(|&entity| audience.includes(entity))
+    }
+
+    /// Returns `true` if handlers with the priority pass the filters.
+    pub fn traces_priority(&self, priority: i32) -> bool {
+        self.priorities
+            .as_ref()
+            .is_none_or(|priorities| priorities.contains(&priority))
+    }
+
+    /// Returns the configuration if the world has one and a post of [`Event`] `E` to the audience
+    /// passes its filters.
+    pub(crate) fn get<'w, E: Event>(world: &'w World, audience: &E::Audience) -> Option<&'w Self> {
+        let config = world.get_resource::<Self>()?;
+        config
+            .traces::<E>(world.get_resource::<EventCatalog>(), audience)
+            .then_some(config)
+    }
+}
+
+/// Returns `true` if the text matches the pattern, where `*` matches any sequence of characters and
+/// `?` matches a single character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
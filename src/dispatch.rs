@@ -0,0 +1,465 @@
+use std::{
+    borrow::{Borrow, Cow},
+    fmt::{self, Debug},
+    hash::Hash,
+    sync::Arc,
+    time::Duration,
+};
+
+use bevy_ecs::world::World;
+
+use crate::{
+    DeliveryTracker, DispatchTrace, Event, EventBudgets, EventBusSettings, EventBusStats,
+    Mutability, MutabilityRef,
+};
+
+mod alias;
+mod batch;
+mod blueprint;
+mod context;
+mod defer;
+mod deterministic;
+mod hooks;
+mod inbox;
+mod input;
+mod keyed;
+mod pair;
+mod panic;
+mod param;
+mod pause;
+mod post;
+mod queue;
+mod registry;
+mod rollback;
+mod scoped;
+mod sequence;
+mod storage;
+mod strategy;
+mod system;
+mod thread;
+mod throttle;
+mod world;
+
+pub use alias::*;
+pub use batch::*;
+pub use blueprint::*;
+pub use context::*;
+pub use defer::*;
+pub use deterministic::*;
+pub use inbox::*;
+pub use input::*;
+pub use keyed::*;
+pub use pair::*;
+pub use param::*;
+pub use pause::*;
+pub use post::*;
+pub use queue::*;
+pub use registry::*;
+pub use rollback::*;
+pub use scoped::*;
+pub use sequence::*;
+pub use storage::*;
+pub use strategy::*;
+pub use system::*;
+pub use thread::*;
+pub use throttle::*;
+pub use world::*;
+
+pub(crate) use panic::report_handler_panic;
+
+/// Runs all handlers for [`Event`] `E` whose run conditions pass in order, until the event is
+/// cancelled, followed by [`Event::after_dispatch`]. The event is recorded into its
+/// [`EventHistory`](crate::EventHistory) first, if enabled, and its [`EventMeta`] is tracked by the
+/// [`EventContext`] while it is dispatched. The [`PostOptions`] only apply to this post.
+///
+/// `inspect` is called after each handler with the state of the event it left behind, and the
+/// handler that cancelled the event so far, if any. If `E` is an alias, the event is redirected
+/// to the aliased type instead and `inspect` is never called.
+///
+/// Main-thread-only handlers are skipped with a warning when dispatching from any other thread
+/// than the [`MainThread`], and side-effect-only handlers are skipped while [`Resimulating`].
+/// Handlers whose required resources don't exist are skipped or removed.
+///
+/// The post must already have been counted with the throttle of `E`, see
+/// [`HandlerRegistry::throttled`], before [`Event::before_dispatch`] was called.
+pub(crate) fn dispatch<E: Event>(
+    world: &mut World,
+    mut event: MutabilityRef<'_, E>,
+    audience: &E::Audience,
+    options: &PostOptions,
+    inspect: impl FnMut(&HandlerEntry<E>, &E, Option<&CancelledBy<E>>),
+) -> E::Cancellation {
+    EventContext::enter::<E>(world, options);
+    let cancellation = run_handlers(
+        world,
+        E::Mutability::reborrow(&mut event),
+        audience,
+        options,
+        inspect,
+    );
+    event.borrow().after_dispatch(&cancellation, world);
+    EventContext::exit(world);
+    cancellation
+}
+
+/// Runs the handlers for [`Event`] `E` subscribed to the key in order like [`dispatch`], see
+/// [`KeyedHandlers`]. The event isn't recorded into its [`EventHistory`](crate::EventHistory),
+/// but is collected into the [`EventInbox`] of its target like any other post.
+pub(crate) fn dispatch_keyed<E: Event, K: Eq + Hash + Clone + Send + Sync + 'static>(
+    world: &mut World,
+    mut event: MutabilityRef<'_, E>,
+    key: &K,
+    audience: &E::Audience,
+    inspect: impl FnMut(&HandlerEntry<E>, &E, Option<&CancelledBy<E>>),
+) -> E::Cancellation {
+    EventBudgets::count::<E>(world);
+    let options = PostOptions::default();
+    EventContext::enter::<E>(world, &options);
+    let inbox = world
+        .get_resource::<HandlerRegistry<E>>()
+        .and_then(|registry| registry.hooks().inbox);
+    if inbox.is_some_and(|deliver| !deliver(world, event.borrow(), audience)) {
+        record_unhandled::<E>(world);
+        let cancellation = E::Cancellation::default();
+        event.borrow().after_dispatch(&cancellation, world);
+        EventContext::exit(world);
+        return cancellation;
+    }
+    let handlers = world
+        .get_resource::<KeyedHandlers<E, K>>()
+        .and_then(|keyed| keyed.get(key))
+        .map(HandlerRegistry::snapshot)
+        .unwrap_or_default();
+    let key = key.clone();
+    let source = HandlerSource::Keyed(Arc::new(move |world: &mut World| {
+        world
+            .get_resource_mut::<KeyedHandlers<E, K>>()?
+            .into_inner()
+            .get_mut(&key)
+    }));
+    let cancellation = run_entries(
+        world,
+        &handlers,
+        source,
+        E::Mutability::reborrow(&mut event),
+        audience,
+        &options,
+        inspect,
+    );
+    event.borrow().after_dispatch(&cancellation, world);
+    EventContext::exit(world);
+    cancellation
+}
+
+/// Dispatches a batch of posts of [`Event`] `E` in order like [`dispatch`], but resolving the
+/// handlers only once for the whole batch. `on_dispatched` is called with the final cancellation
+/// state of each post.
+pub(crate) fn dispatch_batch<E: Event>(
+    world: &mut World,
+    posts: impl IntoIterator<Item = (E, E::Audience)>,
+    mut on_dispatched: impl FnMut(E::Cancellation),
+) {
+    remove_dropped_handlers::<E>(world);
+    initialize_reset_handlers::<E>(world);
+    let shared = world
+        .get_resource::<HandlerRegistry<E>>()
+        .filter(|registry| !registry.is_aliased())
+        .map(|registry| (registry.hooks().clone(), registry.snapshot()));
+    let Some((hooks, handlers)) = shared else {
+        for (mut event, audience) in posts {
+            if HandlerRegistry::<E>::throttled(world, &audience) {
+                on_dispatched(E::Cancellation::default());
+                continue;
+            }
+            event.before_dispatch(world);
+            let event = E::Mutability::to_ref(&mut event);
+            let options = PostOptions::default();
+            on_dispatched(dispatch::<E>(
+                world,
+                event,
+                &audience,
+                &options,
+                |_, _, _| {},
+            ));
+        }
+        return;
+    };
+
+    for (mut event, audience) in posts {
+        if hooks.throttle.is_some_and(|admit| !admit(world, &audience)) {
+            EventBusStats::record_throttled::<E>(world);
+            on_dispatched(E::Cancellation::default());
+            continue;
+        }
+        EventBudgets::count::<E>(world);
+        let audience = hooks
+            .normalizer
+            .and_then(|normalizer| normalizer.apply(world, &audience))
+            .unwrap_or(audience);
+        event.before_dispatch(world);
+        let options = PostOptions::default();
+        EventContext::enter::<E>(world, &options);
+        let mut event = E::Mutability::to_ref(&mut event);
+        if let Some(record) = hooks.recorder {
+            record(world, event.borrow(), &audience);
+        }
+        if let Some(hash) = hooks.hasher {
+            hash(world, event.borrow(), &audience);
+        }
+        if hooks
+            .inbox
+            .is_some_and(|deliver| !deliver(world, event.borrow(), &audience))
+        {
+            record_unhandled::<E>(world);
+            let cancellation = E::Cancellation::default();
+            event.borrow().after_dispatch(&cancellation, world);
+            EventContext::exit(world);
+            on_dispatched(cancellation);
+            continue;
+        }
+        DeliveryTracker::offer(world, &handlers);
+        let cancellation = run_entries(
+            world,
+            &handlers,
+            HandlerSource::Registry,
+            E::Mutability::reborrow(&mut event),
+            &audience,
+            &options,
+            |_, _, _| {},
+        );
+        event.borrow().after_dispatch(&cancellation, world);
+        EventContext::exit(world);
+        on_dispatched(cancellation);
+    }
+}
+
+fn run_handlers<E: Event>(
+    world: &mut World,
+    event: MutabilityRef<'_, E>,
+    audience: &E::Audience,
+    options: &PostOptions,
+    mut inspect: impl FnMut(&HandlerEntry<E>, &E, Option<&CancelledBy<E>>),
+) -> E::Cancellation {
+    remove_dropped_handlers::<E>(world);
+    initialize_reset_handlers::<E>(world);
+    EventBudgets::count::<E>(world);
+    let Some(registry) = world.get_resource::<HandlerRegistry<E>>() else {
+        record_unhandled::<E>(world);
+        return E::Cancellation::default();
+    };
+    let normalized = registry
+        .hooks()
+        .normalizer
+        .and_then(|normalizer| normalizer.apply(world, audience));
+    let audience = normalized.as_ref().unwrap_or(audience);
+    if let Some(record) = registry.hooks().recorder {
+        record(world, event.borrow(), audience);
+    }
+    let registry = world.resource::<HandlerRegistry<E>>();
+    if let Some(hash) = registry.hooks().hasher {
+        hash(world, event.borrow(), audience);
+    }
+
+    let registry = world.resource::<HandlerRegistry<E>>();
+    if registry
+        .hooks()
+        .inbox
+        .is_some_and(|deliver| !deliver(world, event.borrow(), audience))
+    {
+        record_unhandled::<E>(world);
+        return E::Cancellation::default();
+    }
+    let registry = world.resource::<HandlerRegistry<E>>();
+    if let Some(alias) = registry.hooks().alias.clone() {
+        return alias.dispatch(world, event, audience, options, &mut inspect);
+    }
+
+    run_registered(world, event, audience, options, inspect)
+}
+
+/// Records a post of [`Event`] `E` that no handler runs for into the [`EventBusStats`] and the
+/// [`DispatchTrace`], e.g. because it was collected into an exclusive [`EventInbox`].
+fn record_unhandled<E: Event>(world: &mut World) {
+    EventBusStats::record::<E>(world, false, 0, Duration::ZERO);
+    DispatchTrace::post::<E>(world);
+}
+
+/// Runs the handlers in the [`HandlerRegistry`] of [`Event`] `E`, offering the post to the
+/// [`DeliveryTracker`], once the post was admitted, recorded and not redirected.
+fn run_registered<E: Event>(
+    world: &mut World,
+    event: MutabilityRef<'_, E>,
+    audience: &E::Audience,
+    options: &PostOptions,
+    inspect: impl FnMut(&HandlerEntry<E>, &E, Option<&CancelledBy<E>>),
+) -> E::Cancellation {
+    let handlers = world.resource::<HandlerRegistry<E>>().snapshot();
+    DeliveryTracker::offer(world, &handlers);
+    run_entries(
+        world,
+        &handlers,
+        HandlerSource::Registry,
+        event,
+        audience,
+        options,
+        inspect,
+    )
+}
+
+/// Runs a snapshot of handlers for a post of [`Event`] `E` outside of its regular dispatch, e.g. to
+/// catch a handler up on the posts it missed, see [`DeliveryTracker`]. The post isn't recorded,
+/// and [`Event::after_dispatch`] isn't called.
+pub(crate) fn dispatch_entries<E: Event>(
+    world: &mut World,
+    handlers: &[HandlerEntry<E>],
+    event: MutabilityRef<'_, E>,
+    audience: &E::Audience,
+    options: &PostOptions,
+) -> E::Cancellation {
+    EventContext::enter::<E>(world, options);
+    let cancellation = run_entries(
+        world,
+        handlers,
+        HandlerSource::Registry,
+        event,
+        audience,
+        options,
+        |_, _, _| {},
+    );
+    EventContext::exit(world);
+    cancellation
+}
+
+/// Initializes the handlers for [`Event`] `E` whose state was reset since the last dispatch.
+pub(crate) fn initialize_reset_handlers<E: Event>(world: &mut World) {
+    if !world
+        .get_resource::<HandlerRegistry<E>>()
+        .is_some_and(HandlerRegistry::has_uninitialized)
+    {
+        return;
+    }
+
+    let handlers = world
+        .resource_mut::<HandlerRegistry<E>>()
+        .take_uninitialized();
+    for handler in handlers {
+        handler.lock().initialize(world);
+    }
+}
+
+/// Runs the snapshot of handlers for [`Event`] `E`, taken from the source, with its
+/// [`DispatchStrategy`], by default in order until the event is cancelled.
+fn run_entries<E: Event>(
+    world: &mut World,
+    handlers: &[HandlerEntry<E>],
+    source: HandlerSource<E>,
+    mut event: MutabilityRef<'_, E>,
+    audience: &E::Audience,
+    options: &PostOptions,
+    mut inspect: impl FnMut(&HandlerEntry<E>, &E, Option<&CancelledBy<E>>),
+) -> E::Cancellation {
+    let strategy = world
+        .get_resource::<HandlerRegistry<E>>()
+        .and_then(|registry| registry.hooks().strategy.clone());
+    let shuffled;
+    let handlers = match shuffle_seed(world) {
+        Some(seed) => {
+            let mut handlers = handlers.to_vec();
+            if let Some(registry) = source.registry(world) {
+                registry.shuffle(&mut handlers, seed);
+            }
+            shuffled = handlers;
+            &shuffled
+        }
+        None => handlers,
+    };
+    let mut dispatcher = Dispatcher::new(
+        world,
+        handlers,
+        source,
+        E::Mutability::reborrow(&mut event),
+        audience,
+        options,
+        &mut inspect,
+    );
+    match strategy {
+        Some(strategy) => strategy.dispatch(&mut dispatcher),
+        None => Sequential.dispatch(&mut dispatcher),
+    }
+    dispatcher.finish()
+}
+
+/// Returns the seed with which handlers of the same priority are shuffled for the current post,
+/// if [`EventBusSettings::shuffle_same_priority`] is enabled.
+fn shuffle_seed(world: &World) -> Option<u64> {
+    let seed = world.get_resource::<EventBusSettings>()?.shuffle_seed?;
+    let post = world
+        .get_resource::<EventContext>()
+        .and_then(EventContext::current)
+        .map_or(0, |meta| meta.id.0);
+    Some(derive_seed(seed, post))
+}
+
+/// Detailed outcome of posting an [`Event`], returned by [`PostBuilder::reported`] and
+/// [`PostBuilder::tracked`].
+pub struct PostReport<E: Event> {
+    /// The final cancellation state of the event.
+    pub cancellation: E::Cancellation,
+    /// The handlers that modified the event, in the order they ran.
+    pub mutated_by: Vec<HandlerMutation<E>>,
+    /// The handler that cancelled the event, if it ended up cancelled.
+    pub cancelled_by: Option<CancelledBy<E>>,
+}
+
+impl<E: Event> Default for PostReport<E> {
+    fn default() -> Self {
+        Self {
+            cancellation: E::Cancellation::default(),
+            mutated_by: Vec::new(),
+            cancelled_by: None,
+        }
+    }
+}
+
+/// The handler that cancelled an event, see [`Receive::cancelled_by`] and
+/// [`PostReport::cancelled_by`].
+///
+/// Only the handler that turned the event from not cancelled into cancelled is recorded: handlers
+/// that change the cancellation state of an already cancelled event, e.g. to replace its reason,
+/// don't take over, and [uncancelling](Receive::uncancel) the event clears the record.
+pub struct CancelledBy<E: Event> {
+    /// The id of the handler.
+    pub id: HandlerId<E>,
+    /// The name of the handler.
+    pub name: Cow<'static, str>,
+    /// The effective priority of the handler.
+    pub priority: i32,
+}
+
+impl<E: Event> Clone for CancelledBy<E> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            name: self.name.clone(),
+            priority: self.priority,
+        }
+    }
+}
+
+impl<E: Event> Debug for CancelledBy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancelledBy")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// A handler that modified an event, see [`PostReport::mutated_by`].
+pub struct HandlerMutation<E: Event> {
+    /// The id of the handler.
+    pub id: HandlerId<E>,
+    /// The name of the handler.
+    pub name: Cow<'static, str>,
+}
@@ -0,0 +1,245 @@
+use std::{
+    any::type_name,
+    borrow::Borrow,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::world::World;
+use bevy_utils::tracing::warn;
+use parking_lot::Mutex;
+
+use crate::{
+    dispatch::{dispatch, run_registered, strategy::Inspect},
+    Cancellation, Event, HandlerRegistry, Mutability, MutabilityRef, PostOptions,
+};
+
+/// Bidirectional converter that makes [`Event`] `Old` an alias of [`Event`] `New`.
+///
+/// Once registered with [`WorldEventBus::alias_event`](crate::WorldEventBus::alias_event),
+/// posting `Old` converts it into `New` and runs the handlers registered for `New`. If `Old` is
+/// [`Mutable`](crate::Mutable), the modified `New` is converted back and written into the posted
+/// `Old` afterwards.
+///
+/// This keeps old event types working after they were renamed or restructured:
+///
+/// ```rust
+/// use bevy_ecs::world::World;
+/// use bevy_eventbus::prelude::*;
+/// use bevy_eventbus::EventAlias;
+///
+/// mod old_api {
+///     pub struct PlayerHurt(pub u32);
+/// }
+///
+/// mod new_api {
+///     pub struct EntityDamaged {
+///         pub amount: u32,
+///     }
+/// }
+///
+/// impl BusEvent for old_api::PlayerHurt {
+///     type Mutability = Mutable;
+///     type Cancellation = bool;
+///     type Audience = ();
+/// }
+///
+/// impl BusEvent for new_api::EntityDamaged {
+///     type Mutability = Mutable;
+///     type Cancellation = bool;
+///     type Audience = ();
+/// }
+///
+/// fn halve(mut event: Receive<new_api::EntityDamaged>) {
+///     event.amount /= 2;
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(halve);
+/// world.alias_event(EventAlias::new(
+///     |old: &old_api::PlayerHurt| new_api::EntityDamaged { amount: old.0 },
+///     |new: new_api::EntityDamaged| old_api::PlayerHurt(new.amount),
+/// ));
+///
+/// let mut event = old_api::PlayerHurt(10);
+/// world.post_mut(&mut event);
+/// assert_eq!(event.0, 5);
+/// ```
+pub struct EventAlias<Old, New> {
+    forward: Box<dyn Fn(&Old) -> New + Send + Sync>,
+    backward: Box<dyn Fn(New) -> Old + Send + Sync>,
+}
+
+impl<Old: Event, New: Event> EventAlias<Old, New> {
+    /// Creates a new alias from a conversion into the new event type and a conversion back.
+    pub fn new(
+        forward: impl Fn(&Old) -> New + Send + Sync + 'static,
+        backward: impl Fn(New) -> Old + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            forward: Box::new(forward),
+            backward: Box::new(backward),
+        }
+    }
+}
+
+/// Type-erased [`EventAlias`] stored in the [`HandlerRegistry`](crate::HandlerRegistry) of the
+/// aliased [`Event`].
+pub(crate) trait Redirect<E: Event>: Send + Sync {
+    /// Posts the event as the aliased type, returning its cancellation state. The options and
+    /// `inspect` only apply to the handlers of `E` itself, as those of the aliased type aren't
+    /// handlers of `E`.
+    fn dispatch(
+        &self,
+        world: &mut World,
+        event: MutabilityRef<'_, E>,
+        audience: &E::Audience,
+        options: &PostOptions,
+        inspect: &mut Inspect<'_, E>,
+    ) -> E::Cancellation;
+
+    /// Called after a handler was added for the aliased event.
+    fn handler_added(&self) {
+        warn!(
+            "Added a handler for {}, which will not run as the event is an alias",
+            type_name::<E>()
+        );
+    }
+}
+
+impl<Old, New> Redirect<Old> for EventAlias<Old, New>
+where
+    Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+    New: Event,
+{
+    fn dispatch(
+        &self,
+        world: &mut World,
+        mut event: MutabilityRef<'_, Old>,
+        audience: &Old::Audience,
+        _options: &PostOptions,
+        _inspect: &mut Inspect<'_, Old>,
+    ) -> Old::Cancellation {
+        if HandlerRegistry::<New>::throttled(world, audience) {
+            return Old::Cancellation::default();
+        }
+        let mut new = (self.forward)(event.borrow());
+        new.before_dispatch(world);
+        let cancellation = dispatch::<New>(
+            world,
+            New::Mutability::to_ref(&mut new),
+            audience,
+            &PostOptions::default(),
+            |_, _, _| {},
+        );
+
+        if let Some(old) = Old::Mutability::get_mut(&mut event) {
+            *old = (self.backward)(new);
+        }
+
+        cancellation
+    }
+}
+
+/// Forwards posts of the deprecated [`Event`] `Old` to [`Event`] `New`, registered with
+/// [`WorldEventBus::register_deprecated_event`](crate::WorldEventBus::register_deprecated_event).
+///
+/// Unlike an [`EventAlias`], the handlers of `Old` keep running for posts of `Old`, before the
+/// post is converted and forwarded to the handlers of `New` unless they cancelled it. Posting
+/// `Old` and adding handlers for it logs a warning, at most once a minute.
+pub(crate) struct DeprecatedEvent<Old, New> {
+    convert: Box<dyn Fn(&Old) -> New + Send + Sync>,
+    log: Mutex<DeprecationLog>,
+    _marker: PhantomData<fn() -> New>,
+}
+
+/// How often the use of a deprecated event is logged.
+pub(crate) const WARN_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct DeprecationLog {
+    /// When the last warning was logged.
+    last: Option<Instant>,
+    /// The uses of the event since then.
+    suppressed: u64,
+}
+
+impl<Old: Event, New: Event> DeprecatedEvent<Old, New> {
+    pub(crate) fn new(convert: impl Fn(&Old) -> New + Send + Sync + 'static) -> Self {
+        Self {
+            convert: Box::new(convert),
+            log: Mutex::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Logs a use of the deprecated event, unless a warning was logged recently.
+    pub(crate) fn warn(&self, usage: &str) {
+        self.warn_at(usage, Instant::now());
+    }
+
+    /// Logs a use of the deprecated event at the given instant, unless a warning was logged less
+    /// than [`WARN_INTERVAL`] before. Returns `true` if a warning was logged.
+    pub(crate) fn warn_at(&self, usage: &str, now: Instant) -> bool {
+        let mut log = self.log.lock();
+        if log
+            .last
+            .is_some_and(|last| now.saturating_duration_since(last) < WARN_INTERVAL)
+        {
+            log.suppressed += 1;
+            return false;
+        }
+        warn!(
+            "{usage} {}, which is deprecated in favor of {} ({} more uses since the last warning)",
+            type_name::<Old>(),
+            type_name::<New>(),
+            log.suppressed,
+        );
+        *log = DeprecationLog {
+            last: Some(now),
+            suppressed: 0,
+        };
+        true
+    }
+}
+
+impl<Old, New> Redirect<Old> for DeprecatedEvent<Old, New>
+where
+    Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+    New: Event,
+{
+    fn dispatch(
+        &self,
+        world: &mut World,
+        mut event: MutabilityRef<'_, Old>,
+        audience: &Old::Audience,
+        options: &PostOptions,
+        inspect: &mut Inspect<'_, Old>,
+    ) -> Old::Cancellation {
+        self.warn("Posted");
+        let cancellation = run_registered(
+            world,
+            Old::Mutability::reborrow(&mut event),
+            audience,
+            options,
+            inspect,
+        );
+        if cancellation.cancelled() || HandlerRegistry::<New>::throttled(world, audience) {
+            return cancellation;
+        }
+
+        let mut new = (self.convert)(event.borrow());
+        new.before_dispatch(world);
+        dispatch::<New>(
+            world,
+            New::Mutability::to_ref(&mut new),
+            audience,
+            &PostOptions::default(),
+            |_, _, _| {},
+        )
+    }
+
+    fn handler_added(&self) {
+        self.warn("Added a handler for");
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{dispatch::dispatch_batch, Cancellation, Event};
+
+/// Orders the posts of a [`TickBatch`] before they are dispatched.
+type BatchOrder<E> = Box<
+    dyn Fn(&(E, <E as Event>::Audience), &(E, <E as Event>::Audience)) -> Ordering + Send + Sync,
+>;
+
+/// [`Resource`] which collects the posts of [`Event`] `E` made during a server tick, and dispatches
+/// them all at once with [`TickBatch::dispatch`].
+///
+/// Compared to posting every event individually, the order of the handlers is only resolved once
+/// per batch. Handlers added or removed while a batch is dispatched take effect on the next batch.
+///
+/// Posts are dispatched in the order they were pushed, unless an order is set with
+/// [`TickBatch::with_order`]. Since the sort is stable, posts that compare equal keep the order
+/// they were pushed in.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy_ecs::{entity::Entity, world::World};
+/// # use bevy_eventbus::{prelude::*, TickBatch};
+/// # struct Damage(u32);
+/// # impl BusEvent for Damage {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = Entity;
+/// # }
+/// let mut world = World::new();
+/// world.insert_resource(TickBatch::<Damage>::new().with_order(|(_, a), (_, b)| a.cmp(b)));
+///
+/// let target = world.spawn_empty().id();
+/// world.resource_mut::<TickBatch<Damage>>().push(Damage(10), target);
+///
+/// let summary = TickBatch::<Damage>::dispatch(&mut world);
+/// assert_eq!(summary.posted, 1);
+/// ```
+#[derive(Resource)]
+pub struct TickBatch<E: Event<Audience: Send + Sync> + Send + Sync> {
+    pending: Vec<(E, E::Audience)>,
+    order: Option<BatchOrder<E>>,
+    last_summary: Option<TickBatchSummary>,
+}
+
+/// Summary of a dispatched [`TickBatch`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TickBatchSummary {
+    /// The number of posts dispatched.
+    pub posted: usize,
+    /// The number of posts that ended up cancelled.
+    pub cancelled: usize,
+    /// The wall time it took to sort and dispatch the batch.
+    pub elapsed: Duration,
+}
+
+impl<E: Event<Audience: Send + Sync> + Send + Sync> Default for TickBatch<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event<Audience: Send + Sync> + Send + Sync> TickBatch<E> {
+    /// Creates an empty batch, which dispatches posts in the order they were pushed.
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            order: None,
+            last_summary: None,
+        }
+    }
+
+    /// Sorts the posts with the comparator before they are dispatched.
+    pub fn with_order(
+        mut self,
+        order: impl Fn(&(E, E::Audience), &(E, E::Audience)) -> Ordering + Send + Sync + 'static,
+    ) -> Self {
+        self.order = Some(Box::new(order));
+        self
+    }
+
+    /// Adds a post to the batch.
+    pub fn push(&mut self, event: E, audience: E::Audience) {
+        self.pending.push((event, audience));
+    }
+
+    /// Returns the number of posts in the batch.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if the batch contains no posts.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns the summary of the last dispatched batch, if any.
+    pub fn last_summary(&self) -> Option<TickBatchSummary> {
+        self.last_summary
+    }
+
+    /// Sorts and dispatches all posts in the batch, returning a summary.
+    ///
+    /// Posts pushed by handlers while the batch is dispatched are kept for the next batch.
+    pub fn dispatch(world: &mut World) -> TickBatchSummary {
+        let start = Instant::now();
+        let Some(mut batch) = world.get_resource_mut::<Self>() else {
+            return TickBatchSummary::default();
+        };
+        let mut posts = std::mem::take(&mut batch.pending);
+        if let Some(order) = &batch.order {
+            posts.sort_by(order);
+        }
+
+        let mut summary = TickBatchSummary {
+            posted: posts.len(),
+            ..Default::default()
+        };
+        dispatch_batch(world, posts, |cancellation| {
+            if cancellation.cancelled() {
+                summary.cancelled += 1;
+            }
+        });
+        summary.elapsed = start.elapsed();
+
+        if let Some(mut batch) = world.get_resource_mut::<Self>() {
+            batch.last_summary = Some(summary);
+        }
+        summary
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+};
+
+use bevy_ecs::{change_detection::Mut, system::Resource, world::World};
+use bevy_utils::tracing::warn;
+
+use crate::{Event, HandlerRegistry};
+
+/// The [`HandlerRegistry`]s of all [`Event`] types, taken out of a world with
+/// [`WorldEventBus::export_handlers`](crate::WorldEventBus::export_handlers), to be reinstalled
+/// with [`WorldEventBus::install_handlers`](crate::WorldEventBus::install_handlers).
+///
+/// The handlers are stored in their registries, so they keep their [`HandlerId`](crate::HandlerId)s,
+/// configuration, and system state. The resources that accompany the handlers are exported along
+/// with them: the [`KeyedHandlers`](crate::KeyedHandlers), the
+/// [`DeliveryTracker`](crate::DeliveryTracker)s, the owners of plugin handlers, and the compiled
+/// [`TickSchedule`](crate::tick::TickSchedule).
+///
+/// [`World::clear_resources`] and [`World::clear_all`] remove all handlers along with the rest of
+/// the resources. Exporting them before, and reinstalling them afterwards, keeps them around, e.g.
+/// when loading a save:
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::prelude::*;
+/// # struct MyEvent;
+/// # impl BusEvent for MyEvent {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// # fn handler(_event: Receive<MyEvent>) {}
+/// let mut world = World::new();
+/// world.add_handler(handler);
+///
+/// let handlers = world.export_handlers();
+/// world.clear_all();
+/// world.install_handlers(handlers);
+/// assert_eq!(world.handler_ids::<MyEvent>().len(), 1);
+/// ```
+///
+/// Handlers must be reinstalled into the same world they were exported from.
+#[derive(Default)]
+pub struct HandlerBlueprints {
+    registries: Vec<ExportedResource>,
+    /// The resources that accompany the handlers, installed after the registries.
+    companions: Vec<ExportedResource>,
+}
+
+/// Takes a resource out of the world, if present, to be reinstalled later.
+pub(crate) type Exporter = fn(&mut World) -> Option<ExportedResource>;
+
+/// A type-erased resource, such as a [`HandlerRegistry`], that knows how to reinstall itself into
+/// the world.
+pub(crate) struct ExportedResource {
+    pub(crate) resource: Box<dyn Any + Send + Sync>,
+    pub(crate) install: fn(&mut World, Box<dyn Any + Send + Sync>),
+}
+
+impl HandlerBlueprints {
+    /// Returns the number of [`Event`] types with exported handlers.
+    pub fn len(&self) -> usize {
+        self.registries.len()
+    }
+
+    /// Returns `true` if no handlers were exported.
+    pub fn is_empty(&self) -> bool {
+        self.registries.is_empty()
+    }
+
+    /// Takes the [`HandlerRegistry`]s of all [`Event`] types out of the world, along with the
+    /// resources that accompany them.
+    pub(crate) fn export(world: &mut World) -> Self {
+        let Some(index) = world.remove_resource::<RegistryIndex>() else {
+            return Self::default();
+        };
+
+        let registries = index
+            .exporters
+            .into_values()
+            .filter_map(|export| export(world))
+            .collect();
+        let companions = index
+            .companions
+            .into_values()
+            .filter_map(|export| export(world))
+            .collect();
+        Self {
+            registries,
+            companions,
+        }
+    }
+
+    /// Reinstalls the [`HandlerRegistry`]s into the world, replacing any registries added since
+    /// they were exported, followed by the resources that accompany them.
+    pub(crate) fn install(self, world: &mut World) {
+        for exported in self.registries.into_iter().chain(self.companions) {
+            (exported.install)(world, exported.resource);
+        }
+    }
+}
+
+/// [`Resource`] which indexes the [`HandlerRegistry`]s of all [`Event`] types in the world, so that
+/// they can be exported without knowing their types.
+#[derive(Resource, Default)]
+pub(crate) struct RegistryIndex {
+    exporters: HashMap<TypeId, Exporter>,
+    /// The exporters of the resources that accompany the handlers, by the type they export.
+    companions: HashMap<TypeId, Exporter>,
+}
+
+impl RegistryIndex {
+    /// Exports the [`Resource`] `R` along with the handlers, as it would be useless or wrong
+    /// without them.
+    pub(crate) fn track<R: Resource>(world: &mut World) {
+        Self::track_with(world, TypeId::of::<R>(), export_companion::<R>);
+    }
+
+    /// Exports the type with the [`TypeId`] along with the handlers, using the exporter.
+    pub(crate) fn track_with(world: &mut World, type_id: TypeId, export: Exporter) {
+        world
+            .get_resource_or_insert_with(Self::default)
+            .companions
+            .insert(type_id, export);
+    }
+}
+
+impl<E: Event> HandlerRegistry<E> {
+    /// Returns the [`HandlerRegistry`] for [`Event`] `E`, inserting an empty one if needed.
+    pub(crate) fn get_or_insert(world: &mut World) -> Mut<'_, Self> {
+        if !world.contains_resource::<Self>() {
+            world
+                .get_resource_or_insert_with(RegistryIndex::default)
+                .exporters
+                .insert(TypeId::of::<E>(), export_registry::<E>);
+            world.insert_resource(Self::default());
+        }
+        world.resource_mut::<Self>()
+    }
+}
+
+fn export_registry<E: Event>(world: &mut World) -> Option<ExportedResource> {
+    let registry = world.remove_resource::<HandlerRegistry<E>>()?;
+    Some(ExportedResource {
+        resource: Box::new(registry),
+        install: install_registry::<E>,
+    })
+}
+
+fn install_registry<E: Event>(world: &mut World, registry: Box<dyn Any + Send + Sync>) {
+    let Ok(registry) = registry.downcast::<HandlerRegistry<E>>() else {
+        return;
+    };
+    if world
+        .get_resource::<HandlerRegistry<E>>()
+        .is_some_and(|existing| !existing.is_empty())
+    {
+        warn!(
+            "Replaced the handlers added for {} since they were exported",
+            type_name::<E>()
+        );
+    }
+    world
+        .get_resource_or_insert_with(RegistryIndex::default)
+        .exporters
+        .insert(TypeId::of::<E>(), export_registry::<E>);
+    world.insert_resource(*registry);
+}
+
+fn export_companion<R: Resource>(world: &mut World) -> Option<ExportedResource> {
+    let resource = world.remove_resource::<R>()?;
+    Some(ExportedResource {
+        resource: Box::new(resource),
+        install: install_companion::<R>,
+    })
+}
+
+fn install_companion<R: Resource>(world: &mut World, resource: Box<dyn Any + Send + Sync>) {
+    let Ok(resource) = resource.downcast::<R>() else {
+        return;
+    };
+    RegistryIndex::track::<R>(world);
+    world.insert_resource(*resource);
+}
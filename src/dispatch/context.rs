@@ -0,0 +1,235 @@
+use std::{
+    any::{type_name, TypeId},
+    fmt,
+    time::Duration,
+};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{derive_seed, BridgeId, Event, EventCausality, Time};
+
+/// Identifier shared by an [`Event`] and every event posted while handling it, transitively.
+///
+/// Correlation IDs propagate through nested posts, including posts queued with
+/// [`Commands`](bevy_ecs::system::Commands) and on the [`EventQueue`](crate::EventQueue), so a
+/// chain of events can be reconstructed even if it spans multiple frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CorrelationId(pub u128);
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// Identifier of a single post of an [`Event`], unique within a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PostId(pub u64);
+
+impl fmt::Display for PostId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How new [`CorrelationId`]s are generated, see [`EventContext::generator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CorrelationGenerator {
+    /// Sequential IDs starting from 0, which are unique within a single world.
+    #[default]
+    Counter,
+    /// Random version 4 UUIDs, which are unique across worlds and processes.
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+/// Metadata about an [`Event`] being dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventMeta {
+    /// The ID of this post.
+    pub id: PostId,
+    /// The ID of the post whose handler caused this one, if any.
+    pub parent: Option<PostId>,
+    /// The correlation ID, inherited from the event that caused this one to be posted.
+    pub correlation: CorrelationId,
+    /// The type name of the event, for display only.
+    pub event: &'static str,
+    /// The [`TypeId`] of the event, which unlike its type name is unique.
+    pub event_type: TypeId,
+    /// How many events caused this one to be posted, transitively. Events posted from outside of
+    /// any handler have a depth of 0.
+    pub depth: usize,
+    /// The seed of the deterministic random numbers drawn while handling this post, see
+    /// [`BusRng`](crate::BusRng).
+    pub seed: u64,
+    /// The elapsed time this post is stamped with, inherited from the event that caused it, see
+    /// [`BusTime`](crate::BusTime).
+    pub elapsed: Duration,
+    /// The bridge this post entered the event bus through, if any, see [`Bridge`](crate::Bridge).
+    pub origin: Option<BridgeId>,
+}
+
+/// [`Resource`] which tracks the [`EventMeta`] of the events currently being dispatched.
+///
+/// Handlers can read the metadata of the event they are handling with [`EventContext::current`]:
+///
+/// ```rust
+/// # use bevy_ecs::system::Res;
+/// # use bevy_eventbus::{prelude::*, EventContext};
+/// # struct MyEvent;
+/// # impl BusEvent for MyEvent {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// fn handler(_event: Receive<MyEvent>, context: Res<EventContext>) {
+///     let meta = context.current().unwrap();
+///     println!("handling {} in chain {}", meta.event, meta.correlation);
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct EventContext {
+    /// How new [`CorrelationId`]s are generated.
+    pub generator: CorrelationGenerator,
+    /// The seed from which the seeds of posts made outside of any handler are derived, see
+    /// [`BusRng`](crate::BusRng).
+    pub seed: u64,
+    next: u128,
+    next_post: u64,
+    stack: Vec<EventMeta>,
+    /// The event that caused a deferred post, while it is being dispatched.
+    resumed: Option<EventMeta>,
+}
+
+/// Options of a single post, handed along with the post from where it is made to its dispatch, so
+/// that they only apply to that post even if it is deferred, or other posts are made before it is
+/// dispatched.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PostOptions {
+    /// The minimum priority of the handlers that the post runs.
+    pub(crate) min_priority: Option<i32>,
+    /// The raw IDs of the handlers that the post skips.
+    pub(crate) excluded: Vec<u64>,
+    /// The bridge that the post enters the event bus through.
+    pub(crate) origin: Option<BridgeId>,
+    /// The seed and time stamp of the recorded post that the post replays, if any.
+    pub(crate) replayed: Option<(u64, Duration)>,
+    /// The correlation ID that the post continues if it isn't caused by another post, e.g.
+    /// because it was restored from a backlog saved by a previous session.
+    pub(crate) correlation: Option<CorrelationId>,
+}
+
+impl EventContext {
+    /// Returns the metadata of the innermost event being dispatched.
+    pub fn current(&self) -> Option<&EventMeta> {
+        self.stack.last()
+    }
+
+    /// Returns the metadata of all events being dispatched, outermost first.
+    pub fn stack(&self) -> &[EventMeta] {
+        &self.stack
+    }
+
+    /// Returns the event that the next post would be caused by.
+    pub(crate) fn parent(world: &World) -> Option<EventMeta> {
+        let context = world.get_resource::<Self>()?;
+        context.stack.last().copied().or(context.resumed)
+    }
+
+    /// Runs `f` as if `parent` was being dispatched, so that posts made by `f` inherit from it.
+    pub(crate) fn resume<R>(
+        world: &mut World,
+        parent: Option<EventMeta>,
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        let Some(parent) = parent else {
+            return f(world);
+        };
+
+        let previous = world
+            .get_resource_or_insert_with(Self::default)
+            .resumed
+            .replace(parent);
+        let result = f(world);
+        world.resource_mut::<Self>().resumed = previous;
+        result
+    }
+
+    /// Runs `f` as if the post with the metadata was being dispatched again, e.g. to resume a post
+    /// deferred by its handlers.
+    pub(crate) fn reenter<R>(
+        world: &mut World,
+        meta: Option<EventMeta>,
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        let Some(meta) = meta else {
+            return f(world);
+        };
+
+        world
+            .get_resource_or_insert_with(Self::default)
+            .stack
+            .push(meta);
+        let result = f(world);
+        Self::exit(world);
+        result
+    }
+
+    /// Pushes the metadata of [`Event`] `E` as it starts being dispatched with the options, and
+    /// records it into the [`EventCausality`] if present.
+    pub(crate) fn enter<E: Event>(world: &mut World, options: &PostOptions) {
+        let parent = Self::parent(world);
+        let now = world
+            .get_resource::<Time>()
+            .map_or(Duration::ZERO, Time::elapsed);
+        let mut context = world.get_resource_or_insert_with(Self::default);
+        let id = PostId(context.next_post);
+        context.next_post += 1;
+        let meta = EventMeta {
+            id,
+            parent: parent.map(|parent| parent.id),
+            correlation: match (parent, options.correlation) {
+                (Some(parent), _) => parent.correlation,
+                (None, Some(correlation)) => correlation,
+                (None, None) => context.generate(),
+            },
+            event: type_name::<E>(),
+            event_type: TypeId::of::<E>(),
+            depth: parent.map_or(0, |parent| parent.depth + 1),
+            seed: parent.map_or_else(
+                || derive_seed(context.seed, id.0),
+                |parent| derive_seed(parent.seed, id.0 - parent.id.0),
+            ),
+            elapsed: parent.map_or(now, |parent| parent.elapsed),
+            origin: options.origin,
+        };
+        let meta = match options.replayed {
+            Some((seed, elapsed)) => EventMeta {
+                seed,
+                elapsed,
+                ..meta
+            },
+            None => meta,
+        };
+        context.stack.push(meta);
+
+        EventCausality::record(world, meta);
+    }
+
+    /// Pops the metadata of the innermost event as it finishes being dispatched.
+    pub(crate) fn exit(world: &mut World) {
+        world.resource_mut::<Self>().stack.pop();
+    }
+
+    fn generate(&mut self) -> CorrelationId {
+        match self.generator {
+            CorrelationGenerator::Counter => {
+                let id = self.next;
+                self.next += 1;
+                CorrelationId(id)
+            }
+            #[cfg(feature = "uuid")]
+            CorrelationGenerator::Uuid => CorrelationId(uuid::Uuid::new_v4().as_u128()),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::mem;
+
+use bevy_ecs::{schedule::BoxedCondition, system::Resource, world::World};
+
+use crate::{
+    dispatch::run_entries, Event, EventContext, EventMeta, HandlerEntry, HandlerSource, Mutability,
+    PostOptions,
+};
+
+/// A type-erased continuation of a deferred post, running its remaining handlers.
+type ParkedPost = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Builds the continuation of a deferred post from the event, its audience, the registry its
+/// handlers were taken from, the options of the post, the handlers left to run, and the metadata
+/// of the post.
+type Park<E> = fn(
+    &E,
+    &<E as Event>::Audience,
+    HandlerSource<E>,
+    PostOptions,
+    Vec<HandlerEntry<E>>,
+    Option<EventMeta>,
+) -> ParkedPost;
+
+/// A handler's request to defer the rest of a post, see [`Receive::defer_until`](crate::Receive::defer_until).
+pub(crate) struct Deferral<E: Event> {
+    pub(crate) condition: BoxedCondition,
+    pub(crate) park: Park<E>,
+}
+
+impl<E: Event> Deferral<E> {
+    /// Creates a deferral whose continuation owns a clone of the event and its audience.
+    pub(crate) fn new(condition: BoxedCondition) -> Self
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        Self {
+            condition,
+            park: park::<E>,
+        }
+    }
+}
+
+/// [`Resource`] which owns the posts deferred by their handlers with
+/// [`Receive::defer_until`](crate::Receive::defer_until), until their conditions return `true`.
+///
+/// Parked posts are resumed by [`ParkedEvents::resume`], which the
+/// [`EventBusPlugin`](crate::EventBusPlugin) calls every frame before posting
+/// [`Tick`](crate::tick::Tick).
+#[derive(Resource, Default)]
+pub struct ParkedEvents {
+    parked: Vec<(BoxedCondition, ParkedPost)>,
+}
+
+impl ParkedEvents {
+    /// Returns the number of parked posts.
+    pub fn len(&self) -> usize {
+        self.parked.len()
+    }
+
+    /// Returns `true` if no posts are parked.
+    pub fn is_empty(&self) -> bool {
+        self.parked.is_empty()
+    }
+
+    /// Discards all parked posts, without running their remaining handlers.
+    pub fn clear(&mut self) {
+        self.parked.clear();
+    }
+
+    /// Resumes the parked posts whose conditions return `true`, running their remaining handlers
+    /// in the order they were parked. Returns the number of posts resumed.
+    pub fn resume(world: &mut World) -> usize {
+        let Some(mut parked) = world.get_resource_mut::<Self>() else {
+            return 0;
+        };
+        let parked = mem::take(&mut parked.parked);
+
+        let mut resumed = 0;
+        let mut waiting = Vec::new();
+        for (mut condition, post) in parked {
+            if condition.validate_param(world) && condition.run((), world) {
+                post(world);
+                resumed += 1;
+            } else {
+                waiting.push((condition, post));
+            }
+        }
+
+        let mut parked = world.resource_mut::<Self>();
+        waiting.append(&mut parked.parked);
+        parked.parked = waiting;
+        resumed
+    }
+
+    /// Parks a post until the deferral's condition returns `true`.
+    pub(crate) fn park<E: Event>(
+        world: &mut World,
+        deferral: Deferral<E>,
+        event: &E,
+        audience: &E::Audience,
+        source: HandlerSource<E>,
+        options: PostOptions,
+        remaining: Vec<HandlerEntry<E>>,
+    ) {
+        let Deferral {
+            mut condition,
+            park,
+        } = deferral;
+        condition.initialize(world);
+        let meta = world
+            .get_resource::<EventContext>()
+            .and_then(EventContext::current)
+            .copied();
+        let post = park(event, audience, source, options, remaining, meta);
+        world
+            .get_resource_or_insert_with(Self::default)
+            .parked
+            .push((condition, post));
+    }
+}
+
+fn park<E>(
+    event: &E,
+    audience: &E::Audience,
+    source: HandlerSource<E>,
+    options: PostOptions,
+    remaining: Vec<HandlerEntry<E>>,
+    meta: Option<EventMeta>,
+) -> ParkedPost
+where
+    E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+{
+    let mut event = event.clone();
+    let audience = audience.clone();
+    Box::new(move |world: &mut World| {
+        let remaining = source
+            .registry(world)
+            .map(|registry| {
+                remaining
+                    .into_iter()
+                    .filter(|entry| registry.contains(entry.id))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        EventContext::reenter(world, meta, |world| {
+            run_entries(
+                world,
+                &remaining,
+                source,
+                E::Mutability::to_ref(&mut event),
+                &audience,
+                &options,
+                |_, _, _| {},
+            );
+        });
+    })
+}
+
+/// Exclusive system that resumes the [`ParkedEvents`] whose conditions return `true`.
+pub fn resume_parked_events(world: &mut World) {
+    ParkedEvents::resume(world);
+}
@@ -0,0 +1,127 @@
+use std::{ops::Range, time::Duration};
+
+use bevy_ecs::system::{Local, Res, SystemParam};
+
+use crate::{EventContext, PostId, Time};
+
+/// [`SystemParam`] for drawing random numbers in handlers deterministically.
+///
+/// Every post is given a seed, derived from [`EventContext::seed`] and the post that caused it, and
+/// recorded into the [`EventHistory`](crate::EventHistory) along with the event. Within a handler,
+/// `BusRng` draws from the seed of the event being handled, so the same post always produces the
+/// same numbers, and a [`Replay`](crate::Replay) of it reproduces them:
+///
+/// ```rust
+/// # use bevy_eventbus::{prelude::*, BusRng};
+/// # struct Attack;
+/// # impl BusEvent for Attack {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// fn roll_damage(_event: Receive<Attack>, mut rng: BusRng) {
+///     let critical = rng.chance(0.1);
+///     let damage = rng.range(10..20);
+///     // ...
+/// }
+/// ```
+///
+/// Outside of any handler, `BusRng` draws from [`EventContext::seed`] directly.
+#[derive(SystemParam)]
+pub struct BusRng<'w, 's> {
+    context: Option<Res<'w, EventContext>>,
+    state: Local<'s, RngState>,
+}
+
+#[derive(Default)]
+pub(crate) struct RngState {
+    /// The post the state was seeded from, or `None` if it was seeded outside of any handler.
+    post: Option<Option<PostId>>,
+    state: u64,
+}
+
+impl BusRng<'_, '_> {
+    /// Returns the next random [`u64`].
+    pub fn next_u64(&mut self) -> u64 {
+        let meta = self.context.as_deref().and_then(EventContext::current);
+        let post = meta.map(|meta| meta.id);
+        if self.state.post != Some(post) {
+            self.state.post = Some(post);
+            self.state.state = meta.map_or_else(
+                || self.context.as_deref().map_or(0, |context| context.seed),
+                |meta| meta.seed,
+            );
+        }
+        splitmix(&mut self.state.state)
+    }
+
+    /// Returns a random [`f64`] between `0.0` inclusive and `1.0` exclusive.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random number within the range, or its start if the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        let len = range.end.saturating_sub(range.start);
+        if len == 0 {
+            return range.start;
+        }
+        range.start + self.next_u64() % len
+    }
+
+    /// Returns `true` with the given probability, between `0.0` and `1.0`.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
+
+/// [`SystemParam`] for reading the time in handlers deterministically.
+///
+/// Every post is stamped with the elapsed [`Time`] when it was made, which is inherited by the
+/// posts it causes and recorded into the [`EventHistory`](crate::EventHistory). Within a handler,
+/// `BusTime` reads the stamp of the event being handled, so a [`Replay`](crate::Replay) of it
+/// sees the same time as the original post did.
+///
+/// Outside of any handler, `BusTime` reads the [`Time`] directly.
+#[derive(SystemParam)]
+pub struct BusTime<'w> {
+    context: Option<Res<'w, EventContext>>,
+    time: Option<Res<'w, Time>>,
+}
+
+impl BusTime<'_> {
+    /// Returns the elapsed time stamped on the event being handled.
+    pub fn elapsed(&self) -> Duration {
+        self.context
+            .as_deref()
+            .and_then(EventContext::current)
+            .map_or_else(
+                || self.time.as_deref().map_or(Duration::ZERO, Time::elapsed),
+                |meta| meta.elapsed,
+            )
+    }
+
+    /// Returns the elapsed time stamped on the event being handled, in seconds.
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed().as_secs_f32()
+    }
+}
+
+/// Derives the seed of a post from the seed it inherits and the offset of its ID.
+pub(crate) fn derive_seed(seed: u64, offset: u64) -> u64 {
+    let mut state = seed ^ offset.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    splitmix(&mut state)
+}
+
+/// Picks an index below `len` deterministically from the seed, advancing it.
+pub(crate) fn pick_index(seed: &mut u64, len: usize) -> usize {
+    (splitmix(seed) % len as u64) as usize
+}
+
+fn splitmix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use bevy_ecs::world::World;
+
+use crate::{
+    dispatch::{alias::Redirect, inbox::InboxCollector, panic::PanicDump, sequence::Sequencer},
+    DispatchStrategy, Event, Normalizer,
+};
+
+/// Records or hashes a post of a known [`Event`] type.
+pub(crate) type PostObserver<E> = fn(&mut World, &E, &<E as Event>::Audience);
+
+/// The optional hooks that posts of [`Event`] `E` go through, stored in its
+/// [`HandlerRegistry`](crate::HandlerRegistry).
+///
+/// Each hook is enabled by a [`WorldEventBus`](crate::WorldEventBus) method, e.g.
+/// [`enable_history`](crate::WorldEventBus::enable_history) for the recorder, and replaces any
+/// previous hook of the same kind. Posts without any hook enabled only pay for checking them.
+pub(crate) struct EventHooks<E: Event> {
+    /// The event type that posts of `E` are redirected to, if `E` is an alias.
+    pub(crate) alias: Option<Arc<dyn Redirect<E>>>,
+    /// Records posts of `E` into its [`EventHistory`](crate::EventHistory), if enabled.
+    pub(crate) recorder: Option<PostObserver<E>>,
+    /// Folds posts of `E` into the [`StreamHasher`](crate::StreamHasher), if enabled.
+    pub(crate) hasher: Option<PostObserver<E>>,
+    /// How posts of `E` run their handlers, or [`Sequential`](crate::Sequential) if `None`.
+    pub(crate) strategy: Option<Arc<dyn DispatchStrategy<E>>>,
+    /// Dumps posts of `E` when a handler panics, if enabled.
+    pub(crate) panic_dump: Option<PanicDump<E>>,
+    /// Sequences posts of `E` by target, if enabled.
+    pub(crate) sequencer: Option<Sequencer<E>>,
+    /// Counts posts of `E` against the [`TargetThrottle`](crate::TargetThrottle), returning
+    /// `false` if they must be dropped, if enabled.
+    pub(crate) throttle: Option<fn(&mut World, &E::Audience) -> bool>,
+    /// Normalizes the audiences of posts of `E`, if enabled.
+    pub(crate) normalizer: Option<Normalizer<E>>,
+    /// Collects posts of `E` into the [`EventInbox`](crate::EventInbox) of their target,
+    /// returning `false` if their handlers must be skipped, if enabled.
+    pub(crate) inbox: Option<InboxCollector<E>>,
+}
+
+impl<E: Event> EventHooks<E> {
+    /// Returns `true` if every post of `E` is observed by a hook even without handlers, i.e. it is
+    /// redirected, recorded, hashed, or collected into inboxes.
+    pub(crate) fn observe_posts(&self) -> bool {
+        self.alias.is_some()
+            || self.recorder.is_some()
+            || self.hasher.is_some()
+            || self.inbox.is_some()
+    }
+}
+
+impl<E: Event> Clone for EventHooks<E> {
+    fn clone(&self) -> Self {
+        Self {
+            alias: self.alias.clone(),
+            recorder: self.recorder,
+            hasher: self.hasher,
+            strategy: self.strategy.clone(),
+            panic_dump: self.panic_dump,
+            sequencer: self.sequencer,
+            throttle: self.throttle,
+            normalizer: self.normalizer,
+            inbox: self.inbox,
+        }
+    }
+}
+
+impl<E: Event> Default for EventHooks<E> {
+    fn default() -> Self {
+        Self {
+            alias: None,
+            recorder: None,
+            hasher: None,
+            strategy: None,
+            panic_dump: None,
+            sequencer: None,
+            throttle: None,
+            normalizer: None,
+            inbox: None,
+        }
+    }
+}
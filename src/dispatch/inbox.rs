@@ -0,0 +1,146 @@
+use std::collections::{vec_deque, VecDeque};
+
+use bevy_ecs::{component::Component, world::World};
+
+use crate::{Event, Unicast};
+
+/// Collects a post of a known [`Event`] type into the inbox of its target, returning `false` if
+/// its handlers must be skipped.
+pub(crate) type InboxCollector<E> = fn(&mut World, &E, &<E as Event>::Audience) -> bool;
+
+/// [`Component`] which collects the posts of [`Event`] `E` targeting its entity, for systems to
+/// drain at their leisure, enabled with
+/// [`WorldEventBus::collect_in_inboxes`](crate::WorldEventBus::collect_in_inboxes).
+///
+/// A clone of each post is pushed into the inbox of its target, if it has one, after the post is
+/// recorded and before any handler runs. By default the handlers still run as usual, but posts
+/// to an entity with an [exclusive](EventInbox::exclusive) inbox skip the handlers and report the
+/// default cancellation state.
+///
+/// An inbox is unbounded by default. Give it a [capacity](EventInbox::with_capacity) if its
+/// entity may go without draining it for a while, so that the oldest posts are dropped instead.
+///
+/// ```rust
+/// # use bevy_ecs::{entity::Entity, world::World};
+/// # use bevy_eventbus::{prelude::*, EventInbox};
+/// # #[derive(Clone)]
+/// # struct Noise(f32);
+/// # impl BusEvent for Noise {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = Entity;
+/// # }
+/// let mut world = World::new();
+/// world.collect_in_inboxes::<Noise>();
+/// let guard = world.spawn(EventInbox::<Noise>::exclusive()).id();
+/// world.post_to(Noise(0.5), guard);
+/// world.post_to(Noise(0.8), guard);
+///
+/// // Later, in the AI system of the guard...
+/// let mut inbox = world.get_mut::<EventInbox<Noise>>(guard).unwrap();
+/// let loudest = inbox.drain().map(|noise| noise.0).fold(0.0, f32::max);
+/// assert_eq!(loudest, 0.8);
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct EventInbox<E: Event + Send + Sync> {
+    events: VecDeque<E>,
+    exclusive: bool,
+    capacity: Option<usize>,
+    dropped: u64,
+}
+
+impl<E: Event + Send + Sync> EventInbox<E> {
+    /// Creates an inbox which collects posts in addition to running their handlers.
+    pub fn new() -> Self {
+        Self {
+            events: VecDeque::new(),
+            exclusive: false,
+            capacity: None,
+            dropped: 0,
+        }
+    }
+
+    /// Creates an inbox which collects posts instead of running their handlers.
+    pub fn exclusive() -> Self {
+        Self {
+            exclusive: true,
+            ..Self::new()
+        }
+    }
+
+    /// Limits the inbox to `capacity` posts. Collecting a post into a full inbox drops its
+    /// oldest post, counted in [`EventInbox::dropped`].
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Returns the maximum number of posts the inbox holds, or `None` if it is unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns the number of posts dropped because the inbox was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Returns `true` if the inbox collects posts instead of running their handlers.
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Returns the number of collected posts.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no posts were collected.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns an iterator over the collected posts, oldest first, without removing them.
+    pub fn iter(&self) -> impl Iterator<Item = &E> + '_ {
+        self.events.iter()
+    }
+
+    /// Removes the collected posts, returning an iterator over them, oldest first.
+    pub fn drain(&mut self) -> vec_deque::Drain<'_, E> {
+        self.events.drain(..)
+    }
+
+    /// Collects the post, dropping the oldest one if the inbox is full.
+    fn push(&mut self, event: E) {
+        if self.capacity == Some(0) {
+            self.dropped += 1;
+            return;
+        }
+        if self
+            .capacity
+            .is_some_and(|capacity| self.events.len() >= capacity)
+        {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+}
+
+impl<E: Event + Send + Sync> Default for EventInbox<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event<Audience: Unicast> + Clone + Send + Sync> EventInbox<E> {
+    /// Collects the post into the inbox of its target, returning `false` if its handlers must be
+    /// skipped.
+    pub(crate) fn deliver(world: &mut World, event: &E, audience: &E::Audience) -> bool {
+        let Some(mut inbox) = world.get_mut::<Self>(audience.target()) else {
+            return true;
+        };
+        inbox.push(event.clone());
+        !inbox.exclusive
+    }
+}
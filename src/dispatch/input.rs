@@ -0,0 +1,316 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    ops::{Deref, DerefMut},
+};
+
+use bevy_ecs::{
+    entity::Entity,
+    schedule::{BoxedCondition, Condition},
+    system::{IntoSystem, SystemInput},
+};
+use bevy_utils::tracing::warn;
+
+use crate::{
+    dispatch::defer::Deferral, Cancellable, CancellableWith, Cancellation, CancellationMut,
+    CancelledBy, Event, Multicast, Mutability, MutabilityRef, Mutable, Unicast,
+};
+
+/// [`SystemInput`] type for receiving events in handlers.
+pub struct Receive<'event, E: Event> {
+    /// The event being received.
+    event: MutabilityRef<'event, E>,
+    /// The cancellation state of the event.
+    cancellation: CancellationMut<'event, E>,
+    /// The intended audience of the event.
+    audience: &'event E::Audience,
+    /// Where a request to defer the rest of the dispatch is stored, if it can be deferred.
+    deferral: Option<&'event mut Option<Deferral<E>>>,
+    /// The handler that cancelled the event, if it is cancelled and known.
+    cancelled_by: Option<&'event CancelledBy<E>>,
+}
+
+impl<'event, E: Event> Receive<'event, E> {
+    /// Creates a new [`Receive`] instance.
+    pub fn new(
+        event: MutabilityRef<'event, E>,
+        cancellation: CancellationMut<'event, E>,
+        audience: &'event E::Audience,
+    ) -> Self {
+        Self {
+            event,
+            cancellation,
+            audience,
+            deferral: None,
+            cancelled_by: None,
+        }
+    }
+
+    /// Reborrows the input, e.g. to pass it on to another handler system.
+    pub(crate) fn reborrow(&mut self) -> Receive<'_, E> {
+        Receive {
+            event: E::Mutability::reborrow(&mut self.event),
+            cancellation: self.cancellation.borrow_mut().as_mut(),
+            audience: self.audience,
+            deferral: self.deferral.as_deref_mut(),
+            cancelled_by: self.cancelled_by,
+        }
+    }
+
+    /// Allows the handler to defer the rest of the dispatch into the slot.
+    pub(crate) fn with_deferral(mut self, deferral: &'event mut Option<Deferral<E>>) -> Self {
+        self.deferral = Some(deferral);
+        self
+    }
+
+    /// Lets the handler see which handler cancelled the event.
+    pub(crate) fn with_cancelled_by(
+        mut self,
+        cancelled_by: Option<&'event CancelledBy<E>>,
+    ) -> Self {
+        self.cancelled_by = cancelled_by;
+        self
+    }
+
+    /// Returns a read-only reference to the event.
+    pub fn event(&self) -> &E {
+        self.event.borrow()
+    }
+
+    /// Returns a mutable reference to the event.
+    /// Requires the [`Event`] `E` to be [`Mutable`].
+    pub fn event_mut(&mut self) -> &mut E
+    where
+        E: Event<Mutability = Mutable>,
+    {
+        self.event
+    }
+
+    /// Returns the result of the function applied to the event, e.g. to pass a field of it on to a
+    /// helper in a chain of calls.
+    pub fn map<R>(&self, f: impl FnOnce(&E) -> R) -> R {
+        f(self.event.borrow())
+    }
+
+    /// Calls the function with the event and returns the input, e.g. to log the event before
+    /// passing the input on.
+    pub fn inspect(self, f: impl FnOnce(&E)) -> Self {
+        f(self.event.borrow());
+        self
+    }
+
+    /// Returns `true` if the event was cancelled.
+    /// This will always return `false` if the [`Event`] `E` is not
+    /// [`Cancellable`] or [`CancellableWith`].
+    ///
+    /// To cancel an event, use [`Receive::cancel`].
+    pub fn cancelled(&self) -> bool {
+        self.cancellation.borrow().cancelled()
+    }
+
+    /// Returns the cancellation state of the event, e.g. to check which targets already blocked a
+    /// [`PerTargetCancellation`](crate::PerTargetCancellation).
+    pub fn cancellation(&self) -> &E::Cancellation {
+        self.cancellation.borrow()
+    }
+
+    /// Returns the handler that cancelled the event, e.g. to find out which plugin cancelled it,
+    /// or `None` if it isn't cancelled.
+    pub fn cancelled_by(&self) -> Option<&CancelledBy<E>> {
+        self.cancelled_by
+            .filter(|_| self.cancellation.borrow().cancelled())
+    }
+
+    /// Cancels the event from being processed further.
+    /// Requires the [`Event`] `E` to be [`Cancellable`].
+    ///
+    /// To check if an event is cancelled, use [`Receive::cancelled`].
+    pub fn cancel(&mut self)
+    where
+        E: Event<Cancellation: Cancellable>,
+    {
+        self.cancellation.borrow_mut().cancel();
+    }
+
+    /// Cancels the event from being processed further with a value.
+    /// Requires the [`Event`] `E` to be [`CancellableWith`] `T`.
+    ///
+    /// To check if an event is cancelled, use [`Receive::cancelled`].
+    pub fn cancel_with<T>(&mut self, value: T)
+    where
+        E: Event<Cancellation: CancellableWith<T>>,
+    {
+        self.cancellation.borrow_mut().cancel_with(value);
+    }
+
+    /// Reverts the cancellation of the event, see [`Cancellation::uncancel`].
+    ///
+    /// Only handlers that [receive cancelled](crate::HandlerConfig::receive_cancelled) events run
+    /// after the event was cancelled, so those are the ones that can veto a cancellation. The
+    /// handlers after them run as usual again.
+    pub fn uncancel(&mut self) {
+        self.cancellation.borrow_mut().uncancel();
+    }
+
+    /// Splits the input into the event, a view of its cancellation state, and its audience, so that
+    /// they can be borrowed independently, e.g. to mutate the event while passing the cancellation
+    /// to a helper function.
+    pub fn split(&mut self) -> (&mut E, CancellationView<'_, E>, &E::Audience)
+    where
+        E: Event<Mutability = Mutable>,
+    {
+        (
+            self.event,
+            CancellationView(self.cancellation.borrow_mut()),
+            self.audience,
+        )
+    }
+
+    /// Consumes the input, returning the reference to the event, the reference to its cancellation
+    /// state, and its audience, for the whole duration of the dispatch.
+    ///
+    /// Unlike with [`Receive::split`], the parts outlive the input, e.g. to store them in a helper
+    /// struct. The input can't [defer](Receive::defer_until) the dispatch anymore afterwards.
+    pub fn into_parts(
+        self,
+    ) -> (
+        MutabilityRef<'event, E>,
+        CancellationMut<'event, E>,
+        &'event E::Audience,
+    ) {
+        (self.event, self.cancellation, self.audience)
+    }
+
+    /// Suspends the dispatch after this handler, so that the remaining lower priority handlers only
+    /// run once the condition returns `true`, e.g. after the player confirmed a deletion:
+    ///
+    /// ```rust
+    /// # use bevy_ecs::system::{Res, Resource};
+    /// # use bevy_eventbus::prelude::*;
+    /// # #[derive(Clone)]
+    /// # struct DeleteSave;
+    /// # impl BusEvent for DeleteSave {
+    /// #     type Mutability = Immutable;
+    /// #     type Cancellation = bool;
+    /// #     type Audience = ();
+    /// # }
+    /// #[derive(Resource)]
+    /// struct Confirmed(bool);
+    ///
+    /// fn confirm_deletion(mut event: Receive<DeleteSave>) {
+    ///     // ...open a "really delete?" dialog...
+    ///     event.defer_until(|confirmed: Res<Confirmed>| confirmed.0);
+    /// }
+    /// ```
+    ///
+    /// The bus parks a clone of the event in the [`ParkedEvents`](crate::ParkedEvents), and checks
+    /// the condition once per frame. The post itself returns as if the event wasn't cancelled, and
+    /// [`Event::after_dispatch`] isn't run again when the remaining handlers do. Cancel the event
+    /// instead to drop the remaining handlers for good.
+    pub fn defer_until<M>(&mut self, condition: impl Condition<M>)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        let Some(deferral) = self.deferral.as_deref_mut() else {
+            warn!(
+                "Can't defer {} outside of a handler dispatch",
+                std::any::type_name::<E>()
+            );
+            return;
+        };
+        let condition: BoxedCondition = Box::new(IntoSystem::into_system(condition));
+        *deferral = Some(Deferral::new(condition));
+    }
+
+    /// Returns the intended audience of the event.
+    pub fn audience(&self) -> &E::Audience {
+        self.audience
+    }
+
+    /// Returns the target entity of the event.
+    pub fn target(&self) -> Entity
+    where
+        E: Event<Audience: Unicast>,
+    {
+        self.audience.target()
+    }
+
+    /// Returns the target entities of the event.
+    pub fn targets(&self) -> impl Iterator<Item = Entity> + '_
+    where
+        E: Event<Audience: Multicast>,
+    {
+        self.audience.targets()
+    }
+}
+
+/// Mutable view of the cancellation state of an [`Event`], split off a [`Receive`] with
+/// [`Receive::split`].
+pub struct CancellationView<'a, E: Event>(&'a mut E::Cancellation);
+
+impl<E: Event> CancellationView<'_, E> {
+    /// Returns `true` if the event was cancelled, see [`Receive::cancelled`].
+    pub fn cancelled(&self) -> bool {
+        self.0.cancelled()
+    }
+
+    /// Cancels the event from being processed further, see [`Receive::cancel`].
+    pub fn cancel(&mut self)
+    where
+        E: Event<Cancellation: Cancellable>,
+    {
+        self.0.cancel();
+    }
+
+    /// Cancels the event from being processed further with a value, see [`Receive::cancel_with`].
+    pub fn cancel_with<T>(&mut self, value: T)
+    where
+        E: Event<Cancellation: CancellableWith<T>>,
+    {
+        self.0.cancel_with(value);
+    }
+
+    /// Reverts the cancellation of the event, see [`Receive::uncancel`].
+    pub fn uncancel(&mut self) {
+        self.0.uncancel();
+    }
+
+    /// Returns the cancellation state.
+    pub fn get(&self) -> &E::Cancellation {
+        self.0
+    }
+}
+
+impl<E: Event> SystemInput for Receive<'_, E> {
+    type Param<'i> = Receive<'i, E>;
+    type Inner<'i> = Receive<'i, E>;
+
+    fn wrap(this: Self::Inner<'_>) -> Self::Param<'_> {
+        this
+    }
+}
+
+impl<E: Event> Deref for Receive<'_, E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        self.event.borrow()
+    }
+}
+
+impl<E: Event<Mutability = Mutable>> DerefMut for Receive<'_, E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.event
+    }
+}
+
+impl<E: Event> AsRef<E> for Receive<'_, E> {
+    fn as_ref(&self) -> &E {
+        self.event.borrow()
+    }
+}
+
+impl<E: Event<Mutability = Mutable>> AsMut<E> for Receive<'_, E> {
+    fn as_mut(&mut self) -> &mut E {
+        self.event
+    }
+}
@@ -0,0 +1,66 @@
+use std::{collections::HashMap, hash::Hash};
+
+use bevy_ecs::system::Resource;
+
+use crate::{Event, HandlerRegistry};
+
+/// [`Resource`] which stores the handlers for [`Event`] `E` that are subscribed to a key of type
+/// `K`, such as a chat room or a map shard, bucketed by key.
+///
+/// Keyed handlers are added with [`WorldEventBus::add_keyed_handler`], and only run for events
+/// posted to their key with [`WorldEventBus::post_keyed`], so that dispatching only visits the
+/// handlers of that key. Unkeyed handlers for `E` don't run for keyed posts, and vice versa.
+///
+/// Each key has its own [`HandlerRegistry`], which orders its handlers like usual.
+///
+/// [`WorldEventBus::add_keyed_handler`]: crate::WorldEventBus::add_keyed_handler
+/// [`WorldEventBus::post_keyed`]: crate::WorldEventBus::post_keyed
+#[derive(Resource)]
+pub struct KeyedHandlers<E: Event, K: Eq + Hash + Send + Sync + 'static> {
+    buckets: HashMap<K, HandlerRegistry<E>>,
+}
+
+impl<E: Event, K: Eq + Hash + Send + Sync + 'static> Default for KeyedHandlers<E, K> {
+    fn default() -> Self {
+        Self {
+            buckets: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Event, K: Eq + Hash + Send + Sync + 'static> KeyedHandlers<E, K> {
+    /// Returns the [`HandlerRegistry`] of the key, if any handlers were added for it.
+    pub fn get(&self, key: &K) -> Option<&HandlerRegistry<E>> {
+        self.buckets.get(key)
+    }
+
+    /// Returns the [`HandlerRegistry`] of the key mutably, if any handlers were added for it.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut HandlerRegistry<E>> {
+        self.buckets.get_mut(key)
+    }
+
+    /// Returns the [`HandlerRegistry`] of the key, inserting an empty one if needed.
+    pub fn get_or_insert(&mut self, key: K) -> &mut HandlerRegistry<E> {
+        self.buckets.entry(key).or_default()
+    }
+
+    /// Removes all handlers of the key, returning its [`HandlerRegistry`] if present.
+    pub fn remove(&mut self, key: &K) -> Option<HandlerRegistry<E>> {
+        self.buckets.remove(key)
+    }
+
+    /// Returns an iterator over all keys with handlers.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.buckets.keys()
+    }
+
+    /// Returns the number of keys with handlers.
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Returns `true` if no key has handlers.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+}
@@ -0,0 +1,176 @@
+use std::{any::TypeId, collections::HashSet, mem, ops::Deref};
+
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap, EntityMapper, MapEntities},
+    system::{ResMut, Resource},
+    world::World,
+};
+
+use crate::{
+    config::priority::Last, Audience, Event, Immutable, IntoHandlerConfig, Receive, WorldEventBus,
+};
+
+/// A type-erased mirrored post, ready to be posted to the presentation world.
+type MirroredPost = Box<dyn FnOnce(&mut World, &mut EntityHashMap<Entity>) + Send + Sync>;
+
+/// Two worlds split into an authoritative simulation and its presentation, where posts made in the
+/// simulation are mirrored read-only into the presentation world's event bus.
+///
+/// Posts of the event types registered with [`BusPair::mirror`] are buffered after their last
+/// handler ran in the simulation world, and posted as [`Mirrored`] events in the presentation
+/// world on the next [`BusPair::sync`], which is typically called once per frame. Cancelled posts
+/// aren't mirrored, and mirrored posts see the event as the simulation's handlers left it.
+///
+/// Entities in the events and their audiences are mapped from the simulation world to the
+/// presentation world. Simulation entities without a mapping, see [`BusPair::map_entity`], are
+/// mapped to new empty presentation entities.
+///
+/// ```rust
+/// # use bevy_ecs::{entity::{Entity, EntityMapper, MapEntities}, world::World};
+/// # use bevy_eventbus::{prelude::*, BusPair, Mirrored};
+/// #[derive(Clone)]
+/// struct Damaged(u32);
+///
+/// impl BusEvent for Damaged {
+///     type Mutability = Immutable;
+///     type Cancellation = ();
+///     type Audience = Entity;
+/// }
+///
+/// impl MapEntities for Damaged {
+///     fn map_entities<M: EntityMapper>(&mut self, _mapper: &mut M) {}
+/// }
+///
+/// let mut pair = BusPair::new(World::new(), World::new());
+/// pair.mirror::<Damaged>();
+/// pair.presentation.add_handler(|event: Receive<Mirrored<Damaged>>| {
+///     // Play a hit animation on `event.target()`...
+/// });
+///
+/// let enemy = pair.simulation.spawn_empty().id();
+/// pair.simulation.post_to(Damaged(5), enemy);
+/// assert_eq!(pair.sync(), 1);
+/// ```
+pub struct BusPair {
+    /// The authoritative world, whose posts are mirrored.
+    pub simulation: World,
+    /// The world that presents the simulation, which receives the mirrored posts.
+    pub presentation: World,
+    entities: EntityHashMap<Entity>,
+}
+
+/// [`Resource`] which buffers the posts of the simulation world of a [`BusPair`] until they are
+/// mirrored.
+#[derive(Resource, Default)]
+struct MirroredPosts {
+    /// The mirrored event types.
+    types: HashSet<TypeId>,
+    posts: Vec<MirroredPost>,
+}
+
+/// [`Event`] posted in the presentation world of a [`BusPair`] for every post of [`Event`] `E` in
+/// its simulation world.
+///
+/// Mirrored events are read-only: they can't be modified or cancelled, as the simulation already
+/// finished handling them.
+pub struct Mirrored<E: Event>(pub E);
+
+impl<E: Event> Event for Mirrored<E> {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = E::Audience;
+}
+
+impl<E: Event> Deref for Mirrored<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl BusPair {
+    /// Creates a pair of the simulation and presentation worlds, without any mirrored events.
+    pub fn new(simulation: World, presentation: World) -> Self {
+        Self {
+            simulation,
+            presentation,
+            entities: EntityHashMap::default(),
+        }
+    }
+
+    /// Mirrors the posts of [`Event`] `E` from the simulation world into the presentation world.
+    ///
+    /// Mirroring an event type more than once has no effect.
+    pub fn mirror<E>(&mut self) -> &mut Self
+    where
+        E: Event + Clone + MapEntities + Send + Sync,
+        E::Audience: Clone + Send + Sync,
+    {
+        let mut mirrored = self
+            .simulation
+            .get_resource_or_insert_with(MirroredPosts::default);
+        if !mirrored.types.insert(TypeId::of::<E>()) {
+            return self;
+        }
+
+        self.simulation.add_handler(
+            (|event: Receive<E>, mut mirrored: ResMut<MirroredPosts>| {
+                let mut mirror = (*event).clone();
+                let mut audience = event.audience().clone();
+                mirrored.posts.push(Box::new(
+                    move |world: &mut World, entities: &mut EntityHashMap<Entity>| {
+                        let mut mapper = PairMapper {
+                            entities,
+                            world: &mut *world,
+                        };
+                        mirror.map_entities(&mut mapper);
+                        audience.map_targets(&mut mapper);
+                        world.post_to(Mirrored(mirror), audience);
+                    },
+                ));
+            })
+            .priority(Last),
+        );
+        self
+    }
+
+    /// Maps a simulation entity to the presentation entity that represents it.
+    pub fn map_entity(&mut self, simulation: Entity, presentation: Entity) {
+        self.entities.insert(simulation, presentation);
+    }
+
+    /// Returns the presentation entity that represents the simulation entity, if it is mapped.
+    pub fn presentation_entity(&self, simulation: Entity) -> Option<Entity> {
+        self.entities.get(&simulation).copied()
+    }
+
+    /// Posts the buffered posts of the simulation world into the presentation world, in the order
+    /// they were made. Returns the number of posts mirrored.
+    pub fn sync(&mut self) -> usize {
+        let Some(mut mirrored) = self.simulation.get_resource_mut::<MirroredPosts>() else {
+            return 0;
+        };
+        let posts = mem::take(&mut mirrored.posts);
+        let len = posts.len();
+        for post in posts {
+            post(&mut self.presentation, &mut self.entities);
+        }
+        len
+    }
+}
+
+/// [`EntityMapper`] from the simulation world of a [`BusPair`] into its presentation world.
+struct PairMapper<'a> {
+    entities: &'a mut EntityHashMap<Entity>,
+    world: &'a mut World,
+}
+
+impl EntityMapper for PairMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        *self
+            .entities
+            .entry(entity)
+            .or_insert_with(|| self.world.spawn_empty().id())
+    }
+}
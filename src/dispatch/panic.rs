@@ -0,0 +1,70 @@
+use std::{any::type_name, borrow::Cow, fmt::Debug};
+
+use bevy_ecs::world::World;
+use bevy_utils::tracing::error;
+
+use crate::{Event, HandlerRegistry};
+
+/// Formats the payload and audience of [`Event`] `E` when one of its handlers panics, see
+/// [`WorldEventBus::dump_on_panic`](crate::WorldEventBus::dump_on_panic).
+pub(crate) struct PanicDump<E: Event> {
+    format: fn(&E, &E::Audience) -> (String, String),
+    max_len: usize,
+}
+
+impl<E: Event> Clone for PanicDump<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Event> Copy for PanicDump<E> {}
+
+impl<E: Event<Audience: Debug> + Debug> PanicDump<E> {
+    /// Dumps the payload and audience, each truncated to `max_len` characters.
+    pub(crate) fn new(max_len: usize) -> Self {
+        Self {
+            format: |event, audience| (format!("{event:?}"), format!("{audience:?}")),
+            max_len,
+        }
+    }
+}
+
+/// Logs which handler panicked while handling which post of [`Event`] `E`, including the event's
+/// payload and audience if dumps are enabled for it.
+pub(crate) fn report_handler_panic<E: Event>(
+    world: &World,
+    handler: Cow<'static, str>,
+    priority: i32,
+    event: &E,
+    audience: &E::Audience,
+) {
+    let dump = world
+        .get_resource::<HandlerRegistry<E>>()
+        .and_then(|registry| registry.hooks().panic_dump);
+    match dump {
+        Some(dump) => {
+            let (payload, audience) = (dump.format)(event, audience);
+            error!(
+                "Handler {handler} (priority {priority}) panicked while handling {}\n  \
+                 payload: {}\n  audience: {}",
+                type_name::<E>(),
+                truncate(payload, dump.max_len),
+                truncate(audience, dump.max_len),
+            );
+        }
+        None => error!(
+            "Handler {handler} (priority {priority}) panicked while handling {}",
+            type_name::<E>()
+        ),
+    }
+}
+
+/// Truncates the dump to `max_len` characters.
+fn truncate(mut dump: String, max_len: usize) -> String {
+    if let Some((index, _)) = dump.char_indices().nth(max_len) {
+        dump.truncate(index);
+        dump.push_str("...");
+    }
+    dump
+}
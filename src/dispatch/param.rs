@@ -0,0 +1,74 @@
+use std::{marker::PhantomData, time::Duration};
+
+use bevy_ecs::system::{Commands, SystemParam};
+
+use crate::{CommandEventBus, Event};
+
+/// [`SystemParam`] for posting [`Event`] `E` from regular systems and handlers.
+///
+/// Posts are deferred like any other [`Commands`], and are applied to the world when the system's
+/// commands are applied.
+#[derive(SystemParam)]
+pub struct Poster<'w, 's, E: Event> {
+    commands: Commands<'w, 's>,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E: Event<Audience: Send> + Send> Poster<'_, '_, E> {
+    /// Posts an [`Event`] to the world.
+    pub fn post(&mut self, event: E)
+    where
+        E: Event<Audience = ()>,
+    {
+        self.commands.post(event);
+    }
+
+    /// Posts an [`Event`] to the world with a specific [`Audience`](Event::Audience).
+    pub fn post_to(&mut self, event: E, audience: E::Audience) {
+        self.commands.post_to(event, audience);
+    }
+
+    /// Posts the [`Event`] built by `f` to the world, only calling `f` if any handler would
+    /// receive it, see [`WorldEventBus::post_with`](crate::WorldEventBus::post_with).
+    pub fn post_with(&mut self, f: impl FnOnce() -> E + Send + 'static)
+    where
+        E: Event<Audience = ()>,
+    {
+        self.commands.post_with(f);
+    }
+
+    /// Posts the [`Event`] built by `f` to the world with a specific
+    /// [`Audience`](Event::Audience), only calling `f` if any handler would receive it.
+    pub fn post_with_to(&mut self, f: impl FnOnce() -> E + Send + 'static, audience: E::Audience) {
+        self.commands.post_with_to(f, audience);
+    }
+
+    /// Queues an [`Event`] on the [`EventQueue`](crate::EventQueue).
+    pub fn enqueue(&mut self, event: E)
+    where
+        E: Event<Audience = ()>,
+    {
+        self.commands.enqueue(event);
+    }
+
+    /// Queues an [`Event`] with a specific [`Audience`](Event::Audience) on the
+    /// [`EventQueue`](crate::EventQueue).
+    pub fn enqueue_to(&mut self, event: E, audience: E::Audience) {
+        self.commands.enqueue_to(event, audience);
+    }
+
+    /// Queues an [`Event`] on the [`EventQueue`](crate::EventQueue), which is dropped if it isn't
+    /// flushed within `ttl`.
+    pub fn enqueue_with_ttl(&mut self, event: E, ttl: Duration)
+    where
+        E: Event<Audience = ()>,
+    {
+        self.commands.enqueue_with_ttl(event, ttl);
+    }
+
+    /// Queues an [`Event`] with a specific [`Audience`](Event::Audience) on the
+    /// [`EventQueue`](crate::EventQueue), which is dropped if it isn't flushed within `ttl`.
+    pub fn enqueue_with_ttl_to(&mut self, event: E, audience: E::Audience, ttl: Duration) {
+        self.commands.enqueue_with_ttl_to(event, audience, ttl);
+    }
+}
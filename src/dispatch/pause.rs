@@ -0,0 +1,167 @@
+use std::{
+    any::{type_name, TypeId},
+    borrow::Cow,
+    collections::{HashSet, VecDeque},
+    thread::{self, ThreadId},
+};
+
+use bevy_ecs::{system::Resource, world::World};
+use bevy_utils::tracing::warn;
+
+use crate::{dispatch::world::post_with_options, Event, EventCatalog, EventContext, PostOptions};
+
+/// A type-erased deferred post, ready to be dispatched to the world.
+type PausedPost = Box<dyn FnOnce(&mut World)>;
+
+/// [`Resource`] which pauses the event bus, deferring posts until it is resumed with
+/// [`WorldEventBus::resume_event_bus`].
+///
+/// The bus is either paused for all events, or only for the event types paused with
+/// [`WorldEventBus::pause_events`] and the events with the [tags](crate::EventInfo::tag) paused
+/// with [`WorldEventBus::pause_tagged_events`].
+///
+/// While paused, posts of owned events of a paused type, including posts made with
+/// [`Commands`](bevy_ecs::system::Commands) and flushed from the [`EventQueue`](crate::EventQueue),
+/// are buffered instead of dispatched, and report the default cancellation state. On resume they
+/// are dispatched in the order they were posted.
+///
+/// Posts of event references, such as [`WorldEventBus::post_ref`], can't be buffered, so they are
+/// dropped with a warning instead of running any handlers. Posts made from another thread than the
+/// one that paused the bus are dispatched as usual, with a warning.
+///
+/// [`WorldEventBus::resume_event_bus`]: crate::WorldEventBus::resume_event_bus
+/// [`WorldEventBus::pause_events`]: crate::WorldEventBus::pause_events
+/// [`WorldEventBus::pause_tagged_events`]: crate::WorldEventBus::pause_tagged_events
+/// [`WorldEventBus::post_ref`]: crate::WorldEventBus::post_ref
+#[derive(Resource, Debug)]
+pub struct EventBusPause {
+    /// The paused event types, or `None` if all of them are paused.
+    types: Option<HashSet<TypeId>>,
+    /// The paused event tags, see [`EventInfo::tag`](crate::EventInfo::tag).
+    tags: HashSet<Cow<'static, str>>,
+    /// The thread that owns the buffered posts.
+    thread: ThreadId,
+}
+
+/// Non-send resource which buffers the posts deferred by the [`EventBusPause`].
+#[derive(Default)]
+struct PausedPosts(VecDeque<PausedPost>);
+
+/// The events to pause, see [`EventBusPause::pause`].
+pub(crate) enum PauseFilter {
+    All,
+    Type(TypeId),
+    Tag(Cow<'static, str>),
+}
+
+impl EventBusPause {
+    /// Returns `true` if posts of [`Event`] `E` are deferred, looking up its tags in the
+    /// [`EventCatalog`], if any.
+    pub fn is_paused<E: Event>(&self, catalog: Option<&EventCatalog>) -> bool {
+        let Some(types) = &self.types else {
+            return true;
+        };
+        types.contains(&TypeId::of::<E>())
+            || catalog
+                .and_then(EventCatalog::get::<E>)
+                .is_some_and(|info| info.tags.iter().any(|tag| self.tags.contains(tag)))
+    }
+
+    /// Pauses the events that pass the filter, in addition to those already paused.
+    pub(crate) fn pause(world: &mut World, filter: PauseFilter) {
+        if !world.contains_resource::<Self>() {
+            world.insert_resource(Self {
+                types: Some(HashSet::new()),
+                tags: HashSet::new(),
+                thread: thread::current().id(),
+            });
+            world.insert_non_send_resource(PausedPosts::default());
+        }
+
+        let mut pause = world.resource_mut::<Self>();
+        match filter {
+            PauseFilter::All => pause.types = None,
+            PauseFilter::Type(type_id) => {
+                if let Some(types) = &mut pause.types {
+                    types.insert(type_id);
+                }
+            }
+            PauseFilter::Tag(tag) => {
+                pause.tags.insert(tag);
+            }
+        }
+    }
+
+    /// Returns `true` if posts of [`Event`] `E` are paused on the current thread, i.e. posts of
+    /// owned events would be deferred.
+    pub(crate) fn applies<E: Event>(world: &World) -> bool {
+        let Some(pause) = world.get_resource::<Self>() else {
+            return false;
+        };
+        if !pause.is_paused::<E>(world.get_resource::<EventCatalog>()) {
+            return false;
+        }
+        if pause.thread != thread::current().id() {
+            warn!(
+                "Dispatched {} while the event bus is paused, as it was posted from another thread",
+                type_name::<E>()
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Returns `true` if a post of a reference to [`Event`] `E` must be dropped because it is
+    /// paused, as it can't be buffered, logging a warning.
+    pub(crate) fn rejects<E: Event>(world: &World) -> bool {
+        if !Self::applies::<E>(world) {
+            return false;
+        }
+        warn!(
+            "Dropped a post of a reference to {} while the event bus is paused",
+            type_name::<E>()
+        );
+        true
+    }
+
+    /// Resumes the event bus, dispatching all buffered posts in order.
+    /// Returns the number of posts dispatched.
+    pub(crate) fn resume(world: &mut World) -> usize {
+        if world.remove_resource::<Self>().is_none() {
+            return 0;
+        }
+
+        let posts = world
+            .remove_non_send_resource::<PausedPosts>()
+            .unwrap_or_default();
+        let len = posts.0.len();
+        for post in posts.0 {
+            post(world);
+        }
+        len
+    }
+
+    /// Buffers the post along with its options if [`Event`] `E` is paused, or hands the post back
+    /// otherwise.
+    pub(crate) fn defer<E: Event>(
+        world: &mut World,
+        event: E,
+        audience: E::Audience,
+        options: PostOptions,
+    ) -> Result<(), (E, E::Audience, PostOptions)> {
+        if !Self::applies::<E>(world) {
+            return Err((event, audience, options));
+        }
+
+        let parent = EventContext::parent(world);
+        world
+            .non_send_resource_mut::<PausedPosts>()
+            .0
+            .push_back(Box::new(move |world: &mut World| {
+                EventContext::resume(world, parent, |world| {
+                    post_with_options(world, event, audience, options)
+                });
+            }));
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use bevy_ecs::world::World;
+
+use crate::{
+    dispatch::{dispatch, world::post_with_options},
+    Event, EventBusPause, HandlerId, HandlerMutation, HandlerPriority, HandlerRegistry, Mutability,
+    Mutable, PostOptions, PostReport,
+};
+
+/// Builder for a post of [`Event`] `E` with options that only apply to that post, created with
+/// [`WorldEventBus::build_post`](crate::WorldEventBus::build_post).
+///
+/// The options also apply if the post is deferred, e.g. while the event bus is paused, but not to
+/// the posts made by its handlers, which run all of their handlers.
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::prelude::*;
+/// struct Alert;
+///
+/// impl BusEvent for Alert {
+///     type Mutability = Immutable;
+///     type Cancellation = bool;
+///     type Audience = ();
+/// }
+///
+/// let mut world = World::new();
+/// let echo = world.add_handler(|_event: Receive<Alert>| unreachable!());
+/// world.add_handler(|mut event: Receive<Alert>| event.cancel());
+///
+/// let report = world.build_post(Alert).excluding([echo]).reported();
+/// assert!(report.cancellation);
+/// ```
+#[must_use = "the event is only posted once the builder is sent"]
+pub struct PostBuilder<'w, E: Event> {
+    world: &'w mut World,
+    event: E,
+    audience: E::Audience,
+    options: PostOptions,
+}
+
+impl<'w, E: Event> PostBuilder<'w, E> {
+    pub(crate) fn new(world: &'w mut World, event: E, audience: E::Audience) -> Self {
+        Self {
+            world,
+            event,
+            audience,
+            options: PostOptions::default(),
+        }
+    }
+
+    /// Only runs the handlers with at least the priority, e.g. for admin tooling that needs to
+    /// bypass heavy gameplay handlers.
+    pub fn min_priority(mut self, priority: impl HandlerPriority) -> Self {
+        self.options.min_priority = Some(priority.priority());
+        self
+    }
+
+    /// Skips the handlers, e.g. so that the subsystem that originated the event doesn't receive
+    /// it and cause a feedback loop. Can be called several times to skip more handlers.
+    pub fn excluding(mut self, handlers: impl IntoIterator<Item = HandlerId<E>>) -> Self {
+        self.options
+            .excluded
+            .extend(handlers.into_iter().map(HandlerId::to_raw));
+        self
+    }
+
+    /// Posts the event, returning its final cancellation state, like
+    /// [`WorldEventBus::post_to`](crate::WorldEventBus::post_to).
+    pub fn send(self) -> E::Cancellation {
+        post_with_options(self.world, self.event, self.audience, self.options)
+    }
+
+    /// Posts the event, reporting which handler cancelled it.
+    ///
+    /// Unlike [`PostBuilder::tracked`], this works for any event, but doesn't track
+    /// modifications, so [`PostReport::mutated_by`] is always empty. While the event bus is paused,
+    /// the post is deferred and an empty report is returned, see [`EventBusPause`].
+    pub fn reported(self) -> PostReport<E> {
+        let Self {
+            world,
+            event,
+            audience,
+            options,
+        } = self;
+        let Err((mut event, audience, options)) =
+            EventBusPause::defer(world, event, audience, options)
+        else {
+            return PostReport::default();
+        };
+        if HandlerRegistry::<E>::throttled(world, &audience) {
+            return PostReport::default();
+        }
+        event.before_dispatch(world);
+        let mut cancelled_by = None;
+        let cancellation = dispatch::<E>(
+            world,
+            E::Mutability::to_ref(&mut event),
+            &audience,
+            &options,
+            |_, _, by| cancelled_by = by.cloned(),
+        );
+
+        PostReport {
+            cancellation,
+            mutated_by: Vec::new(),
+            cancelled_by,
+        }
+    }
+}
+
+impl<E: Event<Mutability = Mutable> + Clone + PartialEq> PostBuilder<'_, E> {
+    /// Posts the event, tracking which handlers modified it, and returns the modified event along
+    /// with the report.
+    ///
+    /// Modifications are detected by comparing a snapshot of the event before and after each
+    /// handler runs. While `E` is [paused](EventBusPause), or if its throttle drops the post, the
+    /// event isn't posted, and is returned as is with an empty report.
+    pub fn tracked(self) -> (E, PostReport<E>) {
+        let Self {
+            world,
+            mut event,
+            audience,
+            options,
+        } = self;
+        if EventBusPause::applies::<E>(world) || HandlerRegistry::<E>::throttled(world, &audience) {
+            return (event, PostReport::default());
+        }
+        event.before_dispatch(world);
+        let mut previous = event.clone();
+        let mut mutated_by = Vec::new();
+        let mut cancelled_by = None;
+        let cancellation = dispatch::<E>(
+            world,
+            &mut event,
+            &audience,
+            &options,
+            |entry, event, by| {
+                cancelled_by = by.cloned();
+                if *event != previous {
+                    mutated_by.push(HandlerMutation {
+                        id: entry.id,
+                        name: entry.handler.lock().name(),
+                    });
+                    previous = event.clone();
+                }
+            },
+        );
+
+        let report = PostReport {
+            cancellation,
+            mutated_by,
+            cancelled_by,
+        };
+        (event, report)
+    }
+}
@@ -0,0 +1,620 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::{entity::Entity, system::Resource, world::World};
+use parking_lot::Mutex;
+
+use crate::{
+    dispatch::{
+        sequence::SequenceNumber,
+        world::{post_now, post_with_options},
+    },
+    CorrelationId, Event, EventBusPause, EventContext, EventMeta, HandlerRegistry, Immutable,
+    PostOptions, WorldEventBus,
+};
+
+#[cfg(feature = "journal")]
+mod persist;
+
+#[cfg(feature = "journal")]
+pub use persist::*;
+
+/// A type-erased queued post, ready to be dispatched to the world.
+type QueuedPost = Box<dyn ErasedPost>;
+
+/// Clones a queued post of an [`Event`] type with rollback enabled, see
+/// [`EventQueue::enable_rollback`].
+type PostCloner = fn(&dyn Any) -> SnapshotPost;
+
+/// A queued post saved in an [`EventQueueSnapshot`].
+type SnapshotPost = Box<dyn ErasedPost + Sync>;
+
+trait ErasedPost: Send {
+    fn post(self: Box<Self>, world: &mut World);
+
+    /// Drops the post after its TTL elapsed, posting [`EventExpired`] if it has any handlers.
+    fn expire(self: Box<Self>, world: &mut World, now: Instant);
+
+    /// Drops the post without dispatching it, e.g. because a rollback rewound past it.
+    fn discard(self: Box<Self>, world: &mut World);
+
+    fn expires(&self) -> Option<Instant>;
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// A queued post of [`Event`] `E`, which inherits its correlation from `parent`, and is dropped if
+/// it isn't flushed before `expires`.
+struct QueuedEvent<E: Event> {
+    event: E,
+    audience: E::Audience,
+    parent: Option<EventMeta>,
+    /// The correlation the post continues, if it was restored from a backlog without its parent.
+    correlation: Option<CorrelationId>,
+    expires: Option<Instant>,
+    /// The target entity and its sequence number, if `E` is sequenced by target.
+    sequence: Option<SequenceNumber>,
+}
+
+impl<E: Event<Audience: Send> + Send> ErasedPost for QueuedEvent<E> {
+    fn post(self: Box<Self>, world: &mut World) {
+        let Self {
+            event,
+            audience,
+            parent,
+            correlation,
+            sequence,
+            ..
+        } = *self;
+        let options = PostOptions {
+            correlation,
+            ..PostOptions::default()
+        };
+        let Some(number) = sequence else {
+            EventContext::resume(world, parent, |world| {
+                post_with_options(world, event, audience, options)
+            });
+            return;
+        };
+        let Some(sequencer) = HandlerRegistry::<E>::sequenced(world) else {
+            EventContext::resume(world, parent, |world| {
+                post_now(world, event, audience, options)
+            });
+            return;
+        };
+        EventContext::resume(world, parent, |world| {
+            // Posts deferred by a pause are sequenced again on resume, so skip this number.
+            match EventBusPause::defer(world, event, audience, options) {
+                Ok(()) => (sequencer.skip)(world, number),
+                Err((event, audience, options)) => {
+                    (sequencer.deliver)(world, number, event, audience, options);
+                }
+            }
+        });
+    }
+
+    fn expire(self: Box<Self>, world: &mut World, now: Instant) {
+        self.skip_sequence(world);
+        if !world.contains_resource::<HandlerRegistry<EventExpired<E>>>() {
+            return;
+        }
+
+        let Self {
+            event,
+            audience,
+            parent,
+            expires,
+            ..
+        } = *self;
+        let expired = EventExpired {
+            event,
+            audience,
+            late: expires.map_or(Duration::ZERO, |expires| now - expires),
+        };
+        EventContext::resume(world, parent, |world| world.post(expired));
+    }
+
+    fn discard(self: Box<Self>, world: &mut World) {
+        self.skip_sequence(world);
+    }
+
+    fn expires(&self) -> Option<Instant> {
+        self.expires
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<E: Event> QueuedEvent<E> {
+    /// Skips the sequence number of the post, so that later posts to its target aren't held back.
+    fn skip_sequence(&self, world: &mut World) {
+        if let (Some(number), Some(sequencer)) =
+            (self.sequence, HandlerRegistry::<E>::sequenced(world))
+        {
+            (sequencer.skip)(world, number);
+        }
+    }
+}
+
+/// Clones a queued post for an [`EventQueueSnapshot`]. The clone doesn't keep the sequence number
+/// of the post, as the number may be delivered or reused by the time the snapshot is restored, so
+/// restored posts are sequenced again when they are flushed.
+fn clone_post<E>(post: &dyn Any) -> SnapshotPost
+where
+    E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+{
+    let post = post
+        .downcast_ref::<QueuedEvent<E>>()
+        .expect("queued post of the wrong type");
+    Box::new(QueuedEvent {
+        event: post.event.clone(),
+        audience: post.audience.clone(),
+        parent: post.parent,
+        correlation: post.correlation,
+        expires: post.expires,
+        sequence: None,
+    })
+}
+
+/// [`Event`] posted when a queued post of [`Event`] `E` is dropped because its TTL elapsed before
+/// the [`EventQueue`] was flushed, see [`WorldEventBus::enqueue_with_ttl`].
+///
+/// Only posted if any handlers are registered for it.
+pub struct EventExpired<E: Event> {
+    /// The event that expired.
+    pub event: E,
+    /// The audience the event was queued for.
+    pub audience: E::Audience,
+    /// How long ago the event expired.
+    pub late: Duration,
+}
+
+impl<E: Event> Event for EventExpired<E> {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+/// [`Resource`] which stores posts that are deferred until the next flush.
+///
+/// # Fairness
+///
+/// Posts are grouped into one lane per [`Event`] type. When flushing with a budget, lanes are
+/// visited round-robin, one post at a time, so that one spammy event type can't starve the others.
+/// Lanes left with pending posts at the end of a budgeted flush have their starvation counter
+/// incremented, see [`EventQueue::starvation`].
+///
+/// With [per-source fairness](EventQueue::set_per_source_fairness) enabled, posts within a lane
+/// are additionally visited round-robin by source entity (see [`WorldEventBus::enqueue_from`]).
+///
+/// # Expiry
+///
+/// Posts can be queued with a TTL, see [`WorldEventBus::enqueue_with_ttl`]. Posts that weren't
+/// flushed before their TTL elapsed are dropped instead of dispatched, e.g. so that stale input
+/// isn't applied seconds late after a hitch. Dropped posts are counted per lane, see
+/// [`EventQueue::expired`], and notified with [`EventExpired`].
+///
+/// # Rollback
+///
+/// The pending posts of [`Event`] types with [rollback](EventQueue::enable_rollback) enabled can be
+/// saved with [`EventQueue::snapshot`], and rewound to with [`EventQueue::restore`].
+///
+/// # Persistence
+///
+/// With the `journal` feature, the pending posts of [persisted](EventQueue::persist) event types
+/// can be saved into a backlog with `EventQueue::save_backlog` and queued again after a restart
+/// with `WorldEventBus::restore_queue_backlog`, see `QueueBacklogPlugin`.
+#[derive(Resource, Default)]
+pub struct EventQueue {
+    state: Mutex<QueueState>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    lanes: Vec<Lane>,
+    lane_index: HashMap<TypeId, usize>,
+    cursor: usize,
+    next_sequence: u64,
+    per_source_fairness: bool,
+    /// Posts rewound by a rollback, whose sequence numbers are skipped on the next flush.
+    discarded: Vec<QueuedPost>,
+    /// The event types whose pending posts are persisted in backlogs.
+    #[cfg(feature = "journal")]
+    journal: persist::QueueJournal,
+}
+
+impl QueueState {
+    /// Returns the lane for [`Event`] `E`, creating it if needed.
+    fn lane<E: Event>(&mut self) -> &mut Lane {
+        let index = *self.lane_index.entry(TypeId::of::<E>()).or_insert_with(|| {
+            self.lanes.push(Lane {
+                type_id: TypeId::of::<E>(),
+                name: type_name::<E>(),
+                cloner: None,
+                sources: VecDeque::new(),
+                len: 0,
+                starved: 0,
+                expired: 0,
+            });
+            self.lanes.len() - 1
+        });
+        &mut self.lanes[index]
+    }
+}
+
+struct Lane {
+    type_id: TypeId,
+    name: &'static str,
+    cloner: Option<PostCloner>,
+    sources: VecDeque<SourceQueue>,
+    len: usize,
+    starved: u64,
+    expired: u64,
+}
+
+/// Pending posts of a single source within a [`Lane`], tagged with their queueing sequence.
+struct SourceQueue {
+    source: Option<Entity>,
+    posts: VecDeque<(u64, QueuedPost)>,
+}
+
+impl Lane {
+    fn pop(&mut self, per_source_fairness: bool) -> Option<QueuedPost> {
+        let index = if per_source_fairness {
+            0
+        } else {
+            // Without per-source fairness, posts are taken in the order they were queued.
+            self.sources
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, queue)| queue.posts.front().map(|(seq, _)| *seq))
+                .map(|(index, _)| index)?
+        };
+
+        let mut queue = self.sources.remove(index)?;
+        let (_, post) = queue.posts.pop_front()?;
+        if !queue.posts.is_empty() {
+            self.sources.push_back(queue);
+        }
+        self.len -= 1;
+        Some(post)
+    }
+}
+
+/// Statistics about a single lane of the [`EventQueue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueLaneStats {
+    /// The type name of the [`Event`] this lane is for.
+    pub name: &'static str,
+    /// The number of posts currently pending in this lane.
+    pub pending: usize,
+    /// The number of budgeted flushes that ended with posts still pending in this lane.
+    pub starved: u64,
+    /// The number of posts in this lane dropped because their TTL elapsed.
+    pub expired: u64,
+}
+
+impl EventQueue {
+    /// Queues a post for the next flush.
+    pub fn push<E: Event<Audience: Send> + Send>(
+        &mut self,
+        source: Option<Entity>,
+        event: E,
+        audience: E::Audience,
+    ) {
+        self.push_caused_by(source, event, audience, None, None, None);
+    }
+
+    /// Queues a post for the next flush, which is dropped if it isn't flushed within `ttl`.
+    pub fn push_with_ttl<E: Event<Audience: Send> + Send>(
+        &mut self,
+        source: Option<Entity>,
+        event: E,
+        audience: E::Audience,
+        ttl: Duration,
+    ) {
+        self.push_caused_by(source, event, audience, None, Some(ttl), None);
+    }
+
+    /// Queues a post for the next flush, which inherits its correlation from `parent`, is
+    /// dropped if it isn't flushed within `ttl`, and is delivered in `sequence` if `E` is
+    /// sequenced by target.
+    pub(crate) fn push_caused_by<E: Event<Audience: Send> + Send>(
+        &mut self,
+        source: Option<Entity>,
+        event: E,
+        audience: E::Audience,
+        parent: Option<EventMeta>,
+        ttl: Option<Duration>,
+        sequence: Option<SequenceNumber>,
+    ) {
+        self.push_post(
+            source,
+            QueuedEvent {
+                event,
+                audience,
+                parent,
+                correlation: None,
+                expires: ttl.map(|ttl| Instant::now() + ttl),
+                sequence,
+            },
+        );
+    }
+
+    /// Queues a post after all pending posts.
+    fn push_post<E: Event<Audience: Send> + Send>(
+        &mut self,
+        source: Option<Entity>,
+        post: QueuedEvent<E>,
+    ) {
+        let state = self.state.get_mut();
+        let order = state.next_sequence;
+        state.next_sequence += 1;
+
+        let lane = state.lane::<E>();
+        let post: QueuedPost = Box::new(post);
+        match lane.sources.iter_mut().find(|queue| queue.source == source) {
+            Some(queue) => queue.posts.push_back((order, post)),
+            None => lane.sources.push_back(SourceQueue {
+                source,
+                posts: VecDeque::from([(order, post)]),
+            }),
+        }
+        lane.len += 1;
+    }
+
+    /// Returns the total number of pending posts.
+    pub fn len(&self) -> usize {
+        self.state.lock().lanes.iter().map(|lane| lane.len).sum()
+    }
+
+    /// Returns `true` if there are no pending posts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of pending posts for [`Event`] `E`.
+    pub fn pending<E: Event>(&self) -> usize {
+        let state = self.state.lock();
+        state
+            .lane_index
+            .get(&TypeId::of::<E>())
+            .map_or(0, |&lane| state.lanes[lane].len)
+    }
+
+    /// Returns the number of budgeted flushes that ended with posts for [`Event`] `E` still pending.
+    pub fn starvation<E: Event>(&self) -> u64 {
+        let state = self.state.lock();
+        state
+            .lane_index
+            .get(&TypeId::of::<E>())
+            .map_or(0, |&lane| state.lanes[lane].starved)
+    }
+
+    /// Returns the number of posts for [`Event`] `E` dropped because their TTL elapsed.
+    pub fn expired<E: Event>(&self) -> u64 {
+        let state = self.state.lock();
+        state
+            .lane_index
+            .get(&TypeId::of::<E>())
+            .map_or(0, |&lane| state.lanes[lane].expired)
+    }
+
+    /// Returns statistics for every lane of the queue, in round-robin order.
+    pub fn lane_stats(&self) -> Vec<QueueLaneStats> {
+        self.state
+            .lock()
+            .lanes
+            .iter()
+            .map(|lane| QueueLaneStats {
+                name: lane.name,
+                pending: lane.len,
+                starved: lane.starved,
+                expired: lane.expired,
+            })
+            .collect()
+    }
+
+    /// Sets whether posts within a lane are visited round-robin by source entity.
+    pub fn set_per_source_fairness(&mut self, enabled: bool) {
+        self.state.get_mut().per_source_fairness = enabled;
+    }
+
+    /// Enables [rollback](EventQueue::snapshot) for the pending posts of [`Event`] `E`.
+    pub fn enable_rollback<E>(&mut self)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        self.state.get_mut().lane::<E>().cloner = Some(clone_post::<E>);
+    }
+
+    /// Saves the pending posts of all [`Event`] types with rollback enabled, along with the
+    /// round-robin position of the queue.
+    pub fn snapshot(&self) -> EventQueueSnapshot {
+        let state = self.state.lock();
+        let lanes = state
+            .lanes
+            .iter()
+            .filter_map(|lane| {
+                let cloner = lane.cloner?;
+                let sources = lane
+                    .sources
+                    .iter()
+                    .map(|queue| {
+                        let posts = queue
+                            .posts
+                            .iter()
+                            .map(|(sequence, post)| (*sequence, cloner(post.as_any())))
+                            .collect();
+                        SourceSnapshot {
+                            source: queue.source,
+                            posts,
+                        }
+                    })
+                    .collect();
+                Some(LaneSnapshot {
+                    type_id: lane.type_id,
+                    cloner,
+                    sources,
+                })
+            })
+            .collect();
+
+        EventQueueSnapshot {
+            lanes,
+            cursor: state.cursor,
+            next_sequence: state.next_sequence,
+        }
+    }
+
+    /// Rewinds the pending posts of all [`Event`] types with rollback enabled to a snapshot taken
+    /// with [`EventQueue::snapshot`]. Posts of other event types are left as is.
+    ///
+    /// The snapshot is left intact, so it can be restored again. Restored posts of [`Event`] types
+    /// [sequenced by target](crate::WorldEventBus::sequence_by_target) are sequenced again when
+    /// they are flushed, after any posts to the same entity made in the meantime.
+    pub fn restore(&mut self, snapshot: &EventQueueSnapshot) {
+        let state = self.state.get_mut();
+        for lane in state.lanes.iter_mut().filter(|lane| lane.cloner.is_some()) {
+            let discarded = lane.sources.drain(..).flat_map(|queue| queue.posts);
+            state.discarded.extend(discarded.map(|(_, post)| post));
+            lane.len = 0;
+        }
+
+        for saved in &snapshot.lanes {
+            let Some(&index) = state.lane_index.get(&saved.type_id) else {
+                continue;
+            };
+            let lane = &mut state.lanes[index];
+            for saved_source in &saved.sources {
+                let posts = saved_source
+                    .posts
+                    .iter()
+                    .map(|(sequence, post)| {
+                        (*sequence, (saved.cloner)(post.as_any()) as QueuedPost)
+                    })
+                    .collect::<VecDeque<_>>();
+                lane.len += posts.len();
+                lane.sources.push_back(SourceQueue {
+                    source: saved_source.source,
+                    posts,
+                });
+            }
+        }
+
+        if !state.lanes.is_empty() {
+            state.cursor = snapshot.cursor % state.lanes.len();
+        }
+        state.next_sequence = state.next_sequence.max(snapshot.next_sequence);
+    }
+
+    /// Takes the next post in round-robin order, along with whether its TTL elapsed by `now`.
+    fn pop(&mut self, now: Instant) -> Option<(QueuedPost, bool)> {
+        let state = self.state.get_mut();
+        let lanes = state.lanes.len();
+        for offset in 0..lanes {
+            let index = (state.cursor + offset) % lanes;
+            let lane = &mut state.lanes[index];
+            if let Some(post) = lane.pop(state.per_source_fairness) {
+                state.cursor = (index + 1) % lanes;
+                let expired = post.expires().is_some_and(|expires| expires <= now);
+                if expired {
+                    lane.expired += 1;
+                }
+                return Some((post, expired));
+            }
+        }
+        None
+    }
+
+    /// Records starvation for every lane that still has pending posts.
+    fn record_starvation(&mut self) {
+        for lane in &mut self.state.get_mut().lanes {
+            if lane.len > 0 {
+                lane.starved += 1;
+            }
+        }
+    }
+
+    /// Flushes up to `budget` pending posts into the world, or all of them if `budget` is `None`.
+    /// Returns the number of posts dispatched.
+    ///
+    /// At most as many posts as were pending when the flush started are dispatched, so handlers
+    /// queueing more posts can't keep a flush going forever. Expired posts are dropped, and count
+    /// towards the budget but not the returned number.
+    pub fn flush(world: &mut World, budget: Option<usize>) -> usize {
+        let Some(mut queue) = world.get_resource_mut::<Self>() else {
+            return 0;
+        };
+        let discarded = std::mem::take(&mut queue.state.get_mut().discarded);
+        for post in discarded {
+            post.discard(world);
+        }
+
+        let pending = world.resource::<Self>().len();
+        let limit = budget.map_or(pending, |budget| budget.min(pending));
+
+        let now = Instant::now();
+        let mut flushed = 0;
+        for _ in 0..limit {
+            let Some((post, expired)) = world.resource_mut::<Self>().pop(now) else {
+                break;
+            };
+            if expired {
+                post.expire(world, now);
+            } else {
+                post.post(world);
+                flushed += 1;
+            }
+        }
+
+        if budget.is_some() {
+            world.resource_mut::<Self>().record_starvation();
+        }
+
+        flushed
+    }
+}
+
+/// The pending posts of the [`EventQueue`] at some point in time, for rolling back to with
+/// [`EventQueue::restore`].
+///
+/// Only contains the posts of [`Event`] types with rollback enabled, see
+/// [`EventQueue::enable_rollback`].
+pub struct EventQueueSnapshot {
+    lanes: Vec<LaneSnapshot>,
+    cursor: usize,
+    next_sequence: u64,
+}
+
+struct LaneSnapshot {
+    type_id: TypeId,
+    cloner: PostCloner,
+    sources: Vec<SourceSnapshot>,
+}
+
+struct SourceSnapshot {
+    source: Option<Entity>,
+    posts: Vec<(u64, SnapshotPost)>,
+}
+
+impl EventQueueSnapshot {
+    /// Returns the number of posts in the snapshot.
+    pub fn len(&self) -> usize {
+        self.lanes
+            .iter()
+            .flat_map(|lane| &lane.sources)
+            .map(|source| source.posts.len())
+            .sum()
+    }
+
+    /// Returns `true` if the snapshot contains no posts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
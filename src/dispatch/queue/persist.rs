@@ -0,0 +1,338 @@
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    collections::HashMap,
+    error::Error,
+    fmt,
+    hash::Hasher,
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::{entity::Entity, world::World};
+use bevy_utils::tracing::warn;
+
+use super::{EventQueue, QueuedEvent};
+use crate::{dispatch::world::assign_sequence, CorrelationId, EntityRemap, Event, StableHasher};
+
+/// [`Event`] whose queued posts can be persisted across app restarts, see
+/// [`EventQueue::persist`].
+///
+/// Implement this with the codec of your choice, e.g. `bincode` or `postcard`. Requires the
+/// `journal` feature.
+pub trait PersistentEvent: Event<Audience: Send> + Send + Sized {
+    /// Encodes the event and its audience.
+    fn encode(&self, audience: &Self::Audience) -> Vec<u8>;
+
+    /// Decodes an event and its audience encoded with [`PersistentEvent::encode`], or returns
+    /// `None` if the bytes are malformed.
+    fn decode(bytes: &[u8]) -> Option<(Self, Self::Audience)>;
+}
+
+/// Encodes a type-erased queued post of a known [`PersistentEvent`] type, returning its
+/// correlation and payload, or `None` if the post isn't of that type.
+type Encoder = fn(&dyn Any) -> Option<(Option<CorrelationId>, Vec<u8>)>;
+
+/// Decodes a post of a known [`PersistentEvent`] type and queues it in the world, returning `false`
+/// if the bytes are malformed.
+type Decoder = fn(&mut World, &[u8], SavedPost) -> bool;
+
+/// Upgrades the body of a backlog to the next version of the format, or returns `None` if it is
+/// malformed.
+type Migration = fn(&[u8]) -> Option<Vec<u8>>;
+
+/// What a backlog keeps about a queued post besides its event and audience.
+struct SavedPost {
+    source: Option<Entity>,
+    ttl: Option<Duration>,
+    correlation: Option<CorrelationId>,
+}
+
+/// The [`PersistentEvent`] types of the [`EventQueue`], by type and by key.
+#[derive(Default)]
+pub(super) struct QueueJournal {
+    encoders: HashMap<TypeId, (Cow<'static, str>, Encoder)>,
+    decoders: HashMap<Cow<'static, str>, Decoder>,
+}
+
+/// Identifies the bytes as a backlog saved with [`EventQueue::save_backlog`].
+const MAGIC: [u8; 4] = *b"EBQB";
+
+/// The version of the backlog format, incremented whenever it changes.
+const VERSION: u32 = 2;
+
+/// Upgrades backlogs saved in older versions of the format, starting with version 1. Whenever
+/// [`VERSION`] is incremented, a migration from the previous version is added here, so that
+/// backlogs saved before an update are still restored.
+const MIGRATIONS: [Migration; VERSION as usize - 1] = [migrate_v1];
+
+/// Written instead of a TTL for posts that don't expire.
+const NO_TTL: u64 = u64::MAX;
+
+/// Error returned by [`EventQueue::save_backlog`] when a backlog can't be saved, and by
+/// [`WorldEventBus::restore_queue_backlog`](crate::WorldEventBus::restore_queue_backlog) when the
+/// backlog as a whole can't be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BacklogError {
+    /// The bytes aren't a backlog.
+    NotABacklog,
+    /// The backlog was saved in a newer format.
+    UnsupportedVersion(u32),
+    /// The backlog was truncated or modified after it was saved.
+    Corrupted,
+    /// A queued post isn't of the event type it was queued as, and can't be encoded.
+    WrongPostType,
+}
+
+impl fmt::Display for BacklogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotABacklog => write!(f, "not an event queue backlog"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported event queue backlog version {version}")
+            }
+            Self::Corrupted => write!(f, "corrupted event queue backlog"),
+            Self::WrongPostType => write!(f, "queued post of the wrong type"),
+        }
+    }
+}
+
+impl Error for BacklogError {}
+
+impl EventQueue {
+    /// Persists the pending posts of [`Event`] `E` in the backlogs saved with
+    /// [`EventQueue::save_backlog`], under the key.
+    ///
+    /// The key identifies the event type in the backlog, so it must stay the same across builds
+    /// of the app, unlike its type name. Requires the `journal` feature.
+    pub fn persist<E: PersistentEvent>(&mut self, key: impl Into<Cow<'static, str>>) {
+        let key = key.into();
+        let journal = &mut self.state.get_mut().journal;
+        journal.decoders.insert(key.clone(), decode::<E>);
+        journal
+            .encoders
+            .insert(TypeId::of::<E>(), (key, encode::<E>));
+    }
+
+    /// Encodes the pending posts of all [`PersistentEvent`] types into a backlog, e.g. to write it
+    /// to disk before the app exits, see [`EventQueue::persist`].
+    ///
+    /// Posts are saved in the order they were queued, along with their remaining TTL, source
+    /// entity, and correlation ID. Posts whose TTL already elapsed are left out. Requires the
+    /// `journal` feature.
+    pub fn save_backlog(&self) -> Result<Vec<u8>, BacklogError> {
+        let state = self.state.lock();
+        let now = Instant::now();
+        let mut posts = Vec::new();
+        for lane in &state.lanes {
+            let Some((key, encode)) = state.journal.encoders.get(&lane.type_id) else {
+                continue;
+            };
+            for queue in &lane.sources {
+                for (order, post) in &queue.posts {
+                    let ttl = match post.expires() {
+                        Some(expires) if expires <= now => continue,
+                        Some(expires) => {
+                            u64::try_from((expires - now).as_nanos()).unwrap_or(NO_TTL - 1)
+                        }
+                        None => NO_TTL,
+                    };
+                    let (correlation, payload) =
+                        encode(post.as_any()).ok_or(BacklogError::WrongPostType)?;
+                    posts.push((*order, key, ttl, queue.source, correlation, payload));
+                }
+            }
+        }
+        posts.sort_by_key(|(order, ..)| *order);
+
+        let mut body = Vec::new();
+        for (_, key, ttl, source, correlation, payload) in posts {
+            write_chunk(&mut body, key.as_bytes());
+            body.extend_from_slice(&ttl.to_le_bytes());
+            write_optional(
+                &mut body,
+                source.map(|source| source.to_bits().to_le_bytes()),
+            );
+            write_optional(&mut body, correlation.map(|id| id.0.to_le_bytes()));
+            write_chunk(&mut body, &payload);
+        }
+        let mut bytes = Vec::with_capacity(body.len() + 16);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&checksum(&body).to_le_bytes());
+        bytes.extend_from_slice(&body);
+        Ok(bytes)
+    }
+}
+
+/// Queues the posts of a backlog saved with [`EventQueue::save_backlog`] in the world, see
+/// [`WorldEventBus::restore_queue_backlog`](crate::WorldEventBus::restore_queue_backlog).
+pub(crate) fn restore_backlog(world: &mut World, bytes: &[u8]) -> Result<usize, BacklogError> {
+    let (magic, rest) = bytes
+        .split_first_chunk::<4>()
+        .ok_or(BacklogError::NotABacklog)?;
+    if *magic != MAGIC {
+        return Err(BacklogError::NotABacklog);
+    }
+    let (version, rest) = rest
+        .split_first_chunk::<4>()
+        .ok_or(BacklogError::Corrupted)?;
+    let version = u32::from_le_bytes(*version);
+    if version == 0 || version > VERSION {
+        return Err(BacklogError::UnsupportedVersion(version));
+    }
+    let (sum, body) = rest
+        .split_first_chunk::<8>()
+        .ok_or(BacklogError::Corrupted)?;
+    if u64::from_le_bytes(*sum) != checksum(body) {
+        return Err(BacklogError::Corrupted);
+    }
+    let mut body = Cow::Borrowed(body);
+    for migrate in &MIGRATIONS[version as usize - 1..] {
+        body = Cow::Owned(migrate(&body).ok_or(BacklogError::Corrupted)?);
+    }
+
+    let mut posts = Vec::new();
+    let mut body = &*body;
+    while !body.is_empty() {
+        let key = read_chunk(&mut body).ok_or(BacklogError::Corrupted)?;
+        let ttl = match read_array(&mut body).map(u64::from_le_bytes) {
+            Some(NO_TTL) => None,
+            Some(nanos) => Some(Duration::from_nanos(nanos)),
+            None => return Err(BacklogError::Corrupted),
+        };
+        let source = read_optional(&mut body)
+            .ok_or(BacklogError::Corrupted)?
+            .map(|bits| Entity::try_from_bits(u64::from_le_bytes(bits)))
+            .transpose()
+            .map_err(|_| BacklogError::Corrupted)?;
+        let correlation = read_optional(&mut body)
+            .ok_or(BacklogError::Corrupted)?
+            .map(|id| CorrelationId(u128::from_le_bytes(id)));
+        let payload = read_chunk(&mut body).ok_or(BacklogError::Corrupted)?;
+        let saved = SavedPost {
+            source,
+            ttl,
+            correlation,
+        };
+        posts.push((key, saved, payload));
+    }
+
+    let mut restored = 0;
+    for (key, saved, payload) in posts {
+        let key = String::from_utf8_lossy(key);
+        let decode = world
+            .get_resource_mut::<EventQueue>()
+            .and_then(|mut queue| {
+                let journal = &queue.state.get_mut().journal;
+                journal.decoders.get(&*key).copied()
+            });
+        let Some(decode) = decode else {
+            warn!("Skipped a queued post of {key}, which isn't persisted");
+            continue;
+        };
+        if decode(world, payload, saved) {
+            restored += 1;
+        } else {
+            warn!("Skipped a queued post of {key}, which failed to decode");
+        }
+    }
+    Ok(restored)
+}
+
+fn encode<E: PersistentEvent>(post: &dyn Any) -> Option<(Option<CorrelationId>, Vec<u8>)> {
+    let post = post.downcast_ref::<QueuedEvent<E>>()?;
+    let correlation = post
+        .parent
+        .map(|parent| parent.correlation)
+        .or(post.correlation);
+    Some((correlation, post.event.encode(&post.audience)))
+}
+
+/// Decodes a post, remaps its entities with the [`EntityRemap`] of the world, and queues it after
+/// the pending posts, with a new sequence number if `E` is sequenced by target.
+fn decode<E: PersistentEvent>(world: &mut World, bytes: &[u8], saved: SavedPost) -> bool {
+    let Some((mut event, mut audience)) = E::decode(bytes) else {
+        return false;
+    };
+    EntityRemap::apply(world, &mut event, &mut audience);
+    let source = saved.source.map(|source| {
+        world
+            .get_resource::<EntityRemap>()
+            .and_then(|remap| remap.get(source))
+            .unwrap_or(source)
+    });
+    let sequence = assign_sequence::<E>(world, &audience);
+    world
+        .get_resource_or_insert_with(EventQueue::default)
+        .push_post(
+            source,
+            QueuedEvent {
+                event,
+                audience,
+                parent: None,
+                correlation: saved.correlation,
+                expires: saved.ttl.map(|ttl| Instant::now() + ttl),
+                sequence,
+            },
+        );
+    true
+}
+
+/// Upgrades a version 1 body, which didn't save the source entities and correlation IDs of posts.
+fn migrate_v1(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut migrated = Vec::with_capacity(body.len());
+    while !body.is_empty() {
+        write_chunk(&mut migrated, read_chunk(&mut body)?);
+        migrated.extend_from_slice(&read_array::<8>(&mut body)?);
+        write_optional::<8>(&mut migrated, None);
+        write_optional::<16>(&mut migrated, None);
+        write_chunk(&mut migrated, read_chunk(&mut body)?);
+    }
+    Some(migrated)
+}
+
+fn write_chunk(bytes: &mut Vec<u8>, chunk: &[u8]) {
+    bytes.extend_from_slice(&(chunk.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(chunk);
+}
+
+fn write_optional<const N: usize>(bytes: &mut Vec<u8>, value: Option<[u8; N]>) {
+    match value {
+        Some(value) => {
+            bytes.push(1);
+            bytes.extend_from_slice(&value);
+        }
+        None => bytes.push(0),
+    }
+}
+
+fn read_array<const N: usize>(bytes: &mut &[u8]) -> Option<[u8; N]> {
+    let (array, rest) = bytes.split_first_chunk::<N>()?;
+    *bytes = rest;
+    Some(*array)
+}
+
+/// Reads a value written with [`write_optional`], returning `None` if the bytes are malformed.
+fn read_optional<const N: usize>(bytes: &mut &[u8]) -> Option<Option<[u8; N]>> {
+    let (&present, rest) = bytes.split_first()?;
+    *bytes = rest;
+    match present {
+        0 => Some(None),
+        1 => read_array(bytes).map(Some),
+        _ => None,
+    }
+}
+
+fn read_chunk<'a>(bytes: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let (len, rest) = bytes.split_first_chunk::<8>()?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+    let (chunk, rest) = rest.split_at_checked(len)?;
+    *bytes = rest;
+    Some(chunk)
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
@@ -0,0 +1,931 @@
+use std::{
+    any::type_name,
+    borrow::Cow,
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use bevy_ecs::{schedule::InternedSystemSet, system::Resource, world::World};
+use bevy_utils::tracing::error;
+use parking_lot::Mutex;
+
+#[cfg(feature = "bevy_reflect")]
+use crate::FieldWatch;
+use crate::{
+    dispatch::{hooks::EventHooks, scoped::DroppedHandlers},
+    pick_index, ArcCondition, ArcHandlerSystem, BreakerState, DispatchStrategy, Event,
+    FeatureFlags, HandlerConfig, HandlerOrder, HandlerPriority, HandlerSetConfig, HandlerStorage,
+    Immutable, MonitorView, Normal, RequiredResource, Resimulating, StoredHandler, VecStorage,
+};
+
+/// Opaque identifier for a handler registered for [`Event`] `E`.
+///
+/// Returned by [`HandlerRegistry::insert`], and usable to inspect, reconfigure, or remove the
+/// handler afterwards.
+pub struct HandlerId<E: Event> {
+    index: u64,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E: Event> HandlerId<E> {
+    pub(crate) fn from_raw(index: u64) -> Self {
+        Self {
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn to_raw(self) -> u64 {
+        self.index
+    }
+}
+
+impl<E: Event> Clone for HandlerId<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Event> Copy for HandlerId<E> {}
+
+impl<E: Event> PartialEq for HandlerId<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<E: Event> Eq for HandlerId<E> {}
+
+impl<E: Event> Hash for HandlerId<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<E: Event> Debug for HandlerId<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HandlerId").field(&self.index).finish()
+    }
+}
+
+/// [`Event`] posted after a handler for [`Event`] `E` was added, so that subsystems can lazily
+/// set up the resources their events need once someone actually subscribes to them.
+///
+/// Only posted if any handlers are registered for it. Handlers added before that aren't reported.
+pub struct HandlerAdded<E: Event> {
+    /// The ID of the added handler.
+    pub id: HandlerId<E>,
+    /// The name of the added handler.
+    pub name: Cow<'static, str>,
+    /// The priority the handler was added with.
+    pub priority: i32,
+}
+
+impl<E: Event> Event for HandlerAdded<E> {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+/// A handler taken out of the [`HandlerRegistry`] for the duration of a dispatch.
+pub(crate) struct HandlerEntry<E: Event> {
+    pub(crate) id: HandlerId<E>,
+    pub(crate) handler: ArcHandlerSystem<E>,
+    /// The effective priority of the handler.
+    pub(crate) priority: i32,
+    /// Run conditions of the handler and its sets, which must all return `true` for it to run.
+    pub(crate) conditions: Vec<ArcCondition>,
+    /// Whether the handler only runs on the [`MainThread`](crate::MainThread).
+    pub(crate) main_thread: bool,
+    /// Whether the handler is skipped while [`Resimulating`](crate::Resimulating).
+    pub(crate) side_effect: bool,
+    /// Whether the handler is removed after it first ran.
+    pub(crate) once: bool,
+    /// Whether the handler still runs after the event was cancelled.
+    pub(crate) receive_cancelled: bool,
+    /// Resources the handler requires to run.
+    pub(crate) resources: Arc<Vec<RequiredResource>>,
+    /// Feature flags the handler requires to run.
+    pub(crate) flags: Arc<Vec<Cow<'static, str>>>,
+    /// Copies the final state of the post for the handler, if it always runs.
+    pub(crate) always_run: Option<MonitorView<E>>,
+    /// The state of the handler's circuit breaker, if any.
+    pub(crate) breaker: Option<Arc<BreakerState>>,
+    /// The key that the handler's delivery is tracked by, if not its ID.
+    pub(crate) delivery_key: Option<Arc<Cow<'static, str>>>,
+    /// The fields of the event the handler watches, if any.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) watch: Option<Arc<FieldWatch<E>>>,
+}
+
+impl<E: Event> Clone for HandlerEntry<E> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            handler: self.handler.clone(),
+            priority: self.priority,
+            conditions: self.conditions.clone(),
+            main_thread: self.main_thread,
+            side_effect: self.side_effect,
+            once: self.once,
+            receive_cancelled: self.receive_cancelled,
+            resources: self.resources.clone(),
+            flags: self.flags.clone(),
+            always_run: self.always_run,
+            breaker: self.breaker.clone(),
+            delivery_key: self.delivery_key.clone(),
+            #[cfg(feature = "bevy_reflect")]
+            watch: self.watch.clone(),
+        }
+    }
+}
+
+/// Returns the [`HandlerRegistry`] of a key of [`KeyedHandlers`](crate::KeyedHandlers), if any
+/// handlers were added for it.
+type KeyedRegistry<E> =
+    dyn for<'w> Fn(&'w mut World) -> Option<&'w mut HandlerRegistry<E>> + Send + Sync;
+
+/// The [`HandlerRegistry`] that the handlers of a post were taken from, so that the handlers the
+/// post removes, e.g. because they only run [once](HandlerConfig::once), are removed from the
+/// registry they belong to. Handler ids are only unique within a registry.
+pub(crate) enum HandlerSource<E: Event> {
+    /// The [`HandlerRegistry`] resource of `E`.
+    Registry,
+    /// The registry of a key of [`KeyedHandlers`](crate::KeyedHandlers).
+    Keyed(Arc<KeyedRegistry<E>>),
+}
+
+impl<E: Event> HandlerSource<E> {
+    /// Returns the registry, if it still exists.
+    pub(crate) fn registry<'w>(&self, world: &'w mut World) -> Option<&'w mut HandlerRegistry<E>> {
+        match self {
+            Self::Registry => world
+                .get_resource_mut::<HandlerRegistry<E>>()
+                .map(|registry| registry.into_inner()),
+            Self::Keyed(registry) => registry(world),
+        }
+    }
+
+    /// Removes a handler from the registry, if it is still present.
+    pub(crate) fn remove(&self, world: &mut World, id: HandlerId<E>) {
+        if let Some(registry) = self.registry(world) {
+            registry.remove(id);
+        }
+    }
+}
+
+impl<E: Event> Clone for HandlerSource<E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Registry => Self::Registry,
+            Self::Keyed(registry) => Self::Keyed(registry.clone()),
+        }
+    }
+}
+
+/// A handler in the resolved order of a [`HandlerRegistry`], along with why it runs where it does,
+/// see [`HandlerRegistry::explain_order`].
+pub struct OrderedHandler<E: Event> {
+    /// The id of the handler.
+    pub id: HandlerId<E>,
+    /// The name of the handler.
+    pub name: Cow<'static, str>,
+    /// The effective priority of the handler.
+    pub priority: i32,
+    /// Where the effective priority of the handler comes from.
+    pub priority_source: PrioritySource,
+    /// Whether the handler runs at all, as opposed to being in a disabled set.
+    pub enabled: bool,
+    /// Why the handler runs after the handlers before it.
+    pub reasons: Vec<String>,
+}
+
+impl<E: Event> fmt::Display for OrderedHandler<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (priority {}", self.name, self.priority)?;
+        match &self.priority_source {
+            PrioritySource::Handler => {}
+            PrioritySource::Set(set) => write!(f, " from {set:?}")?,
+            PrioritySource::Default => write!(f, " by default")?,
+        }
+        if !self.enabled {
+            write!(f, ", disabled")?;
+        }
+        write!(f, ")")?;
+        for reason in &self.reasons {
+            write!(f, "\n  - {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by [`HandlerRegistry::validate_order`] when the ordering constraints between
+/// handlers for [`Event`] `E` and their sets form a cycle.
+pub struct OrderingCycle<E: Event> {
+    /// The handlers that couldn't be ordered, in the order they were added. Includes the handlers
+    /// in the cycle, and those ordered after them.
+    pub handlers: Vec<HandlerId<E>>,
+}
+
+impl<E: Event> Debug for OrderingCycle<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderingCycle")
+            .field("handlers", &self.handlers)
+            .finish()
+    }
+}
+
+impl<E: Event> fmt::Display for OrderingCycle<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cycle in the handler ordering of {} between {} handlers",
+            type_name::<E>(),
+            self.handlers.len()
+        )
+    }
+}
+
+impl<E: Event> std::error::Error for OrderingCycle<E> {}
+
+/// Where the effective priority of a handler comes from, see [`OrderedHandler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrioritySource {
+    /// The priority was assigned to the handler itself.
+    Handler,
+    /// The priority was inherited from a [`HandlerSet`](crate::HandlerSet) of the handler.
+    Set(InternedSystemSet),
+    /// The handler has the [`Normal`] priority, by default.
+    Default,
+}
+
+/// [`Resource`] which stores the registry of [`HandlerConfig`]s for a specific [`Event`] `E`,
+/// sorted by priority.
+///
+/// The resolved order of the handlers is cached, and only recomputed after the registry changes.
+///
+/// # Changes during dispatch
+///
+/// Every post runs a snapshot of the handlers taken when its dispatch starts. Handlers added,
+/// removed, or reconfigured while a post is being dispatched, e.g. by one of its own handlers, only
+/// take effect for subsequent posts, including posts nested within the current one. A post
+/// deferred with [`Receive::defer_until`](crate::Receive::defer_until) skips the handlers removed
+/// by the time it resumes.
+#[derive(Resource)]
+pub struct HandlerRegistry<E: Event> {
+    /// Handlers in the order they were added.
+    handlers: Box<dyn HandlerStorage<E>>,
+    sets: HashMap<InternedSystemSet, HandlerSetConfig>,
+    /// Indices into `handlers`, in the order they run.
+    order: Mutex<Option<Vec<usize>>>,
+    /// Incremented whenever the handlers or their configuration change.
+    generation: u64,
+    next_id: u64,
+    /// The optional hooks that posts of `E` go through.
+    hooks: EventHooks<E>,
+    /// Handlers that are initialized or removed before the next dispatch.
+    pending: PendingHandlers<E>,
+}
+
+/// Handlers of a [`HandlerRegistry`] whose changes are applied before the next dispatch, as they
+/// happen without access to the [`World`].
+struct PendingHandlers<E: Event> {
+    /// Handlers whose state was reset, and that need to be initialized before they run.
+    uninitialized: Vec<ArcHandlerSystem<E>>,
+    /// Handlers whose [`ScopedHandler`](crate::ScopedHandler) guards were dropped, and that are
+    /// removed before the next dispatch.
+    dropped: DroppedHandlers<E>,
+}
+
+impl<E: Event> HandlerRegistry<E> {
+    /// Inserts a handler into the registry, returning its [`HandlerId`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`HandlerStorage`] of the registry is full, see
+    /// [`HandlerRegistry::try_insert`].
+    pub fn insert(&mut self, config: HandlerConfig<E>) -> HandlerId<E> {
+        match self.try_insert(config) {
+            Ok(id) => id,
+            Err(config) => panic!(
+                "The handler storage of {} is full, can't add {}",
+                type_name::<E>(),
+                config.name()
+            ),
+        }
+    }
+
+    /// Inserts a handler into the registry, returning its [`HandlerId`], or its config back if the
+    /// [`HandlerStorage`] of the registry is full.
+    pub fn try_insert(
+        &mut self,
+        config: HandlerConfig<E>,
+    ) -> Result<HandlerId<E>, HandlerConfig<E>> {
+        let id = HandlerId::from_raw(self.next_id);
+        self.handlers.insert(self.handlers.len(), id, config)?;
+        self.next_id += 1;
+        self.invalidate();
+        Ok(id)
+    }
+
+    /// Moves all handlers into a new [`HandlerStorage`], keeping their order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new storage can't hold all handlers.
+    pub fn set_storage(&mut self, storage: impl HandlerStorage<E>) {
+        let mut storage: Box<dyn HandlerStorage<E>> = Box::new(storage);
+        while !self.handlers.is_empty() {
+            let (id, config) = self.handlers.remove(0);
+            assert!(
+                storage.insert(storage.len(), id, config).is_ok(),
+                "The new handler storage of {} can't hold all of its handlers",
+                type_name::<E>()
+            );
+        }
+        self.handlers = storage;
+    }
+
+    /// Removes a handler from the registry, returning its [`HandlerConfig`] if it was present.
+    pub fn remove(&mut self, id: HandlerId<E>) -> Option<HandlerConfig<E>> {
+        let index = self.position(id)?;
+        let (_, config) = self.handlers.remove(index);
+        self.invalidate();
+        Some(config)
+    }
+
+    /// Removes all handlers for which `f` returns `false`, keeping the order of the rest.
+    pub fn retain(&mut self, mut f: impl FnMut(&HandlerConfig<E>) -> bool) {
+        let len = self.handlers.len();
+        for index in (0..len).rev() {
+            if !f(&self.entry(index).1) {
+                self.handlers.remove(index);
+            }
+        }
+        if self.handlers.len() != len {
+            self.invalidate();
+        }
+    }
+
+    /// Returns the [`HandlerConfig`] of a handler, if it is present.
+    pub fn get(&self, id: HandlerId<E>) -> Option<&HandlerConfig<E>> {
+        self.position(id).map(|index| &self.entry(index).1)
+    }
+
+    /// Reconfigures a handler in place, keeping its [`HandlerId`].
+    /// Returns `false` if the handler is not present.
+    pub fn configure(
+        &mut self,
+        id: HandlerId<E>,
+        f: impl FnOnce(HandlerConfig<E>) -> HandlerConfig<E>,
+    ) -> bool {
+        let Some(index) = self.position(id) else {
+            return false;
+        };
+        let (_, config) = self.handlers.remove(index);
+        assert!(
+            self.handlers.insert(index, id, f(config)).is_ok(),
+            "The handler storage of {} rejected a reconfigured handler",
+            type_name::<E>()
+        );
+        self.invalidate();
+        true
+    }
+
+    /// Configures a [`HandlerSet`](crate::HandlerSet), merging with any previous configuration of
+    /// the same set.
+    pub fn configure_set(&mut self, config: HandlerSetConfig) {
+        match self.sets.get_mut(&config.set) {
+            Some(existing) => existing.merge(config),
+            None => {
+                self.sets.insert(config.set, config);
+            }
+        }
+        self.invalidate();
+    }
+
+    /// Returns the [`HandlerSetConfig`] of a set, if it was configured.
+    pub fn get_set(&self, set: InternedSystemSet) -> Option<&HandlerSetConfig> {
+        self.sets.get(&set)
+    }
+
+    /// Returns `true` if the registry contains the handler.
+    pub fn contains(&self, id: HandlerId<E>) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Returns the number of handlers in the registry.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Returns `true` if the registry contains no handlers.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Returns all [`HandlerId`]s in the registry, in the order they run.
+    pub fn ids(&self) -> impl Iterator<Item = HandlerId<E>> + '_ {
+        self.order().into_iter().map(|index| self.entry(index).0)
+    }
+
+    /// Returns an iterator over all handlers in the registry, in the order they run.
+    pub fn handlers(&self) -> impl Iterator<Item = &ArcHandlerSystem<E>> {
+        self.order()
+            .into_iter()
+            .map(|index| &self.entry(index).1.handler)
+    }
+
+    /// Rebuilds the system state of a [`Resettable`](crate::Resettable) handler, such as its
+    /// [`Local`](bevy_ecs::system::Local)s and cached queries, without re-adding it. The fresh
+    /// state is initialized the next time the event is dispatched.
+    ///
+    /// Returns `false` if the handler is not present or not resettable.
+    pub fn reset_handler_state(&mut self, id: HandlerId<E>) -> bool {
+        let Some(index) = self.position(id) else {
+            return false;
+        };
+        let config = &mut self
+            .handlers
+            .get_mut(index)
+            .expect("handler index out of bounds")
+            .1;
+        let Some(factory) = &config.factory else {
+            return false;
+        };
+        config.handler = factory();
+        self.pending.uninitialized.push(config.handler.clone());
+        self.invalidate();
+        true
+    }
+
+    /// Rebuilds the system state of all [`Resettable`](crate::Resettable) handlers in the
+    /// registry, see [`HandlerRegistry::reset_handler_state`]. Returns the number of handlers reset.
+    pub fn reset_all(&mut self) -> usize {
+        let ids = self
+            .entries()
+            .filter(|(_, config)| config.is_resettable())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for &id in &ids {
+            self.reset_handler_state(id);
+        }
+        ids.len()
+    }
+
+    /// Returns `true` if any handler needs to be initialized after its state was reset.
+    pub(crate) fn has_uninitialized(&self) -> bool {
+        !self.pending.uninitialized.is_empty()
+    }
+
+    /// Takes the handlers that need to be initialized after their state was reset.
+    pub(crate) fn take_uninitialized(&mut self) -> Vec<ArcHandlerSystem<E>> {
+        std::mem::take(&mut self.pending.uninitialized)
+    }
+
+    /// Returns `true` if `E` is an alias of another event type, see
+    /// [`EventAlias`](crate::EventAlias).
+    pub fn is_aliased(&self) -> bool {
+        self.hooks.alias.is_some()
+    }
+
+    /// Sets the [`DispatchStrategy`] that posts of `E` run their handlers with.
+    pub fn set_strategy(&mut self, strategy: impl DispatchStrategy<E>) {
+        self.hooks.strategy = Some(Arc::new(strategy));
+    }
+
+    /// Returns the optional hooks that posts of `E` go through.
+    pub(crate) fn hooks(&self) -> &EventHooks<E> {
+        &self.hooks
+    }
+
+    /// Returns the optional hooks that posts of `E` go through, to enable or replace one.
+    pub(crate) fn hooks_mut(&mut self) -> &mut EventHooks<E> {
+        &mut self.hooks
+    }
+
+    /// Returns the queue into which [`ScopedHandler`](crate::ScopedHandler) guards push their
+    /// handler when they are dropped.
+    pub(crate) fn dropped_handlers(&self) -> DroppedHandlers<E> {
+        self.pending.dropped.clone()
+    }
+
+    /// Returns `true` if the guards of any handlers were dropped since they were last removed.
+    pub(crate) fn has_dropped(&self) -> bool {
+        !self.pending.dropped.lock().is_empty()
+    }
+
+    /// Removes the handlers whose guards were dropped.
+    pub(crate) fn remove_dropped(&mut self) {
+        let dropped = std::mem::take(&mut *self.pending.dropped.lock());
+        for id in dropped {
+            self.remove(id);
+        }
+    }
+
+    /// Returns `true` if a post of `E` would run any handler, or be recorded into its
+    /// [`EventHistory`](crate::EventHistory) or the [`StreamHasher`](crate::StreamHasher).
+    ///
+    /// Handlers in disabled sets, whose required resources don't exist or feature flags aren't
+    /// enabled, or that are skipped while resimulating don't count. Run conditions are only
+    /// evaluated while dispatching, so handlers with run conditions always count. Aliased events
+    /// always count, as their handlers are the ones of the event they are aliased to.
+    pub(crate) fn is_listened(world: &World) -> bool {
+        let Some(registry) = world.get_resource::<Self>() else {
+            return false;
+        };
+        if registry.hooks.observe_posts() {
+            return true;
+        }
+
+        let resimulating = Resimulating::is_active(world);
+        registry.entries().any(|(_, config)| {
+            let disabled = config.sets.iter().any(|set| {
+                registry
+                    .sets
+                    .get(set)
+                    .is_some_and(|set| set.enabled == Some(false))
+            });
+            let skipped = config.side_effect && resimulating;
+            !disabled
+                && !skipped
+                && config
+                    .resources
+                    .iter()
+                    .all(|resource| (resource.exists)(world))
+                && FeatureFlags::all_enabled(world, &config.flags)
+        })
+    }
+
+    /// Returns a snapshot of all enabled handlers in the registry, in the order they run.
+    pub(crate) fn snapshot(&self) -> Vec<HandlerEntry<E>> {
+        self.order()
+            .into_iter()
+            .filter_map(|index| {
+                let (id, config) = self.entry(index);
+                let sets = config.sets.iter().filter_map(|set| self.sets.get(set));
+                if sets.clone().any(|set| set.enabled == Some(false)) {
+                    return None;
+                }
+
+                Some(HandlerEntry {
+                    id: *id,
+                    handler: config.handler.clone(),
+                    priority: self.priority_of(config),
+                    conditions: config
+                        .conditions
+                        .iter()
+                        .chain(sets.flat_map(|set| &set.conditions))
+                        .cloned()
+                        .collect(),
+                    main_thread: config.main_thread,
+                    side_effect: config.side_effect,
+                    once: config.once,
+                    receive_cancelled: config.receive_cancelled,
+                    resources: config.resources.clone(),
+                    flags: config.flags.clone(),
+                    always_run: config.always_run,
+                    breaker: config.breaker.clone(),
+                    delivery_key: config.delivery_key.clone(),
+                    #[cfg(feature = "bevy_reflect")]
+                    watch: config.watch.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns all handlers in the registry in the order they run, along with why each handler
+    /// runs after the ones before it: its priority, the ordering constraints of it and its sets, or
+    /// the order in which handlers were added.
+    pub fn explain_order(&self) -> Vec<OrderedHandler<E>> {
+        let order = self.order();
+        order
+            .iter()
+            .enumerate()
+            .map(|(position, &index)| {
+                let (id, config) = self.entry(index);
+                let priority_source = match config.priority {
+                    Some(_) => PrioritySource::Handler,
+                    None => config
+                        .sets
+                        .iter()
+                        .find(|set| {
+                            self.sets
+                                .get(*set)
+                                .is_some_and(|set| set.priority.is_some())
+                        })
+                        .map_or(PrioritySource::Default, |set| PrioritySource::Set(*set)),
+                };
+
+                let mut reasons = Vec::new();
+                if let Some(&previous) = position.checked_sub(1).and_then(|p| order.get(p)) {
+                    reasons.push(self.explain_pair(previous, index));
+                }
+                for &other in &order[..position.saturating_sub(1)] {
+                    if let Some(constraint) = self.constraint_between(other, index) {
+                        reasons.push(format!(
+                            "runs after {} because of {constraint}",
+                            self.entry(other).1.name()
+                        ));
+                    }
+                }
+
+                OrderedHandler {
+                    id: *id,
+                    name: config.name(),
+                    priority: self.priority_of(config),
+                    priority_source,
+                    enabled: !config.sets.iter().any(|set| {
+                        self.sets
+                            .get(set)
+                            .is_some_and(|set| set.enabled == Some(false))
+                    }),
+                    reasons,
+                }
+            })
+            .collect()
+    }
+
+    /// Explains why the handler at index `index` runs right after the one at index `previous`.
+    fn explain_pair(&self, previous: usize, index: usize) -> String {
+        let (previous_config, config) = (&self.entry(previous).1, &self.entry(index).1);
+        let previous_name = previous_config.name();
+        let (previous_priority, priority) =
+            (self.priority_of(previous_config), self.priority_of(config));
+
+        if previous_priority != priority {
+            format!(
+                "runs after {previous_name} because its priority {priority} is lower than \
+                 {previous_priority}"
+            )
+        } else if let Some(constraint) = self.constraint_between(previous, index) {
+            format!("runs after {previous_name} because of {constraint}")
+        } else if previous < index {
+            format!("runs after {previous_name} because it was added later")
+        } else {
+            format!(
+                "runs after {previous_name} because of ordering constraints with other handlers \
+                 of priority {priority}"
+            )
+        }
+    }
+
+    /// Returns the ordering constraint that makes the handler at index `a` run before the handler
+    /// at index `b`, if any.
+    fn constraint_between(&self, a: usize, b: usize) -> Option<String> {
+        let (a, b) = (&self.entry(a).1, &self.entry(b).1);
+        let handler_constraint = a
+            .ordering
+            .iter()
+            .find_map(|order| match order {
+                HandlerOrder::Before(set) if b.sets.contains(set) => {
+                    Some(format!("{}.before({set:?})", a.name()))
+                }
+                _ => None,
+            })
+            .or_else(|| {
+                b.ordering.iter().find_map(|order| match order {
+                    HandlerOrder::After(set) if a.sets.contains(set) => {
+                        Some(format!("{}.after({set:?})", b.name()))
+                    }
+                    _ => None,
+                })
+            });
+        if handler_constraint.is_some() {
+            return handler_constraint;
+        }
+
+        let (a, b) = (&a.sets, &b.sets);
+        a.iter()
+            .find_map(|set| {
+                let other = self
+                    .sets
+                    .get(set)?
+                    .before
+                    .iter()
+                    .find(|other| b.contains(other))?;
+                Some(format!("{set:?}.before({other:?})"))
+            })
+            .or_else(|| {
+                b.iter().find_map(|set| {
+                    let other = self
+                        .sets
+                        .get(set)?
+                        .after
+                        .iter()
+                        .find(|other| a.contains(other))?;
+                    Some(format!("{set:?}.after({other:?})"))
+                })
+            })
+    }
+
+    /// Returns the effective priority of a handler.
+    pub(crate) fn priority_of(&self, config: &HandlerConfig<E>) -> i32 {
+        config
+            .priority
+            .or_else(|| {
+                config
+                    .sets
+                    .iter()
+                    .find_map(|set| self.sets.get(set)?.priority)
+            })
+            .unwrap_or_else(|| HandlerPriority::priority(&Normal))
+    }
+
+    fn position(&self, id: HandlerId<E>) -> Option<usize> {
+        self.handlers.position(id)
+    }
+
+    fn entry(&self, index: usize) -> &StoredHandler<E> {
+        self.handlers
+            .get(index)
+            .expect("handler index out of bounds")
+    }
+
+    /// Returns an iterator over all handlers in the order they were added.
+    fn entries(&self) -> impl Iterator<Item = &StoredHandler<E>> {
+        (0..self.handlers.len()).map(|index| self.entry(index))
+    }
+
+    fn invalidate(&mut self) {
+        *self.order.get_mut() = None;
+        self.generation += 1;
+    }
+
+    /// Returns a counter that changes whenever the handlers or their configuration change.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the cached order of the handlers, resolving it if needed.
+    fn order(&self) -> Vec<usize> {
+        self.order
+            .lock()
+            .get_or_insert_with(|| self.resolve())
+            .clone()
+    }
+
+    /// Sorts the handlers by priority, then by the ordering constraints between them and their
+    /// sets, then by the order they were added. Handlers involved in a cycle are logged as an
+    /// error, and keep the order they were added.
+    fn resolve(&self) -> Vec<usize> {
+        let mut resolved = Vec::with_capacity(self.handlers.len());
+        for group in self.priority_groups() {
+            if let Err(cycle) = self.sort_group(&group, &mut resolved, |_| 0) {
+                let names = cycle
+                    .iter()
+                    .map(|&index| self.entry(index).1.name())
+                    .collect::<Vec<_>>();
+                error!(
+                    "Cycle in the handler ordering of {} between {}, falling back to insertion \
+                     order",
+                    type_name::<E>(),
+                    names.join(", ")
+                );
+                resolved.extend(cycle);
+            }
+        }
+        resolved
+    }
+
+    /// Checks the ordering constraints between the handlers and their sets for cycles, e.g. right
+    /// after adding handlers, instead of waiting for the error logged when the event is first
+    /// dispatched.
+    pub fn validate_order(&self) -> Result<(), OrderingCycle<E>> {
+        let mut sorted = Vec::with_capacity(self.handlers.len());
+        for group in self.priority_groups() {
+            if let Err(cycle) = self.sort_group(&group, &mut sorted, |_| 0) {
+                return Err(OrderingCycle {
+                    handlers: cycle.iter().map(|&index| self.entry(index).0).collect(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the indices of the handlers, grouped by priority from highest to lowest.
+    fn priority_groups(&self) -> Vec<Vec<usize>> {
+        let mut order = (0..self.handlers.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| Reverse(self.priority_of(&self.entry(index).1)));
+        order
+            .chunk_by(|&a, &b| {
+                self.priority_of(&self.entry(a).1) == self.priority_of(&self.entry(b).1)
+            })
+            .map(<[usize]>::to_vec)
+            .collect()
+    }
+
+    /// Returns `true` if the handler at index `a` must run before the handler at index `b`.
+    fn runs_before(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.entry(a).1, &self.entry(b).1);
+        let handlers = a.ordering.iter().any(|order| match order {
+            HandlerOrder::Before(set) => b.sets.contains(set),
+            HandlerOrder::After(_) => false,
+        }) || b.ordering.iter().any(|order| match order {
+            HandlerOrder::After(set) => a.sets.contains(set),
+            HandlerOrder::Before(_) => false,
+        });
+        if handlers {
+            return true;
+        }
+
+        let (a, b) = (&a.sets, &b.sets);
+        a.iter().any(|set| {
+            self.sets
+                .get(set)
+                .is_some_and(|config| config.before.iter().any(|other| b.contains(other)))
+        }) || b.iter().any(|set| {
+            self.sets
+                .get(set)
+                .is_some_and(|config| config.after.iter().any(|other| a.contains(other)))
+        })
+    }
+
+    /// Topologically sorts a group of handlers with the same priority into `sorted`. Whenever
+    /// several handlers could run next, `pick` chooses among them given their count, where `0` is
+    /// the one added first. Returns the handlers that couldn't be sorted because of a cycle, in
+    /// the order they were added.
+    fn sort_group(
+        &self,
+        group: &[usize],
+        sorted: &mut Vec<usize>,
+        mut pick: impl FnMut(usize) -> usize,
+    ) -> Result<(), Vec<usize>> {
+        let mut remaining = group.to_vec();
+        while !remaining.is_empty() {
+            let ready = (0..remaining.len())
+                .filter(|&candidate| {
+                    !remaining.iter().any(|&other| {
+                        other != remaining[candidate]
+                            && self.runs_before(other, remaining[candidate])
+                    })
+                })
+                .collect::<Vec<_>>();
+            if ready.is_empty() {
+                return Err(remaining);
+            }
+            sorted.push(remaining.remove(ready[pick(ready.len())]));
+        }
+        Ok(())
+    }
+
+    /// Shuffles the handlers of the same priority in a snapshot deterministically from the seed,
+    /// while keeping the ordering constraints between them and their sets. See
+    /// [`EventBusSettings::shuffle_same_priority`](crate::EventBusSettings::shuffle_same_priority).
+    ///
+    /// Handlers that are no longer in the registry, or are part of a cycle, keep their place.
+    pub(crate) fn shuffle(&self, handlers: &mut [HandlerEntry<E>], mut seed: u64) {
+        let mut start = 0;
+        while start < handlers.len() {
+            let priority = handlers[start].priority;
+            let len = handlers[start..]
+                .iter()
+                .take_while(|entry| entry.priority == priority)
+                .count();
+            let run = &mut handlers[start..start + len];
+            start += len;
+
+            let Some(group) = run
+                .iter()
+                .map(|entry| self.position(entry.id))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let mut order = Vec::with_capacity(group.len());
+            if self
+                .sort_group(&group, &mut order, |ready| pick_index(&mut seed, ready))
+                .is_ok()
+            {
+                let shuffled = order
+                    .iter()
+                    .map(|index| {
+                        run[group.iter().position(|other| other == index).unwrap()].clone()
+                    })
+                    .collect::<Vec<_>>();
+                run.clone_from_slice(&shuffled);
+            }
+        }
+    }
+}
+
+impl<E: Event> Default for HandlerRegistry<E> {
+    fn default() -> Self {
+        Self {
+            handlers: Box::new(VecStorage::default()),
+            sets: HashMap::new(),
+            order: Mutex::new(None),
+            generation: 0,
+            next_id: 0,
+            hooks: EventHooks::default(),
+            pending: PendingHandlers {
+                uninitialized: Vec::new(),
+                dropped: DroppedHandlers::default(),
+            },
+        }
+    }
+}
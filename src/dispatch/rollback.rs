@@ -0,0 +1,56 @@
+use bevy_ecs::{system::Resource, world::World};
+
+/// [`Resource`] which is `true` while a rollback framework resimulates past frames.
+///
+/// Handlers can read it to tell resimulated events apart from live ones. Handlers that only cause
+/// side effects, such as playing sounds or spawning particles, can be configured with
+/// [`HandlerConfig::side_effect_only`](crate::HandlerConfig::side_effect_only) to be skipped
+/// entirely while resimulating.
+///
+/// Together with [`EventQueue::snapshot`](crate::EventQueue::snapshot) and
+/// [`EventQueue::restore`](crate::EventQueue::restore), this allows rollback frameworks to rewind
+/// the queued events along with the rest of the world and dispatch them again:
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::{prelude::*, EventQueue, Resimulating};
+/// # #[derive(Clone)]
+/// # struct Input;
+/// # impl BusEvent for Input {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// let mut world = World::new();
+/// world.enable_rollback::<Input>();
+///
+/// world.enqueue(Input);
+/// let snapshot = world.resource::<EventQueue>().snapshot();
+/// world.flush_event_queue(None);
+///
+/// // Rewind and resimulate the frame.
+/// world.resource_mut::<EventQueue>().restore(&snapshot);
+/// Resimulating::scope(&mut world, |world| world.flush_event_queue(None));
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Resimulating(pub bool);
+
+impl Resimulating {
+    /// Runs `f` with [`Resimulating`] set to `true`, restoring its previous value afterwards.
+    pub fn scope<R>(world: &mut World, f: impl FnOnce(&mut World) -> R) -> R {
+        let previous = std::mem::replace(
+            &mut world.get_resource_or_insert_with(Self::default).0,
+            true,
+        );
+        let result = f(world);
+        world.resource_mut::<Self>().0 = previous;
+        result
+    }
+
+    /// Returns `true` if the world is resimulating.
+    pub(crate) fn is_active(world: &World) -> bool {
+        world
+            .get_resource::<Self>()
+            .is_some_and(|resimulating| resimulating.0)
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use bevy_ecs::world::World;
+use parking_lot::Mutex;
+
+use crate::{Event, HandlerId, HandlerRegistry};
+
+/// The handlers whose [`ScopedHandler`] guards were dropped, shared between the guards and the
+/// [`HandlerRegistry`] of their [`Event`].
+pub(crate) type DroppedHandlers<E> = Arc<Mutex<Vec<HandlerId<E>>>>;
+
+/// Guard for an event handler added with
+/// [`WorldEventBus::add_scoped_handler`](crate::WorldEventBus::add_scoped_handler), which
+/// removes the handler when it is dropped.
+///
+/// Dropping the guard doesn't need access to the world: the handler is only marked for removal,
+/// and removed before the next post of its [`Event`] `E` is dispatched. A post that is already
+/// being dispatched still runs the handler if it didn't yet, like with
+/// [`WorldEventBus::remove_handler`](crate::WorldEventBus::remove_handler).
+///
+/// ```rust
+/// # use bevy_ecs::{system::Resource, world::World};
+/// # use bevy_eventbus::{prelude::*, HandlerRegistry, ScopedHandler};
+/// # struct KeyPressed;
+/// # impl BusEvent for KeyPressed {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// #[derive(Resource)]
+/// struct PauseMenu {
+///     _input: ScopedHandler<KeyPressed>,
+/// }
+///
+/// fn navigate(_event: Receive<KeyPressed>) {
+///     // Move the selection...
+/// }
+///
+/// let mut world = World::new();
+/// let input = world.add_scoped_handler(navigate);
+/// world.insert_resource(PauseMenu { _input: input });
+///
+/// // Closing the menu unsubscribes it.
+/// world.remove_resource::<PauseMenu>();
+/// world.post(KeyPressed);
+/// assert!(world.resource::<HandlerRegistry<KeyPressed>>().is_empty());
+/// ```
+#[must_use = "Dropping the guard removes the handler"]
+pub struct ScopedHandler<E: Event> {
+    id: HandlerId<E>,
+    dropped: DroppedHandlers<E>,
+}
+
+impl<E: Event> ScopedHandler<E> {
+    pub(crate) fn new(id: HandlerId<E>, dropped: DroppedHandlers<E>) -> Self {
+        Self { id, dropped }
+    }
+
+    /// Returns the ID of the handler.
+    pub fn id(&self) -> HandlerId<E> {
+        self.id
+    }
+
+    /// Consumes the guard without removing the handler, returning its ID.
+    pub fn detach(self) -> HandlerId<E> {
+        let id = self.id;
+        std::mem::forget(self);
+        id
+    }
+}
+
+impl<E: Event> Drop for ScopedHandler<E> {
+    fn drop(&mut self) {
+        self.dropped.lock().push(self.id);
+    }
+}
+
+/// Removes the handlers for [`Event`] `E` whose [`ScopedHandler`] guards were dropped.
+pub(crate) fn remove_dropped_handlers<E: Event>(world: &mut World) {
+    if !world
+        .get_resource::<HandlerRegistry<E>>()
+        .is_some_and(HandlerRegistry::has_dropped)
+    {
+        return;
+    }
+    world.resource_mut::<HandlerRegistry<E>>().remove_dropped();
+}
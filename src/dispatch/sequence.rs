@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy_ecs::{entity::Entity, system::Resource, world::World};
+
+use crate::{dispatch::world::post_now, Event, HandlerRegistry, PostOptions, Unicast};
+
+/// The target entity of a post, and its sequence number for that entity.
+pub(crate) type SequenceNumber = (Entity, u64);
+
+/// [`Resource`] which delivers the posts of [`Event`] `E` targeting the same entity in the order
+/// they were made, enabled with
+/// [`WorldEventBus::sequence_by_target`](crate::WorldEventBus::sequence_by_target).
+///
+/// Every post gets the next sequence number of its target entity when it is made, whether it is
+/// posted immediately or queued on the [`EventQueue`](crate::EventQueue). A post is only
+/// dispatched once all earlier posts to the same entity were, so an immediate post made while an
+/// earlier post to the same entity is still queued waits for that post to be flushed first, and
+/// reports the default cancellation state. Queued posts that expire are skipped.
+///
+/// Posts of event references, such as [`WorldEventBus::post_ref`](crate::WorldEventBus::post_ref),
+/// can't wait and aren't sequenced. Posts deferred while the event bus is
+/// [paused](crate::EventBusPause) are sequenced again when it resumes.
+#[derive(Resource)]
+pub struct EntitySequencer<E: Event> {
+    entities: HashMap<Entity, EntitySequence<E>>,
+}
+
+struct EntitySequence<E: Event> {
+    /// The sequence number of the next post.
+    next: u64,
+    /// The sequence number of the next post to dispatch.
+    delivered: u64,
+    /// Posts made out of order along with their options, or `None` if the post was skipped.
+    waiting: BTreeMap<u64, Option<(E, E::Audience, PostOptions)>>,
+}
+
+impl<E: Event> Default for EntitySequencer<E> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Event> Default for EntitySequence<E> {
+    fn default() -> Self {
+        Self {
+            next: 0,
+            delivered: 0,
+            waiting: BTreeMap::new(),
+        }
+    }
+}
+
+impl<E: Event> EntitySequencer<E> {
+    /// Returns the number of posts to the entity that were made but not dispatched yet.
+    pub fn pending(&self, entity: Entity) -> u64 {
+        self.entities
+            .get(&entity)
+            .map_or(0, |sequence| sequence.next - sequence.delivered)
+    }
+}
+
+/// Type-erased operations of the [`EntitySequencer`] of [`Event`] `E`, stored in its
+/// [`HandlerRegistry`] so that they can be used without the bounds that sequencing requires.
+pub(crate) struct Sequencer<E: Event> {
+    /// Returns the target entity of the audience, and its next sequence number.
+    pub(crate) assign: fn(&mut World, &E::Audience) -> SequenceNumber,
+    /// Dispatches the post with its options once all earlier posts to the entity were dispatched.
+    pub(crate) deliver:
+        fn(&mut World, SequenceNumber, E, E::Audience, PostOptions) -> E::Cancellation,
+    /// Skips the post, e.g. because it expired.
+    pub(crate) skip: fn(&mut World, SequenceNumber),
+}
+
+impl<E: Event> Clone for Sequencer<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Event> Copy for Sequencer<E> {}
+
+impl<E: Event<Audience: Unicast + Send + Sync> + Send + Sync> Sequencer<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            assign: assign::<E>,
+            deliver: deliver::<E>,
+            skip: skip::<E>,
+        }
+    }
+}
+
+/// Returns the next sequence number of the target of the audience.
+fn assign<E>(world: &mut World, audience: &E::Audience) -> SequenceNumber
+where
+    E: Event<Audience: Unicast + Send + Sync> + Send + Sync,
+{
+    let target = audience.target();
+    let mut sequencer = world.get_resource_or_insert_with(EntitySequencer::<E>::default);
+    let sequence = sequencer.entities.entry(target).or_default();
+    sequence.next += 1;
+    (target, sequence.next - 1)
+}
+
+fn deliver<E>(
+    world: &mut World,
+    (target, number): SequenceNumber,
+    event: E,
+    audience: E::Audience,
+    options: PostOptions,
+) -> E::Cancellation
+where
+    E: Event<Audience: Unicast + Send + Sync> + Send + Sync,
+{
+    let mut sequencer = world.get_resource_or_insert_with(EntitySequencer::<E>::default);
+    let sequence = sequencer.entities.entry(target).or_default();
+    if number > sequence.delivered {
+        sequence
+            .waiting
+            .insert(number, Some((event, audience, options)));
+        return E::Cancellation::default();
+    }
+
+    let in_order = number == sequence.delivered;
+    let cancellation = post_now(world, event, audience, options);
+    if in_order {
+        advance::<E>(world, target);
+    }
+    cancellation
+}
+
+fn skip<E>(world: &mut World, (target, number): SequenceNumber)
+where
+    E: Event<Audience: Unicast + Send + Sync> + Send + Sync,
+{
+    let mut sequencer = world.get_resource_or_insert_with(EntitySequencer::<E>::default);
+    let sequence = sequencer.entities.entry(target).or_default();
+    if number > sequence.delivered {
+        sequence.waiting.insert(number, None);
+    } else if number == sequence.delivered {
+        advance::<E>(world, target);
+    }
+}
+
+/// Marks the next post to the entity as dispatched, and dispatches the posts that waited for it.
+fn advance<E>(world: &mut World, target: Entity)
+where
+    E: Event<Audience: Unicast + Send + Sync> + Send + Sync,
+{
+    loop {
+        let mut sequencer = world.resource_mut::<EntitySequencer<E>>();
+        let Some(sequence) = sequencer.entities.get_mut(&target) else {
+            return;
+        };
+        sequence.delivered += 1;
+        let next = sequence.waiting.remove(&sequence.delivered);
+        if next.is_none() && sequence.delivered == sequence.next {
+            sequencer.entities.remove(&target);
+        }
+        match next {
+            Some(Some((event, audience, options))) => {
+                post_now(world, event, audience, options);
+            }
+            Some(None) => {}
+            None => return,
+        }
+    }
+}
+
+impl<E: Event> HandlerRegistry<E> {
+    /// Returns the sequencer of `E`, if posts are sequenced by target.
+    pub(crate) fn sequenced(world: &World) -> Option<Sequencer<E>> {
+        world.get_resource::<Self>()?.hooks().sequencer
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::{Event, HandlerConfig, HandlerId};
+
+/// A handler stored in a [`HandlerStorage`], along with its [`HandlerId`].
+pub type StoredHandler<E> = (HandlerId<E>, HandlerConfig<E>);
+
+/// Storage backend for the handlers in a [`HandlerRegistry`](crate::HandlerRegistry), which keeps
+/// them in the order they were added.
+///
+/// The backend of an event type is selected with
+/// [`WorldEventBus::set_handler_storage`](crate::WorldEventBus::set_handler_storage), and
+/// defaults to [`VecStorage`]. The built-in backends are:
+/// - [`VecStorage`]: a growable list, suited for most apps.
+/// - [`FixedCapacityStorage`]: a list that never reallocates, and rejects handlers past its
+///   capacity, suited for memory-constrained targets.
+/// - [`IndexedStorage`]: a list with an index from [`HandlerId`] to position, suited for servers
+///   with many handlers that are frequently looked up, reconfigured, or removed.
+pub trait HandlerStorage<E: Event>: Send + Sync + 'static {
+    /// Inserts a handler at `index`, shifting all handlers after it. Returns the handler's config
+    /// back if the storage can't hold it.
+    fn insert(
+        &mut self,
+        index: usize,
+        id: HandlerId<E>,
+        config: HandlerConfig<E>,
+    ) -> Result<(), HandlerConfig<E>>;
+
+    /// Removes and returns the handler at `index`, shifting all handlers after it.
+    fn remove(&mut self, index: usize) -> StoredHandler<E>;
+
+    /// Returns the handler at `index`, if any.
+    fn get(&self, index: usize) -> Option<&StoredHandler<E>>;
+
+    /// Returns the handler at `index` mutably, if any.
+    fn get_mut(&mut self, index: usize) -> Option<&mut StoredHandler<E>>;
+
+    /// Returns the number of handlers.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no handlers.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of a handler, if present.
+    fn position(&self, id: HandlerId<E>) -> Option<usize> {
+        (0..self.len()).position(|index| self.get(index).is_some_and(|(other, _)| *other == id))
+    }
+}
+
+/// [`HandlerStorage`] which stores the handlers in a growable list. This is the default.
+pub struct VecStorage<E: Event> {
+    handlers: Vec<StoredHandler<E>>,
+}
+
+impl<E: Event> Default for VecStorage<E> {
+    fn default() -> Self {
+        Self {
+            handlers: Vec::new(),
+        }
+    }
+}
+
+impl<E: Event> HandlerStorage<E> for VecStorage<E> {
+    fn insert(
+        &mut self,
+        index: usize,
+        id: HandlerId<E>,
+        config: HandlerConfig<E>,
+    ) -> Result<(), HandlerConfig<E>> {
+        self.handlers.insert(index, (id, config));
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> StoredHandler<E> {
+        self.handlers.remove(index)
+    }
+
+    fn get(&self, index: usize) -> Option<&StoredHandler<E>> {
+        self.handlers.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut StoredHandler<E>> {
+        self.handlers.get_mut(index)
+    }
+
+    fn len(&self) -> usize {
+        self.handlers.len()
+    }
+}
+
+/// [`HandlerStorage`] which allocates room for a fixed number of handlers up front, and never
+/// reallocates. Handlers added past its capacity are rejected.
+pub struct FixedCapacityStorage<E: Event> {
+    handlers: Vec<StoredHandler<E>>,
+    capacity: usize,
+}
+
+impl<E: Event> FixedCapacityStorage<E> {
+    /// Creates a storage with room for `capacity` handlers.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            handlers: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of handlers.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<E: Event> HandlerStorage<E> for FixedCapacityStorage<E> {
+    fn insert(
+        &mut self,
+        index: usize,
+        id: HandlerId<E>,
+        config: HandlerConfig<E>,
+    ) -> Result<(), HandlerConfig<E>> {
+        if self.handlers.len() == self.capacity {
+            return Err(config);
+        }
+        self.handlers.insert(index, (id, config));
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> StoredHandler<E> {
+        self.handlers.remove(index)
+    }
+
+    fn get(&self, index: usize) -> Option<&StoredHandler<E>> {
+        self.handlers.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut StoredHandler<E>> {
+        self.handlers.get_mut(index)
+    }
+
+    fn len(&self) -> usize {
+        self.handlers.len()
+    }
+}
+
+/// [`HandlerStorage`] which additionally indexes the handlers by [`HandlerId`], so that looking
+/// them up doesn't scan the list.
+pub struct IndexedStorage<E: Event> {
+    handlers: Vec<StoredHandler<E>>,
+    positions: HashMap<HandlerId<E>, usize>,
+}
+
+impl<E: Event> Default for IndexedStorage<E> {
+    fn default() -> Self {
+        Self {
+            handlers: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Event> IndexedStorage<E> {
+    /// Updates the positions of the handlers from `start` onwards.
+    fn reindex(&mut self, start: usize) {
+        for (index, (id, _)) in self.handlers.iter().enumerate().skip(start) {
+            self.positions.insert(*id, index);
+        }
+    }
+}
+
+impl<E: Event> HandlerStorage<E> for IndexedStorage<E> {
+    fn insert(
+        &mut self,
+        index: usize,
+        id: HandlerId<E>,
+        config: HandlerConfig<E>,
+    ) -> Result<(), HandlerConfig<E>> {
+        self.handlers.insert(index, (id, config));
+        self.reindex(index);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> StoredHandler<E> {
+        let removed = self.handlers.remove(index);
+        self.positions.remove(&removed.0);
+        self.reindex(index);
+        removed
+    }
+
+    fn get(&self, index: usize) -> Option<&StoredHandler<E>> {
+        self.handlers.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut StoredHandler<E>> {
+        self.handlers.get_mut(index)
+    }
+
+    fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    fn position(&self, id: HandlerId<E>) -> Option<usize> {
+        self.positions.get(&id).copied()
+    }
+}
@@ -0,0 +1,511 @@
+use std::{
+    any::type_name,
+    borrow::{Borrow, Cow},
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::world::World;
+use bevy_utils::tracing::{trace, warn};
+
+use crate::{
+    dispatch::{defer::Deferral, panic::report_handler_panic},
+    BreakerState, BusTraceConfig, Cancellation, CancelledBy, DispatchTrace, Event, EventBusStats,
+    FeatureFlags, HandlerEntry, HandlerId, HandlerSource, MainThread, Mutability, MutabilityRef,
+    ParkedEvents, PostOptions, Receive, Resimulating,
+};
+
+/// Strategy that decides which of the handlers of [`Event`] `E` run for a post, and in what order.
+///
+/// The strategy of an event type is selected with
+/// [`WorldEventBus::set_dispatch_strategy`](crate::WorldEventBus::set_dispatch_strategy), and
+/// defaults to [`Sequential`]. The built-in strategies are:
+/// - [`Sequential`]: runs the handlers in order, until the event is cancelled.
+/// - [`Phased`]: runs the handlers in phases of equal priority, and only stops between phases.
+/// - [`Transactional`]: like [`Sequential`], but discards the handlers' modifications of the event
+///   if it ends up cancelled.
+///
+/// Handlers that [receive cancelled](crate::HandlerConfig::receive_cancelled) events still run
+/// once the built-in strategies stop, and if they [uncancel](Receive::uncancel) the event, the
+/// strategies resume running the other handlers.
+///
+/// Handlers have exclusive access to the world, so they always run one at a time.
+///
+/// Custom strategies drive the post through the [`Dispatcher`]:
+///
+/// ```rust
+/// use bevy_eventbus::{DispatchStrategy, Dispatcher, Event};
+///
+/// /// Runs the handlers in reverse order, ignoring cancellation.
+/// struct Reverse;
+///
+/// impl<E: Event> DispatchStrategy<E> for Reverse {
+///     fn dispatch(&self, dispatcher: &mut Dispatcher<'_, E>) {
+///         for index in (0..dispatcher.len()).rev() {
+///             dispatcher.run(index);
+///         }
+///     }
+/// }
+/// ```
+pub trait DispatchStrategy<E: Event>: Send + Sync + 'static {
+    /// Runs the handlers of a single post.
+    fn dispatch(&self, dispatcher: &mut Dispatcher<'_, E>);
+}
+
+/// [`DispatchStrategy`] which runs the handlers in order, until the event is cancelled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sequential;
+
+impl<E: Event> DispatchStrategy<E> for Sequential {
+    fn dispatch(&self, dispatcher: &mut Dispatcher<'_, E>) {
+        for index in 0..dispatcher.len() {
+            if !dispatcher.is_cancelled() || dispatcher.receives_cancelled(index) {
+                dispatcher.run(index);
+            }
+        }
+    }
+}
+
+/// [`DispatchStrategy`] which runs the handlers in phases of equal priority. Cancelling the event
+/// only stops the phases after the current one, so all handlers of the same priority see the event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Phased;
+
+impl<E: Event> DispatchStrategy<E> for Phased {
+    fn dispatch(&self, dispatcher: &mut Dispatcher<'_, E>) {
+        let mut index = 0;
+        let mut stopped = false;
+        while index < dispatcher.len() {
+            let priority = dispatcher.priority(index);
+            while index < dispatcher.len() && dispatcher.priority(index) == priority {
+                if !stopped || dispatcher.receives_cancelled(index) {
+                    dispatcher.run(index);
+                }
+                index += 1;
+            }
+            stopped = dispatcher.is_cancelled();
+        }
+    }
+}
+
+/// [`DispatchStrategy`] which runs the handlers like [`Sequential`], but restores the event to its
+/// state before the dispatch if it ends up cancelled.
+///
+/// Only the event itself is restored: changes that handlers made to the world are kept.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Transactional;
+
+impl<E: Event + Clone> DispatchStrategy<E> for Transactional {
+    fn dispatch(&self, dispatcher: &mut Dispatcher<'_, E>) {
+        let before = dispatcher.event_mut().map(|event| event.clone());
+        Sequential.dispatch(dispatcher);
+        if let (true, Some(before)) = (dispatcher.is_cancelled(), before) {
+            if let Some(event) = dispatcher.event_mut() {
+                *event = before;
+            }
+        }
+    }
+}
+
+/// Called after each handler with the state of the event it left behind, and the handler that
+/// cancelled the event so far, if any.
+pub(crate) type Inspect<'a, E> = dyn FnMut(&HandlerEntry<E>, &E, Option<&CancelledBy<E>>) + 'a;
+
+/// The state of a single post of [`Event`] `E`, driven by a [`DispatchStrategy`].
+///
+/// Handlers are addressed by their index in the resolved handler order.
+pub struct Dispatcher<'a, E: Event> {
+    world: &'a mut World,
+    handlers: &'a [HandlerEntry<E>],
+    /// The registry the handlers were taken from.
+    source: HandlerSource<E>,
+    event: MutabilityRef<'a, E>,
+    audience: &'a E::Audience,
+    cancellation: E::Cancellation,
+    inspect: &'a mut Inspect<'a, E>,
+    on_main_thread: bool,
+    resimulating: bool,
+    removed: Vec<HandlerId<E>>,
+    /// Whether handler runs are counted into the [`EventBusStats`].
+    timed: bool,
+    handler_runs: u64,
+    handler_time: Duration,
+    /// Where the running handler requests to defer the rest of the dispatch.
+    deferral: Option<Deferral<E>>,
+    /// The request of the handler that deferred the rest of the dispatch.
+    deferred: Option<Deferral<E>>,
+    /// Whether the strategy got to each handler before the dispatch was deferred, so that only
+    /// the others resume, whatever order the strategy runs them in.
+    visited: Vec<bool>,
+    /// Whether handler runs are traced, see [`BusTraceConfig`].
+    traced: bool,
+    /// Whether the dispatch is recorded into the [`DispatchTrace`].
+    recorded: bool,
+    /// The options of the post, such as the handlers it skips, see
+    /// [`PostBuilder::min_priority`](crate::PostBuilder::min_priority) and
+    /// [`PostBuilder::excluding`](crate::PostBuilder::excluding).
+    options: &'a PostOptions,
+    /// The handler that cancelled the event, if it is cancelled.
+    cancelled_by: Option<CancelledBy<E>>,
+    /// A copy of the event as it was posted, if any handler watches its fields.
+    #[cfg(feature = "bevy_reflect")]
+    baseline: Option<Box<dyn bevy_reflect::PartialReflect>>,
+}
+
+impl<'a, E: Event> Dispatcher<'a, E> {
+    pub(crate) fn new(
+        world: &'a mut World,
+        handlers: &'a [HandlerEntry<E>],
+        source: HandlerSource<E>,
+        event: MutabilityRef<'a, E>,
+        audience: &'a E::Audience,
+        options: &'a PostOptions,
+        inspect: &'a mut Inspect<'a, E>,
+    ) -> Self {
+        let on_main_thread = world
+            .get_resource::<MainThread>()
+            .is_none_or(MainThread::is_current);
+        let resimulating = Resimulating::is_active(world);
+        let timed = EventBusStats::is_enabled(world);
+        let traced = BusTraceConfig::get::<E>(world, audience).is_some();
+        #[cfg(feature = "bevy_reflect")]
+        let baseline = handlers
+            .iter()
+            .find_map(|entry| entry.watch.as_ref())
+            .map(|watch| (watch.baseline)(event.borrow()));
+        if traced {
+            trace!("Dispatching {}", type_name::<E>());
+        }
+        let recorded = world.contains_resource::<DispatchTrace>();
+        if recorded {
+            DispatchTrace::post::<E>(world);
+        }
+        Self {
+            world,
+            handlers,
+            source,
+            event,
+            audience,
+            cancellation: E::Cancellation::default(),
+            inspect,
+            on_main_thread,
+            resimulating,
+            removed: Vec::new(),
+            timed,
+            handler_runs: 0,
+            handler_time: Duration::ZERO,
+            deferral: None,
+            deferred: None,
+            visited: vec![false; handlers.len()],
+            traced,
+            recorded,
+            options,
+            cancelled_by: None,
+            #[cfg(feature = "bevy_reflect")]
+            baseline,
+        }
+    }
+
+    /// Returns the number of handlers.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Returns `true` if there are no handlers.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Returns the id of a handler.
+    pub fn id(&self, index: usize) -> HandlerId<E> {
+        self.handlers[index].id
+    }
+
+    /// Returns the name of a handler.
+    pub fn name(&self, index: usize) -> Cow<'static, str> {
+        self.handlers[index].handler.lock().name()
+    }
+
+    /// Returns the effective priority of a handler.
+    pub fn priority(&self, index: usize) -> i32 {
+        self.handlers[index].priority
+    }
+
+    /// Returns `true` if a handler still runs after the event was cancelled, see
+    /// [`HandlerConfig::receive_cancelled`](crate::HandlerConfig::receive_cancelled).
+    pub fn receives_cancelled(&self, index: usize) -> bool {
+        self.handlers[index].receive_cancelled
+    }
+
+    /// Returns the event.
+    pub fn event(&self) -> &E {
+        self.event.borrow()
+    }
+
+    /// Returns the event mutably, if it is [`Mutable`](crate::Mutable).
+    pub fn event_mut(&mut self) -> Option<&mut E> {
+        E::Mutability::get_mut(&mut self.event)
+    }
+
+    /// Returns the cancellation state of the event.
+    pub fn cancellation(&self) -> &E::Cancellation {
+        &self.cancellation
+    }
+
+    /// Returns `true` if the event is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.cancelled()
+    }
+
+    /// Returns the handler that cancelled the event, if it is cancelled.
+    pub fn cancelled_by(&self) -> Option<&CancelledBy<E>> {
+        self.cancelled_by.as_ref()
+    }
+
+    /// Returns `true` if a handler deferred the rest of the dispatch, see
+    /// [`Receive::defer_until`]. Once deferred, no more handlers run.
+    pub fn is_deferred(&self) -> bool {
+        self.deferred.is_some()
+    }
+
+    /// Returns the world, e.g. to decide which handlers to run.
+    pub fn world(&mut self) -> &mut World {
+        self.world
+    }
+
+    /// Runs a handler, unless it is skipped by its run conditions, required resources, feature
+    /// flags, circuit breaker, thread, side effects, priority, or exclusion, or the dispatch was
+    /// deferred. Returns `true` if the handler ran.
+    ///
+    /// Handlers that [always run](crate::HandlerConfig::always_run) are skipped too, as they run
+    /// once the strategy is done.
+    ///
+    /// Panics of the handler propagate, unless it has a [`CircuitBreaker`](crate::CircuitBreaker).
+    pub fn run(&mut self, index: usize) -> bool {
+        if self.deferred.is_some() || self.handlers[index].always_run.is_some() {
+            return false;
+        }
+        self.visited[index] = true;
+        self.invoke(index)
+    }
+
+    /// Runs a handler, unless it is skipped, see [`Dispatcher::run`]. Handlers that always run get
+    /// a copy of the cancellation state, and can't defer the dispatch.
+    fn invoke(&mut self, index: usize) -> bool {
+        let entry = &self.handlers[index];
+        if self
+            .options
+            .min_priority
+            .is_some_and(|min| entry.priority < min)
+            || self.options.excluded.contains(&entry.id.to_raw())
+        {
+            return false;
+        }
+        let world = &mut *self.world;
+        if entry.main_thread && !self.on_main_thread {
+            warn!(
+                "Skipped main thread handler {} for {}, posted from another thread",
+                entry.handler.lock().name(),
+                type_name::<E>()
+            );
+            return false;
+        }
+        if entry.side_effect && self.resimulating {
+            return false;
+        }
+        if !entry
+            .resources
+            .iter()
+            .all(|resource| (resource.exists)(world))
+        {
+            if entry
+                .resources
+                .iter()
+                .any(|resource| resource.remove && !(resource.exists)(world))
+            {
+                self.removed.push(entry.id);
+            }
+            return false;
+        }
+        if !FeatureFlags::all_enabled(world, &entry.flags) {
+            return false;
+        }
+        if entry
+            .breaker
+            .as_ref()
+            .is_some_and(|breaker| breaker.is_open(BreakerState::now(world)))
+        {
+            return false;
+        }
+
+        #[cfg(feature = "bevy_reflect")]
+        if let (Some(watch), Some(baseline)) = (&entry.watch, &self.baseline) {
+            if !watch.any_changed(self.event.borrow(), &**baseline) {
+                return false;
+            }
+        }
+
+        if !entry.conditions.iter().all(|condition| {
+            let mut condition = condition.lock();
+            condition.validate_param(world) && condition.run((), world)
+        }) {
+            return false;
+        }
+
+        if self.traced
+            && world
+                .get_resource::<BusTraceConfig>()
+                .is_some_and(|config| config.traces_priority(entry.priority))
+        {
+            trace!(
+                "Running handler {} for {} at priority {}",
+                entry.handler.lock().name(),
+                type_name::<E>(),
+                entry.priority
+            );
+        }
+
+        if self.recorded {
+            DispatchTrace::handler(world, &entry.handler.lock().name());
+        }
+
+        let was_cancelled = self.cancellation.cancelled();
+        let (mut copy, mut event_copy) = match entry.always_run {
+            Some(view) => {
+                let (cancellation, event) = view(&self.cancellation, self.event.borrow());
+                (Some(cancellation), event)
+            }
+            None => (None, None),
+        };
+        let cancellation = match &mut copy {
+            Some(copy) => copy.as_mut(),
+            None => self.cancellation.as_mut(),
+        };
+        let event = match &mut event_copy {
+            Some(event) => E::Mutability::to_ref(event),
+            None => E::Mutability::reborrow(&mut self.event),
+        };
+        let input = Receive::<E>::new(event, cancellation, self.audience)
+            .with_cancelled_by(self.cancelled_by.as_ref());
+        let input = match entry.always_run {
+            Some(_) => input,
+            None => input.with_deferral(&mut self.deferral),
+        };
+        let start = (self.timed || entry.breaker.is_some()).then(Instant::now);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            entry.handler.lock().run(input, world);
+        }));
+        let panicked = if let Err(payload) = result {
+            report_handler_panic(
+                world,
+                entry.handler.lock().name(),
+                entry.priority,
+                self.event.borrow(),
+                self.audience,
+            );
+            if entry.breaker.is_none() {
+                panic::resume_unwind(payload);
+            }
+            true
+        } else {
+            false
+        };
+        let elapsed = start.map_or(Duration::ZERO, |start| start.elapsed());
+        if self.timed {
+            self.handler_runs += 1;
+            self.handler_time += elapsed;
+        }
+        if let Some(breaker) = &entry.breaker {
+            let now = BreakerState::now(world);
+            if let Some(failures) = breaker.record(panicked, elapsed, now) {
+                let name = entry.handler.lock().name();
+                warn!(
+                    "Tripped the circuit breaker of handler {name} for {} after {failures} failed \
+                     runs",
+                    type_name::<E>()
+                );
+                breaker.post_tripped(world, entry.id.to_raw(), name, failures);
+            }
+        }
+
+        if entry.once {
+            // Removed right away, so that posts nested within this one don't run it again.
+            self.source.remove(world, entry.id);
+        }
+
+        // Handlers that always run only change copies of the cancellation state and event.
+        match (was_cancelled, self.cancellation.cancelled()) {
+            (false, true) => {
+                let name = entry.handler.lock().name();
+                if self.traced {
+                    trace!("Handler {name} cancelled {}", type_name::<E>());
+                }
+                self.cancelled_by = Some(CancelledBy {
+                    id: entry.id,
+                    name,
+                    priority: entry.priority,
+                });
+            }
+            (true, false) => self.cancelled_by = None,
+            _ => {}
+        }
+
+        (self.inspect)(entry, self.event.borrow(), self.cancelled_by.as_ref());
+        if let Some(deferral) = self.deferral.take() {
+            self.deferred = Some(deferral);
+        }
+        true
+    }
+
+    /// Runs the handlers that always run, removes the handlers whose required resources were
+    /// removed, counts the post into the [`EventBusStats`], parks the post if it was deferred,
+    /// and returns the final cancellation state.
+    pub(crate) fn finish(mut self) -> E::Cancellation {
+        if self.deferred.is_none() {
+            for index in 0..self.handlers.len() {
+                if self.handlers[index].always_run.is_some() {
+                    self.invoke(index);
+                }
+            }
+        }
+
+        if let Some(deferral) = self.deferred.take() {
+            // The handlers that always run were never visited, so they resume with the rest.
+            let remaining = self
+                .handlers
+                .iter()
+                .zip(&self.visited)
+                .filter(|(_, visited)| !**visited)
+                .map(|(entry, _)| entry.clone())
+                .collect();
+            ParkedEvents::park(
+                self.world,
+                deferral,
+                self.event.borrow(),
+                self.audience,
+                self.source.clone(),
+                self.options.clone(),
+                remaining,
+            );
+        }
+
+        if self.recorded && self.cancellation.cancelled() {
+            DispatchTrace::cancelled(self.world);
+        }
+
+        if self.timed {
+            EventBusStats::record::<E>(
+                self.world,
+                self.cancellation.cancelled(),
+                self.handler_runs,
+                self.handler_time,
+            );
+        }
+
+        for id in self.removed {
+            self.source.remove(self.world, id);
+        }
+
+        self.cancellation
+    }
+}
@@ -0,0 +1,23 @@
+use std::thread::{self, ThreadId};
+
+use bevy_ecs::system::Resource;
+
+/// [`Resource`] which identifies the main thread, the only one whose posts run the handlers
+/// configured with [`HandlerConfig::main_thread_only`](crate::HandlerConfig::main_thread_only).
+///
+/// Inserted by the [`EventBusPlugin`](crate::EventBusPlugin) on the thread that builds the app.
+/// Without it, main-thread-only handlers run on whichever thread the event is posted from.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MainThread(pub ThreadId);
+
+impl MainThread {
+    /// Returns the current thread as the main thread.
+    pub fn current() -> Self {
+        Self(thread::current().id())
+    }
+
+    /// Returns `true` if called from the main thread.
+    pub fn is_current(&self) -> bool {
+        self.0 == thread::current().id()
+    }
+}
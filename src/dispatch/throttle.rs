@@ -0,0 +1,110 @@
+use std::{marker::PhantomData, time::Duration};
+
+use bevy_ecs::{
+    change_detection::Mut,
+    entity::{Entity, EntityHashMap},
+    system::Resource,
+    world::World,
+};
+
+use crate::{Event, EventBusStats, HandlerRegistry, Time, Unicast};
+
+/// [`Resource`] which limits how many posts of [`Event`] `E` each target entity receives per
+/// second, enabled with
+/// [`WorldEventBus::throttle_per_target`](crate::WorldEventBus::throttle_per_target).
+///
+/// Posts over the limit are dropped before [`Event::before_dispatch`] or any handler runs, or the
+/// post is recorded, and report the default cancellation state. Dropped posts are counted in the
+/// [`EventBusStats`]. Posts are counted per target in windows of one second of the
+/// elapsed [`Time`], so worlds without it count all posts against a single window.
+///
+/// The counters of targets that were despawned, or haven't been posted to within the last second,
+/// are cleaned up as the windows pass.
+#[derive(Resource)]
+pub struct TargetThrottle<E: Event> {
+    /// The maximum number of posts per target per second.
+    pub max_per_target_per_second: u32,
+    /// The start of the current window of each target, and the posts counted in it.
+    targets: EntityHashMap<(Duration, u32)>,
+    /// When the counters were last cleaned up.
+    cleaned: Duration,
+    dropped: u64,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E: Event> TargetThrottle<E> {
+    /// Creates a throttle with the limit.
+    pub fn new(max_per_target_per_second: u32) -> Self {
+        Self {
+            max_per_target_per_second,
+            targets: EntityHashMap::default(),
+            cleaned: Duration::ZERO,
+            dropped: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of posts to the target counted in its current window.
+    pub fn posts(&self, target: Entity) -> u32 {
+        self.targets.get(&target).map_or(0, |&(_, posts)| posts)
+    }
+
+    /// Returns the number of posts dropped because their target was over the limit.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+impl<E: Event<Audience: Unicast>> TargetThrottle<E> {
+    /// Counts a post to the target of the audience, returning `false` if it must be dropped.
+    pub(crate) fn admit(world: &mut World, audience: &E::Audience) -> bool {
+        if !world.contains_resource::<Self>() {
+            return true;
+        }
+        let now = world
+            .get_resource::<Time>()
+            .map_or(Duration::ZERO, Time::elapsed);
+        let target = audience.target();
+        world.resource_scope(|world, mut throttle: Mut<Self>| {
+            if now.saturating_sub(throttle.cleaned) >= WINDOW {
+                throttle.cleaned = now;
+                throttle.targets.retain(|&entity, &mut (start, _)| {
+                    now.saturating_sub(start) < WINDOW && world.entities().contains(entity)
+                });
+            }
+
+            let max = throttle.max_per_target_per_second;
+            let (start, posts) = throttle.targets.entry(target).or_insert((now, 0));
+            if now.saturating_sub(*start) >= WINDOW {
+                *start = now;
+                *posts = 0;
+            }
+            if *posts >= max {
+                throttle.dropped += 1;
+                return false;
+            }
+            *posts += 1;
+            true
+        })
+    }
+}
+
+impl<E: Event> HandlerRegistry<E> {
+    /// Counts a post of `E` with its throttle, if any, returning `true` if the post must be
+    /// dropped. Called once per post, before [`Event::before_dispatch`].
+    pub(crate) fn throttled(world: &mut World, audience: &E::Audience) -> bool {
+        let Some(admit) = world
+            .get_resource::<Self>()
+            .and_then(|registry| registry.hooks().throttle)
+        else {
+            return false;
+        };
+        if admit(world, audience) {
+            return false;
+        }
+        EventBusStats::record_throttled::<E>(world);
+        true
+    }
+}
+
+const WINDOW: Duration = Duration::from_secs(1);
@@ -0,0 +1,1391 @@
+use std::{
+    any::{type_name_of_val, TypeId},
+    borrow::Cow,
+    fmt::Debug,
+    hash::Hash,
+    sync::Arc,
+    time::Duration,
+};
+
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, MapEntities},
+    system::{Commands, IntoSystem},
+    world::{Command, World},
+};
+use bevy_utils::tracing::warn;
+
+use crate::{
+    dispatch::{
+        alias::DeprecatedEvent,
+        blueprint::RegistryIndex,
+        dispatch, dispatch_keyed, initialize_reset_handlers,
+        panic::PanicDump,
+        pause::PauseFilter,
+        sequence::{SequenceNumber, Sequencer},
+    },
+    history,
+    join::Join,
+    owner::HandlerOwners,
+    tick::TickAnchor,
+    AudienceNormalization, AudienceResolver, BusStats, DeliveryTracker, DispatchStrategy,
+    EntityRemap, Event, EventAccess, EventAlias, EventBudgetGuard, EventBudgets, EventBusPause,
+    EventBusSettings, EventBusStats, EventCatalog, EventContext, EventHistory, EventInbox,
+    EventInfo, EventMeta, EventQueue, EventReplayer, FromTargets, HandlerAdded, HandlerBlueprints,
+    HandlerConfig, HandlerId, HandlerRegistry, HandlerStorage, Immutable, IntoHandlerConfig,
+    IntoHandlerSetConfig, KeyedHandlers, LifecycleBridges, LoadRequested, Mutability, Mutable,
+    Normalizer, OrderedHandler, OwnerChain, PostBuilder, PostOptions, ProgressEmitter,
+    ProgressTracker, Receive, SameTeam, SaveBlob, SaveRequested, ScopedHandler, Shared,
+    StreamHasher, TargetThrottle, Unicast, WithContext,
+};
+
+/// [`World`] extension trait for registering event handlers and posting events.
+pub trait WorldEventBus {
+    /// Adds an event handler for [`Event`] `E` to the world.
+    ///
+    /// Returns the ID of the handler, which can be used to remove it again with
+    /// [`WorldEventBus::remove_handler`], e.g. when a temporary UI screen closes.
+    ///
+    /// Handlers added while a post of `E` is being dispatched only run for subsequent posts, see
+    /// [`HandlerRegistry`].
+    fn add_handler<E: Event, M>(&mut self, system: impl IntoHandlerConfig<E, M>) -> HandlerId<E>;
+
+    /// Adds an event handler for [`Event`] `E` to the world, which is removed again when the
+    /// returned [`ScopedHandler`] guard is dropped, e.g. together with a menu or dialog.
+    fn add_scoped_handler<E: Event, M>(
+        &mut self,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> ScopedHandler<E>;
+
+    /// Adds a closure as an event handler for [`Event`] `E` to the world.
+    ///
+    /// Unlike [`WorldEventBus::add_handler`], the closure doesn't declare any system parameters.
+    /// It receives exclusive access to the world instead, and can capture owned state. The handler
+    /// is named after the closure, rather than the wrapper system running it.
+    fn add_handler_fn<E: Event>(
+        &mut self,
+        handler: impl FnMut(Receive<E>, &mut World) + Send + Sync + 'static,
+    ) -> HandlerId<E> {
+        let name = type_name_of_val(&handler);
+        let mut handler = handler;
+        let system = IntoSystem::into_system(move |event: Receive<E>, world: &mut World| {
+            handler(event, world)
+        })
+        .with_name(name);
+        self.add_handler(system)
+    }
+
+    /// Adds an event handler for [`Event`] `E` to the world, which only runs for events posted to
+    /// the key with [`WorldEventBus::post_keyed`], see [`KeyedHandlers`].
+    fn add_keyed_handler<E: Event, K: Eq + Hash + Send + Sync + 'static, M>(
+        &mut self,
+        key: K,
+        handler: impl IntoHandlerConfig<E, M>,
+    );
+
+    /// Removes all event handlers for [`Event`] `E` subscribed to the key.
+    /// Returns how many handlers were removed.
+    fn remove_keyed_handlers<E: Event, K: Eq + Hash + Send + Sync + 'static>(
+        &mut self,
+        key: &K,
+    ) -> usize;
+
+    /// Sets the [`DispatchStrategy`] that posts of [`Event`] `E` run their handlers with.
+    fn set_dispatch_strategy<E: Event>(&mut self, strategy: impl DispatchStrategy<E>);
+
+    /// Moves the event handlers for [`Event`] `E` into a new [`HandlerStorage`] backend.
+    fn set_handler_storage<E: Event>(&mut self, storage: impl HandlerStorage<E>);
+
+    /// Removes an event handler for [`Event`] `E` from the world.
+    /// Returns `false` if the handler was not registered.
+    ///
+    /// Handlers removed while a post of `E` is being dispatched still run for that post if they
+    /// didn't yet, see [`HandlerRegistry`].
+    fn remove_handler<E: Event>(&mut self, id: HandlerId<E>) -> bool;
+
+    /// Reconfigures an already registered event handler for [`Event`] `E`.
+    /// Returns `false` if the handler was not registered.
+    fn configure_handler<E: Event>(
+        &mut self,
+        id: HandlerId<E>,
+        f: impl FnOnce(HandlerConfig<E>) -> HandlerConfig<E>,
+    ) -> bool;
+
+    /// Removes all event handlers for [`Event`] `E` for which `f` returns `true`, e.g. all handlers
+    /// in a [`HandlerSet`](crate::HandlerSet). Returns how many handlers were removed.
+    ///
+    /// Each handler is removed like with [`WorldEventBus::remove_handler`], so its
+    /// [`DeliveryTracker`] and the plugin owning it, if any, forget it too.
+    fn remove_handlers_where<E: Event>(
+        &mut self,
+        f: impl FnMut(&HandlerConfig<E>) -> bool,
+    ) -> usize;
+
+    /// Rebuilds the system state of a [`Resettable`](crate::Resettable) event handler for
+    /// [`Event`] `E`, see [`HandlerRegistry::reset_handler_state`].
+    /// Returns `false` if the handler was not registered or is not resettable.
+    fn reset_handler_state<E: Event>(&mut self, id: HandlerId<E>) -> bool;
+
+    /// Closes the tripped [`CircuitBreaker`](crate::CircuitBreaker) of an event handler for
+    /// [`Event`] `E`, so that it runs again. Returns `false` if the handler was not registered or
+    /// its breaker was not tripped.
+    fn reset_circuit_breaker<E: Event>(&mut self, id: HandlerId<E>) -> bool;
+
+    /// Rebuilds the system state of all [`Resettable`](crate::Resettable) event handlers for
+    /// [`Event`] `E`, e.g. after loading a save. Returns the number of handlers reset.
+    fn reset_all_handler_states<E: Event>(&mut self) -> usize;
+
+    /// Takes the event handlers for all [`Event`] types out of the world, e.g. before clearing its
+    /// resources, see [`HandlerBlueprints`].
+    fn export_handlers(&mut self) -> HandlerBlueprints;
+
+    /// Reinstalls event handlers taken out of the world with
+    /// [`WorldEventBus::export_handlers`], replacing any handlers added since.
+    fn install_handlers(&mut self, handlers: HandlerBlueprints);
+
+    /// Returns the [`HandlerId`]s of all event handlers for [`Event`] `E`,
+    /// in the order they run.
+    fn handler_ids<E: Event>(&self) -> Vec<HandlerId<E>>;
+
+    /// Returns the number of event handlers for [`Event`] `E`, without registering the event.
+    ///
+    /// Handlers subscribed to a key with [`WorldEventBus::add_keyed_handler`] aren't counted.
+    fn handler_count<E: Event>(&self) -> usize;
+
+    /// Returns `true` if any event handlers for [`Event`] `E` are registered, e.g. to skip building
+    /// an expensive event that nobody would receive.
+    fn has_handlers<E: Event>(&self) -> bool {
+        self.handler_count::<E>() > 0
+    }
+
+    /// Returns all event handlers for [`Event`] `E` in the order they run, along with why they run
+    /// in that order, see [`HandlerRegistry::explain_order`].
+    fn explain_handler_order<E: Event>(&self) -> Vec<OrderedHandler<E>>;
+
+    /// Configures a [`HandlerSet`](crate::HandlerSet) for [`Event`] `E`, merging with any previous
+    /// configuration of the same set.
+    fn configure_handler_set<E: Event>(&mut self, config: impl IntoHandlerSetConfig);
+
+    /// Removes all event handlers owned by `P` (usually a [`Plugin`](bevy_app::Plugin)),
+    /// across all [`Event`] types. Returns how many handlers were removed.
+    fn remove_plugin_handlers<P: 'static>(&mut self) -> usize;
+
+    /// Makes [`Event`] `Old` an alias of [`Event`] `New`, so that posting `Old` runs the handlers
+    /// registered for `New` through the [`EventAlias`] converter.
+    ///
+    /// Handlers registered for `Old` itself are no longer run while it is aliased.
+    fn alias_event<Old, New>(&mut self, alias: EventAlias<Old, New>)
+    where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event;
+
+    /// Deprecates [`Event`] `Old` in favor of [`Event`] `New`, so that plugins can migrate to
+    /// `New` one at a time.
+    ///
+    /// Posts of `Old` still run the handlers registered for `Old`, and unless they cancel the
+    /// post, it is converted with `converter` and posted as `New` afterwards, returning the
+    /// cancellation state of `New`. Modifications of `New` aren't written back into `Old`. Posting
+    /// `Old` and adding handlers for it logs a warning, at most once a minute.
+    fn register_deprecated_event<Old, New>(
+        &mut self,
+        converter: impl Fn(&Old) -> New + Send + Sync + 'static,
+    ) where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event;
+
+    /// Registers human-readable metadata of [`Event`] `E` into the [`EventCatalog`], replacing any
+    /// previous metadata.
+    fn register_bus_event<E: Event>(&mut self, info: EventInfo);
+
+    /// Posts bevy's [`OnAdd`](bevy_ecs::world::OnAdd), [`OnInsert`](bevy_ecs::world::OnInsert),
+    /// [`OnReplace`](bevy_ecs::world::OnReplace) and [`OnRemove`](bevy_ecs::world::OnRemove)
+    /// triggers for component `T` as [`BusOnAdd`](crate::BusOnAdd),
+    /// [`BusOnInsert`](crate::BusOnInsert), [`BusOnReplace`](crate::BusOnReplace) and
+    /// [`BusOnRemove`](crate::BusOnRemove) events, targeted at the entity.
+    ///
+    /// The events are posted with [`Commands`](bevy_ecs::system::Commands) when the observers'
+    /// commands are applied, so [`BusOnRemove`](crate::BusOnRemove) handlers run after the component was removed.
+    /// Bridging a component more than once has no effect.
+    fn bridge_lifecycle<T: Component>(&mut self);
+
+    /// Returns the metadata of [`Event`] `E`, if registered with
+    /// [`WorldEventBus::register_bus_event`].
+    fn event_info<E: Event>(&self) -> Option<&EventInfo>;
+
+    /// Returns the metadata of all events currently being dispatched, outermost first, see
+    /// [`EventContext::stack`].
+    fn current_event_stack(&self) -> &[EventMeta];
+
+    /// Returns `true` if [`Event`] `E` is currently being dispatched, e.g. to avoid posting it again
+    /// from within one of its own handlers.
+    fn is_dispatching<E: Event>(&self) -> bool {
+        self.current_event_stack()
+            .iter()
+            .any(|meta| meta.event_type == TypeId::of::<E>())
+    }
+
+    /// Starts retaining the last `capacity` posted events of type `E` in an [`EventHistory`].
+    /// If history is already enabled, only its capacity is changed.
+    fn enable_history<E>(&mut self, capacity: usize)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Remembers the last post of [`Event`] `E` offered to each of its handlers, so that handlers
+    /// catch up on the posts retained in its [`EventHistory`] that they missed while disabled or
+    /// removed, see [`DeliveryTracker`]. Requires history to be enabled for `E`.
+    fn track_delivery<E>(&mut self)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Folds every post of [`Event`] `E` into the rolling hash of the [`StreamHasher`], inserting
+    /// it if needed, e.g. to detect desyncs between lockstep clients.
+    fn hash_stream<E: Event<Audience: Hash> + Hash>(&mut self);
+
+    /// Delivers the posts of [`Event`] `E` targeting the same entity in the order they were made,
+    /// including posts queued on the [`EventQueue`], see [`EntitySequencer`](crate::EntitySequencer).
+    fn sequence_by_target<E: Event<Audience: Unicast + Send + Sync> + Send + Sync>(&mut self);
+
+    /// Limits how many posts of [`Event`] `E` each target entity receives per second, dropping the
+    /// posts over the limit, see [`TargetThrottle`]. Calling this again changes the limit.
+    fn throttle_per_target<E: Event<Audience: Unicast>>(&mut self, max_per_target_per_second: u32);
+
+    /// Normalizes the audiences of posts of [`Event`] `E` before any handler runs, e.g. removing
+    /// duplicate and despawned targets, see [`AudienceNormalization`]. Calling this again replaces
+    /// the normalization.
+    fn normalize_audience<E: Event<Audience: FromTargets>>(
+        &mut self,
+        policy: AudienceNormalization,
+    );
+
+    /// Collects the posts of [`Event`] `E` into the [`EventInbox`] of their target entity, if it
+    /// has one, for systems to drain at their leisure.
+    fn collect_in_inboxes<E: Event<Audience: Unicast> + Clone + Send + Sync>(&mut self);
+
+    /// Includes the payload and audience of posts of [`Event`] `E` in the error logged when one of
+    /// its handlers panics, each truncated to `max_len` characters.
+    ///
+    /// The handler's name and priority are always logged.
+    fn dump_on_panic<E: Event<Audience: Debug> + Debug>(&mut self, max_len: usize);
+
+    /// Enables rollback for the queued posts of [`Event`] `E`, see [`EventQueue::snapshot`].
+    fn enable_rollback<E>(&mut self)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Persists the queued posts of [`Event`] `E` under the key in the backlogs of the
+    /// [`EventQueue`], see [`EventQueue::persist`].
+    #[cfg(feature = "journal")]
+    fn persist_queued<E: crate::PersistentEvent>(
+        &mut self,
+        key: impl Into<std::borrow::Cow<'static, str>>,
+    );
+
+    /// Queues the posts of a backlog saved with
+    /// [`EventQueue::save_backlog`](crate::EventQueue::save_backlog), e.g. when the app starts,
+    /// returning the number of posts restored.
+    ///
+    /// Restored posts keep their source entity and correlation ID. Their entities are remapped
+    /// with the [`EntityRemap`], and posts of events sequenced by target are sequenced again in
+    /// the order they were saved. Backlogs saved in older versions of the format are migrated.
+    ///
+    /// The whole backlog is rejected if it is from a newer version or was corrupted, e.g. by a
+    /// crash while it was written. Posts of event types that aren't
+    /// [persisted](WorldEventBus::persist_queued) anymore, or that fail to decode, are skipped with
+    /// a warning. Requires the `journal` feature.
+    #[cfg(feature = "journal")]
+    fn restore_queue_backlog(&mut self, bytes: &[u8]) -> Result<usize, crate::BacklogError>;
+
+    /// Returns the [`EventHistory`] of [`Event`] `E`, if history is enabled.
+    fn event_history<E: Event<Audience: Send + Sync> + Send + Sync>(
+        &self,
+    ) -> Option<&EventHistory<E>>;
+
+    /// Reconciles the posts of [`Event`] `E` recorded in the `predicted` and `authoritative`
+    /// worlds by correlation ID, and re-posts the authoritative posts that weren't predicted into
+    /// this world, see [`EventHistory::reconcile`]. Requires history to be enabled for `E` in both
+    /// worlds. The re-posted posts are remapped with the [`EntityRemap`] of this world. Returns the
+    /// number of posts re-posted.
+    fn reconcile_posts<E>(&mut self, predicted: &World, authoritative: &World) -> usize
+    where
+        E: Event<Audience: Clone + PartialEq + Send + Sync> + Clone + PartialEq + Send + Sync;
+
+    /// Replays all events of type `E` targeting the entity that were posted within the last
+    /// `window` of time, see [`EventReplayer::replay_window`]. Requires history to be enabled for
+    /// `E`. Returns the number of events scheduled for replay.
+    fn replay_window<E>(&mut self, entity: Entity, window: Duration) -> usize
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync;
+
+    /// Maps the entity from another domain, such as a journal or a network peer, to the entity of
+    /// this world when events re-enter the event bus, see [`EntityRemap`].
+    fn remap_entity(&mut self, foreign: Entity, local: Entity);
+
+    /// Remaps the entities of [`Event`] `E` itself when it re-enters the event bus from another
+    /// domain, rather than only the entities of its audience, see [`EntityRemap`].
+    fn remap_event_entities<E: Event + MapEntities>(&mut self);
+
+    /// Registers the internal handlers of a [`Join`], which runs its callback once all of its
+    /// awaited events have been posted.
+    fn add_join<K: Eq + Hash + Send + 'static>(&mut self, join: Join<K>);
+
+    /// Runs the [`Tick`](crate::tick::Tick) handlers of a [`HandlerSet`](crate::HandlerSet) in a
+    /// bevy schedule, ordered relative to the engine's systems, instead of when `Tick` is posted.
+    /// See [`TickAnchor`].
+    ///
+    /// Anchors are added to the schedule right away, so they must be added while it isn't
+    /// running, e.g. while building the app.
+    fn anchor_tick_set(&mut self, anchor: TickAnchor);
+
+    /// Pauses the event bus, deferring posts of all [`Event`] types until
+    /// [`WorldEventBus::resume_event_bus`], see [`EventBusPause`].
+    fn pause_event_bus(&mut self);
+
+    /// Pauses the event bus for [`Event`] `E` only, deferring its posts until
+    /// [`WorldEventBus::resume_event_bus`], see [`EventBusPause`].
+    fn pause_events<E: Event>(&mut self);
+
+    /// Pauses the event bus for the events with the tag only, as registered with
+    /// [`EventInfo::tag`](crate::EventInfo::tag), deferring their posts until
+    /// [`WorldEventBus::resume_event_bus`], see [`EventBusPause`].
+    fn pause_tagged_events(&mut self, tag: impl Into<Cow<'static, str>>);
+
+    /// Resumes the event bus, dispatching the posts deferred while it was paused in order.
+    /// Returns the number of posts dispatched.
+    fn resume_event_bus(&mut self) -> usize;
+
+    /// Posts an [`Event`] to the world.
+    fn post<E: Event<Audience = ()>>(&mut self, event: E) -> E::Cancellation {
+        self.post_to(event, ())
+    }
+
+    /// Posts an [`Event`] to the world with a specific [`Audience`](Event::Audience).
+    fn post_to<E: Event>(&mut self, event: E, audience: E::Audience) -> E::Cancellation;
+
+    /// Starts a long-running operation, returning the [`ProgressEmitter`] to stream its progress
+    /// with, see [`ProgressTracker`].
+    fn start_progress(&mut self) -> ProgressEmitter;
+
+    /// Posts [`SaveRequested`] and returns the [`SaveBlob`] its handlers contributed to, or `None`
+    /// if a handler cancelled the save.
+    fn request_save(&mut self) -> Option<SaveBlob> {
+        let mut request = SaveRequested::default();
+        (!self.post_mut(&mut request)).then_some(request.blob)
+    }
+
+    /// Posts [`LoadRequested`] so that its handlers restore their state from the [`SaveBlob`].
+    /// Returns `false` if a handler cancelled the load.
+    fn request_load(&mut self, blob: SaveBlob) -> bool {
+        !self.post(LoadRequested { blob })
+    }
+
+    /// Posts an [`Event`] along with per-post context to the handlers of [`WithContext<E, C>`],
+    /// see [`ReceiveWith`](crate::ReceiveWith).
+    fn post_with_context<E: Event<Audience = ()>, C: 'static>(
+        &mut self,
+        event: E,
+        context: C,
+    ) -> E::Cancellation {
+        self.post_to(WithContext { event, context }, ())
+    }
+
+    /// Posts an [`Event`] with a specific [`Audience`](Event::Audience) along with per-post
+    /// context to the handlers of [`WithContext<E, C>`], see [`ReceiveWith`](crate::ReceiveWith).
+    fn post_with_context_to<E: Event, C: 'static>(
+        &mut self,
+        event: E,
+        context: C,
+        audience: E::Audience,
+    ) -> E::Cancellation {
+        self.post_to(WithContext { event, context }, audience)
+    }
+
+    /// Posts the [`Event`] built by `f`, only calling `f` if any handler would receive it, e.g.
+    /// to skip building an expensive event that nobody listens to. Otherwise, returns the default
+    /// cancellation state.
+    ///
+    /// Handlers in disabled sets, or whose required resources don't exist, don't count. Run
+    /// conditions and audience filters are only evaluated once the event is built.
+    fn post_with<E: Event<Audience = ()>>(&mut self, f: impl FnOnce() -> E) -> E::Cancellation {
+        self.post_with_to(f, ())
+    }
+
+    /// Posts the [`Event`] built by `f` with a specific [`Audience`](Event::Audience), only calling
+    /// `f` if any handler would receive it, see [`WorldEventBus::post_with`].
+    fn post_with_to<E: Event>(
+        &mut self,
+        f: impl FnOnce() -> E,
+        audience: E::Audience,
+    ) -> E::Cancellation;
+
+    /// Starts building a post of an [`Event`] with options that only apply to that post, such as
+    /// the handlers it skips, see [`PostBuilder`].
+    fn build_post<E: Event<Audience = ()>>(&mut self, event: E) -> PostBuilder<'_, E> {
+        self.build_post_to(event, ())
+    }
+
+    /// Starts building a post of an [`Event`] with a specific [`Audience`](Event::Audience), see
+    /// [`WorldEventBus::build_post`].
+    fn build_post_to<E: Event>(&mut self, event: E, audience: E::Audience) -> PostBuilder<'_, E>;
+
+    /// Posts a shared payload of [`Event`] `E` to the handlers of [`Shared<E>`], see [`Shared`].
+    fn post_shared<E: Event<Audience = ()>>(&mut self, event: Arc<E>) -> E::Cancellation {
+        self.post_to(Shared(event), ())
+    }
+
+    /// Posts a shared payload of [`Event`] `E` with a specific [`Audience`](Event::Audience) to
+    /// the handlers of [`Shared<E>`], see [`Shared`].
+    fn post_shared_to<E: Event>(
+        &mut self,
+        event: Arc<E>,
+        audience: E::Audience,
+    ) -> E::Cancellation {
+        self.post_to(Shared(event), audience)
+    }
+
+    /// Posts an [`Event`] to the handlers subscribed to the key, see [`KeyedHandlers`].
+    fn post_keyed<E: Event<Audience = ()>, K: Eq + Hash + Clone + Send + Sync + 'static>(
+        &mut self,
+        event: E,
+        key: &K,
+    ) -> E::Cancellation {
+        self.post_keyed_to(event, key, ())
+    }
+
+    /// Posts an [`Event`] with a specific [`Audience`](Event::Audience) to the handlers subscribed
+    /// to the key, see [`KeyedHandlers`].
+    fn post_keyed_to<E: Event, K: Eq + Hash + Clone + Send + Sync + 'static>(
+        &mut self,
+        event: E,
+        key: &K,
+        audience: E::Audience,
+    ) -> E::Cancellation;
+
+    /// Posts an [`Event`] to the entities that the [`AudienceResolver`] resolves `target` to.
+    fn post_resolved<E: Event<Audience = Vec<Entity>>>(
+        &mut self,
+        event: E,
+        resolver: &impl AudienceResolver,
+        target: Entity,
+    ) -> E::Cancellation;
+
+    /// Posts an [`Event`] to the entity and its owners, see [`OwnerChain`].
+    fn post_to_owner_chain<E: Event<Audience = Vec<Entity>>>(
+        &mut self,
+        event: E,
+        target: Entity,
+    ) -> E::Cancellation {
+        self.post_resolved(event, &OwnerChain, target)
+    }
+
+    /// Posts an [`Event`] to every entity on the same team as the entity, see [`SameTeam`].
+    fn post_to_team<E: Event<Audience = Vec<Entity>>>(
+        &mut self,
+        event: E,
+        target: Entity,
+    ) -> E::Cancellation {
+        self.post_resolved(event, &SameTeam, target)
+    }
+
+    /// Posts an immutable reference to an [`Event`] to the world.
+    ///
+    /// Dropped while `E` is paused, see [`WorldEventBus::post_ref_to`].
+    fn post_ref<E: Event<Audience = (), Mutability = Immutable>>(
+        &mut self,
+        event: &E,
+    ) -> E::Cancellation {
+        self.post_ref_to(event, ())
+    }
+
+    /// Posts an immutable reference to an [`Event`] to the world with a specific [`Audience`](Event::Audience).
+    ///
+    /// A reference can't be buffered while `E` is [paused](EventBusPause), so the post is dropped
+    /// with a warning instead, without running any handlers, and reports the default cancellation
+    /// state.
+    fn post_ref_to<E: Event<Mutability = Immutable>>(
+        &mut self,
+        event: &E,
+        audience: E::Audience,
+    ) -> E::Cancellation;
+
+    /// Posts a mutable reference to an [`Event`] to the world.
+    ///
+    /// Dropped while `E` is paused, see [`WorldEventBus::post_mut_to`].
+    fn post_mut<E: Event<Audience = (), Mutability = Mutable>>(
+        &mut self,
+        event: &mut E,
+    ) -> E::Cancellation {
+        self.post_mut_to(event, ())
+    }
+
+    /// Posts a mutable reference to an [`Event`] to the world with a specific [`Audience`](Event::Audience).
+    ///
+    /// While `E` is [paused](EventBusPause), the post is dropped with a warning instead of
+    /// buffered, as the reference can't outlive the call. The event is left as is, and the default
+    /// cancellation state is reported.
+    fn post_mut_to<E: Event<Mutability = Mutable>>(
+        &mut self,
+        event: &mut E,
+        audience: E::Audience,
+    ) -> E::Cancellation;
+
+    /// Queues an [`Event`] to be posted on the next [`EventQueue`] flush.
+    fn enqueue<E: Event<Audience = ()> + Send>(&mut self, event: E) {
+        self.enqueue_to(event, ());
+    }
+
+    /// Queues an [`Event`] with a specific [`Audience`](Event::Audience) to be posted on the next
+    /// [`EventQueue`] flush.
+    fn enqueue_to<E: Event<Audience: Send> + Send>(&mut self, event: E, audience: E::Audience);
+
+    /// Queues an [`Event`] with a specific [`Audience`](Event::Audience) on behalf of a source
+    /// entity, to be posted on the next [`EventQueue`] flush.
+    ///
+    /// The source is used for [per-source fairness](EventQueue::set_per_source_fairness).
+    fn enqueue_from<E: Event<Audience: Send> + Send>(
+        &mut self,
+        source: Entity,
+        event: E,
+        audience: E::Audience,
+    );
+
+    /// Queues an [`Event`] to be posted on the next [`EventQueue`] flush, or dropped if it isn't
+    /// flushed within `ttl`. See [`EventExpired`](crate::EventExpired).
+    fn enqueue_with_ttl<E: Event<Audience = ()> + Send>(&mut self, event: E, ttl: Duration) {
+        self.enqueue_with_ttl_to(event, (), ttl);
+    }
+
+    /// Queues an [`Event`] with a specific [`Audience`](Event::Audience) to be posted on the next
+    /// [`EventQueue`] flush, or dropped if it isn't flushed within `ttl`.
+    /// See [`EventExpired`](crate::EventExpired).
+    fn enqueue_with_ttl_to<E: Event<Audience: Send> + Send>(
+        &mut self,
+        event: E,
+        audience: E::Audience,
+        ttl: Duration,
+    );
+
+    /// Returns the [`EventBusStats`] counters since the previous call, and resets them.
+    /// The first call starts collecting stats.
+    fn event_bus_stats(&mut self) -> BusStats;
+
+    /// Returns the merged access of all handlers for [`Event`] `E`, along with the handlers whose
+    /// access conflicts with the dispatch of `E`, see [`EventAccess`].
+    fn event_access<E: Event>(&mut self) -> EventAccess<E>;
+
+    /// Fails the test by panicking when [`Event`] `E` is posted more than `max_posts_per_frame`
+    /// times in a frame, until the returned guard is dropped, e.g. to catch a refactor that
+    /// accidentally posts an event per entity per frame:
+    ///
+    /// ```rust
+    /// # use bevy_ecs::world::World;
+    /// # use bevy_eventbus::prelude::*;
+    /// # struct Spawned;
+    /// # impl BusEvent for Spawned {
+    /// #     type Mutability = Immutable;
+    /// #     type Cancellation = ();
+    /// #     type Audience = ();
+    /// # }
+    /// let mut world = World::new();
+    /// let _budget = world.assert_event_budget::<Spawned>(1);
+    /// world.post(Spawned);
+    /// ```
+    fn assert_event_budget<E: Event>(&mut self, max_posts_per_frame: usize) -> EventBudgetGuard;
+
+    /// Fails the test by panicking when [`Event`] `E` is posted at all, until the returned guard is
+    /// dropped, see [`WorldEventBus::assert_event_budget`].
+    fn assert_no_event<E: Event>(&mut self) -> EventBudgetGuard {
+        self.assert_event_budget::<E>(0)
+    }
+
+    /// Posts up to `budget` queued events, or all of them if `budget` is `None`.
+    /// Returns the number of events posted.
+    fn flush_event_queue(&mut self, budget: Option<usize>) -> usize;
+}
+
+impl WorldEventBus for World {
+    fn add_handler<E: Event, M>(&mut self, handler: impl IntoHandlerConfig<E, M>) -> HandlerId<E> {
+        insert_handler(self, handler)
+    }
+
+    fn add_scoped_handler<E: Event, M>(
+        &mut self,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) -> ScopedHandler<E> {
+        let id = insert_handler(self, handler);
+        let dropped = self.resource::<HandlerRegistry<E>>().dropped_handlers();
+        ScopedHandler::new(id, dropped)
+    }
+
+    fn add_keyed_handler<E: Event, K: Eq + Hash + Send + Sync + 'static, M>(
+        &mut self,
+        key: K,
+        handler: impl IntoHandlerConfig<E, M>,
+    ) {
+        let config = initialize_config(self, handler);
+        if !self.contains_resource::<KeyedHandlers<E, K>>() {
+            RegistryIndex::track::<KeyedHandlers<E, K>>(self);
+        }
+        self.get_resource_or_insert_with(KeyedHandlers::<E, K>::default)
+            .get_or_insert(key)
+            .insert(config);
+    }
+
+    fn remove_keyed_handlers<E: Event, K: Eq + Hash + Send + Sync + 'static>(
+        &mut self,
+        key: &K,
+    ) -> usize {
+        self.get_resource_mut::<KeyedHandlers<E, K>>()
+            .and_then(|mut keyed| keyed.remove(key))
+            .map_or(0, |registry| registry.len())
+    }
+
+    fn set_dispatch_strategy<E: Event>(&mut self, strategy: impl DispatchStrategy<E>) {
+        HandlerRegistry::<E>::get_or_insert(self).set_strategy(strategy);
+    }
+
+    fn set_handler_storage<E: Event>(&mut self, storage: impl HandlerStorage<E>) {
+        HandlerRegistry::<E>::get_or_insert(self).set_storage(storage);
+    }
+
+    fn remove_handler<E: Event>(&mut self, id: HandlerId<E>) -> bool {
+        if let Some(mut tracker) = self.get_resource_mut::<DeliveryTracker<E>>() {
+            tracker.forget(id);
+        }
+        if let Some(mut owners) = self.get_resource_mut::<HandlerOwners>() {
+            owners.forget(id);
+        }
+        self.get_resource_mut::<HandlerRegistry<E>>()
+            .is_some_and(|mut registry| registry.remove(id).is_some())
+    }
+
+    fn configure_handler<E: Event>(
+        &mut self,
+        id: HandlerId<E>,
+        f: impl FnOnce(HandlerConfig<E>) -> HandlerConfig<E>,
+    ) -> bool {
+        self.get_resource_mut::<HandlerRegistry<E>>()
+            .is_some_and(|mut registry| registry.configure(id, f))
+    }
+
+    fn remove_handlers_where<E: Event>(
+        &mut self,
+        mut f: impl FnMut(&HandlerConfig<E>) -> bool,
+    ) -> usize {
+        let Some(registry) = self.get_resource::<HandlerRegistry<E>>() else {
+            return 0;
+        };
+        let matching = registry
+            .ids()
+            .filter(|&id| registry.get(id).is_some_and(&mut f))
+            .collect::<Vec<_>>();
+        matching
+            .into_iter()
+            .filter(|&id| self.remove_handler(id))
+            .count()
+    }
+
+    fn reset_handler_state<E: Event>(&mut self, id: HandlerId<E>) -> bool {
+        let reset = self
+            .get_resource_mut::<HandlerRegistry<E>>()
+            .is_some_and(|mut registry| registry.reset_handler_state(id));
+        initialize_reset_handlers::<E>(self);
+        reset
+    }
+
+    fn reset_circuit_breaker<E: Event>(&mut self, id: HandlerId<E>) -> bool {
+        self.get_resource::<HandlerRegistry<E>>()
+            .and_then(|registry| registry.get(id))
+            .and_then(|config| config.breaker.as_ref())
+            .is_some_and(|breaker| breaker.reset())
+    }
+
+    fn reset_all_handler_states<E: Event>(&mut self) -> usize {
+        let reset = self
+            .get_resource_mut::<HandlerRegistry<E>>()
+            .map_or(0, |mut registry| registry.reset_all());
+        initialize_reset_handlers::<E>(self);
+        reset
+    }
+
+    fn export_handlers(&mut self) -> HandlerBlueprints {
+        HandlerBlueprints::export(self)
+    }
+
+    fn install_handlers(&mut self, handlers: HandlerBlueprints) {
+        handlers.install(self);
+    }
+
+    fn handler_ids<E: Event>(&self) -> Vec<HandlerId<E>> {
+        self.get_resource::<HandlerRegistry<E>>()
+            .map(|registry| registry.ids().collect())
+            .unwrap_or_default()
+    }
+
+    fn handler_count<E: Event>(&self) -> usize {
+        self.get_resource::<HandlerRegistry<E>>()
+            .map_or(0, HandlerRegistry::len)
+    }
+
+    fn explain_handler_order<E: Event>(&self) -> Vec<OrderedHandler<E>> {
+        self.get_resource::<HandlerRegistry<E>>()
+            .map(HandlerRegistry::explain_order)
+            .unwrap_or_default()
+    }
+
+    fn configure_handler_set<E: Event>(&mut self, config: impl IntoHandlerSetConfig) {
+        let config = config.into_set_config();
+        for condition in &config.conditions {
+            condition.lock().initialize(self);
+        }
+
+        HandlerRegistry::<E>::get_or_insert(self).configure_set(config);
+        DeliveryTracker::<E>::catch_up(self);
+    }
+
+    fn remove_plugin_handlers<P: 'static>(&mut self) -> usize {
+        HandlerOwners::remove_all::<P>(self)
+    }
+
+    fn alias_event<Old, New>(&mut self, alias: EventAlias<Old, New>)
+    where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event,
+    {
+        let mut registry = HandlerRegistry::<Old>::get_or_insert(self);
+        if !registry.is_empty() {
+            warn!(
+                "{} has {} handler(s) which will no longer run now that it is an alias of {}",
+                std::any::type_name::<Old>(),
+                registry.len(),
+                std::any::type_name::<New>(),
+            );
+        }
+        registry.hooks_mut().alias = Some(Arc::new(alias));
+    }
+
+    fn register_deprecated_event<Old, New>(
+        &mut self,
+        converter: impl Fn(&Old) -> New + Send + Sync + 'static,
+    ) where
+        Old: Event<Audience = New::Audience, Cancellation = New::Cancellation>,
+        New: Event,
+    {
+        let deprecated = DeprecatedEvent::<Old, New>::new(converter);
+        let mut registry = HandlerRegistry::<Old>::get_or_insert(self);
+        if !registry.is_empty() {
+            deprecated.warn(&format!("{} handler(s) subscribed to", registry.len()));
+        }
+        registry.hooks_mut().alias = Some(Arc::new(deprecated));
+    }
+
+    fn start_progress(&mut self) -> ProgressEmitter {
+        self.get_resource_or_insert_with(ProgressTracker::default)
+            .start()
+    }
+
+    fn bridge_lifecycle<T: Component>(&mut self) {
+        LifecycleBridges::bridge::<T>(self);
+    }
+
+    fn register_bus_event<E: Event>(&mut self, info: EventInfo) {
+        self.get_resource_or_insert_with(EventCatalog::default)
+            .register::<E>(info);
+    }
+
+    fn event_info<E: Event>(&self) -> Option<&EventInfo> {
+        self.get_resource::<EventCatalog>()?.get::<E>()
+    }
+
+    fn current_event_stack(&self) -> &[EventMeta] {
+        self.get_resource::<EventContext>()
+            .map_or(&[], EventContext::stack)
+    }
+
+    fn enable_history<E>(&mut self, capacity: usize)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        self.get_resource_or_insert_with(|| EventHistory::<E>::new(capacity))
+            .set_capacity(capacity);
+        HandlerRegistry::<E>::get_or_insert(self)
+            .hooks_mut()
+            .recorder = Some(history::record::<E>);
+    }
+
+    fn track_delivery<E>(&mut self)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        if !self.contains_resource::<DeliveryTracker<E>>() {
+            RegistryIndex::track::<DeliveryTracker<E>>(self);
+            self.insert_resource(DeliveryTracker::<E>::new());
+        }
+    }
+
+    fn hash_stream<E: Event<Audience: Hash> + Hash>(&mut self) {
+        self.init_resource::<StreamHasher>();
+        HandlerRegistry::<E>::get_or_insert(self).hooks_mut().hasher =
+            Some(StreamHasher::record::<E>);
+    }
+
+    fn sequence_by_target<E: Event<Audience: Unicast + Send + Sync> + Send + Sync>(&mut self) {
+        HandlerRegistry::<E>::get_or_insert(self)
+            .hooks_mut()
+            .sequencer = Some(Sequencer::new());
+    }
+
+    fn throttle_per_target<E: Event<Audience: Unicast>>(&mut self, max_per_target_per_second: u32) {
+        self.get_resource_or_insert_with(|| TargetThrottle::<E>::new(max_per_target_per_second))
+            .max_per_target_per_second = max_per_target_per_second;
+        HandlerRegistry::<E>::get_or_insert(self)
+            .hooks_mut()
+            .throttle = Some(TargetThrottle::<E>::admit);
+    }
+
+    fn normalize_audience<E: Event<Audience: FromTargets>>(
+        &mut self,
+        policy: AudienceNormalization,
+    ) {
+        HandlerRegistry::<E>::get_or_insert(self)
+            .hooks_mut()
+            .normalizer = Some(Normalizer::new(policy));
+    }
+
+    fn collect_in_inboxes<E: Event<Audience: Unicast> + Clone + Send + Sync>(&mut self) {
+        HandlerRegistry::<E>::get_or_insert(self).hooks_mut().inbox =
+            Some(EventInbox::<E>::deliver);
+    }
+
+    fn dump_on_panic<E: Event<Audience: Debug> + Debug>(&mut self, max_len: usize) {
+        HandlerRegistry::<E>::get_or_insert(self)
+            .hooks_mut()
+            .panic_dump = Some(PanicDump::new(max_len));
+    }
+
+    fn enable_rollback<E>(&mut self)
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        self.get_resource_or_insert_with(EventQueue::default)
+            .enable_rollback::<E>();
+    }
+
+    #[cfg(feature = "journal")]
+    fn persist_queued<E: crate::PersistentEvent>(
+        &mut self,
+        key: impl Into<std::borrow::Cow<'static, str>>,
+    ) {
+        self.get_resource_or_insert_with(EventQueue::default)
+            .persist::<E>(key);
+    }
+
+    #[cfg(feature = "journal")]
+    fn restore_queue_backlog(&mut self, bytes: &[u8]) -> Result<usize, crate::BacklogError> {
+        crate::dispatch::queue::restore_backlog(self, bytes)
+    }
+
+    fn event_history<E: Event<Audience: Send + Sync> + Send + Sync>(
+        &self,
+    ) -> Option<&EventHistory<E>> {
+        self.get_resource::<EventHistory<E>>()
+    }
+
+    fn reconcile_posts<E>(&mut self, predicted: &World, authoritative: &World) -> usize
+    where
+        E: Event<Audience: Clone + PartialEq + Send + Sync> + Clone + PartialEq + Send + Sync,
+    {
+        let (Some(predicted), Some(authoritative)) = (
+            predicted.event_history::<E>(),
+            authoritative.event_history::<E>(),
+        ) else {
+            return 0;
+        };
+        let divergences = predicted.reconcile(authoritative).divergences;
+        let len = divergences.len();
+        for mut entry in divergences {
+            EntityRemap::apply(self, &mut entry.event, &mut entry.audience);
+            self.post_to(entry.event, entry.audience);
+        }
+        len
+    }
+
+    fn replay_window<E>(&mut self, entity: Entity, window: Duration) -> usize
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        EventReplayer::replay_window::<E>(self, entity, window)
+    }
+
+    fn remap_entity(&mut self, foreign: Entity, local: Entity) {
+        self.get_resource_or_insert_with(EntityRemap::default)
+            .insert(foreign, local);
+    }
+
+    fn remap_event_entities<E: Event + MapEntities>(&mut self) {
+        self.get_resource_or_insert_with(EntityRemap::default)
+            .register::<E>();
+    }
+
+    fn add_join<K: Eq + Hash + Send + 'static>(&mut self, join: Join<K>) {
+        join.register(self);
+    }
+
+    fn anchor_tick_set(&mut self, anchor: TickAnchor) {
+        anchor.install(self);
+    }
+
+    fn enqueue_to<E: Event<Audience: Send> + Send>(&mut self, event: E, audience: E::Audience) {
+        let parent = EventContext::parent(self);
+        let sequence = assign_sequence::<E>(self, &audience);
+        self.get_resource_or_insert_with(EventQueue::default)
+            .push_caused_by(None, event, audience, parent, None, sequence);
+    }
+
+    fn enqueue_from<E: Event<Audience: Send> + Send>(
+        &mut self,
+        source: Entity,
+        event: E,
+        audience: E::Audience,
+    ) {
+        let parent = EventContext::parent(self);
+        let sequence = assign_sequence::<E>(self, &audience);
+        self.get_resource_or_insert_with(EventQueue::default)
+            .push_caused_by(Some(source), event, audience, parent, None, sequence);
+    }
+
+    fn enqueue_with_ttl_to<E: Event<Audience: Send> + Send>(
+        &mut self,
+        event: E,
+        audience: E::Audience,
+        ttl: Duration,
+    ) {
+        let parent = EventContext::parent(self);
+        let sequence = assign_sequence::<E>(self, &audience);
+        self.get_resource_or_insert_with(EventQueue::default)
+            .push_caused_by(None, event, audience, parent, Some(ttl), sequence);
+    }
+
+    fn event_bus_stats(&mut self) -> BusStats {
+        EventBusStats::snapshot(self)
+    }
+
+    fn event_access<E: Event>(&mut self) -> EventAccess<E> {
+        EventAccess::compute(self)
+    }
+
+    fn assert_event_budget<E: Event>(&mut self, max_posts_per_frame: usize) -> EventBudgetGuard {
+        EventBudgets::guard::<E>(self, max_posts_per_frame)
+    }
+
+    fn flush_event_queue(&mut self, budget: Option<usize>) -> usize {
+        EventQueue::flush(self, budget)
+    }
+
+    fn pause_event_bus(&mut self) {
+        EventBusPause::pause(self, PauseFilter::All);
+    }
+
+    fn pause_events<E: Event>(&mut self) {
+        EventBusPause::pause(self, PauseFilter::Type(TypeId::of::<E>()));
+    }
+
+    fn pause_tagged_events(&mut self, tag: impl Into<Cow<'static, str>>) {
+        EventBusPause::pause(self, PauseFilter::Tag(tag.into()));
+    }
+
+    fn resume_event_bus(&mut self) -> usize {
+        EventBusPause::resume(self)
+    }
+
+    fn post_with_to<E: Event>(
+        &mut self,
+        f: impl FnOnce() -> E,
+        audience: E::Audience,
+    ) -> E::Cancellation {
+        if !HandlerRegistry::<E>::is_listened(self) {
+            return E::Cancellation::default();
+        }
+        self.post_to(f(), audience)
+    }
+
+    fn build_post_to<E: Event>(&mut self, event: E, audience: E::Audience) -> PostBuilder<'_, E> {
+        PostBuilder::new(self, event, audience)
+    }
+
+    fn post_to<E: Event>(&mut self, event: E, audience: E::Audience) -> E::Cancellation {
+        post_with_options(self, event, audience, PostOptions::default())
+    }
+
+    fn post_keyed_to<E: Event, K: Eq + Hash + Clone + Send + Sync + 'static>(
+        &mut self,
+        mut event: E,
+        key: &K,
+        audience: E::Audience,
+    ) -> E::Cancellation {
+        event.before_dispatch(self);
+        dispatch_keyed::<E, K>(
+            self,
+            E::Mutability::to_ref(&mut event),
+            key,
+            &audience,
+            |_, _, _| {},
+        )
+    }
+
+    fn post_resolved<E: Event<Audience = Vec<Entity>>>(
+        &mut self,
+        event: E,
+        resolver: &impl AudienceResolver,
+        target: Entity,
+    ) -> E::Cancellation {
+        let audience = resolver.resolve(self, target);
+        self.post_to(event, audience)
+    }
+
+    fn post_ref_to<E: Event<Mutability = Immutable>>(
+        &mut self,
+        event: &E,
+        audience: E::Audience,
+    ) -> E::Cancellation {
+        if EventBusPause::rejects::<E>(self) || HandlerRegistry::<E>::throttled(self, &audience) {
+            return E::Cancellation::default();
+        }
+        dispatch::<E>(
+            self,
+            event,
+            &audience,
+            &PostOptions::default(),
+            |_, _, _| {},
+        )
+    }
+
+    fn post_mut_to<E: Event<Mutability = Mutable>>(
+        &mut self,
+        event: &mut E,
+        audience: E::Audience,
+    ) -> E::Cancellation {
+        if EventBusPause::rejects::<E>(self) || HandlerRegistry::<E>::throttled(self, &audience) {
+            return E::Cancellation::default();
+        }
+        event.before_dispatch(self);
+        dispatch::<E>(
+            self,
+            event,
+            &audience,
+            &PostOptions::default(),
+            |_, _, _| {},
+        )
+    }
+}
+
+/// Posts an [`Event`] with the options, unless the event bus is paused or sequences the event by
+/// target, in which case the options are kept along with the deferred post.
+pub(crate) fn post_with_options<E: Event>(
+    world: &mut World,
+    event: E,
+    audience: E::Audience,
+    options: PostOptions,
+) -> E::Cancellation {
+    let Err((event, audience, options)) = EventBusPause::defer(world, event, audience, options)
+    else {
+        return E::Cancellation::default();
+    };
+    if let Some(sequencer) = HandlerRegistry::<E>::sequenced(world) {
+        let number = (sequencer.assign)(world, &audience);
+        return (sequencer.deliver)(world, number, event, audience, options);
+    }
+    post_now(world, event, audience, options)
+}
+
+/// Posts an [`Event`] with the options right away, regardless of whether the event bus is paused
+/// or sequences the event by target, unless its throttle drops it.
+pub(crate) fn post_now<E: Event>(
+    world: &mut World,
+    mut event: E,
+    audience: E::Audience,
+    options: PostOptions,
+) -> E::Cancellation {
+    if HandlerRegistry::<E>::throttled(world, &audience) {
+        return E::Cancellation::default();
+    }
+    event.before_dispatch(world);
+    dispatch::<E>(
+        world,
+        E::Mutability::to_ref(&mut event),
+        &audience,
+        &options,
+        |_, _, _| {},
+    )
+}
+
+/// Assigns the next sequence number of the target of a queued post, if [`Event`] `E` is sequenced
+/// by target.
+pub(crate) fn assign_sequence<E: Event>(
+    world: &mut World,
+    audience: &E::Audience,
+) -> Option<SequenceNumber> {
+    let sequencer = HandlerRegistry::<E>::sequenced(world)?;
+    Some((sequencer.assign)(world, audience))
+}
+
+/// Initializes and inserts a handler into the [`HandlerRegistry`] for [`Event`] `E`.
+pub(crate) fn insert_handler<E: Event, M>(
+    world: &mut World,
+    handler: impl IntoHandlerConfig<E, M>,
+) -> HandlerId<E> {
+    let config = initialize_config(world, handler);
+    let registry = HandlerRegistry::<E>::get_or_insert(world);
+    if let Some(alias) = &registry.hooks().alias {
+        alias.handler_added();
+    }
+    let priority = registry.priority_of(&config);
+    check_reserved_priority(world, &config, priority);
+
+    let name = config.name();
+    let id = world.resource_mut::<HandlerRegistry<E>>().insert(config);
+
+    if let Some(mut owners) = world.get_resource_mut::<HandlerOwners>() {
+        if let Some(owner) = owners.current_scope() {
+            owners.insert(owner, id);
+        }
+    }
+
+    if world.contains_resource::<HandlerRegistry<HandlerAdded<E>>>() {
+        world.post(HandlerAdded { id, name, priority });
+    }
+
+    DeliveryTracker::<E>::catch_up(world);
+    id
+}
+
+/// Converts a handler into its [`HandlerConfig`] and initializes its system.
+pub(crate) fn initialize_config<E: Event, M>(
+    world: &mut World,
+    handler: impl IntoHandlerConfig<E, M>,
+) -> HandlerConfig<E> {
+    let mut config = handler.into_config();
+    // A handler that is running can only be re-added from within its own dispatch, and is
+    // already initialized.
+    if let Some(mut system) = config.handler.try_lock() {
+        system.initialize(world);
+        config.main_thread |= !system.is_send();
+    }
+    for condition in config.conditions.iter() {
+        condition.lock().initialize(world);
+    }
+    config
+}
+
+/// Warns, or panics if denied by the [`EventBusSettings`], if a handler is added with a priority
+/// within a band reserved by an [`Event`] defined in another crate.
+fn check_reserved_priority<E: Event>(world: &World, config: &HandlerConfig<E>, priority: i32) {
+    let Some(band) = E::reserved_priorities()
+        .iter()
+        .find(|band| band.contains(&priority))
+    else {
+        return;
+    };
+    let event = std::any::type_name::<E>();
+    let name = config.name();
+    if config.anonymous || crate_of(&name) == crate_of(event) {
+        return;
+    }
+
+    let message = format!(
+        "Handler {name} was added with priority {priority}, within the band {band:?} reserved by {event}"
+    );
+    let deny = world
+        .get_resource::<EventBusSettings>()
+        .is_some_and(|settings| settings.deny_reserved_priorities);
+    if deny {
+        panic!("{message}");
+    }
+    warn!("{message}");
+}
+
+/// Returns the name of the crate from a type or system name.
+pub(crate) fn crate_of(name: &str) -> &str {
+    let name = name.trim_start_matches(['<', '&']);
+    name.split("::").next().unwrap_or(name)
+}
+
+/// [`Commands`] extension trait for registering event handlers and posting events.
+pub trait CommandEventBus {
+    /// Queues a [`Command`] that adds an event handler for [`Event`] `E` to the world.
+    fn add_handler<E: Event, M>(&mut self, system: impl IntoHandlerConfig<E, M>);
+
+    /// Queues a [`Command`] that removes an event handler for [`Event`] `E` from the world, see
+    /// [`WorldEventBus::remove_handler`].
+    fn remove_handler<E: Event>(&mut self, id: HandlerId<E>);
+
+    /// Queues a [`Command`] that posts an [`Event`] to the world.
+    fn post<E: Event<Audience = ()> + Send>(&mut self, event: E) {
+        self.post_to(event, ());
+    }
+
+    /// Queues a [`Command`] that posts an [`Event`] to the world with a specific [`Audience`](Event::Audience).
+    fn post_to<E: Event<Audience: Send> + Send>(&mut self, event: E, audience: E::Audience);
+
+    /// Queues a [`Command`] that posts the [`Event`] built by `f`, only calling `f` if any handler
+    /// would receive it when the command is applied, see [`WorldEventBus::post_with`].
+    fn post_with<E: Event<Audience = ()> + Send>(
+        &mut self,
+        f: impl FnOnce() -> E + Send + 'static,
+    ) {
+        self.post_with_to(f, ());
+    }
+
+    /// Queues a [`Command`] that posts the [`Event`] built by `f` with a specific
+    /// [`Audience`](Event::Audience), only calling `f` if any handler would receive it when the
+    /// command is applied, see [`WorldEventBus::post_with`].
+    fn post_with_to<E: Event<Audience: Send> + Send>(
+        &mut self,
+        f: impl FnOnce() -> E + Send + 'static,
+        audience: E::Audience,
+    );
+
+    /// Queues a [`Command`] that posts a shared payload of [`Event`] `E` to the handlers of
+    /// [`Shared<E>`], see [`Shared`].
+    fn post_shared<E: Event<Audience = ()> + Send + Sync>(&mut self, event: Arc<E>) {
+        self.post_to(Shared(event), ());
+    }
+
+    /// Queues a [`Command`] that queues an [`Event`] on the [`EventQueue`].
+    fn enqueue<E: Event<Audience = ()> + Send>(&mut self, event: E) {
+        self.enqueue_to(event, ());
+    }
+
+    /// Queues a [`Command`] that queues an [`Event`] with a specific [`Audience`](Event::Audience)
+    /// on the [`EventQueue`].
+    fn enqueue_to<E: Event<Audience: Send> + Send>(&mut self, event: E, audience: E::Audience);
+
+    /// Queues a [`Command`] that queues an [`Event`] on the [`EventQueue`], which is dropped if it
+    /// isn't flushed within `ttl`. See [`EventExpired`](crate::EventExpired).
+    fn enqueue_with_ttl<E: Event<Audience = ()> + Send>(&mut self, event: E, ttl: Duration) {
+        self.enqueue_with_ttl_to(event, (), ttl);
+    }
+
+    /// Queues a [`Command`] that queues an [`Event`] with a specific
+    /// [`Audience`](Event::Audience) on the [`EventQueue`], which is dropped if it isn't flushed
+    /// within `ttl`. See [`EventExpired`](crate::EventExpired).
+    fn enqueue_with_ttl_to<E: Event<Audience: Send> + Send>(
+        &mut self,
+        event: E,
+        audience: E::Audience,
+        ttl: Duration,
+    );
+}
+
+impl CommandEventBus for Commands<'_, '_> {
+    fn add_handler<E: Event, M>(&mut self, system: impl IntoHandlerConfig<E, M>) {
+        self.queue(AddHandler {
+            system: system.into_config(),
+        });
+    }
+
+    fn remove_handler<E: Event>(&mut self, id: HandlerId<E>) {
+        self.queue(RemoveHandler { id });
+    }
+
+    fn post_to<E: Event<Audience: Send> + Send>(&mut self, event: E, audience: E::Audience) {
+        self.queue(PostEvent { event, audience });
+    }
+
+    fn post_with_to<E: Event<Audience: Send> + Send>(
+        &mut self,
+        f: impl FnOnce() -> E + Send + 'static,
+        audience: E::Audience,
+    ) {
+        self.queue(PostEventWith { f, audience });
+    }
+
+    fn enqueue_to<E: Event<Audience: Send> + Send>(&mut self, event: E, audience: E::Audience) {
+        self.queue(EnqueueEvent {
+            event,
+            audience,
+            ttl: None,
+        });
+    }
+
+    fn enqueue_with_ttl_to<E: Event<Audience: Send> + Send>(
+        &mut self,
+        event: E,
+        audience: E::Audience,
+        ttl: Duration,
+    ) {
+        self.queue(EnqueueEvent {
+            event,
+            audience,
+            ttl: Some(ttl),
+        });
+    }
+}
+
+/// [`Command`] that adds a [`HandlerSystem`] to the [`World`].
+///
+/// [`HandlerSystem`]: crate::HandlerSystem
+pub struct AddHandler<E: Event> {
+    system: HandlerConfig<E>,
+}
+
+impl<E: Event> Command for AddHandler<E> {
+    fn apply(self, world: &mut World) {
+        world.add_handler(self.system);
+    }
+}
+
+/// [`Command`] that removes an event handler from the [`World`].
+pub struct RemoveHandler<E: Event> {
+    id: HandlerId<E>,
+}
+
+impl<E: Event> Command for RemoveHandler<E> {
+    fn apply(self, world: &mut World) {
+        world.remove_handler(self.id);
+    }
+}
+
+/// [`Command`] that posts an [`Event`] to the [`World`].
+pub struct PostEvent<E: Event> {
+    event: E,
+    audience: E::Audience,
+}
+
+impl<E: Event<Audience: Send> + Send> Command for PostEvent<E> {
+    fn apply(self, world: &mut World) {
+        world.post_to(self.event, self.audience);
+    }
+}
+
+/// [`Command`] that posts an [`Event`] to the [`World`], only building it if any handler would
+/// receive it.
+pub struct PostEventWith<E: Event, F> {
+    f: F,
+    audience: E::Audience,
+}
+
+impl<E, F> Command for PostEventWith<E, F>
+where
+    E: Event<Audience: Send> + Send,
+    F: FnOnce() -> E + Send + 'static,
+{
+    fn apply(self, world: &mut World) {
+        world.post_with_to(self.f, self.audience);
+    }
+}
+
+/// [`Command`] that queues an [`Event`] on the [`EventQueue`].
+pub struct EnqueueEvent<E: Event> {
+    event: E,
+    audience: E::Audience,
+    ttl: Option<Duration>,
+}
+
+impl<E: Event<Audience: Send> + Send> Command for EnqueueEvent<E> {
+    fn apply(self, world: &mut World) {
+        match self.ttl {
+            Some(ttl) => world.enqueue_with_ttl_to(self.event, self.audience, ttl),
+            None => world.enqueue_to(self.event, self.audience),
+        }
+    }
+}
@@ -1,11 +1,43 @@
 use std::{
     borrow::{Borrow, BorrowMut},
     fmt::Debug,
+    ops::RangeInclusive,
 };
 
-use bevy_ecs::entity::Entity;
+use bevy_ecs::{
+    entity::{Entity, EntityMapper},
+    world::World,
+};
 
+#[cfg(feature = "bytes")]
+mod bytes;
+mod info;
+mod lazy;
+mod macros;
+mod normalize;
+mod outcome;
+mod per_target;
+mod progress;
+mod remap;
+mod resolver;
+mod save;
+mod shared;
 pub mod tick;
+mod with;
+
+#[cfg(feature = "bytes")]
+pub use bytes::*;
+pub use info::*;
+pub use lazy::*;
+pub use normalize::*;
+pub use outcome::*;
+pub use per_target::*;
+pub use progress::*;
+pub use remap::*;
+pub use resolver::*;
+pub use save::*;
+pub use shared::*;
+pub use with::*;
 
 /// Messages sent between event handlers.
 ///
@@ -39,9 +71,11 @@ pub mod tick;
 /// ## Unmodifiable, uncancellable, no audience
 ///
 /// ```rust
+/// use bevy_eventbus::prelude::*;
+///
 /// struct MyEvent(String);
 ///
-/// impl Event for MyEvent {
+/// impl BusEvent for MyEvent {
 ///     type Mutability = Immutable;
 ///     type Cancellation = ();
 ///     type Audience = ();
@@ -55,15 +89,18 @@ pub mod tick;
 /// ## Modifiable, cancellable, single entity audience
 ///
 /// ```rust
+/// use bevy_ecs::{entity::Entity, world::World};
+/// use bevy_eventbus::prelude::*;
+///
 /// struct MyEvent(i32);
 ///
-/// impl Event for MyEvent {
-///    type Mutability = Mutable;
-///    type Cancellation = bool;
-///    type Audience = Entity;
+/// impl BusEvent for MyEvent {
+///     type Mutability = Mutable;
+///     type Cancellation = bool;
+///     type Audience = Entity;
 /// }
 ///
-/// fn my_handler_system(event: Receive<MyEvent>) {
+/// fn my_handler_system(mut event: Receive<MyEvent>) {
 ///     event.0 += 1;
 ///     if event.0 > 10 {
 ///         event.cancel();
@@ -80,6 +117,60 @@ pub trait Event: 'static {
     type Cancellation: Cancellation;
     /// Who the event is intended for.
     type Audience: Audience;
+
+    /// Priority bands reserved for handlers defined in the same crate as the event, e.g. so that a
+    /// framework's validation handler is guaranteed to run first.
+    ///
+    /// Adding a handler from another crate with a priority within a reserved band logs a warning,
+    /// or panics if [`EventBusSettings::deny_reserved_priorities`](crate::EventBusSettings) is set.
+    /// The crate of a handler is determined from its system name, which for closures added with
+    /// [`WorldEventBus::add_handler_fn`](crate::WorldEventBus::add_handler_fn) is the name of the
+    /// closure. Handlers running a one-shot system by its [`SystemId`](bevy_ecs::system::SystemId)
+    /// are exempt, as the crate that registered the system is unknown.
+    ///
+    /// ```rust
+    /// # use std::ops::RangeInclusive;
+    /// # use bevy_ecs::world::World;
+    /// # use bevy_eventbus::{prelude::*, EventBusSettings};
+    /// struct Validate;
+    ///
+    /// impl BusEvent for Validate {
+    ///     type Mutability = Immutable;
+    ///     type Cancellation = bool;
+    ///     type Audience = ();
+    ///
+    ///     fn reserved_priorities() -> &'static [RangeInclusive<i32>] {
+    ///         &[i32::MIN..=i32::MAX]
+    ///     }
+    /// }
+    ///
+    /// fn validate(_event: Receive<Validate>) {}
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(EventBusSettings {
+    ///     deny_reserved_priorities: true,
+    ///     ..Default::default()
+    /// });
+    /// // Handlers from the crate of the event may use the reserved band, however they are added.
+    /// world.add_handler(validate);
+    /// world.add_handler_fn(|_event: Receive<Validate>, _world: &mut World| {});
+    /// let system = world.register_system(validate);
+    /// world.add_handler(system);
+    /// ```
+    fn reserved_priorities() -> &'static [RangeInclusive<i32>] {
+        &[]
+    }
+
+    /// Called before any handler runs, to enforce invariants on the event regardless of which
+    /// handlers are registered, e.g. clamping values.
+    ///
+    /// Only called when the dispatcher has mutable access to the event, so not for posts of event
+    /// references with [`WorldEventBus::post_ref`](crate::WorldEventBus::post_ref).
+    fn before_dispatch(&mut self, _world: &mut World) {}
+
+    /// Called after all handlers ran, or the event was cancelled, with the final cancellation
+    /// state of the event, e.g. to record metrics.
+    fn after_dispatch(&self, _cancellation: &Self::Cancellation, _world: &mut World) {}
 }
 
 /// [`Event`] configuration that determines if an event can be modified or not.
@@ -91,6 +182,12 @@ pub trait Mutability: sealed::Mutability {
 
     /// Converts a mutable reference into the allowed reference type.
     fn to_ref<T: ?Sized>(value: &mut T) -> Self::Ref<'_, T>;
+
+    /// Reborrows the allowed reference type for a shorter lifetime.
+    fn reborrow<'a, T: ?Sized>(value: &'a mut Self::Ref<'_, T>) -> Self::Ref<'a, T>;
+
+    /// Returns a mutable reference to the data, if this [`Mutability`] allows it.
+    fn get_mut<'a, T: ?Sized>(value: &'a mut Self::Ref<'_, T>) -> Option<&'a mut T>;
 }
 
 /// [`Event`] [`Mutability`] that only allows read-only access.
@@ -102,6 +199,14 @@ impl Mutability for Immutable {
     fn to_ref<T: ?Sized>(value: &mut T) -> Self::Ref<'_, T> {
         value
     }
+
+    fn reborrow<'a, T: ?Sized>(value: &'a mut Self::Ref<'_, T>) -> Self::Ref<'a, T> {
+        *value
+    }
+
+    fn get_mut<'a, T: ?Sized>(_value: &'a mut Self::Ref<'_, T>) -> Option<&'a mut T> {
+        None
+    }
 }
 
 /// [`Event`] [`Mutability`] that allows read-write access.
@@ -113,6 +218,36 @@ impl Mutability for Mutable {
     fn to_ref<T: ?Sized>(value: &mut T) -> Self::Ref<'_, T> {
         value
     }
+
+    fn reborrow<'a, T: ?Sized>(value: &'a mut Self::Ref<'_, T>) -> Self::Ref<'a, T> {
+        value
+    }
+
+    fn get_mut<'a, T: ?Sized>(value: &'a mut Self::Ref<'_, T>) -> Option<&'a mut T> {
+        Some(value)
+    }
+}
+
+/// [`Mutability`] of [`Event`] `E` that handlers which
+/// [always run](crate::HandlerConfig::always_run) can be given a read-only view of.
+///
+/// [`Immutable`] events are shared with them as is. [`Mutable`] events are cloned, so that the
+/// changes they make are discarded.
+pub trait ReadOnlyView<E>: Mutability {
+    /// Copies the event, if it needs to be copied so that it isn't changed.
+    fn copy(event: &E) -> Option<E>;
+}
+
+impl<E> ReadOnlyView<E> for Immutable {
+    fn copy(_event: &E) -> Option<E> {
+        None
+    }
+}
+
+impl<E: Clone> ReadOnlyView<E> for Mutable {
+    fn copy(event: &E) -> Option<E> {
+        Some(event.clone())
+    }
 }
 
 /// Shorthand for the type of reference that the [`Mutability`] allows for an [`Event`].
@@ -126,6 +261,10 @@ pub type MutabilityRef<'event, E> = <<E as Event>::Mutability as Mutability>::Re
 /// - `()`: An uncancellable event.
 /// - [`bool`]: A simple boolean flag.
 /// - [`Option<T>`]: Cancellation with a reason.
+/// - [`PerTargetCancellation`]: Cancellation by each target of a multicast event.
+///
+/// The cancellation state returned by posting an event can be inspected uniformly with
+/// [`PostOutcomeExt`].
 pub trait Cancellation: Debug + Default {
     /// A mutable reference to the cancellation state.
     type Mut<'event>: BorrowMut<Self>
@@ -138,6 +277,12 @@ pub trait Cancellation: Debug + Default {
     /// Returns `true` if the event is cancelled.
     /// To cancel an event, use [`Cancellable::cancel`].
     fn cancelled(&self) -> bool;
+
+    /// Reverts the cancellation of the event, so that it is processed further again, e.g. to veto
+    /// the cancellation of a lower priority plugin. Resets the state to its default by default.
+    fn uncancel(&mut self) {
+        *self = Self::default();
+    }
 }
 
 /// [`Event`] configuration to allow them to be cancelled.
@@ -228,7 +373,20 @@ pub type CancellationMut<'event, E> = <<E as Event>::Cancellation as Cancellatio
 /// Provided implementations:
 /// - `()`: No target entities.
 /// - [`Entity`]: A single target entity.
-pub trait Audience {}
+pub trait Audience {
+    /// Returns `true` if the entity is one of the targets of the [`Event`].
+    ///
+    /// Audiences without target entities never include any entity.
+    fn includes(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    /// Maps the target entities, when the [`Event`] crosses into another world, e.g. with the
+    /// [`BusPair`](crate::BusPair) or the [`EntityRemap`].
+    ///
+    /// Audiences without target entities have nothing to map.
+    fn map_targets(&mut self, _mapper: &mut dyn EntityMapper) {}
+}
 
 impl Audience for () {}
 
@@ -237,12 +395,23 @@ impl Audience for () {}
 /// Provided implementations:
 /// - [`Vec<Entity>`]: A collection of target entities.
 /// - `[Entity; N]`: A fixed-size array of target entities.
+/// - [`LazyAudience`]: Target entities resolved when first inspected.
 pub trait Multicast: Audience {
     /// The target entities of the [`Event`].
     fn targets(&self) -> impl Iterator<Item = Entity> + '_;
 }
 
-impl Audience for Vec<Entity> {}
+impl Audience for Vec<Entity> {
+    fn includes(&self, entity: Entity) -> bool {
+        self.contains(&entity)
+    }
+
+    fn map_targets(&mut self, mapper: &mut dyn EntityMapper) {
+        for entity in self {
+            *entity = mapper.map_entity(*entity);
+        }
+    }
+}
 
 impl Multicast for Vec<Entity> {
     fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
@@ -250,7 +419,17 @@ impl Multicast for Vec<Entity> {
     }
 }
 
-impl<const N: usize> Audience for [Entity; N] {}
+impl<const N: usize> Audience for [Entity; N] {
+    fn includes(&self, entity: Entity) -> bool {
+        self.contains(&entity)
+    }
+
+    fn map_targets(&mut self, mapper: &mut dyn EntityMapper) {
+        for entity in self {
+            *entity = mapper.map_entity(*entity);
+        }
+    }
+}
 
 impl<const N: usize> Multicast for [Entity; N] {
     fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
@@ -267,7 +446,15 @@ pub trait Unicast: Audience {
     fn target(&self) -> Entity;
 }
 
-impl Audience for Entity {}
+impl Audience for Entity {
+    fn includes(&self, entity: Entity) -> bool {
+        *self == entity
+    }
+
+    fn map_targets(&mut self, mapper: &mut dyn EntityMapper) {
+        *self = mapper.map_entity(*self);
+    }
+}
 
 impl Unicast for Entity {
     fn target(&self) -> Entity {
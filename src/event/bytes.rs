@@ -0,0 +1,102 @@
+use std::{convert::Infallible, string::FromUtf8Error};
+
+use bytes::Bytes;
+
+use crate::{Event, Immutable, Receive};
+
+/// [`Event`] whose payload is kept as raw [`Bytes`] next to a typed header `H`, e.g. a packet
+/// received from the network.
+///
+/// The payload is only decoded when a handler asks for it with [`Receive::decode`], so handlers
+/// that only look at the header, or ignore the event, don't pay for deserialization. Cloning the
+/// payload with [`Receive::payload`] doesn't copy it.
+///
+/// Requires the `bytes` feature.
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::{prelude::*, BytesEvent};
+/// struct PacketHeader {
+///     channel: u8,
+/// }
+///
+/// fn chat(event: Receive<BytesEvent<PacketHeader>>) {
+///     if event.header.channel == 1 {
+///         let message = event.decode::<String>().unwrap();
+///         assert_eq!(message, "hello");
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(chat);
+/// world.post(BytesEvent::new(PacketHeader { channel: 1 }, "hello"));
+/// ```
+pub struct BytesEvent<H> {
+    /// The typed header of the event.
+    pub header: H,
+    /// The raw payload of the event.
+    pub payload: Bytes,
+}
+
+impl<H> BytesEvent<H> {
+    /// Creates an event from its header and raw payload.
+    pub fn new(header: H, payload: impl Into<Bytes>) -> Self {
+        Self {
+            header,
+            payload: payload.into(),
+        }
+    }
+}
+
+impl<H: 'static> Event for BytesEvent<H> {
+    type Mutability = Immutable;
+    type Cancellation = bool;
+    type Audience = ();
+}
+
+/// Types that can be decoded from the raw payload of a [`BytesEvent`], see [`Receive::decode`].
+///
+/// Implement this with the codec of your choice, e.g. `bincode` or `postcard`.
+pub trait DecodeBytes: Sized {
+    /// The error returned when the payload is malformed.
+    type Error;
+
+    /// Decodes the payload.
+    fn decode(payload: &Bytes) -> Result<Self, Self::Error>;
+}
+
+impl DecodeBytes for Bytes {
+    type Error = Infallible;
+
+    fn decode(payload: &Bytes) -> Result<Self, Self::Error> {
+        Ok(payload.clone())
+    }
+}
+
+impl DecodeBytes for Vec<u8> {
+    type Error = Infallible;
+
+    fn decode(payload: &Bytes) -> Result<Self, Self::Error> {
+        Ok(payload.to_vec())
+    }
+}
+
+impl DecodeBytes for String {
+    type Error = FromUtf8Error;
+
+    fn decode(payload: &Bytes) -> Result<Self, Self::Error> {
+        String::from_utf8(payload.to_vec())
+    }
+}
+
+impl<H: 'static> Receive<'_, BytesEvent<H>> {
+    /// Decodes the payload of the event.
+    pub fn decode<T: DecodeBytes>(&self) -> Result<T, T::Error> {
+        T::decode(&self.event().payload)
+    }
+
+    /// Returns a new reference to the raw payload, without copying it.
+    pub fn payload(&self) -> Bytes {
+        self.event().payload.clone()
+    }
+}
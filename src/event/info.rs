@@ -0,0 +1,136 @@
+use std::{
+    any::{type_name, TypeId},
+    borrow::Cow,
+    collections::BTreeMap,
+};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{crate_of, Event, WorldEventBus};
+
+/// How often an [`Event`] is expected to be posted, see [`EventInfo`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventFrequency {
+    /// Posted rarely, e.g. on startup or when a level loads.
+    Rare,
+    /// Posted occasionally, e.g. in response to player input.
+    #[default]
+    Occasional,
+    /// Posted every frame, or several times per frame.
+    Frequent,
+}
+
+/// How stable the shape and semantics of an [`Event`] are, see [`EventInfo`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventStability {
+    /// May change or be removed at any time.
+    Experimental,
+    /// Only changes in breaking releases.
+    #[default]
+    Stable,
+    /// Kept for compatibility, and will be removed in a future release.
+    Deprecated,
+}
+
+/// Human-readable metadata of an [`Event`], registered with
+/// [`WorldEventBus::register_bus_event`] and read back from the [`EventCatalog`], e.g. by modding
+/// documentation generators and debug UIs.
+#[derive(Debug, Clone)]
+pub struct EventInfo {
+    /// The type name of the event.
+    pub name: &'static str,
+    /// What the event means, and when it is posted.
+    pub description: Cow<'static, str>,
+    /// The crate that posts the event, which defaults to the crate that defines it.
+    pub emitter: Cow<'static, str>,
+    /// How often the event is expected to be posted.
+    pub frequency: EventFrequency,
+    /// How stable the event is.
+    pub stability: EventStability,
+    /// Free-form tags of the event, e.g. the gameplay system it belongs to, see
+    /// [`BusTraceConfig::tags`](crate::BusTraceConfig::tags).
+    pub tags: Vec<Cow<'static, str>>,
+    handler_count: fn(&World) -> usize,
+}
+
+impl EventInfo {
+    /// Creates the metadata of [`Event`] `E` with a description.
+    pub fn new<E: Event>(description: impl Into<Cow<'static, str>>) -> Self {
+        let name = type_name::<E>();
+        Self {
+            name,
+            description: description.into(),
+            emitter: Cow::Borrowed(crate_of(name)),
+            frequency: EventFrequency::default(),
+            stability: EventStability::default(),
+            tags: Vec::new(),
+            handler_count: |world| world.handler_count::<E>(),
+        }
+    }
+
+    /// Sets the crate that posts the event.
+    pub fn emitter(mut self, emitter: impl Into<Cow<'static, str>>) -> Self {
+        self.emitter = emitter.into();
+        self
+    }
+
+    /// Sets how often the event is expected to be posted.
+    pub fn frequency(mut self, frequency: EventFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Sets how stable the event is.
+    pub fn stability(mut self, stability: EventStability) -> Self {
+        self.stability = stability;
+        self
+    }
+
+    /// Adds a tag to the event.
+    pub fn tag(mut self, tag: impl Into<Cow<'static, str>>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Returns the number of handlers registered for the event in the world, e.g. to list them
+    /// alongside the metadata.
+    pub fn handler_count(&self, world: &World) -> usize {
+        (self.handler_count)(world)
+    }
+}
+
+/// [`Resource`] which stores the [`EventInfo`] of every registered [`Event`].
+#[derive(Resource, Debug, Default)]
+pub struct EventCatalog {
+    events: BTreeMap<&'static str, (TypeId, EventInfo)>,
+}
+
+impl EventCatalog {
+    /// Registers the metadata of [`Event`] `E`, replacing any previous metadata.
+    pub fn register<E: Event>(&mut self, info: EventInfo) {
+        self.events.insert(info.name, (TypeId::of::<E>(), info));
+    }
+
+    /// Returns the metadata of [`Event`] `E`, if registered.
+    pub fn get<E: Event>(&self) -> Option<&EventInfo> {
+        self.events
+            .get(type_name::<E>())
+            .filter(|(type_id, _)| *type_id == TypeId::of::<E>())
+            .map(|(_, info)| info)
+    }
+
+    /// Returns an iterator over the metadata of all registered events, ordered by type name.
+    pub fn iter(&self) -> impl Iterator<Item = &EventInfo> + '_ {
+        self.events.values().map(|(_, info)| info)
+    }
+
+    /// Returns the number of registered events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no events are registered.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
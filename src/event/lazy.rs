@@ -0,0 +1,96 @@
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+use bevy_ecs::entity::{Entity, EntityMapper};
+
+use crate::{Audience, Multicast};
+
+/// [`Multicast`] audience whose target entities are only resolved when they are first inspected,
+/// so that posts whose handlers never look at the audience don't pay for resolving it.
+///
+/// The resolver runs at most once per post, on the first call to [`Multicast::targets`] or
+/// [`Audience::includes`], e.g. through [`Receive::targets`](crate::Receive::targets). Clones of
+/// the audience share the resolved targets.
+///
+/// ```rust
+/// # use bevy_ecs::entity::Entity;
+/// # use bevy_eventbus::{prelude::*, LazyAudience};
+/// struct Announcement;
+///
+/// impl BusEvent for Announcement {
+///     type Mutability = Immutable;
+///     type Cancellation = ();
+///     type Audience = LazyAudience;
+/// }
+///
+/// let members = vec![Entity::from_raw(1), Entity::from_raw(2)];
+/// let audience = LazyAudience::new(move || members.clone());
+/// assert!(!audience.is_resolved());
+/// assert_eq!(audience.targets().count(), 2);
+/// assert!(audience.is_resolved());
+/// ```
+#[derive(Clone)]
+pub struct LazyAudience {
+    targets: Arc<OnceLock<Vec<Entity>>>,
+    resolve: Arc<dyn Fn() -> Vec<Entity> + Send + Sync>,
+}
+
+impl LazyAudience {
+    /// Creates an audience that resolves its targets with the resolver when first inspected.
+    pub fn new(resolve: impl Fn() -> Vec<Entity> + Send + Sync + 'static) -> Self {
+        Self {
+            targets: Arc::new(OnceLock::new()),
+            resolve: Arc::new(resolve),
+        }
+    }
+
+    /// Creates an audience whose targets are already resolved.
+    pub fn resolved(targets: Vec<Entity>) -> Self {
+        Self {
+            targets: Arc::new(OnceLock::from(targets)),
+            resolve: Arc::new(Vec::new),
+        }
+    }
+
+    /// Returns `true` if the targets were resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.targets.get().is_some()
+    }
+
+    /// Returns the target entities, resolving them if needed.
+    pub fn get(&self) -> &[Entity] {
+        self.targets.get_or_init(|| (self.resolve)())
+    }
+}
+
+impl Audience for LazyAudience {
+    fn includes(&self, entity: Entity) -> bool {
+        self.get().contains(&entity)
+    }
+
+    fn map_targets(&mut self, mapper: &mut dyn EntityMapper) {
+        let targets = self
+            .get()
+            .iter()
+            .map(|&entity| mapper.map_entity(entity))
+            .collect();
+        *self = Self::resolved(targets);
+    }
+}
+
+impl Multicast for LazyAudience {
+    fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.get().iter().copied()
+    }
+}
+
+impl fmt::Debug for LazyAudience {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.targets.get() {
+            Some(targets) => f.debug_tuple("LazyAudience").field(targets).finish(),
+            None => f.write_str("LazyAudience(<unresolved>)"),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+/// Defines an [`Event`](crate::Event) type along with its configuration and registration, so that
+/// wiring up an event happens in one place.
+///
+/// The struct is written as usual, with its attributes and derives passed through. It is followed
+/// by a colon and a comma-separated list of options:
+///
+/// - `mutable`: sets the [`Mutability`](crate::Event::Mutability) to [`Mutable`](crate::Mutable)
+///   rather than [`Immutable`](crate::Immutable).
+/// - `cancellable` or `cancellable(T)`: sets the [`Cancellation`](crate::Event::Cancellation) to
+///   [`bool`] or `T` rather than `()`.
+/// - `audience(T)`: sets the [`Audience`](crate::Event::Audience) to `T` rather than `()`.
+/// - `description("...")`: registers the description into the [`EventCatalog`](crate::EventCatalog)
+///   with [`AppEventBus::register_bus_event`](crate::AppEventBus::register_bus_event).
+/// - `retained` or `retained(capacity)`: records the posts into the
+///   [`EventHistory`](crate::EventHistory) with
+///   [`AppEventBus::enable_history`](crate::AppEventBus::enable_history), retaining
+///   [`EventHistory::DEFAULT_CAPACITY`](crate::EventHistory::DEFAULT_CAPACITY) events by default.
+/// - `bridged`: bridges the event with bevy's [`Events`](bevy_ecs::event::Events) using the
+///   [`EventsBridgePlugin`](crate::EventsBridgePlugin). The struct must derive bevy's `Event`.
+/// - `serde`: derives `serde`'s `Serialize` and `Deserialize`. The crate defining the event must
+///   depend on `serde` with its `derive` feature.
+/// - `networked`: shorthand for `serde, bridged`, for networking crates that replicate bevy's
+///   events by serializing them.
+///
+/// The options that need the [`App`](bevy_app::App) are applied by the generated
+/// `register(app: &mut App)` associated function, which is only generated with the `bevy_app`
+/// feature.
+///
+/// ```rust
+/// # use bevy_app::App;
+/// # use bevy_ecs::entity::Entity;
+/// # use bevy_eventbus::{bus_event, prelude::*, EventCatalog};
+/// bus_event! {
+///     /// Damage dealt to an entity.
+///     #[derive(Clone)]
+///     pub struct Damage {
+///         pub amount: f32,
+///     }
+///     : mutable, cancellable(bool), audience(Entity), description("Damage dealt"), retained(64)
+/// }
+///
+/// let mut app = App::new();
+/// Damage::register(&mut app);
+/// assert!(app.world().resource::<EventCatalog>().get::<Damage>().is_some());
+/// ```
+#[macro_export]
+macro_rules! bus_event {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident { $($fields:tt)* }
+        $(: $($options:tt)*)?
+    ) => {
+        $crate::bus_event!(@options $name
+            [$(#[$attr])* $vis struct $name { $($fields)* }]
+            [] [$crate::Immutable] [()] [()] []
+            $($($options)*)?
+        );
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident ( $($fields:tt)* ) $(;)?
+        $(: $($options:tt)*)?
+    ) => {
+        $crate::bus_event!(@options $name
+            [$(#[$attr])* $vis struct $name ( $($fields)* );]
+            [] [$crate::Immutable] [()] [()] []
+            $($($options)*)?
+        );
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident $(;)?
+        $(: $($options:tt)*)?
+    ) => {
+        $crate::bus_event!(@options $name
+            [$(#[$attr])* $vis struct $name;]
+            [] [$crate::Immutable] [()] [()] []
+            $($($options)*)?
+        );
+    };
+
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] $(,)?
+    ) => {
+        $($derive)*
+        $($item)*
+
+        impl $crate::Event for $name {
+            type Mutability = $m;
+            type Cancellation = $c;
+            type Audience = $a;
+        }
+
+        $crate::__bus_event_app! {
+            impl $name {
+                /// Registers the event into the app, as configured by its
+                /// [`bus_event!`]($crate::bus_event) definition.
+                #[allow(unused_variables)]
+                pub fn register(app: &mut $crate::__macro::App) {
+                    $($crate::bus_event!(@register app $register);)*
+                }
+            }
+        }
+    };
+    (@register $app:ident (description $description:expr)) => {
+        $crate::AppEventBus::register_bus_event::<Self>(
+            $app,
+            $crate::EventInfo::new::<Self>($description),
+        );
+    };
+    (@register $app:ident (retained $capacity:expr)) => {
+        $crate::AppEventBus::enable_history::<Self>($app, $capacity);
+    };
+    (@register $app:ident (bridged)) => {
+        $app.add_plugins($crate::EventsBridgePlugin::<Self>::default());
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] mutable $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$crate::Mutable] [$c] [$a] [$($register)*] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] cancellable($cancellation:ty) $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$cancellation] [$a] [$($register)*] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] cancellable $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [bool] [$a] [$($register)*] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] audience($audience:ty) $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$c] [$audience] [$($register)*] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] description($description:expr) $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$c] [$a] [$($register)* (description $description)] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] retained($capacity:expr) $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$c] [$a] [$($register)* (retained $capacity)] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] retained $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*] [$m] [$c] [$a]
+            [$($register)* (retained $crate::EventHistory::<Self>::DEFAULT_CAPACITY)] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] bridged $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$c] [$a] [$($register)* (bridged)] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] serde $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*]
+            [$($derive)* #[derive(::serde::Serialize, ::serde::Deserialize)]]
+            [$m] [$c] [$a] [$($register)*] $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] networked $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$c] [$a] [$($register)*] serde, bridged $($rest)*
+        );
+    };
+    (@options $name:ident [$($item:tt)*] [$($derive:tt)*] [$m:ty] [$c:ty] [$a:ty]
+        [$($register:tt)*] , $($rest:tt)*
+    ) => {
+        $crate::bus_event!(@options $name [$($item)*] [$($derive)*]
+            [$m] [$c] [$a] [$($register)*] $($rest)*
+        );
+    };
+}
+
+/// Expands to its input if the `bevy_app` feature is enabled, for the parts of the crate's macros
+/// that need the [`App`](bevy_app::App).
+#[cfg(feature = "bevy_app")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bus_event_app {
+    ($($tokens:tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Expands to nothing, as the `bevy_app` feature is disabled.
+#[cfg(not(feature = "bevy_app"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bus_event_app {
+    ($($tokens:tt)*) => {};
+}
+
+/// Defines a facade struct whose associated functions post a set of [`Event`](crate::Event)s, so
+/// that a module documents in one place which posts it offers to others.
+///
+/// The facade is sugar only: it doesn't stop other code from posting the events directly. To keep
+/// other modules from posting an event at all, keep one of its fields private to the module, so
+/// that it can only be constructed there.
+///
+/// Each function is written like a function signature, followed by `=>` and the
+/// [`CommandEventBus`](crate::CommandEventBus) method it calls with its arguments, e.g.
+/// `post(...)`, `post_to(...)` or `enqueue(...)`. The generated function takes the
+/// [`Commands`](bevy_ecs::system::Commands) to post with as its first parameter, followed by the
+/// declared parameters. Attributes and doc comments are passed through, and each function keeps its
+/// own visibility, so that a module can expose some posts publicly and keep others to itself.
+///
+/// ```rust
+/// # use bevy_ecs::{entity::Entity, system::Commands};
+/// mod combat {
+///     # use bevy_ecs::entity::Entity;
+///     # use bevy_eventbus::{bus_event, bus_facade};
+///     bus_event! {
+///         pub struct Damage {
+///             pub amount: u32,
+///         }
+///         : audience(Entity)
+///     }
+///
+///     bus_event! {
+///         pub struct Killed
+///         : audience(Entity)
+///     }
+///
+///     bus_facade! {
+///         /// The combat events that other modules may post.
+///         pub struct CombatBus {
+///             /// Deals damage to the target.
+///             pub fn deal_damage(target: Entity, amount: u32)
+///                 => post_to(Damage { amount }, target);
+///
+///             /// Only offered to the combat module itself.
+///             fn kill(target: Entity) => post_to(Killed, target);
+///         }
+///     }
+/// }
+///
+/// fn trap(commands: &mut Commands, victim: Entity) {
+///     combat::CombatBus::deal_damage(commands, victim, 10);
+/// }
+/// ```
+#[macro_export]
+macro_rules! bus_facade {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$fn_attr:meta])*
+                $fn_vis:vis fn $fn_name:ident($($arg:ident: $arg_ty:ty),* $(,)?)
+                    => $method:ident($($post:expr),* $(,)?);
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name;
+
+        impl $name {
+            $(
+                $(#[$fn_attr])*
+                $fn_vis fn $fn_name(
+                    commands: &mut $crate::__macro::Commands,
+                    $($arg: $arg_ty),*
+                ) {
+                    $crate::CommandEventBus::$method(commands, $($post),*);
+                }
+            )*
+        }
+    };
+}
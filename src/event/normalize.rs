@@ -0,0 +1,140 @@
+use bevy_ecs::{
+    entity::{Entity, EntityHashSet},
+    world::World,
+};
+
+use crate::{Event, LazyAudience, Multicast};
+
+/// [`Multicast`] audience that can be rebuilt from a list of target entities, so that it can be
+/// normalized, see [`AudienceNormalization`].
+pub trait FromTargets: Multicast + Sized {
+    /// Creates an audience with the target entities, in order.
+    fn from_targets(targets: Vec<Entity>) -> Self;
+}
+
+impl FromTargets for Vec<Entity> {
+    fn from_targets(targets: Vec<Entity>) -> Self {
+        targets
+    }
+}
+
+impl FromTargets for LazyAudience {
+    fn from_targets(targets: Vec<Entity>) -> Self {
+        Self::resolved(targets)
+    }
+}
+
+/// How the [`Multicast`] audiences of posts of an [`Event`] type are normalized before any handler
+/// runs, set with
+/// [`WorldEventBus::normalize_audience`](crate::WorldEventBus::normalize_audience).
+///
+/// Handlers see the normalized audience, e.g. through
+/// [`Receive::targets`](crate::Receive::targets), and so does the
+/// [`EventHistory`](crate::EventHistory). Audiences that are already normalized are passed through
+/// as they are, and a [`LazyAudience`] is resolved when it is normalized.
+///
+/// ```rust
+/// # use bevy_ecs::{entity::Entity, world::World};
+/// # use bevy_eventbus::{prelude::*, AudienceNormalization};
+/// struct Heal(u32);
+///
+/// impl BusEvent for Heal {
+///     type Mutability = Immutable;
+///     type Cancellation = ();
+///     type Audience = Vec<Entity>;
+/// }
+///
+/// fn heal(event: Receive<Heal>) {
+///     assert_eq!(event.targets().count(), 2);
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(heal);
+/// world.normalize_audience::<Heal>(AudienceNormalization::default());
+/// let tank = world.spawn_empty().id();
+/// let healer = world.spawn_empty().id();
+/// let dead = world.spawn_empty().id();
+/// world.despawn(dead);
+/// world.post_to(Heal(10), vec![tank, healer, tank, dead]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudienceNormalization {
+    /// Removes duplicate targets, keeping the first of each.
+    pub dedup: bool,
+    /// Removes targets that don't exist anymore, e.g. because they were despawned.
+    pub drop_despawned: bool,
+    /// Sorts the targets, so that handlers see them in the same order regardless of how the
+    /// audience was built.
+    pub sort: bool,
+}
+
+impl Default for AudienceNormalization {
+    /// Removes duplicate and despawned targets, keeping the order of the rest.
+    fn default() -> Self {
+        Self {
+            dedup: true,
+            drop_despawned: true,
+            sort: false,
+        }
+    }
+}
+
+impl AudienceNormalization {
+    /// Sorts the targets too.
+    pub const fn sorted(mut self) -> Self {
+        self.sort = true;
+        self
+    }
+}
+
+/// Normalizes the audiences of [`Event`] `E`, see [`AudienceNormalization`].
+pub(crate) struct Normalizer<E: Event> {
+    policy: AudienceNormalization,
+    normalize: fn(&World, AudienceNormalization, &E::Audience) -> Option<E::Audience>,
+}
+
+impl<E: Event> Clone for Normalizer<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Event> Copy for Normalizer<E> {}
+
+impl<E: Event<Audience: FromTargets>> Normalizer<E> {
+    pub(crate) fn new(policy: AudienceNormalization) -> Self {
+        Self {
+            policy,
+            normalize: normalize::<E::Audience>,
+        }
+    }
+}
+
+impl<E: Event> Normalizer<E> {
+    /// Returns the normalized audience, or `None` if the audience is already normalized.
+    pub(crate) fn apply(self, world: &World, audience: &E::Audience) -> Option<E::Audience> {
+        (self.normalize)(world, self.policy, audience)
+    }
+}
+
+fn normalize<A: FromTargets>(
+    world: &World,
+    policy: AudienceNormalization,
+    audience: &A,
+) -> Option<A> {
+    let mut targets = audience.targets().collect::<Vec<_>>();
+    let len = targets.len();
+    if policy.drop_despawned {
+        targets.retain(|&entity| world.entities().contains(entity));
+    }
+    if policy.dedup {
+        let mut seen = EntityHashSet::default();
+        targets.retain(|&entity| seen.insert(entity));
+    }
+    let mut changed = targets.len() != len;
+    if policy.sort && !targets.is_sorted() {
+        targets.sort_unstable();
+        changed = true;
+    }
+    changed.then(|| A::from_targets(targets))
+}
@@ -0,0 +1,105 @@
+use std::convert::Infallible;
+
+use crate::Cancellation;
+
+/// Extension trait for the [`Cancellation`] state returned by posting an [`Event`](crate::Event),
+/// so that call sites handle posts the same way regardless of the cancellation type.
+///
+/// Implemented for all provided cancellation types:
+/// - `()`: Never cancelled, and has no reason.
+/// - [`bool`]: Cancelled without a reason, so its reason is `()`.
+/// - [`Option<T>`]: Cancelled with a reason of type `T`.
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::prelude::*;
+/// struct Purchase(u32);
+///
+/// impl BusEvent for Purchase {
+///     type Mutability = Immutable;
+///     type Cancellation = Option<&'static str>;
+///     type Audience = ();
+/// }
+///
+/// fn check_funds(mut event: Receive<Purchase>) {
+///     if event.0 > 100 {
+///         event.cancel_with("not enough gold");
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(check_funds);
+/// world.post(Purchase(50)).assert_not_cancelled();
+/// let outcome = world.post(Purchase(500));
+/// assert!(outcome.was_cancelled());
+/// assert_eq!(outcome.reason(), Some(&"not enough gold"));
+/// assert_eq!(outcome.into_result(), Err("not enough gold"));
+/// ```
+pub trait PostOutcomeExt: Cancellation + Sized {
+    /// Why the post was cancelled.
+    type Reason;
+
+    /// Returns `true` if the post was cancelled, see [`Cancellation::cancelled`].
+    fn was_cancelled(&self) -> bool {
+        self.cancelled()
+    }
+
+    /// Returns why the post was cancelled, or `None` if it wasn't.
+    fn reason(&self) -> Option<&Self::Reason>;
+
+    /// Converts the outcome into `Ok(())` if the post wasn't cancelled, or the reason otherwise,
+    /// e.g. to propagate cancellations with `?`.
+    fn into_result(self) -> Result<(), Self::Reason>;
+
+    /// Panics if the post was cancelled, e.g. in tests.
+    #[track_caller]
+    fn assert_not_cancelled(&self) {
+        assert!(
+            !self.cancelled(),
+            "The post was unexpectedly cancelled: {self:?}"
+        );
+    }
+}
+
+impl PostOutcomeExt for () {
+    type Reason = Infallible;
+
+    fn reason(&self) -> Option<&Infallible> {
+        None
+    }
+
+    fn into_result(self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+impl PostOutcomeExt for bool {
+    type Reason = ();
+
+    fn reason(&self) -> Option<&()> {
+        self.then_some(&())
+    }
+
+    fn into_result(self) -> Result<(), ()> {
+        if self {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: std::fmt::Debug + 'static> PostOutcomeExt for Option<T> {
+    type Reason = T;
+
+    fn reason(&self) -> Option<&T> {
+        self.as_ref()
+    }
+
+    fn into_result(self) -> Result<(), T> {
+        match self {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::Debug;
+
+use bevy_ecs::entity::{Entity, EntityHashMap};
+
+use crate::{CancellableWith, Cancellation, PostOutcomeExt};
+
+/// [`Cancellation`] state of [`Multicast`](crate::Multicast) events whose targets each decide
+/// whether to block the event, e.g. which players rejected an invite, with an optional reason of
+/// type `R`.
+///
+/// Blocking a target doesn't cancel the post as a whole: the remaining handlers still run, and can
+/// skip the targets that were already blocked with [`PerTargetCancellation::is_blocked`]. The
+/// poster sees which targets blocked the event in the returned state, and
+/// [`PostOutcomeExt`] treats the post as cancelled if any target blocked it.
+///
+/// ```rust
+/// # use bevy_ecs::{entity::Entity, world::World};
+/// # use bevy_eventbus::{prelude::*, PerTargetCancellation};
+/// struct Invite;
+///
+/// impl BusEvent for Invite {
+///     type Mutability = Immutable;
+///     type Cancellation = PerTargetCancellation<&'static str>;
+///     type Audience = Vec<Entity>;
+/// }
+///
+/// let mut world = World::new();
+/// let alice = world.spawn_empty().id();
+/// let bob = world.spawn_empty().id();
+/// world.add_handler(move |mut event: Receive<Invite>| {
+///     event.cancel_with((bob, "busy"));
+/// });
+///
+/// let outcome = world.post_to(Invite, vec![alice, bob]);
+/// assert!(!outcome.is_blocked(alice));
+/// assert_eq!(outcome.reason_for(bob), Some(&"busy"));
+/// assert!(outcome.was_cancelled());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerTargetCancellation<R = ()> {
+    blocked: EntityHashMap<R>,
+}
+
+impl<R> Default for PerTargetCancellation<R> {
+    fn default() -> Self {
+        Self {
+            blocked: EntityHashMap::default(),
+        }
+    }
+}
+
+impl<R> PerTargetCancellation<R> {
+    /// Blocks the event for the target, with a reason. Blocking a target again replaces its
+    /// reason.
+    pub fn block_with(&mut self, target: Entity, reason: R) {
+        self.blocked.insert(target, reason);
+    }
+
+    /// Blocks the event for the target, with the default reason.
+    pub fn block(&mut self, target: Entity)
+    where
+        R: Default,
+    {
+        self.block_with(target, R::default());
+    }
+
+    /// Returns `true` if the target blocked the event.
+    pub fn is_blocked(&self, target: Entity) -> bool {
+        self.blocked.contains_key(&target)
+    }
+
+    /// Returns why the target blocked the event, or `None` if it didn't.
+    pub fn reason_for(&self, target: Entity) -> Option<&R> {
+        self.blocked.get(&target)
+    }
+
+    /// Returns an iterator over the targets that blocked the event and their reasons, in no
+    /// particular order.
+    pub fn blocked(&self) -> impl Iterator<Item = (Entity, &R)> + '_ {
+        self.blocked
+            .iter()
+            .map(|(&target, reason)| (target, reason))
+    }
+
+    /// Returns the number of targets that blocked the event.
+    pub fn len(&self) -> usize {
+        self.blocked.len()
+    }
+
+    /// Returns `true` if no target blocked the event.
+    pub fn is_empty(&self) -> bool {
+        self.blocked.is_empty()
+    }
+}
+
+impl<R: Debug + 'static> Cancellation for PerTargetCancellation<R> {
+    type Mut<'event> = &'event mut Self;
+
+    fn as_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    /// Always returns `false`, as blocking a target doesn't cancel the post as a whole.
+    fn cancelled(&self) -> bool {
+        false
+    }
+}
+
+impl<R: Debug + Default + 'static> CancellableWith<Entity> for PerTargetCancellation<R> {
+    fn cancel_with(&mut self, target: Entity) {
+        self.block(target);
+    }
+}
+
+impl<R: Debug + 'static> CancellableWith<(Entity, R)> for PerTargetCancellation<R> {
+    fn cancel_with(&mut self, (target, reason): (Entity, R)) {
+        self.block_with(target, reason);
+    }
+}
+
+impl<R: Debug + 'static> PostOutcomeExt for PerTargetCancellation<R> {
+    type Reason = EntityHashMap<R>;
+
+    fn was_cancelled(&self) -> bool {
+        !self.is_empty()
+    }
+
+    fn reason(&self) -> Option<&EntityHashMap<R>> {
+        (!self.is_empty()).then_some(&self.blocked)
+    }
+
+    fn into_result(self) -> Result<(), EntityHashMap<R>> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.blocked)
+        }
+    }
+
+    #[track_caller]
+    fn assert_not_cancelled(&self) {
+        assert!(
+            self.is_empty(),
+            "The post was unexpectedly blocked by {:?}",
+            self.blocked
+        );
+    }
+}
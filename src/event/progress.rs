@@ -0,0 +1,200 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+};
+
+use bevy_ecs::{system::Resource, world::World};
+use parking_lot::Mutex;
+
+use crate::{Event, Immutable, WorldEventBus};
+
+/// Identifier of a long-running operation reporting its progress, see [`ProgressEmitter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProgressId(pub u64);
+
+impl fmt::Display for ProgressId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// [`Event`] posted when a long-running operation reports its progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// The operation.
+    pub id: ProgressId,
+    /// How much of the operation is done, from `0.0` to `1.0`.
+    pub fraction: f32,
+}
+
+/// [`Event`] posted when a long-running operation completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressCompleted {
+    /// The operation.
+    pub id: ProgressId,
+}
+
+/// [`Event`] posted when a long-running operation is aborted, including when its
+/// [`ProgressEmitter`] is dropped before completing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressAborted {
+    /// The operation.
+    pub id: ProgressId,
+}
+
+impl Event for Progress {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+impl Event for ProgressCompleted {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+impl Event for ProgressAborted {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+enum ProgressUpdate {
+    Progress(Progress),
+    Completed(ProgressId),
+    Aborted(ProgressId),
+}
+
+/// Handle through which a long-running operation, such as a handler that spawned an async task,
+/// streams its [`Progress`] to the event bus.
+///
+/// Emitters are [`Send`], so they can be moved into other threads. Their reports are buffered in
+/// the [`ProgressTracker`] and posted when it is flushed, which the
+/// [`EventBusPlugin`](crate::EventBusPlugin) does every frame before posting
+/// [`Tick`](crate::tick::Tick). Dropping an emitter without [completing](ProgressEmitter::complete)
+/// it aborts the operation.
+pub struct ProgressEmitter {
+    id: ProgressId,
+    sender: Sender<ProgressUpdate>,
+    finished: bool,
+}
+
+impl ProgressEmitter {
+    /// Returns the ID of the operation.
+    pub fn id(&self) -> ProgressId {
+        self.id
+    }
+
+    /// Reports how much of the operation is done, clamped between `0.0` and `1.0`.
+    pub fn report(&self, fraction: f32) {
+        let _ = self.sender.send(ProgressUpdate::Progress(Progress {
+            id: self.id,
+            fraction: fraction.clamp(0.0, 1.0),
+        }));
+    }
+
+    /// Completes the operation, posting [`ProgressCompleted`].
+    pub fn complete(mut self) {
+        self.finished = true;
+        let _ = self.sender.send(ProgressUpdate::Completed(self.id));
+    }
+
+    /// Aborts the operation, posting [`ProgressAborted`].
+    pub fn abort(self) {}
+}
+
+impl Drop for ProgressEmitter {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.sender.send(ProgressUpdate::Aborted(self.id));
+        }
+    }
+}
+
+/// [`Resource`] which creates [`ProgressEmitter`]s, and posts their buffered reports when flushed.
+pub struct ProgressTracker {
+    next: AtomicU64,
+    sender: Sender<ProgressUpdate>,
+    receiver: Mutex<Receiver<ProgressUpdate>>,
+    active: HashMap<ProgressId, f32>,
+}
+
+impl Resource for ProgressTracker {}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            next: AtomicU64::new(0),
+            sender,
+            receiver: Mutex::new(receiver),
+            active: HashMap::new(),
+        }
+    }
+}
+
+impl ProgressTracker {
+    /// Starts a new operation, returning the emitter to report its progress with.
+    pub fn start(&self) -> ProgressEmitter {
+        let id = ProgressId(self.next.fetch_add(1, Ordering::Relaxed));
+        let _ = self
+            .sender
+            .send(ProgressUpdate::Progress(Progress { id, fraction: 0.0 }));
+        ProgressEmitter {
+            id,
+            sender: self.sender.clone(),
+            finished: false,
+        }
+    }
+
+    /// Returns the last flushed progress of the operation, or `None` if it finished or hasn't
+    /// been flushed yet.
+    pub fn get(&self, id: ProgressId) -> Option<f32> {
+        self.active.get(&id).copied()
+    }
+
+    /// Returns an iterator over the operations that haven't finished as of the last flush, along
+    /// with their progress.
+    pub fn active(&self) -> impl Iterator<Item = (ProgressId, f32)> + '_ {
+        self.active.iter().map(|(&id, &fraction)| (id, fraction))
+    }
+
+    /// Posts the reports buffered since the last flush, in the order they were made. Returns the
+    /// number of events posted.
+    pub fn flush(world: &mut World) -> usize {
+        let Some(mut tracker) = world.get_resource_mut::<Self>() else {
+            return 0;
+        };
+        let updates = tracker.receiver.get_mut().try_iter().collect::<Vec<_>>();
+        for update in &updates {
+            match update {
+                ProgressUpdate::Progress(progress) => {
+                    tracker.active.insert(progress.id, progress.fraction);
+                }
+                ProgressUpdate::Completed(id) | ProgressUpdate::Aborted(id) => {
+                    tracker.active.remove(id);
+                }
+            }
+        }
+
+        let len = updates.len();
+        for update in updates {
+            match update {
+                ProgressUpdate::Progress(progress) => world.post(progress),
+                ProgressUpdate::Completed(id) => world.post(ProgressCompleted { id }),
+                ProgressUpdate::Aborted(id) => world.post(ProgressAborted { id }),
+            }
+        }
+        len
+    }
+}
+
+/// Exclusive system that flushes the [`ProgressTracker`].
+pub fn post_progress(world: &mut World) {
+    ProgressTracker::flush(world);
+}
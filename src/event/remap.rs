@@ -0,0 +1,113 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap, EntityMapper, MapEntities},
+    system::Resource,
+    world::World,
+};
+
+use crate::{Audience, Event};
+
+/// Maps the entities in a type-erased event of a known type.
+type MapEvent = fn(&mut dyn Any, &mut dyn EntityMapper);
+
+/// [`Resource`] which maps entity IDs from other domains, such as a journal written by a previous
+/// session, an authoritative world, or a network peer, to the entities of this world.
+///
+/// Events that re-enter the event bus from another domain are remapped before they are posted:
+/// replays started with [`EventReplayer::replay_window`](crate::EventReplayer::replay_window),
+/// posts re-posted by
+/// [`WorldEventBus::reconcile_posts`](crate::WorldEventBus::reconcile_posts), and posts imported
+/// with [`Bridge::import`](crate::Bridge::import). Their [`Audience`] is always remapped, and the
+/// event itself is remapped if its type was registered with
+/// [`WorldEventBus::remap_event_entities`](crate::WorldEventBus::remap_event_entities).
+///
+/// Entities without a mapping are kept as they are, so events from this world pass through
+/// unchanged.
+///
+/// ```rust
+/// # use bevy_ecs::{entity::Entity, world::World};
+/// # use bevy_eventbus::{prelude::*, Bridge, EntityRemap};
+/// # struct Hit;
+/// # impl BusEvent for Hit {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = Entity;
+/// # }
+/// let mut world = World::new();
+/// let local = world.spawn_empty().id();
+/// let remote = Entity::from_raw(42);
+/// world.remap_entity(remote, local);
+/// world.add_handler(move |event: Receive<Hit>| assert_eq!(event.target(), local));
+///
+/// Bridge::new("network").import(&mut world, Hit, remote);
+/// ```
+#[derive(Resource, Default)]
+pub struct EntityRemap {
+    entities: EntityHashMap<Entity>,
+    events: HashMap<TypeId, MapEvent>,
+}
+
+impl EntityRemap {
+    /// Maps the foreign entity to the local entity, returning the previous mapping if any.
+    pub fn insert(&mut self, foreign: Entity, local: Entity) -> Option<Entity> {
+        self.entities.insert(foreign, local)
+    }
+
+    /// Removes the mapping of the foreign entity, returning it if any.
+    pub fn remove(&mut self, foreign: Entity) -> Option<Entity> {
+        self.entities.remove(&foreign)
+    }
+
+    /// Returns the local entity that the foreign entity is mapped to, if any.
+    pub fn get(&self, foreign: Entity) -> Option<Entity> {
+        self.entities.get(&foreign).copied()
+    }
+
+    /// Returns the number of mapped entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities are mapped.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Remaps the entities of events of type `E` too, rather than only their audience.
+    pub fn register<E: Event + MapEntities>(&mut self) {
+        self.events.insert(TypeId::of::<E>(), |event, mut mapper| {
+            if let Some(event) = event.downcast_mut::<E>() {
+                event.map_entities(&mut mapper);
+            }
+        });
+    }
+
+    /// Remaps the entities of an event and its audience to the entities of this world.
+    pub fn map<E: Event>(&self, event: &mut E, audience: &mut E::Audience) {
+        let mut mapper = RemapMapper(&self.entities);
+        audience.map_targets(&mut mapper);
+        if let Some(map_event) = self.events.get(&TypeId::of::<E>()) {
+            map_event(event, &mut mapper);
+        }
+    }
+
+    /// Remaps an event and its audience with the [`EntityRemap`] of the world, if any.
+    pub(crate) fn apply<E: Event>(world: &World, event: &mut E, audience: &mut E::Audience) {
+        if let Some(remap) = world.get_resource::<Self>() {
+            remap.map(event, audience);
+        }
+    }
+}
+
+/// [`EntityMapper`] which keeps entities without a mapping as they are.
+struct RemapMapper<'a>(&'a EntityHashMap<Entity>);
+
+impl EntityMapper for RemapMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        self.0.get(&entity).copied().unwrap_or(entity)
+    }
+}
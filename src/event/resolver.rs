@@ -0,0 +1,64 @@
+use bevy_ecs::{component::Component, entity::Entity, world::World};
+
+/// Expands a target entity into the entities an [`Event`](crate::Event) is delivered to, through
+/// relationships between entities, when the event is posted.
+///
+/// Used with [`WorldEventBus::post_resolved`](crate::WorldEventBus::post_resolved). Implement this
+/// trait to route events through custom relationship components.
+///
+/// Provided implementations:
+/// - [`OwnerChain`]: The target and its owners, following [`OwnedBy`].
+/// - [`SameTeam`]: Every entity on the same [`Team`] as the target.
+pub trait AudienceResolver {
+    /// Returns the entities that an event posted to `target` is delivered to.
+    fn resolve(&self, world: &mut World, target: Entity) -> Vec<Entity>;
+}
+
+/// [`Component`] which relates an entity to the entity that owns it, e.g. a projectile to the
+/// player that fired it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnedBy(pub Entity);
+
+/// [`Component`] which places an entity on a team.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Team(pub u32);
+
+/// [`AudienceResolver`] which resolves to the target, followed by its owner, its owner's owner,
+/// and so on, following [`OwnedBy`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OwnerChain;
+
+impl AudienceResolver for OwnerChain {
+    fn resolve(&self, world: &mut World, target: Entity) -> Vec<Entity> {
+        let mut chain = vec![target];
+        let mut current = target;
+        while let Some(&OwnedBy(owner)) = world.get::<OwnedBy>(current) {
+            // Stop at ownership cycles instead of looping forever.
+            if chain.contains(&owner) {
+                break;
+            }
+            chain.push(owner);
+            current = owner;
+        }
+        chain
+    }
+}
+
+/// [`AudienceResolver`] which resolves to every entity on the same [`Team`] as the target,
+/// including the target itself. Resolves to no entities if the target isn't on a team.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SameTeam;
+
+impl AudienceResolver for SameTeam {
+    fn resolve(&self, world: &mut World, target: Entity) -> Vec<Entity> {
+        let Some(&team) = world.get::<Team>(target) else {
+            return Vec::new();
+        };
+        world
+            .query::<(Entity, &Team)>()
+            .iter(world)
+            .filter(|(_, other)| **other == team)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+}
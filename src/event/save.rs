@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use bevy_utils::tracing::warn;
+
+use crate::{Event, Immutable, Mutable, Receive};
+
+/// Serialized fragments of a save, keyed by the subsystem that contributed them.
+///
+/// Blobs are collected by posting [`SaveRequested`] with
+/// [`WorldEventBus::request_save`](crate::WorldEventBus::request_save), and distributed again by
+/// posting [`LoadRequested`] with
+/// [`WorldEventBus::request_load`](crate::WorldEventBus::request_load). How each fragment is
+/// serialized is up to its contributor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveBlob {
+    fragments: BTreeMap<String, Vec<u8>>,
+}
+
+impl SaveBlob {
+    /// Creates an empty blob.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the fragment of the key, if any.
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.fragments.get(key).map(Vec::as_slice)
+    }
+
+    /// Inserts the fragment of the key, returning the previous one if any.
+    pub fn insert(&mut self, key: impl Into<String>, bytes: Vec<u8>) -> Option<Vec<u8>> {
+        self.fragments.insert(key.into(), bytes)
+    }
+
+    /// Returns an iterator over the keys and fragments, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> + '_ {
+        self.fragments
+            .iter()
+            .map(|(key, bytes)| (key.as_str(), bytes.as_slice()))
+    }
+
+    /// Returns the number of fragments.
+    pub fn len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// Returns `true` if there are no fragments.
+    pub fn is_empty(&self) -> bool {
+        self.fragments.is_empty()
+    }
+
+    /// Encodes the blob into bytes, e.g. to write it to disk.
+    ///
+    /// Fragments are written ordered by key, each as the little-endian `u64` length of the key,
+    /// the key, the length of the fragment, and the fragment.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (key, fragment) in &self.fragments {
+            bytes.extend_from_slice(&(key.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(key.as_bytes());
+            bytes.extend_from_slice(&(fragment.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(fragment);
+        }
+        bytes
+    }
+
+    /// Decodes a blob encoded with [`SaveBlob::to_bytes`], or returns `None` if the bytes are
+    /// malformed.
+    pub fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        fn chunk<'a>(bytes: &mut &'a [u8]) -> Option<&'a [u8]> {
+            let (len, rest) = bytes.split_first_chunk::<8>()?;
+            let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+            let (chunk, rest) = rest.split_at_checked(len)?;
+            *bytes = rest;
+            Some(chunk)
+        }
+
+        let mut blob = Self::new();
+        while !bytes.is_empty() {
+            let key = String::from_utf8(chunk(&mut bytes)?.to_vec()).ok()?;
+            let fragment = chunk(&mut bytes)?.to_vec();
+            blob.fragments.insert(key, fragment);
+        }
+        Some(blob)
+    }
+}
+
+/// [`Event`] posted to collect a [`SaveBlob`], whose handlers contribute their serialized state
+/// with [`Receive::contribute`].
+#[derive(Debug, Default)]
+pub struct SaveRequested {
+    /// The fragments contributed so far.
+    pub blob: SaveBlob,
+}
+
+impl Event for SaveRequested {
+    type Mutability = Mutable;
+    type Cancellation = bool;
+    type Audience = ();
+}
+
+impl Receive<'_, SaveRequested> {
+    /// Contributes the serialized state of the key to the save. A later contribution to the same
+    /// key replaces the earlier one, with a warning.
+    pub fn contribute(&mut self, key: impl Into<String>, bytes: Vec<u8>) {
+        let key = key.into();
+        if self.event_mut().blob.fragments.contains_key(&key) {
+            warn!("Replaced the save fragment of {key}, which was already contributed");
+        }
+        self.event_mut().blob.fragments.insert(key, bytes);
+    }
+}
+
+/// [`Event`] posted to distribute a [`SaveBlob`], whose handlers restore their state from their
+/// fragment with [`Receive::fragment`].
+#[derive(Debug)]
+pub struct LoadRequested {
+    /// The fragments to restore.
+    pub blob: SaveBlob,
+}
+
+impl Event for LoadRequested {
+    type Mutability = Immutable;
+    type Cancellation = bool;
+    type Audience = ();
+}
+
+impl Receive<'_, LoadRequested> {
+    /// Returns the fragment of the key, if it was saved.
+    pub fn fragment(&self, key: &str) -> Option<&[u8]> {
+        self.event().blob.get(key)
+    }
+}
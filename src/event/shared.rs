@@ -0,0 +1,67 @@
+use std::{ops::Deref, sync::Arc};
+
+use crate::{Event, Immutable, Receive};
+
+/// [`Event`] wrapper for posting a large, immutable payload of [`Event`] `E` behind an [`Arc`], so
+/// that handlers can keep it around without cloning the payload.
+///
+/// Shared events are posted with [`WorldEventBus::post_shared`](crate::WorldEventBus::post_shared),
+/// and received with [`SharedReceive`]. They are a different event type than `E` itself, so
+/// handlers of `E` don't run for them. The bus drops its reference once the event was dispatched,
+/// so the payload lives on only as long as handlers [retained](Receive::retain) it.
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use bevy_ecs::{system::{Local, ResMut, Resource}, world::World};
+/// # use bevy_eventbus::{prelude::*, SharedReceive};
+/// struct NavmeshBuilt {
+///     triangles: Vec<[u32; 3]>,
+/// }
+///
+/// impl BusEvent for NavmeshBuilt {
+///     type Mutability = Immutable;
+///     type Cancellation = ();
+///     type Audience = ();
+/// }
+///
+/// #[derive(Resource, Default)]
+/// struct Navmesh(Option<Arc<NavmeshBuilt>>);
+///
+/// fn store_navmesh(event: SharedReceive<NavmeshBuilt>, mut navmesh: ResMut<Navmesh>) {
+///     navmesh.0 = Some(event.retain());
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Navmesh>();
+/// world.add_handler(store_navmesh);
+///
+/// let built = Arc::new(NavmeshBuilt { triangles: vec![[0, 1, 2]] });
+/// world.post_shared(built.clone());
+/// assert!(Arc::ptr_eq(world.resource::<Navmesh>().0.as_ref().unwrap(), &built));
+/// ```
+pub struct Shared<E>(pub Arc<E>);
+
+impl<E: Event> Event for Shared<E> {
+    type Mutability = Immutable;
+    type Cancellation = E::Cancellation;
+    type Audience = E::Audience;
+}
+
+impl<E> Deref for Shared<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// [`SystemInput`](bevy_ecs::system::SystemInput) type for receiving [`Shared`] events in
+/// handlers.
+pub type SharedReceive<'event, E> = Receive<'event, Shared<E>>;
+
+impl<E: Event> Receive<'_, Shared<E>> {
+    /// Returns a new reference to the shared payload, which can outlive the dispatch.
+    pub fn retain(&self) -> Arc<E> {
+        self.event().0.clone()
+    }
+}
@@ -1,16 +1,24 @@
-use std::{any::TypeId, sync::Arc};
+use std::{any::TypeId, sync::Arc, time::Duration};
 
 use bevy_ecs::{
     archetype::ArchetypeComponentId,
     component::ComponentId,
     prelude::*,
     query::Access,
-    schedule::InternedSystemSet,
+    schedule::{BoxedCondition, Condition, InternedSystemSet},
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld},
 };
 use parking_lot::Mutex;
 
-use crate::{Event, HandlerConfig, Immutable, IntoHandlerConfig, Receive};
+use crate::{Event, HandlerConfig, Immutable, IntoHandlerConfig, Receive, Time};
+
+mod anchor;
+mod compiled;
+
+#[cfg(feature = "bevy_app")]
+pub(crate) use anchor::TickAnchors;
+pub use anchor::*;
+pub use compiled::*;
 
 /// An [`Event`] that represents a tick of the app update loop.
 pub struct Tick;
@@ -28,43 +36,166 @@ impl<Marker, S: IntoSystem<(), (), Marker>> IntoHandlerConfig<Tick, (TickSystemM
     for S
 {
     fn into_config(self) -> HandlerConfig<Tick> {
-        let system = Arc::new(Mutex::new(TickSystem(IntoSystem::into_system(self))));
+        let system = Arc::new(Mutex::new(TickSystem::new(IntoSystem::into_system(self))));
         HandlerConfig::new(system)
     }
 }
 
-pub(crate) struct TickSystem<S: System<In = (), Out = ()>>(S);
+/// Builder for a [`Tick`] handler that carries bevy run conditions and system sets, created with
+/// [`tick_handler`].
+///
+/// ```rust
+/// # use bevy_ecs::{schedule::SystemSet, system::Res};
+/// # use bevy_app::App;
+/// # use bevy_eventbus::{prelude::*, tick::tick_handler, HandlerSetConfig};
+/// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Physics;
+///
+/// fn step_physics() {}
+///
+/// fn physics_enabled() -> bool {
+///     true
+/// }
+///
+/// let mut app = App::new();
+/// app.add_handler(tick_handler(step_physics).run_if(physics_enabled).in_set(Physics));
+/// ```
+///
+/// The handler only runs if all of its conditions return `true`. Periodic work, such as autosaves,
+/// can be spread over ticks with [`TickHandler::every_n_ticks`] and
+/// [`TickHandler::accumulate_ticks`], which keep their counters per handler. Joining a set adds the handler to
+/// the [`HandlerSet`](crate::HandlerSet) like [`HandlerConfig::in_set`], so that the set's
+/// [configuration](crate::HandlerSetConfig) applies, and reports the set from the handler's
+/// [`System::default_system_sets`].
+pub struct TickHandler<S: System<In = (), Out = ()>> {
+    system: S,
+    conditions: Vec<BoxedCondition>,
+    sets: Vec<InternedSystemSet>,
+}
+
+/// Creates a [`Tick`] handler from a system, to attach run conditions and system sets to it.
+pub fn tick_handler<M, S: IntoSystem<(), (), M>>(system: S) -> TickHandler<S::System> {
+    TickHandler {
+        system: IntoSystem::into_system(system),
+        conditions: Vec::new(),
+        sets: Vec::new(),
+    }
+}
+
+impl<S: System<In = (), Out = ()>> TickHandler<S> {
+    /// Only runs the handler if the condition returns `true`.
+    ///
+    /// Multiple conditions can be added, in which case all of them must return `true`.
+    pub fn run_if<M>(mut self, condition: impl Condition<M>) -> Self {
+        self.conditions
+            .push(Box::new(IntoSystem::into_system(condition)));
+        self
+    }
+
+    /// Only runs the handler on every `n`th tick, counting the ticks on which the conditions added
+    /// before passed.
+    pub fn every_n_ticks(self, n: u32) -> Self {
+        self.run_if(move |mut count: Local<u32>| {
+            *count += 1;
+            if *count < n {
+                return false;
+            }
+            *count = 0;
+            true
+        })
+    }
+
+    /// Only runs the handler once the [`Time`] elapsed over the ticks on which the conditions
+    /// added before passed adds up to `period`, e.g. for autosaves. Time left over from one run
+    /// carries over to the next.
+    pub fn accumulate_ticks(self, period: Duration) -> Self {
+        self.run_if(
+            move |time: Option<Res<Time>>, mut accumulated: Local<Duration>| {
+                *accumulated += time.map_or(Duration::ZERO, |time| time.delta());
+                if *accumulated < period {
+                    return false;
+                }
+                *accumulated -= period;
+                true
+            },
+        )
+    }
+
+    /// Adds the handler to a system set.
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.sets.push(set.intern());
+        self
+    }
+}
+
+#[doc(hidden)]
+pub struct TickHandlerMarker;
+
+impl<S: System<In = (), Out = ()>> IntoHandlerConfig<Tick, TickHandlerMarker> for TickHandler<S> {
+    fn into_config(self) -> HandlerConfig<Tick> {
+        let system = TickSystem {
+            system: self.system,
+            conditions: self.conditions,
+            sets: self.sets,
+            component_access: Access::default(),
+            archetype_component_access: Access::default(),
+        };
+        // The sets of the system are its default system sets, which the config picks up.
+        HandlerConfig::new(Arc::new(Mutex::new(system)))
+    }
+}
+
+pub(crate) struct TickSystem<S: System<In = (), Out = ()>> {
+    system: S,
+    conditions: Vec<BoxedCondition>,
+    sets: Vec<InternedSystemSet>,
+    /// The access of the system and its conditions.
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<S: System<In = (), Out = ()>> TickSystem<S> {
+    fn new(system: S) -> Self {
+        Self {
+            system,
+            conditions: Vec::new(),
+            sets: Vec::new(),
+            component_access: Access::default(),
+            archetype_component_access: Access::default(),
+        }
+    }
+}
 
 impl<S: System<In = (), Out = ()>> System for TickSystem<S> {
     type In = Receive<'static, Tick>;
     type Out = ();
 
     fn name(&self) -> std::borrow::Cow<'static, str> {
-        self.0.name()
+        self.system.name()
     }
 
     fn type_id(&self) -> TypeId {
-        self.0.type_id()
+        self.system.type_id()
     }
 
     fn component_access(&self) -> &Access<ComponentId> {
-        self.0.component_access()
+        &self.component_access
     }
 
     fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
-        self.0.archetype_component_access()
+        &self.archetype_component_access
     }
 
     fn is_send(&self) -> bool {
-        self.0.is_send()
+        self.system.is_send() && self.conditions.iter().all(|condition| condition.is_send())
     }
 
     fn is_exclusive(&self) -> bool {
-        self.0.is_exclusive()
+        self.system.is_exclusive()
     }
 
     fn has_deferred(&self) -> bool {
-        self.0.has_deferred()
+        self.system.has_deferred()
     }
 
     unsafe fn run_unsafe(
@@ -72,50 +203,78 @@ impl<S: System<In = (), Out = ()>> System for TickSystem<S> {
         _input: SystemIn<'_, Self>,
         world: UnsafeWorldCell,
     ) -> Self::Out {
-        self.0.run_unsafe((), world)
+        for condition in &mut self.conditions {
+            // SAFETY: The access of the conditions is part of the access of this system.
+            if !unsafe { condition.validate_param_unsafe(world) && condition.run_unsafe((), world) }
+            {
+                return;
+            }
+        }
+        self.system.run_unsafe((), world)
     }
 
     fn run(&mut self, _input: SystemIn<'_, Self>, world: &mut World) -> Self::Out {
-        self.0.run((), world)
+        for condition in &mut self.conditions {
+            if !(condition.validate_param(world) && condition.run((), world)) {
+                return;
+            }
+        }
+        self.system.run((), world)
     }
 
     fn apply_deferred(&mut self, world: &mut World) {
-        self.0.apply_deferred(world)
+        self.system.apply_deferred(world)
     }
 
     fn queue_deferred(&mut self, world: DeferredWorld) {
-        self.0.queue_deferred(world)
+        self.system.queue_deferred(world)
     }
 
     unsafe fn validate_param_unsafe(&self, world: UnsafeWorldCell) -> bool {
-        self.0.validate_param_unsafe(world)
+        self.system.validate_param_unsafe(world)
     }
 
     fn validate_param(&mut self, world: &World) -> bool {
-        self.0.validate_param(world)
+        self.system.validate_param(world)
     }
 
     fn initialize(&mut self, world: &mut World) {
-        self.0.initialize(world)
+        self.system.initialize(world);
+        self.component_access = self.system.component_access().clone();
+        for condition in &mut self.conditions {
+            condition.initialize(world);
+            self.component_access.extend(condition.component_access());
+        }
     }
 
     fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
-        self.0.update_archetype_component_access(world)
+        self.system.update_archetype_component_access(world);
+        self.archetype_component_access = self.system.archetype_component_access().clone();
+        for condition in &mut self.conditions {
+            condition.update_archetype_component_access(world);
+            self.archetype_component_access
+                .extend(condition.archetype_component_access());
+        }
     }
 
     fn check_change_tick(&mut self, change_tick: bevy_ecs::component::Tick) {
-        self.0.check_change_tick(change_tick)
+        self.system.check_change_tick(change_tick);
+        for condition in &mut self.conditions {
+            condition.check_change_tick(change_tick);
+        }
     }
 
     fn default_system_sets(&self) -> Vec<InternedSystemSet> {
-        self.0.default_system_sets()
+        let mut sets = self.system.default_system_sets();
+        sets.extend(self.sets.iter().copied());
+        sets
     }
 
     fn get_last_run(&self) -> bevy_ecs::component::Tick {
-        self.0.get_last_run()
+        self.system.get_last_run()
     }
 
     fn set_last_run(&mut self, last_run: bevy_ecs::component::Tick) {
-        self.0.set_last_run(last_run)
+        self.system.set_last_run(last_run)
     }
 }
@@ -0,0 +1,136 @@
+use bevy_ecs::{
+    schedule::{
+        InternedScheduleLabel, InternedSystemSet, IntoSystemConfigs, IntoSystemSet, ScheduleLabel,
+        Schedules, SystemSet,
+    },
+    system::{Res, Resource},
+    world::World,
+};
+use bevy_utils::tracing::warn;
+
+use crate::{
+    dispatch::dispatch_entries, initialize_reset_handlers, remove_dropped_handlers, tick::Tick,
+    HandlerRegistry, HandlerSet, HandlerSetConfig, PostOptions, WorldEventBus,
+};
+
+/// Anchors the [`Tick`] handlers of a [`HandlerSet`] to a bevy schedule, so that they run ordered
+/// relative to the engine's own systems, e.g. before transforms are propagated, rather than when
+/// [`Tick`] is posted in [`Update`](bevy_app::Update).
+///
+/// The anchored handlers run as a single system in the schedule, which is itself in the set, so
+/// that other systems can be ordered relative to the set too. They run whether or not
+/// [`EventBusSettings::compile_tick`](crate::EventBusSettings::compile_tick) is set, and whether
+/// or not the event bus is [paused](crate::EventBusPause), as they are part of the schedule. They
+/// are skipped by posts of [`Tick`] and by the [`TickSchedule`](crate::tick::TickSchedule).
+///
+/// ```rust
+/// # use bevy_app::{App, PostUpdate};
+/// # use bevy_ecs::schedule::SystemSet;
+/// # use bevy_eventbus::{prelude::*, tick::{tick_handler, TickAnchor}};
+/// # #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// # struct TransformPropagate;
+/// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct CameraFollow;
+///
+/// fn follow_player() {}
+///
+/// let mut app = App::new();
+/// app.add_handler(tick_handler(follow_player).in_set(CameraFollow))
+///     .anchor_tick_set(TickAnchor::new(CameraFollow, PostUpdate).before(TransformPropagate));
+/// ```
+pub struct TickAnchor {
+    set: InternedSystemSet,
+    schedule: InternedScheduleLabel,
+    before: Vec<InternedSystemSet>,
+    after: Vec<InternedSystemSet>,
+}
+
+impl TickAnchor {
+    /// Creates an anchor which runs the [`Tick`] handlers in the set in the schedule, without
+    /// ordering constraints yet.
+    pub fn new(set: impl HandlerSet, schedule: impl ScheduleLabel) -> Self {
+        Self {
+            set: set.intern(),
+            schedule: schedule.intern(),
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+
+    /// Runs the anchored handlers before the systems in the set, or before the system.
+    pub fn before<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        self.before.push(set.into_system_set().intern());
+        self
+    }
+
+    /// Runs the anchored handlers after the systems in the set, or after the system.
+    pub fn after<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        self.after.push(set.into_system_set().intern());
+        self
+    }
+
+    /// Adds the system that runs the anchored handlers to the schedule, and keeps posts of
+    /// [`Tick`] from running them. Sets that are already anchored are skipped with a warning.
+    pub(crate) fn install(self, world: &mut World) {
+        let set = self.set;
+        let mut anchors = world.get_resource_or_insert_with(TickAnchors::default);
+        if anchors.anchored.contains(&set) {
+            warn!("Skipped anchoring the Tick handlers in {set:?}, which are already anchored");
+            return;
+        }
+        anchors.anchored.push(set);
+        world.configure_handler_set::<Tick>(
+            HandlerSetConfig::new(set)
+                .run_if(move |anchors: Res<TickAnchors>| anchors.running == Some(set)),
+        );
+
+        let mut config = (move |world: &mut World| run_anchored(world, set)).in_set(set);
+        for before in self.before {
+            config = config.before(before);
+        }
+        for after in self.after {
+            config = config.after(after);
+        }
+        world
+            .get_resource_or_insert_with(Schedules::default)
+            .add_systems(self.schedule, config);
+    }
+}
+
+/// [`Resource`] which tracks the [`HandlerSet`]s of [`Tick`] handlers with a [`TickAnchor`].
+#[derive(Resource, Default)]
+pub(crate) struct TickAnchors {
+    anchored: Vec<InternedSystemSet>,
+    /// The set whose handlers are currently running, if any.
+    running: Option<InternedSystemSet>,
+}
+
+impl TickAnchors {
+    /// Returns `true` if the set of handlers is anchored.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn is_anchored(world: &World, set: InternedSystemSet) -> bool {
+        world
+            .get_resource::<Self>()
+            .is_some_and(|anchors| anchors.anchored.contains(&set))
+    }
+}
+
+/// Exclusive system that runs the handlers in the anchored set for a [`Tick`], without posting it,
+/// so that pausing the event bus doesn't defer them.
+fn run_anchored(world: &mut World, set: InternedSystemSet) {
+    remove_dropped_handlers::<Tick>(world);
+    initialize_reset_handlers::<Tick>(world);
+    let Some(registry) = world.get_resource::<HandlerRegistry<Tick>>() else {
+        return;
+    };
+    let mut handlers = registry.snapshot();
+    handlers.retain(|entry| {
+        registry
+            .get(entry.id)
+            .is_some_and(|config| config.is_in_set(set))
+    });
+
+    let previous = world.resource_mut::<TickAnchors>().running.replace(set);
+    dispatch_entries(world, &handlers, &Tick, &(), &PostOptions::default());
+    world.resource_mut::<TickAnchors>().running = previous;
+}
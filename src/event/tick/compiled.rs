@@ -0,0 +1,380 @@
+use std::{
+    any::{type_name, TypeId},
+    borrow::Cow,
+    mem,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::{
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick as ChangeTick},
+    query::Access,
+    schedule::{
+        IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleLabel, Schedules, SystemSet,
+    },
+    system::{Resource, System, SystemIn},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+use bevy_utils::tracing::warn;
+use parking_lot::Mutex;
+
+use crate::{
+    dispatch::{
+        initialize_reset_handlers, remove_dropped_handlers, report_handler_panic, ExportedResource,
+        RegistryIndex,
+    },
+    tick::Tick,
+    ArcCondition, ArcHandlerSystem, BreakerState, EventBudgets, EventBusPause, EventContext,
+    FeatureFlags, HandlerId, HandlerRegistry, PostOptions, Receive, Resimulating,
+};
+
+/// [`ScheduleLabel`] of the [`Schedule`] that the [`Tick`] handlers are compiled into, when
+/// [`EventBusSettings::compile_tick`](crate::EventBusSettings::compile_tick) is set.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TickSchedule;
+
+/// [`SystemSet`] of the compiled [`Tick`] handlers with the priority, in the [`TickSchedule`].
+///
+/// Every priority band runs after all higher priority bands, while the handlers within a band may
+/// run in parallel.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TickPriority(pub i32);
+
+/// What the compiled handlers recorded during a tick, handled once the [`TickSchedule`] finished
+/// as the handlers may run in parallel.
+#[derive(Default)]
+struct TickRuns {
+    /// The elapsed [`Time`](crate::Time) of the tick, which circuit breakers cool down by.
+    now: Duration,
+    /// The [`once`](crate::HandlerConfig::once) handlers that ran.
+    once: Vec<HandlerId<Tick>>,
+    /// The name and priority of the handlers with a circuit breaker that panicked.
+    panicked: Vec<(Cow<'static, str>, i32)>,
+    /// The circuit breakers that tripped.
+    tripped: Vec<TrippedBreaker>,
+}
+
+/// A circuit breaker that tripped during a tick.
+struct TrippedBreaker {
+    breaker: Arc<BreakerState>,
+    id: HandlerId<Tick>,
+    name: Cow<'static, str>,
+    failures: u32,
+}
+
+/// The [`TickRuns`] shared by the compiled handlers.
+type SharedRuns = Arc<Mutex<TickRuns>>;
+
+/// [`Resource`] which tracks the version of the [`Tick`] handlers that the [`TickSchedule`] was
+/// compiled from.
+#[derive(Resource)]
+struct CompiledTick {
+    generation: u64,
+    runs: SharedRuns,
+}
+
+/// Exclusive system that runs the [`Tick`] handlers as the [`TickSchedule`], compiling it first if
+/// the handlers changed since it was last compiled.
+///
+/// Unlike posting [`Tick`], the handlers are run by bevy's executor, so handlers with the same
+/// priority run in parallel when their access doesn't conflict. In exchange, the ordering
+/// constraints between [`HandlerSet`](crate::HandlerSet)s within a priority band aren't respected,
+/// handlers whose required resources are missing are skipped rather than removed, and the post
+/// isn't recorded into the [`EventHistory`](crate::EventHistory) or counted into the
+/// [`EventBusStats`](crate::EventBusStats). [`once`](crate::HandlerConfig::once) handlers are
+/// removed after the first tick that they ran in, and the failed runs of handlers with a
+/// [`CircuitBreaker`](crate::CircuitBreaker) are reported once all handlers ran.
+///
+/// While [`Tick`] is paused by the [`EventBusPause`], the tick is buffered like any other post,
+/// and dispatched without the schedule when the bus resumes.
+pub fn run_tick_schedule(world: &mut World) {
+    if EventBusPause::defer(world, Tick, (), PostOptions::default()).is_ok() {
+        return;
+    }
+    remove_dropped_handlers::<Tick>(world);
+    initialize_reset_handlers::<Tick>(world);
+    EventBudgets::count::<Tick>(world);
+    let Some(generation) = world
+        .get_resource::<HandlerRegistry<Tick>>()
+        .map(HandlerRegistry::generation)
+    else {
+        return;
+    };
+    if world
+        .get_resource::<CompiledTick>()
+        .is_none_or(|compiled| compiled.generation != generation)
+    {
+        let runs = SharedRuns::default();
+        let schedule = compile(world.resource::<HandlerRegistry<Tick>>(), &runs);
+        world.add_schedule(schedule);
+        RegistryIndex::track_with(world, TypeId::of::<CompiledTick>(), export_compiled);
+        world.insert_resource(CompiledTick { generation, runs });
+    }
+
+    let runs = world.resource::<CompiledTick>().runs.clone();
+    runs.lock().now = BreakerState::now(world);
+    EventContext::enter::<Tick>(world, &PostOptions::default());
+    world.run_schedule(TickSchedule);
+    EventContext::exit(world);
+
+    let TickRuns {
+        once,
+        panicked,
+        tripped,
+        ..
+    } = mem::take(&mut *runs.lock());
+    for (name, priority) in panicked {
+        report_handler_panic(world, name, priority, &Tick, &());
+    }
+    for TrippedBreaker {
+        breaker,
+        id,
+        name,
+        failures,
+    } in tripped
+    {
+        warn!(
+            "Tripped the circuit breaker of handler {name} for {} after {failures} failed runs",
+            type_name::<Tick>()
+        );
+        breaker.post_tripped(world, id.to_raw(), name, failures);
+    }
+    if !once.is_empty() {
+        let mut registry = world.resource_mut::<HandlerRegistry<Tick>>();
+        for id in once {
+            registry.remove(id);
+        }
+    }
+}
+
+/// Takes the [`TickSchedule`] out of the world along with the [`CompiledTick`] it was compiled
+/// as, see [`HandlerBlueprints`](crate::HandlerBlueprints). Without the schedule, the handlers are
+/// compiled again on the next tick instead.
+fn export_compiled(world: &mut World) -> Option<ExportedResource> {
+    let compiled = world.remove_resource::<CompiledTick>()?;
+    let schedule = world
+        .get_resource_mut::<Schedules>()?
+        .remove(TickSchedule)?;
+    Some(ExportedResource {
+        resource: Box::new((compiled, schedule)),
+        install: install_compiled,
+    })
+}
+
+fn install_compiled(world: &mut World, exported: Box<dyn std::any::Any + Send + Sync>) {
+    let Ok(exported) = exported.downcast::<(CompiledTick, Schedule)>() else {
+        return;
+    };
+    let (compiled, schedule) = *exported;
+    world.add_schedule(schedule);
+    RegistryIndex::track_with(world, TypeId::of::<CompiledTick>(), export_compiled);
+    world.insert_resource(compiled);
+}
+
+/// Compiles the enabled handlers of the registry into the [`TickSchedule`], which record their
+/// runs into `runs`.
+fn compile(registry: &HandlerRegistry<Tick>, runs: &SharedRuns) -> Schedule {
+    let mut schedule = Schedule::new(TickSchedule);
+    let mut bands = Vec::<i32>::new();
+    for entry in registry.snapshot() {
+        if bands.last() != Some(&entry.priority) {
+            bands.push(entry.priority);
+        }
+
+        let system = CompiledHandler {
+            id: entry.id,
+            priority: entry.priority,
+            once: entry.once,
+            breaker: entry.breaker.clone(),
+            runs: runs.clone(),
+            handler: entry.handler,
+            conditions: entry.conditions,
+            main_thread: entry.main_thread,
+            component_access: Access::default(),
+            archetype_component_access: Access::default(),
+        };
+        let config = system.in_set(TickPriority(entry.priority));
+        let (resources, flags, breaker) = (entry.resources, entry.flags, entry.breaker);
+        if entry.side_effect || !resources.is_empty() || !flags.is_empty() || breaker.is_some() {
+            let side_effect = entry.side_effect;
+            schedule.add_systems(config.run_if(move |world: &World| {
+                !(side_effect && Resimulating::is_active(world))
+                    && resources.iter().all(|resource| (resource.exists)(world))
+                    && FeatureFlags::all_enabled(world, &flags)
+                    && !breaker
+                        .as_ref()
+                        .is_some_and(|breaker| breaker.is_open(BreakerState::now(world)))
+            }));
+        } else {
+            schedule.add_systems(config);
+        }
+    }
+    for pair in bands.windows(2) {
+        schedule.configure_sets(TickPriority(pair[0]).before(TickPriority(pair[1])));
+    }
+    schedule
+}
+
+/// [`System`] which runs a [`Tick`] handler along with the run conditions of its sets, as part of
+/// the [`TickSchedule`].
+///
+/// The handler is initialized by its [`HandlerRegistry`], so that its state is shared with posts
+/// of [`Tick`] made outside of the schedule.
+struct CompiledHandler {
+    id: HandlerId<Tick>,
+    priority: i32,
+    once: bool,
+    breaker: Option<Arc<BreakerState>>,
+    runs: SharedRuns,
+    handler: ArcHandlerSystem<Tick>,
+    conditions: Vec<ArcCondition>,
+    main_thread: bool,
+    /// The access of the handler and its conditions.
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl CompiledHandler {
+    /// Runs the handler, recording the run into the [`TickRuns`]. Panics of handlers with a
+    /// circuit breaker are caught and count as failed runs, like when dispatching.
+    fn run_handler(&self, run: impl FnOnce()) {
+        let Some(breaker) = &self.breaker else {
+            run();
+            if self.once {
+                self.runs.lock().once.push(self.id);
+            }
+            return;
+        };
+
+        let start = Instant::now();
+        let panicked = panic::catch_unwind(AssertUnwindSafe(run)).is_err();
+        let elapsed = start.elapsed();
+        let mut runs = self.runs.lock();
+        if panicked {
+            runs.panicked
+                .push((self.handler.lock().name(), self.priority));
+        }
+        if let Some(failures) = breaker.record(panicked, elapsed, runs.now) {
+            runs.tripped.push(TrippedBreaker {
+                breaker: breaker.clone(),
+                id: self.id,
+                name: self.handler.lock().name(),
+                failures,
+            });
+        }
+        if self.once {
+            runs.once.push(self.id);
+        }
+    }
+}
+
+impl System for CompiledHandler {
+    type In = ();
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.handler.lock().name()
+    }
+
+    fn type_id(&self) -> TypeId {
+        self.handler.lock().type_id()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        !self.main_thread
+            && self.handler.lock().is_send()
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.lock().is_send())
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.handler.lock().is_exclusive()
+    }
+
+    fn has_deferred(&self) -> bool {
+        self.handler.lock().has_deferred()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: SystemIn<'_, Self>, world: UnsafeWorldCell) {
+        for condition in &self.conditions {
+            let mut condition = condition.lock();
+            // SAFETY: The access of the conditions is part of the access of this system.
+            if !unsafe { condition.validate_param_unsafe(world) && condition.run_unsafe((), world) }
+            {
+                return;
+            }
+        }
+        self.run_handler(|| {
+            let input = Receive::new(&Tick, (), &());
+            // SAFETY: The access of the handler is part of the access of this system.
+            unsafe { self.handler.lock().run_unsafe(input, world) }
+        });
+    }
+
+    fn run(&mut self, _input: SystemIn<'_, Self>, world: &mut World) {
+        for condition in &self.conditions {
+            let mut condition = condition.lock();
+            if !(condition.validate_param(world) && condition.run((), world)) {
+                return;
+            }
+        }
+        self.run_handler(|| self.handler.lock().run(Receive::new(&Tick, (), &()), world));
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.handler.lock().apply_deferred(world);
+    }
+
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.handler.lock().queue_deferred(world);
+    }
+
+    unsafe fn validate_param_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        // SAFETY: The access of the handler is part of the access of this system.
+        unsafe { self.handler.lock().validate_param_unsafe(world) }
+    }
+
+    fn initialize(&mut self, _world: &mut World) {
+        self.component_access = self.handler.lock().component_access().clone();
+        for condition in &self.conditions {
+            self.component_access
+                .extend(condition.lock().component_access());
+        }
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        let mut handler = self.handler.lock();
+        handler.update_archetype_component_access(world);
+        self.archetype_component_access = handler.archetype_component_access().clone();
+        for condition in &self.conditions {
+            let mut condition = condition.lock();
+            condition.update_archetype_component_access(world);
+            self.archetype_component_access
+                .extend(condition.archetype_component_access());
+        }
+    }
+
+    fn check_change_tick(&mut self, change_tick: ChangeTick) {
+        self.handler.lock().check_change_tick(change_tick);
+    }
+
+    fn get_last_run(&self) -> ChangeTick {
+        self.handler.lock().get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: ChangeTick) {
+        self.handler.lock().set_last_run(last_run);
+    }
+}
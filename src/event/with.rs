@@ -0,0 +1,80 @@
+use std::ops::{Deref, DerefMut};
+
+use bevy_ecs::world::World;
+
+use crate::{Event, Receive};
+
+/// [`Event`] wrapper for posting [`Event`] `E` along with per-post context `C`, such as the
+/// connection a network message arrived on, without baking the context into the event type.
+///
+/// Events with context are posted with
+/// [`WorldEventBus::post_with_context`](crate::WorldEventBus::post_with_context), and received with
+/// [`ReceiveWith`]. They are a different event type than `E` itself, so handlers of `E` don't run
+/// for them.
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::{prelude::*, ReceiveWith};
+/// struct Chat(String);
+///
+/// impl BusEvent for Chat {
+///     type Mutability = Immutable;
+///     type Cancellation = ();
+///     type Audience = ();
+/// }
+///
+/// struct Connection(u32);
+///
+/// fn log_chat(event: ReceiveWith<Chat, Connection>) {
+///     println!("connection {} says {}", event.context().0, event.0);
+/// }
+///
+/// let mut world = World::new();
+/// world.add_handler(log_chat);
+/// world.post_with_context(Chat("hello".to_string()), Connection(7));
+/// ```
+pub struct WithContext<E, C> {
+    /// The event.
+    pub event: E,
+    /// The context of the post.
+    pub context: C,
+}
+
+impl<E: Event, C: 'static> Event for WithContext<E, C> {
+    type Mutability = E::Mutability;
+    type Cancellation = E::Cancellation;
+    type Audience = E::Audience;
+
+    fn before_dispatch(&mut self, world: &mut World) {
+        self.event.before_dispatch(world);
+    }
+
+    fn after_dispatch(&self, cancellation: &Self::Cancellation, world: &mut World) {
+        self.event.after_dispatch(cancellation, world);
+    }
+}
+
+impl<E, C> Deref for WithContext<E, C> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+impl<E, C> DerefMut for WithContext<E, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.event
+    }
+}
+
+/// [`SystemInput`](bevy_ecs::system::SystemInput) type for receiving events posted with context
+/// `C` in handlers, see [`WithContext`].
+pub type ReceiveWith<'event, E, C> = Receive<'event, WithContext<E, C>>;
+
+impl<E: Event, C: 'static> Receive<'_, WithContext<E, C>> {
+    /// Returns the context the event was posted with.
+    pub fn context(&self) -> &C {
+        &self.event().context
+    }
+}
@@ -0,0 +1,154 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{CorrelationId, Event, EventContext, FrameCount, PostId, Time};
+
+mod delivery;
+mod reconcile;
+mod replay;
+
+pub use delivery::*;
+pub use reconcile::*;
+pub use replay::*;
+
+/// [`Resource`] which retains the last posted events of type `E`, oldest first.
+///
+/// History is opt-in per event type with
+/// [`WorldEventBus::enable_history`](crate::WorldEventBus::enable_history), and read back with
+/// [`WorldEventBus::event_history`](crate::WorldEventBus::event_history). Events are recorded as
+/// they were posted, before any handler ran.
+///
+/// Entries are stamped with the [`FrameCount`] and the elapsed [`Time`] at the moment they were
+/// posted. Both stamps are zero if the respective resource is missing.
+#[derive(Resource)]
+pub struct EventHistory<E: Event> {
+    capacity: usize,
+    entries: VecDeque<HistoryEntry<E>>,
+}
+
+/// A single posted event retained in an [`EventHistory`].
+#[derive(Debug, Clone)]
+pub struct HistoryEntry<E: Event> {
+    /// A clone of the event as it was posted.
+    pub event: E,
+    /// The audience the event was posted to.
+    pub audience: E::Audience,
+    /// The ID of the post, see [`EventMeta`](crate::EventMeta).
+    pub post: PostId,
+    /// The correlation ID of the post, see [`EventMeta`](crate::EventMeta).
+    pub correlation: CorrelationId,
+    /// The seed of the post, see [`BusRng`](crate::BusRng).
+    pub seed: u64,
+    /// The frame the event was posted in.
+    pub frame: u32,
+    /// The elapsed time when the event was posted.
+    pub elapsed: Duration,
+}
+
+impl<E: Event> EventHistory<E> {
+    /// The number of events retained by [`bus_event!`](crate::bus_event) events declared as
+    /// `retained` without a capacity.
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Creates an empty history that retains up to `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of events retained.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the maximum number of events retained, dropping the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Returns the number of events retained.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the retained events, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry<E>> + '_ {
+        self.entries.iter()
+    }
+
+    /// Returns the most recently posted event, if any.
+    pub fn latest(&self) -> Option<&HistoryEntry<E>> {
+        self.entries.back()
+    }
+
+    /// Returns an iterator over the events posted at or after the `elapsed` time, oldest first.
+    pub fn since(&self, elapsed: Duration) -> impl DoubleEndedIterator<Item = &HistoryEntry<E>> {
+        let start = self
+            .entries
+            .partition_point(|entry| entry.elapsed < elapsed);
+        self.entries.range(start..)
+    }
+
+    /// Returns an iterator over the events posted in or after the `frame`, oldest first.
+    pub fn since_frame(&self, frame: u32) -> impl DoubleEndedIterator<Item = &HistoryEntry<E>> {
+        let start = self.entries.partition_point(|entry| entry.frame < frame);
+        self.entries.range(start..)
+    }
+
+    /// Removes all retained events.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Retains an entry, dropping the oldest one if the history is full.
+    pub fn push(&mut self, entry: HistoryEntry<E>) {
+        self.entries.push_back(entry);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// Records a posted event into its [`EventHistory`], if the history is enabled.
+pub(crate) fn record<E>(world: &mut World, event: &E, audience: &E::Audience)
+where
+    E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+{
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0);
+    let elapsed = world
+        .get_resource::<Time>()
+        .map_or(Duration::ZERO, Time::elapsed);
+    let meta = world
+        .get_resource::<EventContext>()
+        .and_then(EventContext::current)
+        .copied();
+    let post = meta.map_or(PostId(0), |meta| meta.id);
+    let correlation = meta.map_or(CorrelationId(0), |meta| meta.correlation);
+    let seed = meta.map_or(0, |meta| meta.seed);
+    if let Some(mut history) = world.get_resource_mut::<EventHistory<E>>() {
+        history.push(HistoryEntry {
+            event: event.clone(),
+            audience: audience.clone(),
+            post,
+            correlation,
+            seed,
+            frame,
+            elapsed,
+        });
+    }
+}
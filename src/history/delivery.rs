@@ -0,0 +1,193 @@
+use std::{borrow::Cow, collections::HashMap, marker::PhantomData, slice};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{
+    dispatch::dispatch_entries, Event, EventContext, EventHistory, HandlerEntry, HandlerId,
+    HandlerRegistry, Mutability, PostId, PostOptions,
+};
+
+/// Catches the handlers of an [`Event`] type up on the posts they missed.
+type CatchUp = fn(&mut World) -> usize;
+
+/// [`Resource`] which remembers the last post of [`Event`] `E` that each of its handlers was
+/// offered, so that handlers catch up on the posts they missed while disabled or removed, enabled
+/// with [`WorldEventBus::track_delivery`](crate::WorldEventBus::track_delivery).
+///
+/// A handler is offered a post if it is enabled when the post is dispatched, whether or not it
+/// ends up running, e.g. because the post was cancelled first. Handlers are remembered by their
+/// [`HandlerId`] and forgotten once they are removed, unless they have a
+/// [`delivery_key`](crate::HandlerConfig::delivery_key): then they are remembered by the key, so
+/// that a handler which is removed and added again with the same key is recognized.
+///
+/// Whenever a handler for `E` is added, or a [`HandlerSet`](crate::HandlerSet) of `E` is
+/// reconfigured, every enabled handler that was offered a post before catches up on the posts
+/// retained in the [`EventHistory`] since then, oldest first. Only the handler itself runs, with
+/// the seed and elapsed time of the original post, and cancelled posts are delivered too. Posts
+/// that are no longer retained are lost, so the capacity of the history bounds how long handlers
+/// can be disabled without missing posts.
+#[derive(Resource)]
+pub struct DeliveryTracker<E: Event> {
+    delivered: HashMap<DeliveryKey, PostId>,
+    catch_up: CatchUp,
+    _marker: PhantomData<fn() -> E>,
+}
+
+/// What a [`DeliveryTracker`] remembers a handler by.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DeliveryKey {
+    /// The raw [`HandlerId`] of a handler without a delivery key.
+    Handler(u64),
+    /// The [`delivery_key`](crate::HandlerConfig::delivery_key) of a handler.
+    Named(Cow<'static, str>),
+}
+
+impl DeliveryKey {
+    fn of<E: Event>(entry: &HandlerEntry<E>) -> Self {
+        match &entry.delivery_key {
+            Some(key) => Self::Named((**key).clone()),
+            None => Self::Handler(entry.id.to_raw()),
+        }
+    }
+}
+
+impl<E> DeliveryTracker<E>
+where
+    E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+{
+    /// Creates a tracker which catches handlers up on the posts retained in the history.
+    pub(crate) fn new() -> Self {
+        Self {
+            delivered: HashMap::new(),
+            catch_up: catch_up::<E>,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: Event> DeliveryTracker<E> {
+    /// Returns the last post offered to the handler without a delivery key, if any.
+    pub fn last_delivered(&self, id: HandlerId<E>) -> Option<PostId> {
+        self.delivered
+            .get(&DeliveryKey::Handler(id.to_raw()))
+            .copied()
+    }
+
+    /// Returns the last post offered to the handlers with the delivery key, if any.
+    pub fn last_delivered_by_key(&self, key: &str) -> Option<PostId> {
+        self.delivered
+            .get(&DeliveryKey::Named(Cow::Owned(key.to_owned())))
+            .copied()
+    }
+
+    /// Forgets the posts offered to the handler without a delivery key. Returns the last post it
+    /// was offered, if any.
+    pub fn forget(&mut self, id: HandlerId<E>) -> Option<PostId> {
+        self.delivered.remove(&DeliveryKey::Handler(id.to_raw()))
+    }
+
+    /// Forgets the posts offered to the handlers with the delivery key, so that they don't catch
+    /// up when added again. Returns the last post they were offered, if any.
+    pub fn forget_key(&mut self, key: &str) -> Option<PostId> {
+        self.delivered
+            .remove(&DeliveryKey::Named(Cow::Owned(key.to_owned())))
+    }
+
+    /// Records the current post as offered to the handlers, if delivery is tracked for `E`.
+    pub(crate) fn offer(world: &mut World, handlers: &[HandlerEntry<E>]) {
+        if !world.contains_resource::<Self>() {
+            return;
+        }
+        let Some(post) = world
+            .get_resource::<EventContext>()
+            .and_then(EventContext::current)
+            .map(|meta| meta.id)
+        else {
+            return;
+        };
+
+        let mut tracker = world.resource_mut::<Self>();
+        for entry in handlers {
+            tracker.delivered.insert(DeliveryKey::of(entry), post);
+        }
+    }
+
+    /// Catches the enabled handlers up on the posts they missed, if delivery is tracked for `E`.
+    /// Returns the number of posts delivered.
+    pub(crate) fn catch_up(world: &mut World) -> usize {
+        match world.get_resource::<Self>() {
+            Some(tracker) => (tracker.catch_up)(world),
+            None => 0,
+        }
+    }
+}
+
+fn catch_up<E>(world: &mut World) -> usize
+where
+    E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+{
+    if !world.contains_resource::<EventHistory<E>>() {
+        return 0;
+    }
+    let Some(registry) = world.get_resource::<HandlerRegistry<E>>() else {
+        return 0;
+    };
+    let handlers = registry.snapshot();
+    // Forget the handlers that were removed without going through the world, e.g. once handlers.
+    let removed = world
+        .resource::<DeliveryTracker<E>>()
+        .delivered
+        .keys()
+        .filter_map(|key| match key {
+            DeliveryKey::Handler(id) => Some(HandlerId::<E>::from_raw(*id)),
+            DeliveryKey::Named(_) => None,
+        })
+        .filter(|&id| !registry.contains(id))
+        .collect::<Vec<_>>();
+    let mut tracker = world.resource_mut::<DeliveryTracker<E>>();
+    for id in removed {
+        tracker.forget(id);
+    }
+
+    let mut missed = Vec::new();
+    for entry in handlers {
+        // A running handler can't run again, it catches up the next time.
+        if entry.handler.is_locked() {
+            continue;
+        }
+        let key = DeliveryKey::of(&entry);
+        if let Some(&last) = tracker.delivered.get(&key) {
+            missed.push((entry, key, last));
+        }
+    }
+
+    let mut delivered = 0;
+    for (entry, key, last) in missed {
+        let posts = world
+            .resource::<EventHistory<E>>()
+            .iter()
+            .filter(|post| post.post > last)
+            .cloned()
+            .collect::<Vec<_>>();
+        for post in posts {
+            let mut event = post.event;
+            let options = PostOptions {
+                replayed: Some((post.seed, post.elapsed)),
+                ..Default::default()
+            };
+            dispatch_entries(
+                world,
+                slice::from_ref(&entry),
+                E::Mutability::to_ref(&mut event),
+                &post.audience,
+                &options,
+            );
+            world
+                .resource_mut::<DeliveryTracker<E>>()
+                .delivered
+                .insert(key.clone(), post.post);
+            delivered += 1;
+        }
+    }
+    delivered
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use crate::{CorrelationId, Event, EventHistory, HistoryEntry};
+
+/// The outcome of reconciling the [`EventHistory`] of a predicting world with the one of an
+/// authoritative world, see [`EventHistory::reconcile`].
+pub struct Reconciliation<E: Event> {
+    /// How many authoritative posts were predicted exactly.
+    pub confirmed: usize,
+    /// How many predicted posts have no authoritative counterpart.
+    pub mispredicted: usize,
+    /// The authoritative posts that weren't predicted, oldest first.
+    pub divergences: Vec<HistoryEntry<E>>,
+}
+
+impl<E> EventHistory<E>
+where
+    E: Event<Audience: Clone + PartialEq> + Clone + PartialEq,
+{
+    /// Reconciles the posts predicted in this history with the `authoritative` history, matching
+    /// them by [`CorrelationId`].
+    ///
+    /// An authoritative post is confirmed if a predicted post of the same correlation has an
+    /// equal event and audience, and diverges otherwise. Each predicted post confirms at most one
+    /// authoritative post.
+    ///
+    /// Both worlds must assign the same correlation IDs to the same chains of events, e.g. by
+    /// using the [`CorrelationGenerator::Counter`](crate::CorrelationGenerator::Counter) and
+    /// posting root events in the same order. Only the retained posts are compared.
+    pub fn reconcile(&self, authoritative: &Self) -> Reconciliation<E> {
+        let mut predicted = HashMap::<CorrelationId, Vec<&HistoryEntry<E>>>::new();
+        for entry in self.iter() {
+            predicted.entry(entry.correlation).or_default().push(entry);
+        }
+
+        let mut confirmed = 0;
+        let mut divergences = Vec::new();
+        for entry in authoritative.iter() {
+            let matched = predicted.get_mut(&entry.correlation).and_then(|chain| {
+                let index = chain.iter().position(|prediction| {
+                    prediction.event == entry.event && prediction.audience == entry.audience
+                })?;
+                Some(chain.remove(index))
+            });
+            match matched {
+                Some(_) => confirmed += 1,
+                None => divergences.push(entry.clone()),
+            }
+        }
+
+        Reconciliation {
+            confirmed,
+            mispredicted: predicted.values().map(Vec::len).sum(),
+            divergences,
+        }
+    }
+}
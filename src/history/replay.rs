@@ -0,0 +1,184 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy_ecs::{entity::Entity, system::Resource, world::World};
+
+use crate::{
+    post_with_options, Audience, EntityRemap, Event, Immutable, PostOptions, Time, WorldEventBus,
+};
+
+/// [`Event`] which re-posts a previously posted event `E` during a replay, see
+/// [`EventReplayer`].
+///
+/// Replays are posted as their own event type, so that handlers of the live `E` don't run a
+/// second time. Handlers opt into replays by receiving `Replay<E>` instead. Replays are stamped
+/// with the seed and elapsed time of the original post, so [`BusRng`](crate::BusRng) and
+/// [`BusTime`](crate::BusTime) reproduce what its handlers saw.
+#[derive(Debug, Clone)]
+pub struct Replay<E: Event> {
+    /// The event as it was originally posted.
+    pub event: E,
+    /// The seed of the original post, see [`BusRng`](crate::BusRng).
+    pub seed: u64,
+    /// The frame the event was originally posted in.
+    pub frame: u32,
+    /// The elapsed time when the event was originally posted.
+    pub elapsed: Duration,
+}
+
+impl<E: Event> Event for Replay<E> {
+    type Mutability = Immutable;
+    type Cancellation = ();
+    type Audience = E::Audience;
+}
+
+/// [`Resource`] which re-posts events retained in an [`EventHistory`](crate::EventHistory) as
+/// [`Replay`]s, spaced out like they were originally posted.
+///
+/// Replays are started with [`EventReplayer::replay_window`], and advanced by the
+/// [`EventBusPlugin`](crate::EventBusPlugin) every frame.
+#[derive(Resource)]
+pub struct EventReplayer {
+    /// How fast replays play back relative to the original timing, e.g. `0.5` for half speed.
+    pub speed: f32,
+    /// The playback time, relative to the start of the current replays.
+    clock: Duration,
+    /// Replays yet to be posted, ordered by when they are due.
+    pending: VecDeque<PendingReplay>,
+}
+
+struct PendingReplay {
+    due: Duration,
+    post: Box<dyn FnOnce(&mut World) + Send + Sync>,
+}
+
+impl Default for EventReplayer {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            clock: Duration::ZERO,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl EventReplayer {
+    /// Schedules all events of type `E` retained in its [`EventHistory`](crate::EventHistory) that
+    /// target the entity and were posted within the last `window` of time, to be re-posted as
+    /// [`Replay`]s.
+    ///
+    /// The events and their audiences are remapped with the [`EntityRemap`] of the world, so
+    /// histories restored from a journal of another session can be replayed too.
+    ///
+    /// The first event is replayed on the next advance, and the following ones keep their
+    /// original spacing, scaled by [`EventReplayer::speed`]. Returns the number of events
+    /// scheduled.
+    pub fn replay_window<E>(world: &mut World, entity: Entity, window: Duration) -> usize
+    where
+        E: Event<Audience: Clone + Send + Sync> + Clone + Send + Sync,
+    {
+        let Some(history) = world.event_history::<E>() else {
+            return 0;
+        };
+        let now = world.get_resource::<Time>().map_or_else(
+            || {
+                history
+                    .latest()
+                    .map_or(Duration::ZERO, |entry| entry.elapsed)
+            },
+            Time::elapsed,
+        );
+
+        let entries = history
+            .since(now.saturating_sub(window))
+            .cloned()
+            .map(|mut entry| {
+                EntityRemap::apply(world, &mut entry.event, &mut entry.audience);
+                entry
+            })
+            .filter(|entry| entry.audience.includes(entity))
+            .collect::<Vec<_>>();
+        let Some(start) = entries.first().map(|entry| entry.elapsed) else {
+            return 0;
+        };
+
+        let mut replayer = world.get_resource_or_insert_with(EventReplayer::default);
+        let offset = replayer.clock;
+        for entry in &entries {
+            let due = offset + (entry.elapsed - start);
+            let index = replayer
+                .pending
+                .partition_point(|pending| pending.due <= due);
+            let entry = entry.clone();
+            replayer.pending.insert(
+                index,
+                PendingReplay {
+                    due,
+                    post: Box::new(move |world| {
+                        let options = PostOptions {
+                            replayed: Some((entry.seed, entry.elapsed)),
+                            ..Default::default()
+                        };
+                        let replay = Replay {
+                            event: entry.event,
+                            seed: entry.seed,
+                            frame: entry.frame,
+                            elapsed: entry.elapsed,
+                        };
+                        post_with_options(world, replay, entry.audience, options);
+                    }),
+                },
+            );
+        }
+        entries.len()
+    }
+
+    /// Advances the playback time by `delta`, scaled by [`EventReplayer::speed`], and posts all
+    /// replays that are due, in order.
+    pub fn advance(world: &mut World, delta: Duration) {
+        let Some(mut replayer) = world.get_resource_mut::<EventReplayer>() else {
+            return;
+        };
+        if replayer.pending.is_empty() {
+            return;
+        }
+
+        let speed = replayer.speed.max(0.0);
+        replayer.clock += delta.mul_f32(speed);
+        let clock = replayer.clock;
+        let due = replayer
+            .pending
+            .partition_point(|pending| pending.due <= clock);
+        let due = replayer.pending.drain(..due).collect::<Vec<_>>();
+        if replayer.pending.is_empty() {
+            replayer.clock = Duration::ZERO;
+        }
+
+        for pending in due {
+            (pending.post)(world);
+        }
+    }
+
+    /// Returns `true` if any replays are yet to be posted.
+    pub fn is_replaying(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Returns the number of replays yet to be posted.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Stops all replays, discarding the ones yet to be posted.
+    pub fn stop(&mut self) {
+        self.pending.clear();
+        self.clock = Duration::ZERO;
+    }
+}
+
+/// Exclusive system that advances the [`EventReplayer`] by the frame's [`Time`] delta.
+pub fn advance_replays(world: &mut World) {
+    let delta = world
+        .get_resource::<Time>()
+        .map_or(Duration::ZERO, Time::delta);
+    EventReplayer::advance(world, delta);
+}
@@ -0,0 +1,81 @@
+use bevy_ecs::event::{Event as BevyEvent, EventWriter};
+
+use crate::{Event, Poster};
+
+#[cfg(feature = "bevy_asset")]
+mod asset;
+mod bridge;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod lifecycle;
+#[cfg(feature = "bevy_picking")]
+mod picking;
+
+#[cfg(feature = "bevy_asset")]
+pub use asset::*;
+pub use bridge::*;
+#[cfg(feature = "ffi")]
+pub use ffi::*;
+pub use lifecycle::*;
+#[cfg(feature = "bevy_picking")]
+pub use picking::*;
+
+/// Abstraction over anything that can emit events of type `E`, so library code can be written
+/// once and work with both bevy's [`EventWriter`] and this crate's [`Poster`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_eventbus::prelude::*;
+///
+/// struct Explosion {
+///     radius: f32,
+/// }
+///
+/// fn explode<W: GenericEmitter<Explosion>>(writer: &mut W, radius: f32) {
+///     writer.emit(Explosion { radius });
+/// }
+///
+/// let mut emitted = Vec::new();
+/// explode(&mut emitted, 2.0);
+/// assert_eq!(emitted[0].radius, 2.0);
+/// ```
+pub trait GenericEmitter<E> {
+    /// Emits a single event.
+    fn emit(&mut self, event: E);
+
+    /// Emits a batch of events, in order.
+    fn emit_batch(&mut self, events: impl IntoIterator<Item = E>) {
+        for event in events {
+            self.emit(event);
+        }
+    }
+}
+
+/// Bevy's [`EventWriter`] emits by sending into the [`Events`](bevy_ecs::event::Events) resource.
+impl<E: BevyEvent> GenericEmitter<E> for EventWriter<'_, E> {
+    fn emit(&mut self, event: E) {
+        self.send(event);
+    }
+}
+
+/// [`Poster`] emits by posting to the event bus.
+impl<E: Event<Audience = ()> + Send> GenericEmitter<E> for Poster<'_, '_, E> {
+    fn emit(&mut self, event: E) {
+        self.post(event);
+    }
+}
+
+/// Mutable references to emitters are emitters themselves.
+impl<E, W: GenericEmitter<E> + ?Sized> GenericEmitter<E> for &mut W {
+    fn emit(&mut self, event: E) {
+        (**self).emit(event);
+    }
+}
+
+/// [`Vec`]s collect the emitted events, which is useful for testing library code.
+impl<E> GenericEmitter<E> for Vec<E> {
+    fn emit(&mut self, event: E) {
+        self.push(event);
+    }
+}
@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Last, Plugin};
+use bevy_asset::{Asset, AssetEvent, AssetEvents, AssetId, AssetPath, AssetServer, Assets, Handle};
+use bevy_ecs::{
+    event::{EventCursor, Events},
+    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
+    system::Local,
+    world::World,
+};
+
+use crate::{Event, Immutable, Mutable, WorldEventBus};
+
+/// [`Event`] posted before an asset is loaded with [`AssetEventBus::load_asset`].
+///
+/// Handlers can redirect the load to a fallback path by cancelling the event with it, e.g. to
+/// swap in a localized texture or a modded model:
+///
+/// ```rust
+/// # use bevy_asset::AssetPath;
+/// # use bevy_eventbus::{prelude::*, AssetWillLoad};
+/// fn localize(mut event: Receive<AssetWillLoad>) {
+///     if event.path.path().starts_with("text") {
+///         let localized = format!("fr/{}", event.path);
+///         event.cancel_with(AssetPath::from(localized));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AssetWillLoad {
+    /// The path of the asset to load.
+    pub path: AssetPath<'static>,
+}
+
+impl Event for AssetWillLoad {
+    type Mutability = Immutable;
+    type Cancellation = Option<AssetPath<'static>>;
+    type Audience = ();
+}
+
+/// [`Event`] posted by the [`AssetBridgePlugin`] once an asset of type `A` and its dependencies
+/// finished loading.
+///
+/// Handlers can post-process the asset by modifying the copy in the event, which is written back
+/// into the [`Assets`] afterwards. Systems that run earlier in the frame than the bridge observe
+/// the asset as it was loaded, and get an [`AssetEvent::Modified`] once it was post-processed.
+pub struct AssetLoaded<A: Asset> {
+    /// The ID of the asset.
+    pub id: AssetId<A>,
+    /// The path the asset was loaded from, if any.
+    pub path: Option<AssetPath<'static>>,
+    /// A copy of the loaded asset.
+    pub asset: A,
+}
+
+impl<A: Asset> Event for AssetLoaded<A> {
+    type Mutability = Mutable;
+    type Cancellation = ();
+    type Audience = ();
+}
+
+/// [`World`] extension trait for loading assets through the event bus.
+pub trait AssetEventBus {
+    /// Posts [`AssetWillLoad`] for the path, then loads the asset from the path, or from the
+    /// fallback path a handler cancelled the event with.
+    ///
+    /// Requires the [`AssetServer`] to be present.
+    fn load_asset<A: Asset>(&mut self, path: impl Into<AssetPath<'static>>) -> Handle<A>;
+}
+
+impl AssetEventBus for World {
+    fn load_asset<A: Asset>(&mut self, path: impl Into<AssetPath<'static>>) -> Handle<A> {
+        let path = path.into();
+        let path = self
+            .post(AssetWillLoad { path: path.clone() })
+            .unwrap_or(path);
+        self.resource::<AssetServer>().load(path)
+    }
+}
+
+/// [`Plugin`] which posts [`AssetLoaded`] for every asset of type `A` that finished loading.
+///
+/// Requires bevy's `AssetPlugin` and the asset type to be registered. The events are posted in
+/// [`Last`] after bevy's [`AssetEvents`], and only if any handlers are registered for them.
+pub struct AssetBridgePlugin<A: Asset + Clone>(PhantomData<fn() -> A>);
+
+impl<A: Asset + Clone> Default for AssetBridgePlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// [`SystemSet`] of the systems added by the [`AssetBridgePlugin`]s.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AssetBridgeSystems;
+
+impl<A: Asset + Clone> Plugin for AssetBridgePlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(Last, AssetBridgeSystems.after(AssetEvents))
+            .add_systems(Last, bridge_loaded_assets::<A>.in_set(AssetBridgeSystems));
+    }
+}
+
+/// Exclusive system that posts an [`AssetLoaded`] event for every asset of type `A` that finished
+/// loading since it last ran, and writes the possibly modified asset back.
+///
+/// Each asset is posted once, even if it finished loading several times since the system last ran,
+/// and writing it back emits a single [`AssetEvent::Modified`]. Assets that were removed before
+/// the system ran are skipped.
+pub fn bridge_loaded_assets<A: Asset + Clone>(
+    world: &mut World,
+    mut cursor: Local<EventCursor<AssetEvent<A>>>,
+) {
+    let Some(events) = world.get_resource::<Events<AssetEvent<A>>>() else {
+        return;
+    };
+    let mut loaded = Vec::new();
+    for event in cursor.read(events) {
+        if let AssetEvent::LoadedWithDependencies { id } = event {
+            if !loaded.contains(id) {
+                loaded.push(*id);
+            }
+        }
+    }
+    if loaded.is_empty() || !world.has_handlers::<AssetLoaded<A>>() {
+        return;
+    }
+
+    for id in loaded {
+        let Some(asset) = world.resource::<Assets<A>>().get(id).cloned() else {
+            continue;
+        };
+        let path = world
+            .get_resource::<AssetServer>()
+            .and_then(|server| server.get_path(id))
+            .map(AssetPath::into_owned);
+        let mut loaded = AssetLoaded { id, path, asset };
+        world.post_mut(&mut loaded);
+        if let Some(asset) = world.resource_mut::<Assets<A>>().get_mut(id) {
+            *asset = loaded.asset;
+        }
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "bevy_app")]
+use std::{any::type_name, marker::PhantomData};
+use std::{collections::HashSet, fmt};
+
+#[cfg(feature = "bevy_app")]
+use bevy_app::{App, Plugin, PreUpdate};
+#[cfg(feature = "bevy_app")]
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::{
+    event::{Event as BevyEvent, EventCursor, EventId, Events},
+    schedule::SystemSet,
+    system::{Local, Res, ResMut, Resource},
+    world::World,
+};
+
+#[cfg(feature = "bevy_app")]
+use crate::{config::priority::Last, AppEventBus};
+use crate::{post_with_options, EntityRemap, Event, EventContext, EventMeta, PostOptions, Receive};
+
+/// Identifier of a bridge between the event bus and another event system, such as bevy's own
+/// events or the network, see [`Bridge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BridgeId(pub &'static str);
+
+impl fmt::Display for BridgeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// One instance of a bridge between the event bus and another event system, which protects
+/// bidirectional bridging from infinite loops.
+///
+/// Events imported into the event bus with [`Bridge::import`] are stamped with the ID of the bridge
+/// as their [`EventMeta::origin`], so that the exporting side of the same bridge can check
+/// [`Bridge::is_echo`] and skip them instead of sending them back to where they came from:
+///
+/// ```rust
+/// # use bevy_ecs::system::Res;
+/// # use bevy_eventbus::{prelude::*, Bridge, EventContext};
+/// # struct TransformChanged;
+/// # impl BusEvent for TransformChanged {
+/// #     type Mutability = Immutable;
+/// #     type Cancellation = ();
+/// #     type Audience = ();
+/// # }
+/// const NETWORK: Bridge = Bridge::new("network");
+///
+/// fn export(event: Receive<TransformChanged>, context: Res<EventContext>) {
+///     if NETWORK.is_echo(context.current()) {
+///         return;
+///     }
+///     // Send the event to the network...
+/// }
+/// ```
+///
+/// Only the imported post itself is stamped: events posted by its handlers are exported as usual.
+/// Imported posts that are deferred while the event bus is paused lose their origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bridge {
+    /// The ID that imported events are stamped with.
+    pub id: BridgeId,
+    /// Whether events imported by this bridge are reported as echoes, defaults to `true`.
+    pub suppress_echo: bool,
+}
+
+impl Bridge {
+    /// Creates a bridge with the name as its ID, which suppresses echoes.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            id: BridgeId(name),
+            suppress_echo: true,
+        }
+    }
+
+    /// Lets events imported by this bridge be exported through it again.
+    pub const fn allow_echo(mut self) -> Self {
+        self.suppress_echo = false;
+        self
+    }
+
+    /// Posts an [`Event`] that entered the event bus through this bridge, stamping it with the ID
+    /// of the bridge.
+    ///
+    /// The event and its audience are remapped with the [`EntityRemap`] of the world first.
+    pub fn import<E: Event>(
+        &self,
+        world: &mut World,
+        mut event: E,
+        mut audience: E::Audience,
+    ) -> E::Cancellation {
+        EntityRemap::apply(world, &mut event, &mut audience);
+        let options = PostOptions {
+            origin: Some(self.id),
+            ..Default::default()
+        };
+        post_with_options(world, event, audience, options)
+    }
+
+    /// Returns `true` if the post entered the event bus through this bridge, and so must not be
+    /// exported through it.
+    pub fn is_echo(&self, meta: Option<&EventMeta>) -> bool {
+        self.suppress_echo && meta.is_some_and(|meta| meta.origin == Some(self.id))
+    }
+}
+
+/// [`Plugin`] which bridges bevy's [`Events<E>`] and the event bus in both directions: events sent
+/// to [`Events<E>`] are posted to the event bus, and events posted to the event bus are sent to
+/// [`Events<E>`].
+///
+/// Events are only bridged once: the [`Bridge`] keeps events from being sent back to where they
+/// came from, unless it [allows echoes](Bridge::allow_echo). Events are imported in
+/// [`PreUpdate`], and exported by a handler with [`Last`] priority, so cancelled events aren't
+/// exported.
+#[cfg(feature = "bevy_app")]
+pub struct EventsBridgePlugin<E> {
+    /// The bridge, by default named after the event type.
+    pub bridge: Bridge,
+    _marker: PhantomData<fn() -> E>,
+}
+
+#[cfg(feature = "bevy_app")]
+impl<E> EventsBridgePlugin<E> {
+    /// Creates a plugin with the bridge.
+    pub fn new(bridge: Bridge) -> Self {
+        Self {
+            bridge,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "bevy_app")]
+impl<E> Default for EventsBridgePlugin<E> {
+    fn default() -> Self {
+        Self::new(Bridge::new(type_name::<E>()))
+    }
+}
+
+/// [`SystemSet`] of the systems added by the [`EventsBridgePlugin`]s.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventsBridgeSystems;
+
+#[cfg(feature = "bevy_app")]
+impl<E: Event<Audience = ()> + BevyEvent + Clone> Plugin for EventsBridgePlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<E>()
+            .insert_resource(EventsBridge::<E> {
+                bridge: self.bridge,
+                exported: HashSet::new(),
+            })
+            .add_systems(
+                PreUpdate,
+                import_bevy_events::<E>.in_set(EventsBridgeSystems),
+            )
+            .add_handler(crate::IntoHandlerConfig::priority(
+                export_bevy_events::<E>,
+                Last,
+            ));
+    }
+}
+
+/// [`Resource`] which tracks the events exported by the [`EventsBridgePlugin<E>`], so that they
+/// aren't imported again.
+#[derive(Resource)]
+pub struct EventsBridge<E: BevyEvent> {
+    /// The bridge of the plugin.
+    pub bridge: Bridge,
+    exported: HashSet<EventId<E>>,
+}
+
+/// Exclusive system that posts the events sent to [`Events<E>`] since it last ran, except for the
+/// ones exported by the [`EventsBridgePlugin<E>`].
+pub fn import_bevy_events<E: Event<Audience = ()> + BevyEvent + Clone>(
+    world: &mut World,
+    mut cursor: Local<EventCursor<E>>,
+) {
+    let Some(mut state) = world.get_resource_mut::<EventsBridge<E>>() else {
+        return;
+    };
+    let bridge = state.bridge;
+    let mut exported = std::mem::take(&mut state.exported);
+    let Some(events) = world.get_resource::<Events<E>>() else {
+        return;
+    };
+    let imported = cursor
+        .read_with_id(events)
+        .filter(|(_, id)| !(exported.remove(id) && bridge.suppress_echo))
+        .map(|(event, _)| event.clone())
+        .collect::<Vec<_>>();
+    let oldest = events.oldest_event_count();
+    exported.retain(|id| id.id >= oldest);
+    world.resource_mut::<EventsBridge<E>>().exported = exported;
+
+    for event in imported {
+        bridge.import(world, event, ());
+    }
+}
+
+/// Handler that sends the events posted to the event bus to [`Events<E>`], except for the ones
+/// imported by the [`EventsBridgePlugin<E>`].
+pub fn export_bevy_events<E: Event<Audience = ()> + BevyEvent + Clone>(
+    event: Receive<E>,
+    context: Res<EventContext>,
+    mut events: ResMut<Events<E>>,
+    mut bridge: ResMut<EventsBridge<E>>,
+) {
+    if bridge.bridge.is_echo(context.current()) {
+        return;
+    }
+    let id = events.send((*event).clone());
+    bridge.exported.insert(id);
+}
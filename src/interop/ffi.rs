@@ -0,0 +1,291 @@
+use std::{cell::RefCell, ffi::c_void, slice};
+
+use bevy_ecs::world::World;
+use bevy_utils::tracing::warn;
+
+use crate::{
+    initialize_config, Event, HandlerId, Immutable, KeyedHandlers, Receive, WorldEventBus,
+};
+
+/// The version of the [`EventBusVTable`] layout, bumped whenever it changes incompatibly.
+pub const EVENTBUS_ABI_VERSION: u32 = 1;
+
+/// The ID returned by [`EventBusVTable::subscribe`] if the handler wasn't subscribed.
+pub const EVENTBUS_INVALID_ID: u64 = u64::MAX;
+
+/// [`Event`] identified by name with an opaque byte payload, which non-Rust plugins post and
+/// subscribe to through the [`EventBusVTable`].
+///
+/// Dynamic events are posted to the handlers keyed by their name, see
+/// [`KeyedHandlers`]. Rust code takes part by posting and subscribing with the same key:
+///
+/// ```rust
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::{prelude::*, DynamicEvent};
+/// let mut world = World::new();
+/// world.add_keyed_handler("save".to_string(), |event: Receive<DynamicEvent>| {
+///     assert_eq!(event.payload, b"slot 1");
+/// });
+/// world.post_keyed(DynamicEvent::new("save", b"slot 1".to_vec()), &"save".to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DynamicEvent {
+    /// The name of the event, which handlers subscribe to.
+    pub name: String,
+    /// The payload of the event, in whatever encoding the plugins agreed on.
+    pub payload: Vec<u8>,
+}
+
+impl DynamicEvent {
+    /// Creates a dynamic event.
+    pub fn new(name: impl Into<String>, payload: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            payload,
+        }
+    }
+}
+
+impl Event for DynamicEvent {
+    type Mutability = Immutable;
+    type Cancellation = bool;
+    type Audience = ();
+}
+
+/// Callback of a C plugin subscribed to a [`DynamicEvent`], called with the `user_data` it was
+/// subscribed with, the name of the event and its payload. Returns `true` to cancel the event.
+///
+/// The world is borrowed while the callback runs, so calls it makes to the [`EventBusVTable`] don't
+/// touch the world: posts and unsubscriptions are queued and applied once the callback returns,
+/// and subscriptions are rejected.
+pub type FfiHandler = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    name: *const u8,
+    name_len: usize,
+    payload: *const u8,
+    payload_len: usize,
+) -> bool;
+
+/// Versioned table of `extern "C"` functions through which non-Rust plugins post and subscribe to
+/// [`DynamicEvent`]s, returned by [`bevy_eventbus_vtable`].
+///
+/// Every function takes the world as an opaque pointer, which the host passes to its plugins as
+/// `&mut World as *mut World as *mut c_void`. Strings are UTF-8 and not NUL-terminated. The
+/// functions must only be called while the host isn't otherwise accessing the world, e.g. from an
+/// exclusive system, or from a [`FfiHandler`], in which case the world isn't dereferenced and the
+/// call is queued instead.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct EventBusVTable {
+    /// The [`EVENTBUS_ABI_VERSION`] of the table.
+    pub version: u32,
+    /// The size of the table in bytes, so that plugins can detect functions appended later.
+    pub size: usize,
+    /// Posts a [`DynamicEvent`], returning `true` if a handler cancelled it. Posts from within a
+    /// [`FfiHandler`] are dispatched once it returns, and return `false`.
+    pub post: unsafe extern "C" fn(
+        world: *mut c_void,
+        name: *const u8,
+        name_len: usize,
+        payload: *const u8,
+        payload_len: usize,
+    ) -> bool,
+    /// Subscribes a handler to the [`DynamicEvent`]s of a name, returning its ID. The
+    /// `user_data` is passed back to the handler as is, and must stay valid until it is
+    /// unsubscribed. The handler may be called from any thread that the host dispatches from.
+    ///
+    /// Returns [`EVENTBUS_INVALID_ID`] without subscribing if called from within a [`FfiHandler`].
+    pub subscribe: unsafe extern "C" fn(
+        world: *mut c_void,
+        name: *const u8,
+        name_len: usize,
+        handler: FfiHandler,
+        user_data: *mut c_void,
+    ) -> u64,
+    /// Unsubscribes a handler by the ID returned when subscribing it, returning `false` if it
+    /// wasn't subscribed. Unsubscriptions from within a [`FfiHandler`] are applied once it
+    /// returns, and return `true`.
+    pub unsubscribe:
+        unsafe extern "C" fn(world: *mut c_void, name: *const u8, name_len: usize, id: u64) -> bool,
+}
+
+static VTABLE: EventBusVTable = EventBusVTable {
+    version: EVENTBUS_ABI_VERSION,
+    size: size_of::<EventBusVTable>(),
+    post,
+    subscribe,
+    unsubscribe,
+};
+
+/// Returns the [`EventBusVTable`] if the requested `version` is supported, or null otherwise.
+#[no_mangle]
+pub extern "C" fn bevy_eventbus_vtable(version: u32) -> *const EventBusVTable {
+    if version == EVENTBUS_ABI_VERSION {
+        &VTABLE
+    } else {
+        std::ptr::null()
+    }
+}
+
+/// The `user_data` of a [`FfiHandler`], which the plugin is responsible for sharing safely.
+struct UserData(*mut c_void);
+
+// SAFETY: The bus never dereferences the pointer, it only passes it back to the plugin's handler.
+// Subscribing a handler requires it to be callable from any thread that the host dispatches from,
+// with the same `user_data`, so moving the pointer to such a thread is sound.
+unsafe impl Send for UserData {}
+// SAFETY: Handlers only access the pointer through a shared reference to pass it on, and the
+// plugin synchronizes whatever it points to, as required when subscribing.
+unsafe impl Sync for UserData {}
+
+/// A call to the [`EventBusVTable`] made from within a [`FfiHandler`], applied once it returns.
+enum QueuedCall {
+    Post { name: String, payload: Vec<u8> },
+    Unsubscribe { name: String, id: u64 },
+}
+
+thread_local! {
+    /// The calls queued by the [`FfiHandler`] running on this thread, if any.
+    static QUEUED: RefCell<Option<Vec<QueuedCall>>> = const { RefCell::new(None) };
+}
+
+/// Queues the call if a [`FfiHandler`] is running on this thread, or hands it back otherwise.
+fn queue(call: QueuedCall) -> Result<(), QueuedCall> {
+    QUEUED.with_borrow_mut(|queued| match queued {
+        Some(queued) => {
+            queued.push(call);
+            Ok(())
+        }
+        None => Err(call),
+    })
+}
+
+/// Returns `true` if a [`FfiHandler`] is running on this thread.
+fn in_handler() -> bool {
+    QUEUED.with_borrow(Option::is_some)
+}
+
+/// Runs the callback of a [`FfiHandler`], returning what it returned along with the calls it
+/// queued.
+fn run_handler(callback: impl FnOnce() -> bool) -> (bool, Vec<QueuedCall>) {
+    let outer = QUEUED.replace(Some(Vec::new()));
+    let result = callback();
+    let queued = QUEUED.replace(outer).unwrap_or_default();
+    (result, queued)
+}
+
+/// Applies a call to the world.
+fn apply(world: &mut World, call: QueuedCall) -> bool {
+    match call {
+        QueuedCall::Post { name, payload } => {
+            let key = name.clone();
+            world.post_keyed(DynamicEvent { name, payload }, &key)
+        }
+        QueuedCall::Unsubscribe { name, id } => world
+            .get_resource_mut::<KeyedHandlers<DynamicEvent, String>>()
+            .and_then(|handlers| handlers.into_inner().get_mut(&name))
+            .is_some_and(|handlers| handlers.remove(HandlerId::from_raw(id)).is_some()),
+    }
+}
+
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, or be null if `len` is zero.
+unsafe fn name_of(data: *const u8, len: usize) -> String {
+    // SAFETY: Upheld by the caller.
+    String::from_utf8_lossy(unsafe { bytes(data, len) }).into_owned()
+}
+
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, or be null if `len` is zero.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        return &[];
+    }
+    // SAFETY: Upheld by the caller.
+    unsafe { slice::from_raw_parts(data, len) }
+}
+
+unsafe extern "C" fn post(
+    world: *mut c_void,
+    name: *const u8,
+    name_len: usize,
+    payload: *const u8,
+    payload_len: usize,
+) -> bool {
+    // SAFETY: The vtable requires readable strings.
+    let (name, payload) = unsafe {
+        (
+            name_of(name, name_len),
+            bytes(payload, payload_len).to_vec(),
+        )
+    };
+    let Err(call) = queue(QueuedCall::Post { name, payload }) else {
+        return false;
+    };
+    // SAFETY: The vtable requires a valid world, which is unborrowed outside of handlers.
+    apply(unsafe { &mut *world.cast::<World>() }, call)
+}
+
+unsafe extern "C" fn subscribe(
+    world: *mut c_void,
+    name: *const u8,
+    name_len: usize,
+    handler: FfiHandler,
+    user_data: *mut c_void,
+) -> u64 {
+    // SAFETY: The vtable requires a readable name.
+    let name = unsafe { name_of(name, name_len) };
+    if in_handler() {
+        warn!("Can't subscribe a handler to {name} from within a handler");
+        return EVENTBUS_INVALID_ID;
+    }
+    // SAFETY: The vtable requires a valid world, which is unborrowed outside of handlers.
+    let world = unsafe { &mut *world.cast::<World>() };
+    let user_data = UserData(user_data);
+    let config = initialize_config(
+        world,
+        move |mut event: Receive<DynamicEvent>, world: &mut World| {
+            let user_data = &user_data;
+            let (cancel, queued) = run_handler(|| {
+                // SAFETY: The plugin keeps `user_data` valid until the handler is unsubscribed.
+                unsafe {
+                    handler(
+                        user_data.0,
+                        event.name.as_ptr(),
+                        event.name.len(),
+                        event.payload.as_ptr(),
+                        event.payload.len(),
+                    )
+                }
+            });
+            if cancel {
+                event.cancel();
+            }
+            for call in queued {
+                apply(world, call);
+            }
+        },
+    );
+    world
+        .get_resource_or_insert_with(KeyedHandlers::<DynamicEvent, String>::default)
+        .get_or_insert(name)
+        .insert(config)
+        .to_raw()
+}
+
+unsafe extern "C" fn unsubscribe(
+    world: *mut c_void,
+    name: *const u8,
+    name_len: usize,
+    id: u64,
+) -> bool {
+    // SAFETY: The vtable requires a readable name.
+    let name = unsafe { name_of(name, name_len) };
+    let Err(call) = queue(QueuedCall::Unsubscribe { name, id }) else {
+        return true;
+    };
+    // SAFETY: The vtable requires a valid world, which is unborrowed outside of handlers.
+    apply(unsafe { &mut *world.cast::<World>() }, call)
+}
@@ -0,0 +1,103 @@
+use std::{
+    any::TypeId,
+    collections::HashSet,
+    fmt::{self, Debug},
+    marker::PhantomData,
+};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    observer::Trigger,
+    system::{Commands, Resource},
+    world::{OnAdd, OnInsert, OnRemove, OnReplace, World},
+};
+
+use crate::{CommandEventBus, Event, Immutable};
+
+/// Defines a bus event bridging a bevy component lifecycle trigger.
+macro_rules! lifecycle_events {
+    ($($(#[$attr:meta])* $name:ident => $trigger:ident,)*) => {$(
+        $(#[$attr])*
+        pub struct $name<T: Component>(PhantomData<fn() -> T>);
+
+        impl<T: Component> $name<T> {
+            /// Creates the event.
+            pub fn new() -> Self {
+                Self(PhantomData)
+            }
+        }
+
+        impl<T: Component> Default for $name<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T: Component> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T: Component> Copy for $name<T> {}
+
+        impl<T: Component> Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}<{}>", stringify!($name), std::any::type_name::<T>())
+            }
+        }
+
+        impl<T: Component> Event for $name<T> {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = Entity;
+        }
+    )*
+
+        /// Adds the observers which post the lifecycle events of component `T`.
+        fn observe_lifecycle<T: Component>(world: &mut World) {
+            $(world.add_observer(|trigger: Trigger<$trigger, T>, mut commands: Commands| {
+                commands.post_to($name::<T>::new(), trigger.entity());
+            });)*
+        }
+    };
+}
+
+lifecycle_events! {
+    /// [`Event`] which bridges bevy's [`OnAdd`] trigger for component `T` into the event bus,
+    /// targeted at the entity the component was added to, see
+    /// [`WorldEventBus::bridge_lifecycle`](crate::WorldEventBus::bridge_lifecycle).
+    BusOnAdd => OnAdd,
+    /// [`Event`] which bridges bevy's [`OnInsert`] trigger for component `T` into the event bus,
+    /// targeted at the entity the component was inserted into, see
+    /// [`WorldEventBus::bridge_lifecycle`](crate::WorldEventBus::bridge_lifecycle).
+    BusOnInsert => OnInsert,
+    /// [`Event`] which bridges bevy's [`OnReplace`] trigger for component `T` into the event bus,
+    /// targeted at the entity whose component was replaced or removed, see
+    /// [`WorldEventBus::bridge_lifecycle`](crate::WorldEventBus::bridge_lifecycle).
+    BusOnReplace => OnReplace,
+    /// [`Event`] which bridges bevy's [`OnRemove`] trigger for component `T` into the event bus,
+    /// targeted at the entity the component was removed from, see
+    /// [`WorldEventBus::bridge_lifecycle`](crate::WorldEventBus::bridge_lifecycle).
+    BusOnRemove => OnRemove,
+}
+
+/// [`Resource`] which tracks the components whose lifecycle triggers are bridged into the event
+/// bus, so that each of them is only bridged once.
+#[derive(Resource, Default)]
+pub(crate) struct LifecycleBridges(HashSet<TypeId>);
+
+impl LifecycleBridges {
+    /// Bridges the lifecycle triggers of component `T`, unless they already are.
+    pub(crate) fn bridge<T: Component>(world: &mut World) {
+        if !world
+            .get_resource_or_insert_with(Self::default)
+            .0
+            .insert(TypeId::of::<T>())
+        {
+            return;
+        }
+        observe_lifecycle::<T>(world);
+    }
+}
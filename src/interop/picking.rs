@@ -0,0 +1,96 @@
+use std::{fmt::Debug, ops::Deref};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventReader,
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::Commands,
+};
+use bevy_picking::{
+    events::{Click, Drag, DragEnd, DragStart, Out, Over, Pointer},
+    PickSet,
+};
+use bevy_reflect::Reflect;
+
+use crate::{CommandEventBus, Event, Immutable};
+
+/// [`Event`] which bridges a `bevy_picking` [`Pointer`] event into the event bus, targeted at the
+/// entity under the pointer.
+///
+/// Handlers can cancel the event to consume the interaction, so that lower priority handlers
+/// don't see it, e.g. UI handlers consuming a click before gameplay handlers:
+///
+/// ```rust
+/// use bevy_eventbus::{prelude::*, Picked};
+/// use bevy_picking::events::Click;
+///
+/// fn ui_click(mut event: Receive<Picked<Click>>) {
+///     // ...
+///     event.cancel();
+/// }
+///
+/// fn gameplay_click(event: Receive<Picked<Click>>) {
+///     // Only runs for clicks the UI didn't consume.
+/// }
+/// ```
+///
+/// Unlike the [`Pointer`] observers, bridged events don't bubble up the hierarchy: they are only
+/// posted to the entity that was hit.
+#[derive(Debug, Clone)]
+pub struct Picked<E: Debug + Clone + Reflect>(pub Pointer<E>);
+
+impl<E: Debug + Clone + Reflect> Event for Picked<E> {
+    type Mutability = Immutable;
+    type Cancellation = bool;
+    type Audience = Entity;
+}
+
+impl<E: Debug + Clone + Reflect> Deref for Picked<E> {
+    type Target = Pointer<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// [`Plugin`] which posts [`Picked`] events for the `bevy_picking` pointer events [`Over`],
+/// [`Out`], [`Click`], [`DragStart`], [`Drag`], and [`DragEnd`].
+///
+/// Requires `bevy_picking`'s plugins to be added to the app. The events are posted with
+/// [`Commands`] in [`PreUpdate`], after the pointer events are sent.
+#[derive(Default)]
+pub struct PickingBridgePlugin;
+
+/// [`SystemSet`] of the systems added by the [`PickingBridgePlugin`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PickingBridgeSystems;
+
+impl Plugin for PickingBridgePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (
+                bridge_pointer_events::<Over>,
+                bridge_pointer_events::<Out>,
+                bridge_pointer_events::<Click>,
+                bridge_pointer_events::<DragStart>,
+                bridge_pointer_events::<Drag>,
+                bridge_pointer_events::<DragEnd>,
+            )
+                .chain()
+                .in_set(PickingBridgeSystems)
+                .after(PickSet::Focus),
+        );
+    }
+}
+
+/// System that posts a [`Picked`] event for every [`Pointer`] event of type `E`.
+pub fn bridge_pointer_events<E: Debug + Clone + Reflect>(
+    mut events: EventReader<Pointer<E>>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        commands.post_to(Picked(event.clone()), event.target);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+    sync::Arc,
+};
+
+use bevy_ecs::{system::Commands, world::World};
+use parking_lot::Mutex;
+
+use crate::{priority::Last, Event, IntoHandlerConfig, Receive, WorldEventBus};
+
+/// Barrier which invokes a callback once one of each of several [`Event`] types has been posted,
+/// per correlation key.
+///
+/// Each awaited event type registers an internal handler that maps the event to a key of type
+/// `K`. Once every awaited event type has been observed for the same key, the callback runs with
+/// that key and the key starts over. Events observed again for a key before it completes are
+/// only counted once.
+///
+/// The internal handlers run with [`Last`] priority, so cancelled events are not observed.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_ecs::world::World;
+/// use bevy_eventbus::{join::Join, prelude::*};
+///
+/// # macro_rules! events { ($($name:ident),*) => { $(
+/// #     struct $name;
+/// #     impl BusEvent for $name {
+/// #         type Mutability = Immutable;
+/// #         type Cancellation = ();
+/// #         type Audience = ();
+/// #     }
+/// # )* } }
+/// # events!(AssetsLoaded, PlayerConnected, MapReady, MatchStart);
+/// let mut world = World::new();
+/// world.add_join(
+///     Join::new(|world: &mut World, ()| {
+///         world.post(MatchStart);
+///     })
+///     .wait_for::<AssetsLoaded>()
+///     .wait_for::<PlayerConnected>()
+///     .wait_for::<MapReady>(),
+/// );
+///
+/// world.post(AssetsLoaded);
+/// world.post(MapReady);
+/// world.post(PlayerConnected); // posts `MatchStart`
+/// ```
+pub struct Join<K> {
+    callback: JoinCallback<K>,
+    observers: Vec<Observer<K>>,
+}
+
+/// Runs when a [`Join`] completes for a key.
+type JoinCallback<K> = Box<dyn FnMut(&mut World, K) + Send>;
+
+/// Registers the internal handler for one awaited event, given the join's shared state and the
+/// index of the event.
+type Observer<K> = Box<dyn FnOnce(&mut World, Arc<JoinState<K>>, usize)>;
+
+struct JoinState<K> {
+    /// The number of awaited events.
+    len: usize,
+    /// Which awaited events have been observed, per key.
+    observed: Mutex<HashMap<K, Vec<bool>>>,
+    callback: Mutex<JoinCallback<K>>,
+}
+
+impl<K: Eq + Hash + Send + 'static> Join<K> {
+    /// Creates a new join which runs the callback every time it completes, without any awaited
+    /// events yet.
+    pub fn new(callback: impl FnMut(&mut World, K) + Send + 'static) -> Self {
+        Self {
+            callback: Box::new(callback),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Waits for [`Event`] `E`, correlated by the key returned by `key`.
+    pub fn wait_for_keyed<E: Event>(
+        mut self,
+        key: impl Fn(&E) -> K + Send + Sync + 'static,
+    ) -> Self {
+        self.observers.push(Box::new(move |world, state, index| {
+            let handler = move |event: Receive<E>, mut commands: Commands| {
+                let Some(key) = state.observe(index, key(event.event())) else {
+                    return;
+                };
+                let state = state.clone();
+                commands.queue(move |world: &mut World| (state.callback.lock())(world, key));
+            };
+            world.add_handler(handler.priority(Last));
+        }));
+        self
+    }
+
+    /// Registers the internal handlers of the join.
+    pub(crate) fn register(self, world: &mut World) {
+        let state = Arc::new(JoinState {
+            len: self.observers.len(),
+            observed: Mutex::new(HashMap::new()),
+            callback: Mutex::new(self.callback),
+        });
+        for (index, observer) in self.observers.into_iter().enumerate() {
+            observer(world, state.clone(), index);
+        }
+    }
+}
+
+impl Join<()> {
+    /// Waits for [`Event`] `E`, regardless of its contents.
+    pub fn wait_for<E: Event>(self) -> Self {
+        self.wait_for_keyed::<E>(|_| ())
+    }
+}
+
+impl<K: Eq + Hash> JoinState<K> {
+    /// Marks the awaited event at `index` as observed for the key, returning the key if the join
+    /// completed.
+    fn observe(&self, index: usize, key: K) -> Option<K> {
+        let mut observed = self.observed.lock();
+        let mut seen = match observed.entry(key) {
+            Entry::Occupied(entry) => entry,
+            Entry::Vacant(entry) => entry.insert_entry(vec![false; self.len]),
+        };
+
+        seen.get_mut()[index] = true;
+        seen.get()
+            .iter()
+            .all(|&seen| seen)
+            .then(|| seen.remove_entry().0)
+    }
+}
@@ -1,30 +1,122 @@
-mod app;
-mod config;
-mod event;
-mod input;
-mod registry;
-mod system;
-mod world;
+/// [`App`](bevy_app::App) integration: the [`AppEventBus`] extension trait, the
+/// [`EventBusPlugin`], and the [`ShutdownPlugin`].
+#[cfg(feature = "bevy_app")]
+pub mod app;
+#[cfg(not(feature = "bevy_app"))]
+mod clock;
+/// Handler configuration: [`HandlerConfig`], [`IntoHandlerConfig`], and
+/// [priorities](config::priority).
+pub mod config;
+/// Diagnostics about how the event bus interacts with the rest of the app, and how events cause
+/// each other.
+pub mod diagnostic;
+/// Registering handlers and posting events: the [`WorldEventBus`] and [`CommandEventBus`]
+/// extension traits, the [`HandlerRegistry`], the [`EventQueue`], and the [`Receive`] handler
+/// input.
+pub mod dispatch;
+/// The [`Event`] trait and its configuration.
+pub mod event;
+/// Opt-in per-type history of posted events: the [`EventHistory`], and replaying it with the
+/// [`EventReplayer`].
+pub mod history;
+/// Adapters for writing code that works with both this crate and bevy's own events, and bridges
+/// from other bevy crates into the event bus.
+pub mod interop;
+/// Combinators that wait for several events: the [`Join`](join::Join).
+pub mod join;
+/// A shared notification channel for tools and plugins: the [`Notify`](notify::Notify) event and
+/// the [`NotifyPlugin`](notify::NotifyPlugin).
+pub mod notify;
+mod owner;
+/// Per-frame maintenance of the event bus for a bare [`World`](bevy_ecs::world::World): the
+/// [`EventBusWorldSetup`] and the [`EventBusSettings`].
+pub mod setup;
 
+/// Re-exports used by the crate's macros.
+#[doc(hidden)]
+pub mod __macro {
+    #[cfg(feature = "bevy_app")]
+    pub use bevy_app::App;
+    pub use bevy_ecs::system::Commands;
+}
+
+#[cfg(feature = "bevy_app")]
 pub use app::*;
+#[cfg(not(feature = "bevy_app"))]
+pub use clock::*;
 pub use config::*;
+pub use diagnostic::*;
+pub use dispatch::*;
 pub use event::*;
-pub use input::*;
-pub use registry::*;
-pub use system::*;
-pub use world::*;
+pub use history::*;
+pub use interop::*;
+pub use setup::*;
+
+#[cfg(feature = "bevy_app")]
+use bevy_core::FrameCount;
+#[cfg(feature = "bevy_app")]
+use bevy_time::Time;
+
+/// Commonly used items, for glob importing.
+///
+/// The [`Event`] trait is exported as [`BusEvent`](prelude::BusEvent), and priorities are exported
+/// through the [`priority`] module, so that this prelude can be glob imported next to bevy's
+/// own prelude without name collisions.
+pub mod prelude {
+    #[cfg(feature = "bevy_app")]
+    pub use crate::app::{AppEventBus, EventBusPlugin};
+    pub use crate::{
+        config::{priority, HandlerPriority, HandlerSet, HandlerSetConfig, IntoHandlerConfig},
+        dispatch::{CommandEventBus, Poster, Receive, WorldEventBus},
+        event::{
+            tick::Tick, Cancellable, CancellableWith, Cancellation, Event as BusEvent, Immutable,
+            Multicast, Mutable, PostOutcomeExt, Unicast,
+        },
+        interop::GenericEmitter,
+    };
+}
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        any::TypeId,
+        ops::RangeInclusive,
+        time::{Duration, Instant},
+    };
+
+    #[cfg(feature = "bevy_app")]
+    use bevy_app::{App, AppExit, Plugin, PreUpdate};
     use bevy_ecs::{
+        component::Component,
         entity::Entity,
-        system::{Commands, ResMut, Resource},
+        schedule::SystemSet,
+        system::{
+            Commands, IntoSystem, Local, NonSend, Query, Res, ResMut, Resource, RunSystemOnce,
+        },
         world::World,
     };
 
     use crate::{
-        CommandEventBus, Early, Event, First, Immutable, IntoHandlerConfig, Last, Mutable, Receive,
-        WorldEventBus,
+        coroutine, join::Join, post_tick, Audience, AudienceNormalization, BusOnAdd, BusOnInsert,
+        BusOnRemove, BusOnReplace, BusPair, BusRng, BusTime, BusTraceConfig, CancellationView,
+        CircuitBreaker, CommandEventBus, DeliveryTracker, DeprecatedEvent, DispatchHarness,
+        DispatchStrategy, DispatchTrace, Dispatcher, Early, EntitySequencer, Event, EventAlias,
+        EventBusSettings, EventBusStats, EventBusWorldSetup, EventCatalog, EventCausality,
+        EventContext, EventExpired, EventInbox, EventInfo, EventMeta, EventQueue, EventReplayer,
+        FeatureFlags, First, FixedCapacityStorage, FrameCount, GenericEmitter, HandlerAdded,
+        HandlerPriority, HandlerRegistry, HandlerSetConfig, HandlerTripped, Immutable,
+        IndexedStorage, IntoHandlerConfig, KeyedHandlers, Last, Late, LazyAudience, LoadRequested,
+        MainThread, Mirrored, Mutable, Normal, OwnedBy, ParkedEvents, PerTargetCancellation,
+        Phased, PostId, PostOutcomeExt, Poster, Pre, Progress, ProgressAborted, ProgressCompleted,
+        ProgressTracker, Receive, Replay, Resettable, Resimulating, SaveBlob, SaveRequested,
+        SetFlag, StreamHasher, TargetThrottle, Team, TickBatch, Time, Transactional, WorldEventBus,
+        Yield,
+    };
+    #[cfg(feature = "bevy_app")]
+    use crate::{
+        AppEventBus, EventBusPlugin, EventFrequency, EventHistory, EventStability,
+        EventsBridgePlugin, Post, SavingState, Shutdown, ShutdownComplete, ShutdownPlugin,
+        ShutdownRequested, TickLagOrdering, TickLagReport,
     };
 
     #[derive(Resource, Default)]
@@ -155,4 +247,4011 @@ mod tests {
         let mut world = World::new();
         world.add_handler(system);
     }
+
+    #[test]
+    fn remove_handler() {
+        fn system1(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(0);
+        }
+
+        fn system2(_event: Receive<Bar>) {
+            unreachable!();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(system1);
+        world.add_handler(system2.priority(Last));
+
+        let ids = world.handler_ids::<Bar>();
+        assert_eq!(ids.len(), 2);
+        assert!(world.remove_handler(ids[1]));
+        assert!(!world.remove_handler(ids[1]));
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn app_handler_management() {
+        fn increment(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn double(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 *= 2;
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Counter>()
+            .add_handler(double)
+            .add_handler(increment)
+            .add_handler(|_event: Receive<Bar>| unreachable!());
+        let ids = app.handler_ids::<Bar>();
+        assert_eq!(ids.len(), 3);
+
+        app.remove_handler(ids[2])
+            .configure_handler(ids[1], |config| config.priority(First));
+        assert_eq!(app.handler_ids::<Bar>(), [ids[1], ids[0]]);
+
+        let world = app.world_mut();
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn remove_plugin_handlers() {
+        struct MyPlugin;
+
+        impl Plugin for MyPlugin {
+            fn build(&self, app: &mut App) {
+                app.add_handler_scoped_to_plugin(self, |_event: Receive<Bar>| unreachable!())
+                    .add_handler_scoped_to_plugin(self, |_event: Receive<Baz>| unreachable!());
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MyPlugin);
+        assert_eq!(app.handler_ids::<Bar>().len(), 1);
+
+        app.remove_plugin_handlers::<MyPlugin>();
+        assert!(app.handler_ids::<Bar>().is_empty());
+        assert!(app.handler_ids::<Baz>().is_empty());
+
+        app.world_mut().post(Bar);
+        app.world_mut().post(Baz);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn remove_plugin_handlers_from_context() {
+        struct MyPlugin;
+
+        impl Plugin for MyPlugin {
+            fn build(&self, app: &mut App) {
+                app.add_handler(|_event: Receive<Bar>| unreachable!());
+            }
+        }
+
+        let mut app = App::new();
+        app.add_handler(|_event: Receive<Bar>| {});
+        app.add_plugin_with_handlers(MyPlugin);
+        app.add_handler(|_event: Receive<Bar>| {});
+        assert_eq!(app.handler_ids::<Bar>().len(), 3);
+
+        assert_eq!(app.world_mut().remove_plugin_handlers::<MyPlugin>(), 1);
+        assert_eq!(app.handler_ids::<Bar>().len(), 2);
+
+        let id = app.add_handler_with_id(|_event: Receive<Bar>| unreachable!());
+        assert!(app.world_mut().remove_handler(id));
+        assert_eq!(app.handler_ids::<Bar>().len(), 2);
+
+        app.world_mut().post(Bar);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn export_handlers_across_clear() {
+        use bevy_ecs::schedule::Schedules;
+
+        use crate::tick::{Tick, TickSchedule};
+
+        #[derive(Clone)]
+        struct Changed;
+
+        impl Event for Changed {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        struct MyPlugin;
+
+        impl Plugin for MyPlugin {
+            fn build(&self, app: &mut App) {
+                app.add_handler(|_event: Receive<Baz>| {});
+            }
+        }
+
+        fn first(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(0);
+        }
+
+        fn second(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(1);
+        }
+
+        let mut app = App::new();
+        app.add_plugin_with_handlers(MyPlugin);
+        let world = app.world_mut();
+        world.insert_resource(EventBusSettings {
+            compile_tick: true,
+            ..Default::default()
+        });
+        world.add_handler(second);
+        world.add_handler(first.priority(Early));
+        world.add_keyed_handler(0, |_: Receive<Baz>, mut counter: ResMut<Counter>| {
+            counter.0 += 10;
+        });
+        world.enable_history::<Changed>(2);
+        world.track_delivery::<Changed>();
+        let changed = world.add_handler(|_event: Receive<Changed>| {});
+        world.add_handler(|_: Receive<Tick>, mut counter: ResMut<Counter>| counter.0 += 100);
+        world.init_resource::<Counter>();
+        world.post(Changed);
+        post_tick(world);
+
+        let handlers = world.export_handlers();
+        world.clear_all();
+        world.install_handlers(handlers);
+        world.insert_resource(EventBusSettings {
+            compile_tick: true,
+            ..Default::default()
+        });
+        world.init_resource::<Counter>();
+
+        world.post(Bar);
+        world.post_keyed(Baz, &0);
+        assert_eq!(world.resource::<Counter>().0, 12);
+        assert!(world
+            .resource::<DeliveryTracker<Changed>>()
+            .last_delivered(changed)
+            .is_some());
+        assert!(world.resource::<Schedules>().contains(TickSchedule));
+        post_tick(world);
+        assert_eq!(world.resource::<Counter>().0, 112);
+        assert_eq!(world.remove_plugin_handlers::<MyPlugin>(), 1);
+    }
+
+    #[test]
+    fn queue_round_robin() {
+        #[derive(Resource, Default)]
+        struct Order(Vec<&'static str>);
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+        world.add_handler(|_event: Receive<Bar>, mut order: ResMut<Order>| order.0.push("bar"));
+        world.add_handler(|_event: Receive<Baz>, mut order: ResMut<Order>| order.0.push("baz"));
+
+        for _ in 0..3 {
+            world.enqueue(Bar);
+        }
+        world.enqueue(Baz);
+
+        assert_eq!(world.flush_event_queue(Some(2)), 2);
+        assert_eq!(world.resource::<Order>().0, ["bar", "baz"]);
+
+        let queue = world.resource::<EventQueue>();
+        assert_eq!(queue.pending::<Bar>(), 2);
+        assert_eq!(queue.starvation::<Bar>(), 1);
+        assert_eq!(queue.starvation::<Baz>(), 0);
+
+        assert_eq!(world.flush_event_queue(None), 2);
+        assert!(world.resource::<EventQueue>().is_empty());
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn tick_lag_report() {
+        #[derive(Resource, Default)]
+        struct Health(i32);
+
+        fn read_health(_health: Res<Health>) {}
+
+        fn write_health(mut health: ResMut<Health>) {
+            health.0 += 1;
+        }
+
+        let mut app = App::new();
+        app.add_plugins(EventBusPlugin)
+            .init_resource::<Health>()
+            .add_systems(PreUpdate, read_health)
+            .add_handler(write_health);
+        app.update();
+
+        let report = app.world().resource::<TickLagReport>();
+        assert_eq!(report.hazards.len(), 1);
+        assert_eq!(report.hazards[0].ordering, TickLagOrdering::Before);
+        assert_eq!(app.world().resource::<Health>().0, 1);
+    }
+
+    #[test]
+    fn post_tracked() {
+        #[derive(Clone, PartialEq)]
+        struct Damage(i32);
+
+        impl Event for Damage {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Mutable;
+        }
+
+        fn halve(mut event: Receive<Damage>) {
+            event.0 /= 2;
+        }
+
+        fn inspect(_event: Receive<Damage>) {}
+
+        fn zero(mut event: Receive<Damage>) {
+            event.0 = 0;
+        }
+
+        let mut world = World::new();
+        world.add_handler(halve.priority(First));
+        world.add_handler(inspect);
+        world.add_handler(zero.priority(Last));
+
+        let (damage, report) = world.build_post(Damage(10)).tracked();
+        assert_eq!(damage.0, 0);
+
+        let ids = world.handler_ids::<Damage>();
+        let mutated_by = report.mutated_by.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(mutated_by, [ids[0], ids[2]]);
+
+        let (damage, report) = world.build_post(Damage(10)).excluding([ids[2]]).tracked();
+        assert_eq!(damage.0, 5);
+        assert_eq!(report.mutated_by.len(), 1);
+
+        // A paused post can't be deferred, as the modified event is returned: it is handed back
+        // as is instead.
+        world.pause_event_bus();
+        let (damage, report) = world.build_post(Damage(10)).tracked();
+        assert_eq!(damage.0, 10);
+        assert!(report.mutated_by.is_empty());
+        world.resume_event_bus();
+    }
+
+    #[test]
+    fn generic_emitter() {
+        fn emit_twice<W: GenericEmitter<Baz>>(mut writer: W) {
+            writer.emit_batch([Baz, Baz]);
+        }
+
+        fn system(mut poster: Poster<Baz>) {
+            emit_twice(&mut poster);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(|_event: Receive<Baz>, mut counter: ResMut<Counter>| counter.0 += 1);
+        world.run_system_once(system).unwrap();
+
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn handler_sets() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        enum Phase {
+            Validate,
+            Apply,
+            Disabled,
+            Conditional,
+        }
+
+        #[derive(Resource)]
+        struct Active(bool);
+
+        fn apply(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(1);
+        }
+
+        fn validate(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(0);
+        }
+
+        fn conditional(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(2);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(Active(false));
+        world.add_handler(conditional.in_set(Phase::Conditional));
+        world.add_handler(apply.in_set(Phase::Apply));
+        world.add_handler(validate.in_set(Phase::Validate));
+        world.add_handler((|_event: Receive<Bar>| unreachable!()).in_set(Phase::Disabled));
+        world.configure_handler_set::<Bar>(
+            HandlerSetConfig::new(Phase::Validate).before(Phase::Apply),
+        );
+        world.configure_handler_set::<Bar>(HandlerSetConfig::new(Phase::Disabled).enabled(false));
+        world.configure_handler_set::<Bar>(
+            HandlerSetConfig::new(Phase::Conditional)
+                .priority(Last)
+                .run_if(|active: Res<Active>| active.0),
+        );
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        world.resource_mut::<Counter>().0 = 0;
+        world.resource_mut::<Active>().0 = true;
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 3);
+    }
+
+    #[test]
+    fn event_alias() {
+        struct LegacyFoo;
+
+        impl Event for LegacyFoo {
+            type Cancellation = bool;
+            type Audience = Entity;
+            type Mutability = Immutable;
+        }
+
+        fn cancel_self(mut event: Receive<Foo>) {
+            event.cancel();
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world.add_handler(|_event: Receive<LegacyFoo>| unreachable!());
+        world.add_handler(cancel_self);
+        world.alias_event(EventAlias::new(|_: &LegacyFoo| Foo, |_: Foo| LegacyFoo));
+
+        assert!(world.post_to(LegacyFoo, entity));
+    }
+
+    #[test]
+    fn deprecated_event() {
+        struct LegacyBar(i32);
+
+        impl Event for LegacyBar {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(
+            |mut event: Receive<LegacyBar>, mut counter: ResMut<Counter>| {
+                counter.0 += event.0;
+                if event.0 == 0 {
+                    event.cancel();
+                }
+            },
+        );
+        world.register_deprecated_event(|_: &LegacyBar| Bar);
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 10);
+
+        assert!(!world.post(LegacyBar(1)));
+        assert!(world.post(LegacyBar(0)));
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 21);
+
+        let report = world.build_post(LegacyBar(0)).reported();
+        assert!(report.cancellation);
+        let ids = world.handler_ids::<LegacyBar>();
+        assert_eq!(report.cancelled_by.map(|by| by.id), Some(ids[0]));
+    }
+
+    #[test]
+    fn deprecated_event_warn_interval() {
+        use crate::dispatch::WARN_INTERVAL;
+
+        const MOMENT: Duration = Duration::from_secs(1);
+
+        let deprecated = DeprecatedEvent::<Baz, Bar>::new(|_| Bar);
+        let start = Instant::now();
+        assert!(deprecated.warn_at("Posted", start));
+        assert!(!deprecated.warn_at("Posted", start + WARN_INTERVAL - MOMENT));
+        assert!(deprecated.warn_at("Posted", start + WARN_INTERVAL));
+        assert!(!deprecated.warn_at("Posted", start + WARN_INTERVAL + MOMENT));
+        assert!(deprecated.warn_at("Posted", start + WARN_INTERVAL * 2));
+    }
+
+    #[test]
+    fn event_history() {
+        #[derive(Clone)]
+        struct Score(u32);
+
+        impl Event for Score {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        let mut world = World::new();
+        world.post(Score(0));
+        assert!(world.event_history::<Score>().is_none());
+
+        world.enable_history::<Score>(2);
+        world.init_resource::<Time>();
+        world.post(Score(1));
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        world.post(Score(2));
+        world.post(Score(3));
+
+        let history = world.event_history::<Score>().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| entry.event.0)
+                .collect::<Vec<_>>(),
+            [2, 3]
+        );
+        assert_eq!(history.since(Duration::from_secs(1)).count(), 2);
+        assert_eq!(history.since(Duration::from_secs(2)).count(), 0);
+    }
+
+    #[test]
+    fn replay_window() {
+        #[derive(Clone)]
+        struct Hit(u32);
+
+        impl Event for Hit {
+            type Cancellation = ();
+            type Audience = Entity;
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource, Default)]
+        struct Replayed(Vec<u32>);
+
+        fn record(event: Receive<Replay<Hit>>, mut replayed: ResMut<Replayed>) {
+            replayed.0.push(event.event.0);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Replayed>();
+        world.init_resource::<Time>();
+        world.enable_history::<Hit>(8);
+        world.add_handler(record);
+        world.add_handler(|_event: Receive<Hit>| {});
+
+        let victim = world.spawn_empty().id();
+        let bystander = world.spawn_empty().id();
+        world.post_to(Hit(1), victim);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(5));
+        world.post_to(Hit(2), victim);
+        world.post_to(Hit(3), bystander);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        world.post_to(Hit(4), victim);
+
+        assert_eq!(
+            world.replay_window::<Hit>(victim, Duration::from_secs(3)),
+            2
+        );
+        world.resource_mut::<EventReplayer>().speed = 0.5;
+
+        EventReplayer::advance(&mut world, Duration::ZERO);
+        assert_eq!(world.resource::<Replayed>().0, [2]);
+        EventReplayer::advance(&mut world, Duration::from_secs(1));
+        assert_eq!(world.resource::<Replayed>().0, [2]);
+        EventReplayer::advance(&mut world, Duration::from_secs(1));
+        assert_eq!(world.resource::<Replayed>().0, [2, 4]);
+        assert!(!world.resource::<EventReplayer>().is_replaying());
+    }
+
+    #[test]
+    fn deterministic_params() {
+        #[derive(Clone)]
+        struct Hit;
+
+        impl Event for Hit {
+            type Cancellation = ();
+            type Audience = Entity;
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource, Default)]
+        struct Rolls(Vec<(u64, Duration)>);
+
+        let mut world = World::new();
+        world.init_resource::<Rolls>();
+        world.init_resource::<Time>();
+        world.enable_history::<Hit>(8);
+        world.add_handler(
+            |_event: Receive<Hit>, mut rng: BusRng, time: BusTime, mut rolls: ResMut<Rolls>| {
+                rolls.0.push((rng.range(0..1000), time.elapsed()));
+            },
+        );
+        world.add_handler(
+            |_event: Receive<Replay<Hit>>,
+             mut rng: BusRng,
+             time: BusTime,
+             mut rolls: ResMut<Rolls>| {
+                rolls.0.push((rng.range(0..1000), time.elapsed()));
+            },
+        );
+
+        let target = world.spawn_empty().id();
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        world.post_to(Hit, target);
+        world.post_to(Hit, target);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(5));
+
+        assert_eq!(
+            world.replay_window::<Hit>(target, Duration::from_secs(10)),
+            2
+        );
+        EventReplayer::advance(&mut world, Duration::ZERO);
+
+        let rolls = &world.resource::<Rolls>().0;
+        assert_eq!(rolls.len(), 4);
+        assert_ne!(rolls[0], rolls[1]);
+        assert_eq!(rolls[0], rolls[2]);
+        assert_eq!(rolls[1], rolls[3]);
+        assert_eq!(rolls[2].1, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn join() {
+        struct Loaded(u32);
+
+        impl Event for Loaded {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        struct Connected(u32);
+
+        impl Event for Connected {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource, Default)]
+        struct Started(Vec<u32>);
+
+        let mut world = World::new();
+        world.init_resource::<Started>();
+        world.add_handler(|mut event: Receive<Connected>| {
+            if event.0 == 0 {
+                event.cancel();
+            }
+        });
+        world.add_join(
+            Join::new(|world: &mut World, lobby| world.resource_mut::<Started>().0.push(lobby))
+                .wait_for_keyed(|event: &Loaded| event.0)
+                .wait_for_keyed(|event: &Connected| event.0),
+        );
+
+        world.post(Loaded(1));
+        world.post(Loaded(2));
+        world.post(Loaded(1));
+        world.post(Connected(2));
+        world.post(Loaded(0));
+        world.post(Connected(0));
+        assert_eq!(world.resource::<Started>().0, [2]);
+
+        world.post(Connected(1));
+        world.post(Connected(1));
+        assert_eq!(world.resource::<Started>().0, [2, 1]);
+    }
+
+    #[test]
+    fn correlation_ids() {
+        #[derive(Resource, Default)]
+        struct Seen(Vec<EventMeta>);
+
+        fn forward(_event: Receive<Bar>, mut commands: Commands) {
+            commands.post(Baz);
+            commands.enqueue(Baz);
+        }
+
+        fn record(_event: Receive<Baz>, context: Res<EventContext>, mut seen: ResMut<Seen>) {
+            seen.0.push(*context.current().unwrap());
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        world.add_handler(forward);
+        world.add_handler(record);
+
+        world.post(Bar);
+        world.post(Bar);
+        world.post(Baz);
+        world.flush_event_queue(None);
+
+        let seen = world.resource::<Seen>().0.iter();
+        assert_eq!(
+            seen.clone()
+                .map(|meta| meta.correlation.0)
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 0, 1]
+        );
+        assert_eq!(
+            seen.map(|meta| meta.depth).collect::<Vec<_>>(),
+            [1, 1, 0, 1, 1]
+        );
+        assert!(world.resource::<EventContext>().current().is_none());
+    }
+
+    #[test]
+    fn event_causality() {
+        fn forward(_event: Receive<Bar>, mut commands: Commands) {
+            commands.post(Baz);
+            commands.post(Baz);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<EventCausality>();
+        world.add_handler(forward);
+
+        world.post(Bar);
+        world.post(Baz);
+
+        let causality = world.resource::<EventCausality>();
+        assert_eq!(causality.len(), 4);
+        let root = causality.nodes().next().unwrap().meta.id;
+        assert_eq!(causality.descendants(root).len(), 2);
+
+        let json = causality.to_json(..);
+        assert!(json.contains("\"edges\":[{\"from\":0,\"to\":1},{\"from\":0,\"to\":2}]"));
+        let dot = causality.to_graphviz(..);
+        assert!(dot.contains("0 -> 2;"));
+        assert!(!dot.contains("-> 3"));
+    }
+
+    #[test]
+    fn handler_fn() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+
+        let mut seen = 0;
+        world.add_handler_fn(move |mut event: Receive<Bar>, world: &mut World| {
+            seen += 1;
+            world.resource_mut::<Counter>().0 = seen;
+            if seen == 2 {
+                event.cancel();
+            }
+        });
+
+        assert!(!world.post(Bar));
+        assert!(world.post(Bar));
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn system_id_handler() {
+        fn system(mut event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+            event.cancel();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(system);
+        world.add_handler(id);
+
+        assert!(world.post(Bar));
+        assert!(world.post(Bar));
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        world.unregister_system(id).unwrap();
+        assert!(!world.post(Bar));
+    }
+
+    #[test]
+    fn main_thread_handler() {
+        fn pinned(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 10;
+        }
+
+        fn unpinned(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn non_send(_event: Receive<Bar>, _marker: NonSend<Marker>, mut counter: ResMut<Counter>) {
+            counter.0 += 100;
+        }
+
+        struct Marker;
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(MainThread::current());
+        world.insert_non_send_resource(Marker);
+        world.add_handler(pinned.main_thread_only());
+        world.add_handler(unpinned);
+        world.add_handler(non_send);
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 111);
+
+        world.remove_non_send_resource::<Marker>();
+        world.resource_mut::<Counter>().0 = 0;
+        std::thread::scope(|scope| {
+            scope.spawn(|| world.post(Bar));
+        });
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn pause_event_bus() {
+        fn bar(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn baz(_event: Receive<Baz>, mut counter: ResMut<Counter>) {
+            counter.0 += 10;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(bar);
+        world.add_handler(baz);
+
+        world.pause_events::<Bar>();
+        world.post(Bar);
+        world.enqueue(Bar);
+        world.flush_event_queue(None);
+        world.post(Baz);
+        assert_eq!(world.resource::<Counter>().0, 10);
+
+        world.pause_event_bus();
+        world.post(Baz);
+        assert!(!world.post_mut(&mut Bar));
+        world.post_ref(&Baz);
+        assert_eq!(world.resource::<Counter>().0, 10);
+
+        assert_eq!(world.resume_event_bus(), 3);
+        assert_eq!(world.resource::<Counter>().0, 22);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 23);
+    }
+
+    #[test]
+    fn pause_tagged_events() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1);
+        world.add_handler(|_: Receive<Baz>, mut counter: ResMut<Counter>| counter.0 += 10);
+        world.register_bus_event::<Baz>(EventInfo::new::<Baz>("A baz.").tag("gameplay"));
+
+        world.pause_tagged_events("gameplay");
+        world.post(Bar);
+        world.post(Baz);
+        world.post_ref(&Baz);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        assert_eq!(world.resume_event_bus(), 1);
+        assert_eq!(world.resource::<Counter>().0, 11);
+    }
+
+    #[test]
+    #[cfg(all(feature = "bevy_asset", feature = "bevy_reflect"))]
+    fn asset_bridge() {
+        use bevy_app::Last;
+        use bevy_asset::{Asset, AssetEvent, AssetEvents, Assets};
+        use bevy_ecs::{event::EventReader, schedule::IntoSystemConfigs};
+        use bevy_reflect::TypePath;
+
+        use crate::{AssetBridgePlugin, AssetLoaded};
+
+        #[derive(Asset, TypePath, Clone)]
+        struct Sprite(u32);
+
+        #[derive(Resource, Default)]
+        struct Observed(Vec<AssetEvent<Sprite>>);
+
+        fn observe(mut events: EventReader<AssetEvent<Sprite>>, mut observed: ResMut<Observed>) {
+            observed.0.extend(events.read().copied());
+        }
+
+        fn double(mut event: Receive<AssetLoaded<Sprite>>, mut counter: ResMut<Counter>) {
+            event.asset.0 *= 2;
+            counter.0 += 1;
+        }
+
+        // `in_set` is ambiguous with the one of handlers.
+        let asset_events = IntoSystemConfigs::in_set(Assets::<Sprite>::asset_events, AssetEvents);
+        let mut app = App::new();
+        app.init_resource::<Counter>()
+            .init_resource::<Observed>()
+            .init_resource::<Assets<Sprite>>()
+            .add_event::<AssetEvent<Sprite>>()
+            .add_systems(Last, asset_events)
+            .add_systems(PreUpdate, observe)
+            .add_plugins(AssetBridgePlugin::<Sprite>::default())
+            .add_handler(double);
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<Sprite>>()
+            .add(Sprite(1));
+        let id = handle.id();
+        let sprite = |app: &App| app.world().resource::<Assets<Sprite>>().get(id).unwrap().0;
+        for _ in 0..2 {
+            app.world_mut()
+                .send_event(AssetEvent::LoadedWithDependencies { id });
+        }
+        app.update();
+        assert_eq!(sprite(&app), 2);
+
+        app.world_mut()
+            .resource_mut::<Assets<Sprite>>()
+            .get_mut(id)
+            .unwrap()
+            .0 = 5;
+        app.update();
+        assert_eq!(sprite(&app), 5);
+
+        app.world_mut().resource_mut::<Assets<Sprite>>().remove(id);
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 1);
+
+        let observed = &app.world().resource::<Observed>().0;
+        let count = |expected: AssetEvent<Sprite>| {
+            observed.iter().filter(|&&event| event == expected).count()
+        };
+        assert_eq!(count(AssetEvent::Added { id }), 1);
+        assert_eq!(count(AssetEvent::LoadedWithDependencies { id }), 2);
+        assert_eq!(count(AssetEvent::Modified { id }), 2);
+        assert_eq!(count(AssetEvent::Removed { id }), 1);
+    }
+
+    #[test]
+    fn rollback() {
+        #[derive(Clone)]
+        struct Input(i32);
+
+        impl Event for Input {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        fn simulate(event: Receive<Input>, mut counter: ResMut<Counter>) {
+            counter.0 += event.0;
+        }
+
+        fn effect(_event: Receive<Input>, mut effects: ResMut<Effects>) {
+            effects.0 += 1;
+        }
+
+        #[derive(Resource, Default)]
+        struct Effects(i32);
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.init_resource::<Effects>();
+        world.add_handler(simulate);
+        world.add_handler(effect.side_effect_only());
+        world.enable_rollback::<Input>();
+
+        world.enqueue(Input(1));
+        world.enqueue(Input(2));
+        world.enqueue(Bar);
+        let snapshot = world.resource::<EventQueue>().snapshot();
+        assert_eq!(snapshot.len(), 2);
+        world.flush_event_queue(None);
+        assert_eq!(world.resource::<Counter>().0, 3);
+        assert_eq!(world.resource::<Effects>().0, 2);
+
+        world.resource_mut::<Counter>().0 = 0;
+        world.resource_mut::<EventQueue>().restore(&snapshot);
+        assert_eq!(world.resource::<EventQueue>().len(), 2);
+        Resimulating::scope(&mut world, |world| world.flush_event_queue(None));
+        assert_eq!(world.resource::<Counter>().0, 3);
+        assert_eq!(world.resource::<Effects>().0, 2);
+        assert!(!world.resource::<Resimulating>().0);
+    }
+
+    #[test]
+    fn audience_resolvers() {
+        struct Alert;
+
+        impl Event for Alert {
+            type Cancellation = ();
+            type Audience = Vec<Entity>;
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource, Default)]
+        struct Received(Vec<Entity>);
+
+        fn alert(event: Receive<Alert>, mut received: ResMut<Received>) {
+            received.0 = event.targets().collect();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.add_handler(alert);
+
+        let player = world.spawn(Team(1)).id();
+        let ally = world.spawn(Team(1)).id();
+        world.spawn(Team(2));
+        let turret = world.spawn(OwnedBy(player)).id();
+        let bullet = world.spawn(OwnedBy(turret)).id();
+
+        world.post_to_owner_chain(Alert, bullet);
+        assert_eq!(world.resource::<Received>().0, vec![bullet, turret, player]);
+
+        world.post_to_team(Alert, player);
+        let mut team = world.resource::<Received>().0.clone();
+        team.sort();
+        assert_eq!(team, vec![player, ally]);
+    }
+
+    #[test]
+    fn required_resources() {
+        #[derive(Resource)]
+        struct Match;
+
+        fn skipped(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn removed(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 10;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(Match);
+        world.add_handler(skipped.while_resource_exists::<Match>());
+        world.add_handler(removed.until_resource_removed::<Match>());
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 11);
+
+        world.remove_resource::<Match>();
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 11);
+        assert_eq!(world.handler_ids::<Bar>().len(), 1);
+
+        world.insert_resource(Match);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 12);
+    }
+
+    #[test]
+    fn remove_handlers_where() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct MatchHandlers;
+
+        fn a(_event: Receive<Bar>) {}
+        fn b(_event: Receive<Bar>) {}
+        fn c(_event: Receive<Bar>) {}
+
+        let mut world = World::new();
+        world.add_handler(a.in_set(MatchHandlers));
+        world.add_handler(b.priority(Early));
+        world.add_handler(c.in_set(MatchHandlers).priority(Late));
+
+        assert_eq!(
+            world.remove_handlers_where::<Bar>(|config| config.is_in_set(MatchHandlers)),
+            2
+        );
+        assert_eq!(world.handler_ids::<Bar>().len(), 1);
+        assert_eq!(
+            world.remove_handlers_where::<Bar>(|config| config.name().ends_with("::c")),
+            0
+        );
+
+        #[derive(Clone)]
+        struct Changed;
+
+        impl Event for Changed {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        world.track_delivery::<Changed>();
+        let tracked = world.add_handler(|_event: Receive<Changed>| {});
+        world.post(Changed);
+        let tracker = world.resource::<DeliveryTracker<Changed>>();
+        assert!(tracker.last_delivered(tracked).is_some());
+        assert_eq!(world.remove_handlers_where::<Changed>(|_| true), 1);
+        let tracker = world.resource::<DeliveryTracker<Changed>>();
+        assert!(tracker.last_delivered(tracked).is_none());
+    }
+
+    #[test]
+    fn dispatch_hooks() {
+        struct Heal(i32);
+
+        impl Event for Heal {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Mutable;
+
+            fn before_dispatch(&mut self, _world: &mut World) {
+                self.0 = self.0.clamp(0, 100);
+            }
+
+            fn after_dispatch(&self, cancelled: &bool, world: &mut World) {
+                if !cancelled {
+                    world.resource_mut::<Counter>().0 += self.0;
+                }
+            }
+        }
+
+        fn heal(mut event: Receive<Heal>) {
+            assert!((0..=100).contains(&event.0));
+            if event.0 == 0 {
+                event.cancel();
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(heal);
+
+        assert!(!world.post(Heal(500)));
+        assert!(world.post(Heal(-5)));
+        assert_eq!(world.resource::<Counter>().0, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "reserved by")]
+    fn reserved_priorities() {
+        struct Validate;
+
+        impl Event for Validate {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+
+            fn reserved_priorities() -> &'static [RangeInclusive<i32>] {
+                &[i32::MAX / 2..=i32::MAX]
+            }
+        }
+
+        fn validate(_event: Receive<Validate>) {}
+
+        let mut world = World::new();
+        world.insert_resource(EventBusSettings {
+            deny_reserved_priorities: true,
+            ..Default::default()
+        });
+        world.add_handler(validate.priority(First));
+        world.add_handler(validate.priority(Normal));
+
+        let plugin = IntoSystem::into_system(validate).with_name("other_crate::validate");
+        world.add_handler(plugin.priority(Early));
+    }
+
+    #[test]
+    fn reserved_priorities_wrappers() {
+        struct Validate;
+
+        impl Event for Validate {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+
+            fn reserved_priorities() -> &'static [RangeInclusive<i32>] {
+                &[i32::MIN..=i32::MAX]
+            }
+        }
+
+        fn validate(_event: Receive<Validate>) {}
+
+        let mut world = World::new();
+        world.insert_resource(EventBusSettings {
+            deny_reserved_priorities: true,
+            ..Default::default()
+        });
+        let closure = world.add_handler_fn(|_event: Receive<Validate>, _world: &mut World| {});
+        let system = world.register_system(validate);
+        let one_shot = world.add_handler(system);
+
+        let registry = world.resource::<HandlerRegistry<Validate>>();
+        let name = registry.get(closure).unwrap().name();
+        assert!(
+            name.starts_with("bevy_eventbus::tests::reserved_priorities_wrappers::{{closure}}"),
+            "{name}"
+        );
+        assert!(registry.get(one_shot).unwrap().anonymous);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn shutdown_sequence() {
+        fn requested(
+            _event: Receive<ShutdownRequested>,
+            mut counter: ResMut<Counter>,
+            mut shutdown: ResMut<Shutdown>,
+        ) {
+            counter.assert_order(0);
+            shutdown.hold("flush");
+        }
+
+        fn saving(_event: Receive<SavingState>, mut counter: ResMut<Counter>) {
+            counter.assert_order(1);
+        }
+
+        fn complete(_event: Receive<ShutdownComplete>, mut counter: ResMut<Counter>) {
+            counter.assert_order(2);
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Counter>()
+            .add_plugins(ShutdownPlugin::default())
+            .add_handler(requested)
+            .add_handler(saving)
+            .add_handler(complete);
+
+        app.world_mut().send_event(AppExit::error());
+        app.update();
+        assert_eq!(app.should_exit(), None);
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 1);
+
+        app.world_mut().resource_mut::<Shutdown>().release("flush");
+        app.update();
+        assert_eq!(app.should_exit(), None);
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 3);
+        assert_eq!(app.should_exit(), Some(AppExit::error()));
+    }
+
+    #[test]
+    fn explain_handler_order() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        enum Sets {
+            Validate,
+            Apply,
+        }
+
+        fn first(_event: Receive<Bar>) {}
+        fn apply(_event: Receive<Bar>) {}
+        fn validate(_event: Receive<Bar>) {}
+        fn last(_event: Receive<Bar>) {}
+
+        let mut world = World::new();
+        world.add_handler(first.priority(Early));
+        world.add_handler(apply.in_set(Sets::Apply));
+        world.add_handler(validate.in_set(Sets::Validate));
+        world.add_handler(last);
+        world
+            .configure_handler_set::<Bar>(HandlerSetConfig::new(Sets::Apply).after(Sets::Validate));
+
+        let order = world.explain_handler_order::<Bar>();
+        let names = order
+            .iter()
+            .map(|handler| handler.name.rsplit("::").next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["first", "validate", "apply", "last"]);
+        assert!(order[1].reasons[0].contains("priority 0 is lower than"));
+        assert!(order[2].reasons[0].ends_with("because of Apply.after(Validate)"));
+        assert!(order[3].reasons[0].ends_with("because it was added later"));
+        assert!(order[0]
+            .to_string()
+            .ends_with("first (priority 1073741823)"));
+    }
+
+    #[test]
+    fn reset_handler_state() {
+        fn count(_event: Receive<Bar>, mut seen: Local<i32>, mut counter: ResMut<Counter>) {
+            *seen += 1;
+            counter.0 = *seen;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(Resettable(count));
+        world.add_handler(count.priority(Last));
+        let ids = world.handler_ids::<Bar>();
+
+        world.post(Bar);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        assert!(world.reset_handler_state(ids[0]));
+        assert!(!world.reset_handler_state(ids[1]));
+        world.configure_handler(ids[1], |config| config.priority(First));
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        let reset = world.resource_mut::<HandlerRegistry<Bar>>().reset_all();
+        assert_eq!(reset, 1);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn tick_batch() {
+        struct Hit(i32);
+
+        impl Event for Hit {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        fn hit(mut event: Receive<Hit>, mut counter: ResMut<Counter>) {
+            counter.assert_order(event.0);
+            if event.0 % 2 == 0 {
+                event.cancel();
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(hit);
+        world.insert_resource(TickBatch::<Hit>::new().with_order(|(a, _), (b, _)| a.0.cmp(&b.0)));
+
+        let mut batch = world.resource_mut::<TickBatch<Hit>>();
+        for index in [3, 1, 0, 2] {
+            batch.push(Hit(index), ());
+        }
+
+        let summary = TickBatch::<Hit>::dispatch(&mut world);
+        assert_eq!(summary.posted, 4);
+        assert_eq!(summary.cancelled, 2);
+        assert_eq!(world.resource::<Counter>().0, 4);
+        let batch = world.resource::<TickBatch<Hit>>();
+        assert!(batch.is_empty());
+        assert_eq!(batch.last_summary(), Some(summary));
+    }
+
+    #[test]
+    fn keyed_handlers() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct RoomId(u32);
+
+        fn lobby(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn arena(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 10;
+        }
+
+        fn unkeyed(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 100;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_keyed_handler(RoomId(0), lobby);
+        world.add_keyed_handler(RoomId(1), arena);
+        world.add_handler(unkeyed);
+
+        world.post_keyed(Bar, &RoomId(0));
+        assert_eq!(world.resource::<Counter>().0, 1);
+        world.post_keyed(Bar, &RoomId(1));
+        assert_eq!(world.resource::<Counter>().0, 11);
+        world.post_keyed(Bar, &RoomId(2));
+        assert_eq!(world.resource::<Counter>().0, 11);
+
+        assert_eq!(world.remove_keyed_handlers::<Bar, _>(&RoomId(0)), 1);
+        world.post_keyed(Bar, &RoomId(0));
+        assert_eq!(world.resource::<Counter>().0, 11);
+        assert_eq!(world.resource::<KeyedHandlers<Bar, RoomId>>().len(), 1);
+    }
+
+    #[test]
+    fn keyed_once_handlers() {
+        fn keyed(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn unkeyed(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 10;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        // Both are the first handler of their registry, so they share the same id.
+        world.add_keyed_handler(0u32, keyed.once());
+        world.add_handler(unkeyed);
+
+        world.post_keyed(Bar, &0u32);
+        world.post_keyed(Bar, &0u32);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert_eq!(
+            world
+                .resource::<KeyedHandlers<Bar, u32>>()
+                .get(&0)
+                .unwrap()
+                .len(),
+            0
+        );
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 11);
+    }
+
+    #[test]
+    fn event_ttl() {
+        fn bar(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        fn expired(event: Receive<EventExpired<Bar>>, mut counter: ResMut<Counter>) {
+            assert!(event.late >= Duration::ZERO);
+            counter.0 += 10;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(bar);
+        world.add_handler(expired);
+
+        world.enqueue(Bar);
+        world.enqueue_with_ttl(Bar, Duration::ZERO);
+        world.enqueue_with_ttl(Bar, Duration::from_secs(60));
+
+        assert_eq!(world.flush_event_queue(None), 2);
+        assert_eq!(world.resource::<Counter>().0, 12);
+        assert_eq!(world.resource::<EventQueue>().expired::<Bar>(), 1);
+    }
+
+    #[test]
+    fn dispatch_strategy() {
+        #[derive(Clone)]
+        struct Value(i32);
+
+        impl Event for Value {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Mutable;
+        }
+
+        fn add(mut event: Receive<Value>) {
+            event.0 += 1;
+        }
+
+        fn add_and_cancel(mut event: Receive<Value>) {
+            event.0 += 1;
+            event.cancel();
+        }
+
+        let mut world = World::new();
+        world.add_handler(add_and_cancel.priority(Early));
+        world.add_handler(add.priority(Early));
+        world.add_handler(add.priority(Late));
+
+        let mut value = Value(0);
+        assert!(world.post_mut(&mut value));
+        assert_eq!(value.0, 1);
+
+        world.set_dispatch_strategy::<Value>(Phased);
+        let mut value = Value(0);
+        assert!(world.post_mut(&mut value));
+        assert_eq!(value.0, 2);
+
+        world.set_dispatch_strategy::<Value>(Transactional);
+        let mut value = Value(0);
+        assert!(world.post_mut(&mut value));
+        assert_eq!(value.0, 0);
+    }
+
+    #[test]
+    fn handler_storage() {
+        fn a(_event: Receive<Bar>) {}
+        fn b(_event: Receive<Bar>) {}
+        fn c(_event: Receive<Bar>) {}
+
+        let mut world = World::new();
+        world.add_handler(a);
+        world.set_handler_storage::<Bar>(IndexedStorage::default());
+        world.add_handler(b.priority(Early));
+        let ids = world.handler_ids::<Bar>();
+        assert_eq!(ids.len(), 2);
+        assert!(world.remove_handler(ids[0]));
+        assert_eq!(world.handler_ids::<Bar>(), [ids[1]]);
+
+        world.set_handler_storage::<Bar>(FixedCapacityStorage::new(2));
+        world.add_handler(b);
+        let mut registry = world.resource_mut::<HandlerRegistry<Bar>>();
+        assert!(registry.try_insert(c.into_config()).is_err());
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn event_bus_stats() {
+        fn cancel(mut event: Receive<Bar>) {
+            event.cancel();
+        }
+
+        let mut world = World::new();
+        world.add_handler(cancel);
+        assert!(world.event_bus_stats().events.is_empty());
+
+        world.post(Bar);
+        world.post(Baz);
+        world.enqueue(Bar);
+        let stats = world.event_bus_stats();
+        let bar = stats.get::<Bar>().unwrap();
+        assert_eq!(bar.posts, 1);
+        assert_eq!(bar.cancellations, 1);
+        assert_eq!(bar.handler_runs, 1);
+        assert_eq!(bar.queue_depth, 1);
+        assert_eq!(stats.get::<Baz>().unwrap().posts, 1);
+        assert!(stats.to_json().contains("\"posts\":1"));
+
+        world.flush_event_queue(None);
+        let stats = world.event_bus_stats();
+        assert_eq!(stats.get::<Bar>().unwrap().posts, 1);
+        assert!(stats.get::<Baz>().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn dump_on_panic() {
+        #[derive(Debug)]
+        struct Packet(#[allow(dead_code)] Vec<u8>);
+
+        impl Event for Packet {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        fn explode(_event: Receive<Packet>) {
+            panic!("boom");
+        }
+
+        let mut world = World::new();
+        world.add_handler(explode);
+        world.dump_on_panic::<Packet>(16);
+        world.post(Packet(vec![0; 64]));
+    }
+
+    #[test]
+    fn sequence_by_target() {
+        struct Hit(u32);
+
+        impl Event for Hit {
+            type Cancellation = ();
+            type Audience = Entity;
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource, Default)]
+        struct Delivered(Vec<u32>);
+
+        fn record(event: Receive<Hit>, mut delivered: ResMut<Delivered>) {
+            delivered.0.push(event.0);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Delivered>();
+        world.add_handler(record);
+        world.sequence_by_target::<Hit>();
+
+        let victim = world.spawn_empty().id();
+        let bystander = world.spawn_empty().id();
+        world.enqueue_to(Hit(1), victim);
+        world.post_to(Hit(2), victim);
+        world.post_to(Hit(3), bystander);
+        assert_eq!(world.resource::<Delivered>().0, [3]);
+        assert_eq!(world.resource::<EntitySequencer<Hit>>().pending(victim), 2);
+
+        world.flush_event_queue(None);
+        assert_eq!(world.resource::<Delivered>().0, [3, 1, 2]);
+        assert_eq!(world.resource::<EntitySequencer<Hit>>().pending(victim), 0);
+    }
+
+    #[test]
+    fn sequence_by_target_rollback() {
+        #[derive(Clone)]
+        struct Hit(u32);
+
+        impl Event for Hit {
+            type Cancellation = ();
+            type Audience = Entity;
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource, Default)]
+        struct Delivered(Vec<u32>);
+
+        fn record(event: Receive<Hit>, mut delivered: ResMut<Delivered>) {
+            delivered.0.push(event.0);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Delivered>();
+        world.add_handler(record);
+        world.sequence_by_target::<Hit>();
+        world.enable_rollback::<Hit>();
+
+        let victim = world.spawn_empty().id();
+        world.enqueue_to(Hit(1), victim);
+        world.enqueue_to(Hit(2), victim);
+        let snapshot = world.resource::<EventQueue>().snapshot();
+        world.flush_event_queue(None);
+        assert_eq!(world.resource::<Delivered>().0, [1, 2]);
+
+        world.enqueue_to(Hit(3), victim);
+        world.resource_mut::<EventQueue>().restore(&snapshot);
+        world.flush_event_queue(None);
+        world.post_to(Hit(4), victim);
+        assert_eq!(world.resource::<Delivered>().0, [1, 2, 1, 2, 4]);
+        assert_eq!(world.resource::<EntitySequencer<Hit>>().pending(victim), 0);
+    }
+
+    #[test]
+    fn reconcile_posts() {
+        #[derive(Clone, PartialEq)]
+        struct Score(u32);
+
+        impl Event for Score {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        let mut predicted = World::new();
+        let mut authoritative = World::new();
+        for world in [&mut predicted, &mut authoritative] {
+            world.enable_history::<Score>(8);
+            world.post(Score(1));
+        }
+        predicted.post(Score(2));
+        predicted.post(Score(4));
+        authoritative.post(Score(3));
+        authoritative.post(Score(4));
+
+        let reconciliation = predicted
+            .event_history::<Score>()
+            .unwrap()
+            .reconcile(authoritative.event_history::<Score>().unwrap());
+        assert_eq!(reconciliation.confirmed, 2);
+        assert_eq!(reconciliation.mispredicted, 1);
+
+        let mut render = World::new();
+        render.init_resource::<Counter>();
+        render.add_handler(|event: Receive<Score>, mut counter: ResMut<Counter>| {
+            counter.0 += event.0 as i32;
+        });
+        assert_eq!(
+            render.reconcile_posts::<Score>(&predicted, &authoritative),
+            1
+        );
+        assert_eq!(render.resource::<Counter>().0, 3);
+    }
+
+    #[test]
+    fn handler_added() {
+        fn bar(_event: Receive<Bar>) {}
+
+        let mut world = World::new();
+        world.add_handler(
+            |event: Receive<HandlerAdded<Bar>>, mut commands: Commands| {
+                assert!(event.name.ends_with("bar"));
+                commands.init_resource::<Counter>();
+            },
+        );
+        assert!(!world.contains_resource::<Counter>());
+
+        world.add_handler(bar);
+        assert!(world.contains_resource::<Counter>());
+    }
+
+    #[test]
+    fn handler_count() {
+        let mut world = World::new();
+        assert!(!world.has_handlers::<Bar>());
+        assert!(!world.contains_resource::<HandlerRegistry<Bar>>());
+
+        world.add_handler(|_event: Receive<Bar>| {});
+        world.add_handler(|_event: Receive<Bar>| {});
+        assert!(world.has_handlers::<Bar>());
+        assert_eq!(world.handler_count::<Bar>(), 2);
+    }
+
+    #[test]
+    fn post_with() {
+        let mut world = World::new();
+        world.post_with(|| -> Bar { panic!("built without handlers") });
+
+        world.init_resource::<Counter>();
+        world.add_handler(
+            (|_event: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1)
+                .while_resource_exists::<Time>(),
+        );
+        world.post_with(|| -> Bar { panic!("built without enabled handlers") });
+
+        world.init_resource::<Time>();
+        world
+            .run_system_once(|mut commands: Commands| commands.post_with(|| Bar))
+            .unwrap();
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn event_catalog() {
+        let mut app = App::new();
+        app.register_bus_event::<Bar>(
+            EventInfo::new::<Bar>("Posted when bar happens.")
+                .frequency(EventFrequency::Frequent)
+                .stability(EventStability::Experimental),
+        );
+        app.add_handler(|_event: Receive<Bar>| {});
+
+        let world = app.world();
+        let info = world.event_info::<Bar>().unwrap();
+        assert_eq!(info.emitter, "bevy_eventbus");
+        assert_eq!(info.frequency, EventFrequency::Frequent);
+        assert_eq!(info.handler_count(world), 1);
+        assert!(world.event_info::<Baz>().is_none());
+        assert_eq!(world.resource::<EventCatalog>().len(), 1);
+    }
+
+    #[test]
+    fn stream_hasher() {
+        #[derive(Hash)]
+        struct Score(u32);
+
+        impl Event for Score {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        let hash = |scores: &[u32]| {
+            let mut world = World::new();
+            world.hash_stream::<Score>();
+            for &score in scores {
+                world.post(Score(score));
+            }
+            world.post(Bar);
+            world.resource_mut::<StreamHasher>().finish_frame(0)
+        };
+
+        assert_eq!(hash(&[1, 2]), hash(&[1, 2]));
+        assert_ne!(hash(&[1, 2]), hash(&[2, 1]));
+        assert_ne!(hash(&[1, 2]), hash(&[1, 2, 2]));
+    }
+
+    #[test]
+    fn tick_handler_conditions() {
+        use bevy_ecs::schedule::common_conditions::resource_exists;
+
+        use crate::tick::{tick_handler, Tick};
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(
+            tick_handler(|mut counter: ResMut<Counter>| counter.0 += 1)
+                .run_if(resource_exists::<Time>),
+        );
+
+        world.post(Tick);
+        assert_eq!(world.resource::<Counter>().0, 0);
+        world.init_resource::<Time>();
+        world.post(Tick);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn tick_anchor() {
+        use bevy_app::PostUpdate;
+
+        use crate::tick::{tick_handler, TickAnchor};
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Follow;
+
+        fn propagate(mut counter: ResMut<Counter>) {
+            counter.assert_order(2);
+        }
+
+        let mut app = App::new();
+        app.add_plugins(EventBusPlugin)
+            .init_resource::<Counter>()
+            .add_systems(PostUpdate, propagate)
+            .add_handler(|mut counter: ResMut<Counter>| counter.assert_order(0))
+            .add_handler(
+                tick_handler(|mut counter: ResMut<Counter>| counter.assert_order(1)).in_set(Follow),
+            )
+            .anchor_tick_set(TickAnchor::new(Follow, PostUpdate).before(propagate))
+            .anchor_tick_set(TickAnchor::new(Follow, PostUpdate));
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 3);
+
+        // Anchored handlers run as part of the schedule while the event bus is paused.
+        app.world_mut().pause_event_bus();
+        app.world_mut().resource_mut::<Counter>().0 = 1;
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 3);
+    }
+
+    #[test]
+    fn periodic_tick_handlers() {
+        use crate::tick::{tick_handler, Tick};
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.init_resource::<Time>();
+        world.add_handler(
+            tick_handler(|mut counter: ResMut<Counter>| counter.0 += 1).every_n_ticks(3),
+        );
+        world.add_handler(
+            tick_handler(|mut counter: ResMut<Counter>| counter.0 += 10)
+                .accumulate_ticks(Duration::from_secs(2)),
+        );
+
+        for _ in 0..6 {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs(1));
+            world.post(Tick);
+        }
+        assert_eq!(world.resource::<Counter>().0, 32);
+    }
+
+    #[test]
+    fn save_and_load() {
+        #[derive(Resource)]
+        struct Score(u32);
+
+        fn save_score(mut event: Receive<SaveRequested>, score: Res<Score>) {
+            event.contribute("score", score.0.to_le_bytes().to_vec());
+        }
+
+        fn load_score(event: Receive<LoadRequested>, mut score: ResMut<Score>) {
+            let bytes = event.fragment("score").unwrap();
+            score.0 = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Score(42));
+        world.add_handler(save_score);
+        world.add_handler(load_score);
+
+        let bytes = world.request_save().unwrap().to_bytes();
+        world.insert_resource(Score(0));
+        let blob = SaveBlob::from_bytes(&bytes).unwrap();
+        assert_eq!(blob.len(), 1);
+        assert!(world.request_load(blob));
+        assert_eq!(world.resource::<Score>().0, 42);
+        assert!(SaveBlob::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn defer_until() {
+        #[derive(Clone)]
+        struct Delete;
+
+        impl Event for Delete {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        #[derive(Resource)]
+        struct Confirmed(bool);
+
+        let mut world = World::new();
+        world.insert_resource(Confirmed(false));
+        world.insert_resource(Counter(0));
+        world.add_handler(
+            (|mut event: Receive<Delete>| {
+                event.defer_until(|confirmed: Res<Confirmed>| confirmed.0);
+            })
+            .priority(First),
+        );
+        world.add_handler(|_event: Receive<Delete>, mut counter: ResMut<Counter>| {
+            counter.0 += 1;
+        });
+
+        assert!(!world.post(Delete));
+        assert_eq!(world.resource::<Counter>().0, 0);
+        assert_eq!(ParkedEvents::resume(&mut world), 0);
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        world.resource_mut::<Confirmed>().0 = true;
+        assert_eq!(ParkedEvents::resume(&mut world), 1);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert!(world.resource::<ParkedEvents>().is_empty());
+    }
+
+    #[test]
+    fn defer_until_out_of_order() {
+        #[derive(Clone)]
+        struct Delete;
+
+        impl Event for Delete {
+            type Cancellation = bool;
+            type Audience = ();
+            type Mutability = Immutable;
+        }
+
+        struct Reverse;
+
+        impl DispatchStrategy<Delete> for Reverse {
+            fn dispatch(&self, dispatcher: &mut Dispatcher<'_, Delete>) {
+                for index in (0..dispatcher.len()).rev() {
+                    dispatcher.run(index);
+                }
+            }
+        }
+
+        #[derive(Resource)]
+        struct Confirmed(bool);
+
+        let mut world = World::new();
+        world.insert_resource(Confirmed(false));
+        world.insert_resource(Counter(0));
+        world.set_dispatch_strategy::<Delete>(Reverse);
+        world.add_handler(
+            (|_event: Receive<Delete>, mut counter: ResMut<Counter>| counter.0 += 1)
+                .priority(First),
+        );
+        world.add_handler(|mut event: Receive<Delete>| {
+            event.defer_until(|confirmed: Res<Confirmed>| confirmed.0);
+        });
+        world.add_handler(
+            (|_event: Receive<Delete>, mut counter: ResMut<Counter>| counter.0 += 10)
+                .priority(Last),
+        );
+
+        assert!(!world.post(Delete));
+        assert_eq!(world.resource::<Counter>().0, 10);
+
+        world.resource_mut::<Confirmed>().0 = true;
+        assert_eq!(ParkedEvents::resume(&mut world), 1);
+        assert_eq!(world.resource::<Counter>().0, 11);
+    }
+
+    #[test]
+    fn coroutine_steps() {
+        #[derive(Resource, Default)]
+        struct Steps(Vec<&'static str>);
+
+        let mut world = World::new();
+        world.init_resource::<Steps>();
+        world.insert_resource(Counter(0));
+        world.add_handler(
+            coroutine::<Bar>()
+                .step(|_event: Receive<Bar>, mut steps: ResMut<Steps>| {
+                    steps.0.push("intro");
+                    Yield::Next
+                })
+                .step(
+                    |_event: Receive<Bar>, mut steps: ResMut<Steps>, counter: Res<Counter>| {
+                        steps.0.push("wait");
+                        if counter.0 > 0 {
+                            Yield::Continue
+                        } else {
+                            Yield::Wait
+                        }
+                    },
+                )
+                .step(|_event: Receive<Bar>, mut steps: ResMut<Steps>| {
+                    steps.0.push("outro");
+                    Yield::Next
+                }),
+        );
+
+        world.post(Bar);
+        world.post(Bar);
+        world.resource_mut::<Counter>().0 = 1;
+        world.post(Bar);
+        world.post(Bar);
+        assert_eq!(
+            world.resource::<Steps>().0,
+            ["intro", "wait", "wait", "outro", "intro"]
+        );
+    }
+
+    #[test]
+    fn trace_config() {
+        let mut world = World::new();
+        world.register_bus_event::<Bar>(EventInfo::new::<Bar>("A bar.").tag("bars"));
+        let player = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        let catalog = world.get_resource::<EventCatalog>();
+
+        let config = BusTraceConfig {
+            events: vec!["bevy_eventbus::*::B?r".into()],
+            tags: vec!["bars".into()],
+            ..Default::default()
+        };
+        assert!(config.traces::<Bar>(catalog, &()));
+        assert!(!config.traces::<Baz>(catalog, &()));
+
+        let config = BusTraceConfig {
+            priorities: Some(0..=10),
+            entities: vec![player],
+            ..Default::default()
+        };
+        assert!(config.traces::<Foo>(catalog, &player));
+        assert!(!config.traces::<Foo>(catalog, &other));
+        assert!(!config.traces::<Bar>(catalog, &()));
+        assert!(config.traces_priority(10));
+        assert!(!config.traces_priority(11));
+    }
+
+    #[test]
+    fn bridge_lifecycle() {
+        #[derive(Component)]
+        struct Health;
+
+        #[derive(Resource, Default)]
+        struct Lifecycle(Vec<&'static str>);
+
+        let mut world = World::new();
+        world.init_resource::<Lifecycle>();
+        world.bridge_lifecycle::<Health>();
+        world.bridge_lifecycle::<Health>();
+        world.add_handler(
+            |_event: Receive<BusOnAdd<Health>>, mut log: ResMut<Lifecycle>| {
+                log.0.push("add");
+            },
+        );
+        world.add_handler(
+            |_event: Receive<BusOnInsert<Health>>, mut log: ResMut<Lifecycle>| {
+                log.0.push("insert");
+            },
+        );
+        world.add_handler(
+            |_event: Receive<BusOnReplace<Health>>, mut log: ResMut<Lifecycle>| {
+                log.0.push("replace");
+            },
+        );
+        world.add_handler(
+            |event: Receive<BusOnRemove<Health>>,
+             mut log: ResMut<Lifecycle>,
+             query: Query<&Health>| {
+                assert!(query.get(event.target()).is_err());
+                log.0.push("remove");
+            },
+        );
+
+        let entity = world.spawn(Health).id();
+        world.entity_mut(entity).insert(Health);
+        world.entity_mut(entity).remove::<Health>();
+        world.flush();
+        assert_eq!(
+            world.resource::<Lifecycle>().0,
+            ["add", "insert", "replace", "insert", "replace", "remove"]
+        );
+    }
+
+    #[test]
+    fn current_event_stack() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        world.add_handler_fn(|_event: Receive<Bar>, world: &mut World| {
+            assert!(world.is_dispatching::<Bar>());
+            assert!(!world.is_dispatching::<Baz>());
+            world.post(Baz);
+        });
+        world.add_handler_fn(|_event: Receive<Baz>, world: &mut World| {
+            let stack = world
+                .current_event_stack()
+                .iter()
+                .map(|meta| (meta.event, meta.event_type))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                stack,
+                [
+                    (std::any::type_name::<Bar>(), TypeId::of::<Bar>()),
+                    (std::any::type_name::<Baz>(), TypeId::of::<Baz>())
+                ]
+            );
+            if !world.is_dispatching::<Bar>() {
+                return;
+            }
+            world.resource_mut::<Counter>().0 += 1;
+        });
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert!(world.current_event_stack().is_empty());
+    }
+
+    #[test]
+    fn registration_during_dispatch() {
+        #[derive(Resource, Default)]
+        struct Runs(Vec<&'static str>);
+
+        fn late(_event: Receive<Bar>, mut runs: ResMut<Runs>) {
+            runs.0.push("late");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Runs>();
+        world.add_handler_fn(|_event: Receive<Bar>, world: &mut World| {
+            world.resource_mut::<Runs>().0.push("first");
+            let registry = world.resource::<HandlerRegistry<Bar>>();
+            if registry.len() == 2 {
+                let removed = registry.ids().nth(1).unwrap();
+                world.remove_handler(removed);
+                world.add_handler(late);
+            }
+        });
+        world.add_handler(|_event: Receive<Bar>, mut runs: ResMut<Runs>| {
+            runs.0.push("removed");
+        });
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Runs>().0, ["first", "removed"]);
+        world.resource_mut::<Runs>().0.clear();
+        world.post(Bar);
+        assert_eq!(world.resource::<Runs>().0, ["first", "late"]);
+    }
+
+    #[test]
+    fn receive_split() {
+        struct Damage(u32);
+
+        impl Event for Damage {
+            type Cancellation = bool;
+            type Audience = Entity;
+            type Mutability = Mutable;
+        }
+
+        fn absorb(amount: &mut u32, mut cancellation: CancellationView<Damage>) {
+            *amount = amount.saturating_sub(5);
+            if *amount == 0 {
+                cancellation.cancel();
+            }
+        }
+
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        world.add_handler(move |mut event: Receive<Damage>| {
+            let (damage, cancellation, audience) = event.split();
+            assert_eq!(*audience, target);
+            absorb(&mut damage.0, cancellation);
+        });
+
+        let mut damage = Damage(8);
+        assert!(!world.post_mut_to(&mut damage, target));
+        assert_eq!(damage.0, 3);
+        let mut damage = Damage(4);
+        assert!(world.post_mut_to(&mut damage, target));
+    }
+
+    #[test]
+    fn post_with_min_priority() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(
+            (|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1).priority(Pre),
+        );
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 10);
+
+        world.build_post(Bar).min_priority(Pre).send();
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 12);
+    }
+
+    #[test]
+    fn post_options_nested() {
+        struct Outer;
+
+        impl Event for Outer {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = ();
+
+            fn before_dispatch(&mut self, world: &mut World) {
+                world.post(Bar);
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(
+            (|_: Receive<Outer>, mut counter: ResMut<Counter>| counter.0 += 1).priority(Pre),
+        );
+        world.add_handler(|_: Receive<Outer>, mut counter: ResMut<Counter>| counter.0 += 10);
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 100);
+
+        world.build_post(Outer).min_priority(Pre).send();
+        assert_eq!(world.resource::<Counter>().0, 101);
+
+        world.pause_events::<Outer>();
+        world.build_post(Outer).min_priority(Pre).send();
+        world.resume_event_bus();
+        assert_eq!(world.resource::<Counter>().0, 202);
+    }
+
+    #[test]
+    fn post_excluding() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1);
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 10);
+        let sync = world.handler_ids::<Bar>()[0];
+
+        world.build_post(Bar).excluding([sync]).send();
+        assert_eq!(world.resource::<Counter>().0, 10);
+
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 21);
+
+        world.add_handler(
+            (|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 100).priority(Pre),
+        );
+        world
+            .build_post(Bar)
+            .excluding([sync])
+            .min_priority(Pre)
+            .send();
+        assert_eq!(world.resource::<Counter>().0, 121);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn events_bridge_echo() {
+        #[derive(bevy_ecs::event::Event, Clone)]
+        struct Ping;
+
+        impl Event for Ping {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = ();
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Counter>()
+            .add_plugins(EventsBridgePlugin::<Ping>::default())
+            .add_handler(|_: Receive<Ping>, mut counter: ResMut<Counter>| counter.0 += 1);
+
+        app.world_mut().send_event(Ping);
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 1);
+        let events = app.world().resource::<bevy_ecs::event::Events<Ping>>();
+        assert_eq!(events.len(), 1);
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 1);
+
+        app.world_mut().post(Ping);
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn bus_pair() {
+        #[derive(Clone)]
+        struct Hit(Entity);
+
+        impl Event for Hit {
+            type Mutability = Mutable;
+            type Cancellation = bool;
+            type Audience = Entity;
+        }
+
+        impl bevy_ecs::entity::MapEntities for Hit {
+            fn map_entities<M: bevy_ecs::entity::EntityMapper>(&mut self, mapper: &mut M) {
+                self.0 = mapper.map_entity(self.0);
+            }
+        }
+
+        let mut pair = BusPair::new(World::new(), World::new());
+        pair.mirror::<Hit>().mirror::<Hit>();
+        pair.simulation.add_handler(|mut event: Receive<Hit>| {
+            if event.0 == event.target() {
+                event.cancel();
+            }
+        });
+        pair.presentation.init_resource::<Counter>();
+        pair.presentation.add_handler(
+            |event: Receive<Mirrored<Hit>>, mut counter: ResMut<Counter>| {
+                assert_ne!(event.0 .0, event.target());
+                counter.0 += 1;
+            },
+        );
+
+        let attacker = pair.simulation.spawn_empty().id();
+        let target = pair.simulation.spawn_empty().id();
+        let shown = pair.presentation.spawn_empty().id();
+        pair.map_entity(target, shown);
+        pair.simulation.post_mut_to(&mut Hit(attacker), target);
+        pair.simulation.post_mut_to(&mut Hit(target), target);
+
+        assert_eq!(pair.sync(), 1);
+        assert_eq!(pair.presentation.resource::<Counter>().0, 1);
+        assert_eq!(pair.presentation_entity(target), Some(shown));
+        assert!(pair.presentation_entity(attacker).is_some());
+    }
+
+    #[test]
+    fn event_budget() {
+        let mut world = World::new();
+        world.insert_resource(FrameCount(0));
+        let budget = world.assert_event_budget::<Bar>(2);
+        world.post(Bar);
+        world.post(Bar);
+        world.resource_mut::<FrameCount>().0 += 1;
+        world.post(Bar);
+        assert_eq!(budget.posts(), 1);
+
+        fn bar(_event: Receive<Bar>) {}
+
+        world.add_handler(bar);
+        world.insert_resource(TickBatch::<Bar>::new());
+        world.resource_mut::<TickBatch<Bar>>().push(Bar, ());
+        TickBatch::<Bar>::dispatch(&mut world);
+        assert_eq!(budget.posts(), 2);
+        world.resource_mut::<FrameCount>().0 += 1;
+        world.add_keyed_handler(0, bar);
+        world.post_keyed(Bar, &0);
+        assert_eq!(budget.posts(), 1);
+
+        let none = world.assert_no_event::<Baz>();
+        drop(none);
+        world.post(Baz);
+
+        let _none = world.assert_no_event::<Baz>();
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.post(Baz);
+        }));
+        assert!(panicked.is_err());
+    }
+
+    #[test]
+    fn dispatch_trace_snapshot() {
+        struct Unheard;
+
+        impl Event for Unheard {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = ();
+        }
+
+        fn forward(_: Receive<Bar>, world: &mut World) {
+            world.post(Baz);
+        }
+
+        let mut world = World::new();
+        world.add_handler(forward);
+        world.add_handler(|_: Receive<Baz>| {});
+        let trace = DispatchTrace::record(&mut world, |world| {
+            world.post(Bar);
+            world.post(Unheard);
+        });
+        assert_eq!(
+            trace.to_snapshot(),
+            format!(
+                "{}\n  {}\n    {}\n      {}\n{}\n",
+                std::any::type_name::<Bar>(),
+                std::any::type_name_of_val(&forward),
+                std::any::type_name::<Baz>(),
+                "bevy_eventbus::tests::dispatch_trace_snapshot::{{closure}}",
+                std::any::type_name::<Unheard>(),
+            )
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!("bevy_eventbus_{}", std::process::id()))
+            .join("dispatch_trace.snap");
+        let _ = std::fs::remove_file(&path);
+        let missing = std::panic::catch_unwind(|| trace.assert_snapshot(&path));
+        assert!(missing.is_err());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, trace.to_snapshot()).unwrap();
+        trace.assert_snapshot(&path);
+
+        let changed = DispatchTrace::record(&mut world, |world| {
+            world.post(Baz);
+        });
+        let panicked = std::panic::catch_unwind(|| changed.assert_snapshot(&path));
+        assert!(panicked.is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn compiled_tick_schedule() {
+        #[derive(Resource, Default)]
+        struct Order(Vec<i32>);
+
+        let mut app = App::new();
+        app.add_plugins(EventBusPlugin)
+            .init_resource::<Order>()
+            .insert_resource(EventBusSettings {
+                compile_tick: true,
+                ..Default::default()
+            })
+            .add_handler((|mut order: ResMut<Order>| order.0.push(0)).priority(Post))
+            .add_handler((|mut order: ResMut<Order>| order.0.push(1)).priority(Pre));
+
+        app.update();
+        assert_eq!(app.world().resource::<Order>().0, [1, 0]);
+
+        app.add_handler((|mut order: ResMut<Order>| order.0.push(2)).priority(First));
+        app.world_mut().resource_mut::<Order>().0.clear();
+        app.update();
+        assert_eq!(app.world().resource::<Order>().0, [2, 1, 0]);
+    }
+
+    #[test]
+    fn compiled_tick_once() {
+        #[derive(Resource)]
+        struct Ready(bool);
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(Ready(false));
+        world.insert_resource(EventBusSettings {
+            compile_tick: true,
+            ..Default::default()
+        });
+        world.add_handler(
+            (|_: Receive<crate::tick::Tick>, mut counter: ResMut<Counter>| counter.0 += 1)
+                .once()
+                .run_if(|ready: Res<Ready>| ready.0),
+        );
+
+        post_tick(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 0);
+        world.resource_mut::<Ready>().0 = true;
+        post_tick(&mut world);
+        post_tick(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn compiled_tick_reset() {
+        fn count(
+            _: Receive<crate::tick::Tick>,
+            mut seen: Local<i32>,
+            mut counter: ResMut<Counter>,
+        ) {
+            *seen += 1;
+            counter.0 = *seen;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.insert_resource(EventBusSettings {
+            compile_tick: true,
+            ..Default::default()
+        });
+        world.add_handler(Resettable(count));
+        let id = world.handler_ids::<crate::tick::Tick>()[0];
+
+        post_tick(&mut world);
+        post_tick(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+        assert!(world.reset_handler_state(id));
+        post_tick(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn compiled_tick_circuit_breaker() {
+        fn flaky(_: Receive<crate::tick::Tick>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+            panic!("flaky");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.init_resource::<Time>();
+        world.insert_resource(EventBusSettings {
+            compile_tick: true,
+            ..Default::default()
+        });
+        world.add_handler(
+            flaky.circuit_breaker(CircuitBreaker::new(2).cool_down(Duration::from_secs(1))),
+        );
+        world.add_handler(
+            |_: Receive<HandlerTripped<crate::tick::Tick>>, mut counter: ResMut<Counter>| {
+                counter.0 += 100;
+            },
+        );
+
+        for _ in 0..3 {
+            post_tick(&mut world);
+        }
+        assert_eq!(world.resource::<Counter>().0, 102);
+
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        post_tick(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 103);
+    }
+
+    #[test]
+    fn event_access() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1);
+        world.add_handler(|_: Receive<Bar>, _: ResMut<HandlerRegistry<Bar>>| {});
+        world.add_handler(|_: Receive<Bar>, _: &mut World| {});
+
+        let access = world.event_access::<Bar>();
+        let counter = world.components().resource_id::<Counter>().unwrap();
+        assert!(access.access.has_resource_write(counter));
+        assert_eq!(access.exclusive.len(), 1);
+        assert!(!access.is_valid());
+        assert_eq!(access.conflicts.len(), 1);
+        assert!(access.conflicts[0].resource.contains("HandlerRegistry"));
+    }
+
+    #[test]
+    fn lazy_audience() {
+        struct Broadcast;
+
+        impl Event for Broadcast {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = LazyAudience;
+        }
+
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let resolved = Arc::new(AtomicUsize::new(0));
+        let audience = {
+            let resolved = resolved.clone();
+            move || {
+                let resolved = resolved.clone();
+                LazyAudience::new(move || {
+                    resolved.fetch_add(1, Ordering::Relaxed);
+                    vec![Entity::from_raw(7)]
+                })
+            }
+        };
+
+        let mut world = World::new();
+        world.add_handler(|_: Receive<Broadcast>| {});
+        world.post_to(Broadcast, audience());
+        assert_eq!(resolved.load(Ordering::Relaxed), 0);
+
+        world.add_handler(|event: Receive<Broadcast>| {
+            assert_eq!(event.targets().collect::<Vec<_>>(), [Entity::from_raw(7)]);
+        });
+        world.add_handler(|event: Receive<Broadcast>| {
+            assert!(event.audience().includes(Entity::from_raw(7)));
+        });
+        world.post_to(Broadcast, audience());
+        assert_eq!(resolved.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn bus_event_macro() {
+        crate::bus_event! {
+            #[derive(bevy_ecs::event::Event, Clone)]
+            struct Ping(u32);
+            : cancellable, retained(4), networked,
+        }
+
+        crate::bus_event! {
+            #[derive(Clone)]
+            struct Pong
+            : retained
+        }
+
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<Ping>();
+
+        let mut app = App::new();
+        Ping::register(&mut app);
+        app.add_handler(|mut event: Receive<Ping>| {
+            if event.0 == 0 {
+                event.cancel();
+            }
+        });
+
+        app.world_mut().post(Ping(0));
+        app.world_mut().post(Ping(1));
+        app.update();
+        let events = app.world().resource::<bevy_ecs::event::Events<Ping>>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(app.world().event_history::<Ping>().unwrap().len(), 2);
+
+        Pong::register(&mut app);
+        let history = app.world().event_history::<Pong>().unwrap();
+        assert_eq!(history.capacity(), EventHistory::<Pong>::DEFAULT_CAPACITY);
+    }
+
+    #[test]
+    fn dispatch_harness() {
+        let mut harness = DispatchHarness::new()
+            .with_resource(Counter(0))
+            .with_handler(|mut event: Receive<Foo>, mut counter: ResMut<Counter>| {
+                counter.0 += 1;
+                if event.target() == Entity::PLACEHOLDER {
+                    event.cancel();
+                }
+            });
+
+        assert!(harness.post_to(Foo, Entity::PLACEHOLDER));
+        assert!(!harness.post_to(Foo, Entity::from_raw(1)));
+        assert_eq!(harness.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn entity_remap() {
+        struct Attack(Entity);
+
+        impl Event for Attack {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = Vec<Entity>;
+        }
+
+        impl bevy_ecs::entity::MapEntities for Attack {
+            fn map_entities<M: bevy_ecs::entity::EntityMapper>(&mut self, mapper: &mut M) {
+                self.0 = mapper.map_entity(self.0);
+            }
+        }
+
+        let mut world = World::new();
+        let attacker = world.spawn_empty().id();
+        let target = world.spawn_empty().id();
+        let (remote_attacker, remote_target) = (Entity::from_raw(100), Entity::from_raw(101));
+        world.remap_entity(remote_attacker, attacker);
+        world.remap_entity(remote_target, target);
+        world.add_handler(
+            move |event: Receive<Attack>, mut counter: ResMut<Counter>| {
+                assert_eq!(event.targets().collect::<Vec<_>>(), [target, attacker]);
+                counter.0 += i32::from(event.0 == attacker);
+            },
+        );
+        world.init_resource::<Counter>();
+
+        let bridge = crate::Bridge::new("network");
+        bridge.import(
+            &mut world,
+            Attack(remote_attacker),
+            vec![remote_target, attacker],
+        );
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        world.remap_event_entities::<Attack>();
+        bridge.import(
+            &mut world,
+            Attack(remote_attacker),
+            vec![remote_target, attacker],
+        );
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn delivery_tracking() {
+        #[derive(Clone)]
+        struct Changed(i32);
+
+        impl Event for Changed {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = ();
+        }
+
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Panel;
+
+        fn panel(event: Receive<Changed>, mut counter: ResMut<Counter>) {
+            counter.0 += event.0;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.enable_history::<Changed>(2);
+        world.track_delivery::<Changed>();
+        world.add_handler(panel.in_set(Panel).delivery_key("panel"));
+
+        world.post(Changed(1));
+        world.configure_handler_set::<Changed>(HandlerSetConfig::new(Panel).enabled(false));
+        world.post(Changed(10));
+        world.post(Changed(100));
+        world.post(Changed(1000));
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        // Only the last 2 posts are retained.
+        world.configure_handler_set::<Changed>(HandlerSetConfig::new(Panel).enabled(true));
+        assert_eq!(world.resource::<Counter>().0, 1101);
+
+        let id = world.handler_ids::<Changed>()[0];
+        world.remove_handler(id);
+        world.post(Changed(10000));
+        world.add_handler(panel.delivery_key("panel"));
+        assert_eq!(world.resource::<Counter>().0, 11101);
+
+        // Handlers without a delivery key are forgotten once removed.
+        world.add_handler(panel);
+        let id = world.handler_ids::<Changed>()[1];
+        world.post(Changed(1));
+        assert!(world
+            .resource::<DeliveryTracker<Changed>>()
+            .last_delivered(id)
+            .is_some());
+        world.remove_handler(id);
+        assert!(world
+            .resource::<DeliveryTracker<Changed>>()
+            .last_delivered(id)
+            .is_none());
+        world.post(Changed(10));
+        world.add_handler(panel);
+        assert_eq!(world.resource::<Counter>().0, 11113);
+    }
+
+    #[test]
+    fn feature_flags() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(
+            (|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1)
+                .requires_flag("pvp_enabled")
+                .requires_flag("arena"),
+        );
+        world.add_handler(|mut event: Receive<SetFlag>| {
+            if event.flag == "locked" {
+                event.cancel();
+            }
+        });
+
+        world.post(Bar);
+        world.post(SetFlag::enable("pvp_enabled"));
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        world.post(SetFlag::enable("arena"));
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        world.post(SetFlag::disable("arena"));
+        world.post(SetFlag::enable("locked"));
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        let flags = world.resource::<FeatureFlags>();
+        assert!(flags.is_enabled("pvp_enabled") && !flags.is_enabled("locked"));
+    }
+
+    #[test]
+    fn throttle_per_target() {
+        struct Shot;
+
+        impl Event for Shot {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = Entity;
+
+            fn before_dispatch(&mut self, world: &mut World) {
+                world.resource_mut::<Counter>().0 += 100;
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.init_resource::<EventBusStats>();
+        world.init_resource::<Time>();
+        world.throttle_per_target::<Foo>(2);
+        world.add_handler(|_: Receive<Foo>, mut counter: ResMut<Counter>| counter.0 += 1);
+        let (a, b) = (world.spawn_empty().id(), world.spawn_empty().id());
+
+        for _ in 0..5 {
+            world.post_to(Foo, a);
+        }
+        world.post_to(Foo, b);
+        assert_eq!(world.resource::<Counter>().0, 3);
+        assert_eq!(world.resource::<TargetThrottle<Foo>>().dropped(), 3);
+        assert_eq!(world.event_bus_stats().get::<Foo>().unwrap().throttled, 3);
+
+        // Throttled posts are dropped before `before_dispatch`, whether posted alone or batched.
+        world.throttle_per_target::<Shot>(1);
+        world.add_handler(|_: Receive<Shot>| {});
+        world.resource_mut::<Counter>().0 = 0;
+        world.post_to(Shot, a);
+        world.post_to(Shot, a);
+        world.insert_resource(TickBatch::<Shot>::new());
+        let mut batch = world.resource_mut::<TickBatch<Shot>>();
+        batch.push(Shot, b);
+        batch.push(Shot, b);
+        TickBatch::<Shot>::dispatch(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 200);
+        assert_eq!(world.event_bus_stats().get::<Shot>().unwrap().throttled, 2);
+        world.resource_mut::<Counter>().0 = 3;
+
+        world.despawn(a);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        world.post_to(Foo, b);
+        assert_eq!(world.resource::<Counter>().0, 4);
+        let throttle = world.resource::<TargetThrottle<Foo>>();
+        assert_eq!((throttle.posts(a), throttle.posts(b)), (0, 1));
+    }
+
+    #[test]
+    fn circuit_breaker() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.add_handler(
+            (|_: Receive<Bar>, mut counter: ResMut<Counter>| {
+                counter.0 += 1;
+                panic!("handler failed");
+            })
+            .circuit_breaker(CircuitBreaker::new(2)),
+        );
+        world.add_handler(
+            |event: Receive<HandlerTripped<Bar>>, mut counter: ResMut<Counter>| {
+                assert_eq!(event.failures, 2);
+                counter.0 += 100;
+            },
+        );
+
+        for _ in 0..3 {
+            world.post(Bar);
+        }
+        assert_eq!(world.resource::<Counter>().0, 102);
+        assert!(world
+            .resource::<HandlerRegistry<Bar>>()
+            .get(id)
+            .unwrap()
+            .is_tripped());
+
+        assert!(world.reset_circuit_breaker(id));
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 103);
+        assert!(!world.reset_circuit_breaker(id));
+    }
+
+    #[test]
+    fn remove_handler_by_id() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let screen = world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| {
+            counter.0 += 1;
+        });
+        world.add_handler(move |_: Receive<Baz>, mut commands: Commands| {
+            commands.remove_handler(screen);
+        });
+
+        world.post(Bar);
+        world.post(Baz);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert!(!world.remove_handler(screen));
+    }
+
+    #[test]
+    fn shuffle_same_priority() {
+        #[derive(Resource, Default)]
+        struct Order(Vec<u8>);
+
+        fn validate(_: Receive<Bar>, mut order: ResMut<Order>) {
+            order.0.push(5);
+        }
+
+        fn apply(_: Receive<Bar>, mut order: ResMut<Order>) {
+            order.0.push(6);
+        }
+
+        fn run(seed: u64) -> Vec<u8> {
+            let mut world = World::new();
+            world.init_resource::<Order>();
+            world.insert_resource(EventBusSettings::default().shuffle_same_priority(seed));
+            world.add_handler(
+                (|_: Receive<Bar>, mut order: ResMut<Order>| order.0.push(0)).priority(First),
+            );
+            for handler in 1..=4 {
+                world.add_handler(move |_: Receive<Bar>, mut order: ResMut<Order>| {
+                    order.0.push(handler);
+                });
+            }
+            world.add_handler(apply.after(validate));
+            world.add_handler(validate);
+            for _ in 0..8 {
+                world.post(Bar);
+            }
+            world.remove_resource::<Order>().unwrap().0
+        }
+
+        let order = run(7);
+        assert_eq!(order, run(7));
+        assert!(order.chunks(7).all(|post| post[0] == 0));
+        assert!(order.chunks(7).any(|post| post[1..5] != [1, 2, 3, 4]));
+        assert!(order.chunks(7).all(|post| {
+            post.iter().position(|&handler| handler == 5)
+                < post.iter().position(|&handler| handler == 6)
+        }));
+    }
+
+    #[test]
+    fn scoped_handler() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let guard = world.add_scoped_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| {
+            counter.0 += 1;
+        });
+        let kept = world
+            .add_scoped_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 10)
+            .detach();
+
+        world.post(Bar);
+        drop(guard);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 21);
+        let registry = world.resource::<HandlerRegistry<Bar>>();
+        assert_eq!(registry.ids().collect::<Vec<_>>(), [kept]);
+    }
+
+    #[test]
+    fn once_handler() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler((|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1).once());
+        world.add_handler(
+            (|mut event: Receive<Bar>, mut posts: Local<u32>| {
+                *posts += 1;
+                if *posts == 1 {
+                    event.cancel();
+                }
+            })
+            .priority(First),
+        );
+
+        for _ in 0..3 {
+            world.post(Bar);
+        }
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert_eq!(world.resource::<HandlerRegistry<Bar>>().len(), 1);
+    }
+
+    #[test]
+    fn bus_facade_macro() {
+        crate::bus_facade! {
+            struct TestBus {
+                fn bar() => post(Bar);
+                fn foo(target: Entity) => post_to(Foo, target);
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 1);
+        world.add_handler(|event: Receive<Foo>, mut counter: ResMut<Counter>| {
+            counter.0 += event.target().index() as i32 * 10;
+        });
+        let target = world.spawn_empty().id();
+        world
+            .run_system_once(move |mut commands: Commands| {
+                TestBus::bar(&mut commands);
+                TestBus::foo(&mut commands, target);
+            })
+            .unwrap();
+        assert_eq!(
+            world.resource::<Counter>().0,
+            1 + target.index() as i32 * 10
+        );
+    }
+
+    #[test]
+    fn event_inbox() {
+        #[derive(Clone)]
+        struct Ping(i32);
+
+        impl Event for Ping {
+            type Mutability = Immutable;
+            type Cancellation = bool;
+            type Audience = Entity;
+
+            fn after_dispatch(&self, _cancelled: &bool, world: &mut World) {
+                if let Some(mut dispatched) = world.get_resource_mut::<Dispatched>() {
+                    dispatched.0 += 1;
+                }
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct Dispatched(u32);
+
+        fn ping(event: Receive<Ping>, mut counter: ResMut<Counter>) {
+            counter.0 += event.0;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(ping);
+        world.collect_in_inboxes::<Ping>();
+        let shared = world.spawn(EventInbox::<Ping>::new()).id();
+        let exclusive = world.spawn(EventInbox::<Ping>::exclusive()).id();
+        let plain = world.spawn_empty().id();
+
+        world.post_to(Ping(1), shared);
+        world.post_to(Ping(10), plain);
+        assert!(!world.post_to(Ping(100), exclusive));
+        world.enqueue_to(Ping(1000), exclusive);
+        world.flush_event_queue(None);
+        assert_eq!(world.resource::<Counter>().0, 11);
+
+        let mut inbox = world.get_mut::<EventInbox<Ping>>(exclusive).unwrap();
+        assert_eq!(
+            inbox.drain().map(|ping| ping.0).collect::<Vec<_>>(),
+            [100, 1000]
+        );
+        assert!(inbox.is_empty());
+        assert_eq!(world.get::<EventInbox<Ping>>(shared).unwrap().len(), 1);
+
+        world.add_keyed_handler(0, ping);
+        assert!(!world.post_keyed_to(Ping(2), &0, exclusive));
+        world.post_keyed_to(Ping(20), &0, shared);
+        assert_eq!(world.resource::<Counter>().0, 31);
+        assert_eq!(world.get::<EventInbox<Ping>>(exclusive).unwrap().len(), 1);
+        assert_eq!(world.get::<EventInbox<Ping>>(shared).unwrap().len(), 2);
+
+        // Collected posts are still counted, traced and finished like any other post.
+        world.init_resource::<Time>();
+        world.init_resource::<EventBusStats>();
+        world.init_resource::<Dispatched>();
+        world.insert_resource(TickBatch::<Ping>::new());
+        let trace = DispatchTrace::record(&mut world, |world| {
+            world.post_to(Ping(3), exclusive);
+            world.post_keyed_to(Ping(4), &0, exclusive);
+            world
+                .resource_mut::<TickBatch<Ping>>()
+                .push(Ping(5), exclusive);
+            TickBatch::<Ping>::dispatch(world);
+        });
+        assert_eq!(
+            trace.to_snapshot(),
+            format!("{0}\n{0}\n{0}\n", std::any::type_name::<Ping>())
+        );
+        assert_eq!(world.event_bus_stats().get::<Ping>().unwrap().posts, 3);
+        assert_eq!(world.resource::<Dispatched>().0, 3);
+        assert_eq!(world.resource::<Counter>().0, 31);
+    }
+
+    #[test]
+    fn event_inbox_capacity() {
+        #[derive(Clone)]
+        struct Ping(i32);
+
+        impl Event for Ping {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = Entity;
+        }
+
+        let mut world = World::new();
+        world.collect_in_inboxes::<Ping>();
+        let bounded = world
+            .spawn(EventInbox::<Ping>::exclusive().with_capacity(2))
+            .id();
+        let closed = world.spawn(EventInbox::<Ping>::new().with_capacity(0)).id();
+        for ping in 1..=5 {
+            world.post_to(Ping(ping), bounded);
+            world.post_to(Ping(ping), closed);
+        }
+
+        let mut inbox = world.get_mut::<EventInbox<Ping>>(bounded).unwrap();
+        assert_eq!(inbox.capacity(), Some(2));
+        assert_eq!(inbox.len(), 2);
+        assert_eq!(inbox.dropped(), 3);
+        assert_eq!(inbox.drain().map(|ping| ping.0).collect::<Vec<_>>(), [4, 5]);
+        world.post_to(Ping(6), bounded);
+        let inbox = world.get::<EventInbox<Ping>>(bounded).unwrap();
+        assert_eq!(inbox.iter().map(|ping| ping.0).collect::<Vec<_>>(), [6]);
+        assert_eq!(inbox.dropped(), 3);
+
+        let inbox = world.get::<EventInbox<Ping>>(closed).unwrap();
+        assert!(inbox.is_empty());
+        assert_eq!(inbox.dropped(), 5);
+        assert_eq!(EventInbox::<Ping>::new().capacity(), None);
+    }
+
+    #[test]
+    fn shared_events() {
+        use std::sync::Arc;
+
+        use crate::{Shared, SharedReceive};
+
+        struct Mesh {
+            triangles: Vec<u32>,
+        }
+
+        impl Event for Mesh {
+            type Mutability = Mutable;
+            type Cancellation = bool;
+            type Audience = ();
+        }
+
+        #[derive(Resource, Default)]
+        struct Retained(Vec<Arc<Mesh>>);
+
+        fn retain(event: SharedReceive<Mesh>, mut retained: ResMut<Retained>) {
+            retained.0.push(event.retain());
+        }
+
+        fn reject_empty(mut event: SharedReceive<Mesh>) {
+            if event.triangles.is_empty() {
+                event.cancel();
+            }
+        }
+
+        fn unshared(_event: Receive<Mesh>) {
+            panic!("handlers of the payload type don't receive shared posts");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Retained>();
+        world.add_handler(reject_empty);
+        world.add_handler(retain);
+        world.add_handler(retain.priority(Late));
+        world.add_handler(unshared);
+
+        // Every handler sees the same allocation, and the bus keeps no reference once the post
+        // returns.
+        let mesh = Arc::new(Mesh {
+            triangles: vec![0, 1, 2],
+        });
+        assert!(!world.post_shared(mesh.clone()));
+        let retained = &world.resource::<Retained>().0;
+        assert_eq!(retained.len(), 2);
+        assert!(retained.iter().all(|kept| Arc::ptr_eq(kept, &mesh)));
+        assert_eq!(Arc::strong_count(&mesh), 3);
+
+        // Sharing is always immutable, even for a mutable payload, while cancellation is
+        // inherited from the payload.
+        assert_eq!(
+            TypeId::of::<<Shared<Mesh> as Event>::Mutability>(),
+            TypeId::of::<Immutable>()
+        );
+        let empty = Arc::new(Mesh {
+            triangles: Vec::new(),
+        });
+        assert!(world.post_shared(empty.clone()));
+        assert_eq!(world.resource::<Retained>().0.len(), 2);
+        assert_eq!(Arc::strong_count(&empty), 1);
+
+        world.resource_mut::<Retained>().0.clear();
+        assert_eq!(Arc::strong_count(&mesh), 1);
+    }
+
+    #[test]
+    fn post_with_context() {
+        use std::sync::Arc;
+
+        use crate::ReceiveWith;
+
+        struct Connection {
+            id: u32,
+            _session: Arc<()>,
+        }
+
+        #[derive(Resource, Default)]
+        struct Senders(Vec<u32>);
+
+        fn record(event: ReceiveWith<Bar, Connection>, mut senders: ResMut<Senders>) {
+            senders.0.push(event.context().id);
+        }
+
+        fn reject(mut event: ReceiveWith<Bar, Connection>) {
+            if event.context().id == 0 {
+                event.cancel();
+            }
+        }
+
+        fn without_context(_event: Receive<Bar>) {
+            panic!("handlers of the event type don't receive posts with context");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Senders>();
+        world.add_handler(reject.priority(Early));
+        world.add_handler(record);
+        world.add_handler(record.priority(Late));
+        world.add_handler(without_context);
+
+        let session = Arc::new(());
+        let connection = |id| Connection {
+            id,
+            _session: session.clone(),
+        };
+        assert!(!world.post_with_context(Bar, connection(7)));
+        assert!(!world.post_with_context_to(Bar, connection(8), ()));
+        assert!(world.post_with_context(Bar, connection(0)));
+        assert_eq!(world.resource::<Senders>().0, [7, 7, 8, 8]);
+
+        // The context is dropped along with the event once the post returns.
+        assert_eq!(Arc::strong_count(&session), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_event() {
+        use bytes::Bytes;
+
+        use crate::{BytesEvent, DecodeBytes};
+
+        struct Header {
+            channel: u8,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Position(u16, u16);
+
+        impl DecodeBytes for Position {
+            type Error = usize;
+
+            fn decode(payload: &Bytes) -> Result<Self, Self::Error> {
+                match **payload {
+                    [x0, x1, y0, y1] => Ok(Self(
+                        u16::from_le_bytes([x0, x1]),
+                        u16::from_le_bytes([y0, y1]),
+                    )),
+                    _ => Err(payload.len()),
+                }
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct Decoded {
+            positions: Vec<Result<Position, usize>>,
+            payloads: Vec<Bytes>,
+        }
+
+        fn filter(mut event: Receive<BytesEvent<Header>>) {
+            if event.header.channel != 1 {
+                event.cancel();
+            }
+        }
+
+        fn decode(event: Receive<BytesEvent<Header>>, mut decoded: ResMut<Decoded>) {
+            decoded.positions.push(event.decode());
+            decoded.payloads.push(event.payload());
+            assert_eq!(event.decode::<Vec<u8>>().unwrap(), *event.payload);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Decoded>();
+        world.add_handler(filter.priority(Early));
+        world.add_handler(decode);
+
+        let payload = Bytes::from_static(&[1, 0, 2, 1]);
+        assert!(!world.post(BytesEvent::new(Header { channel: 1 }, payload.clone())));
+        assert!(!world.post(BytesEvent::new(Header { channel: 1 }, vec![1, 2, 3])));
+        assert!(world.post(BytesEvent::new(Header { channel: 2 }, "ignored")));
+
+        let decoded = world.resource::<Decoded>();
+        assert_eq!(decoded.positions, [Ok(Position(1, 258)), Err(3)]);
+        // The payload handed out to handlers shares the posted buffer instead of copying it.
+        assert_eq!(decoded.payloads[0].as_ptr(), payload.as_ptr());
+
+        let hello = Bytes::from("hello");
+        assert_eq!(String::decode(&hello).unwrap(), "hello");
+        assert_eq!(Bytes::decode(&hello).unwrap().as_ptr(), hello.as_ptr());
+        assert!(String::decode(&Bytes::from_static(&[0xff])).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_picking")]
+    fn picking_bridge() {
+        use bevy_ecs::query::With;
+        use bevy_picking::{
+            backend::HitData,
+            events::{Click, Drag, DragEnd, DragStart, Out, Over, Pointer},
+            pointer::{Location, PointerButton, PointerId},
+        };
+        use bevy_reflect::{
+            DynamicEnum, DynamicStruct, DynamicTuple, DynamicTupleStruct, FromReflect,
+        };
+
+        use crate::{Picked, PickingBridgePlugin};
+
+        #[derive(Component)]
+        struct Button;
+
+        #[derive(Resource, Default)]
+        struct Clicked(Vec<Entity>);
+
+        fn ui_click(mut event: Receive<Picked<Click>>, query: Query<(), With<Button>>) {
+            if query.contains(*event.audience()) {
+                event.cancel();
+            }
+        }
+
+        fn gameplay_click(event: Receive<Picked<Click>>, mut clicked: ResMut<Clicked>) {
+            assert_eq!(event.target, *event.audience());
+            clicked.0.push(event.target);
+        }
+
+        // `Location` holds a render target from `bevy_render`, which isn't a dependency of the
+        // crate, so it is built through reflection.
+        fn location() -> Location {
+            let mut handle = DynamicTupleStruct::default();
+            handle.insert(0u32);
+            let mut variant = DynamicTuple::default();
+            variant.insert(handle);
+            let mut position = DynamicStruct::default();
+            position.insert("x", 0.0f32);
+            position.insert("y", 0.0f32);
+            let mut location = DynamicStruct::default();
+            location.insert("target", DynamicEnum::new("TextureView", variant));
+            location.insert("position", position);
+            Location::from_reflect(&location).unwrap()
+        }
+
+        fn click(target: Entity) -> Pointer<Click> {
+            let event = Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+                duration: Duration::ZERO,
+            };
+            Pointer::new(target, PointerId::Mouse, location(), event)
+        }
+
+        let mut app = App::new();
+        app.add_plugins((EventBusPlugin, PickingBridgePlugin))
+            .add_event::<Pointer<Over>>()
+            .add_event::<Pointer<Out>>()
+            .add_event::<Pointer<Click>>()
+            .add_event::<Pointer<DragStart>>()
+            .add_event::<Pointer<Drag>>()
+            .add_event::<Pointer<DragEnd>>()
+            .init_resource::<Clicked>()
+            .add_handler(ui_click.priority(Early))
+            .add_handler(gameplay_click);
+
+        let button = app.world_mut().spawn(Button).id();
+        let sprite = app.world_mut().spawn_empty().id();
+        app.world_mut().send_event(click(sprite));
+        app.world_mut().send_event(click(button));
+        app.update();
+        assert_eq!(app.world().resource::<Clicked>().0, [sprite]);
+    }
+
+    #[test]
+    fn handler_run_if() {
+        use bevy_ecs::schedule::common_conditions::{not, resource_exists};
+
+        #[derive(Resource)]
+        struct Paused;
+
+        fn count(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(
+            count
+                .run_if(|counter: Res<Counter>| counter.0 < 2)
+                .run_if(not(resource_exists::<Paused>)),
+        );
+
+        world.post(Bar);
+        world.insert_resource(Paused);
+        world.post(Bar);
+        world.remove_resource::<Paused>();
+        world.post(Bar);
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn audience_normalization() {
+        struct Splash;
+
+        impl Event for Splash {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = Vec<Entity>;
+        }
+
+        #[derive(Resource, Default)]
+        struct Hit(Vec<Entity>);
+
+        fn splash(event: Receive<Splash>, mut hit: ResMut<Hit>) {
+            hit.0.extend(event.targets());
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Hit>();
+        world.add_handler(splash);
+        world.normalize_audience::<Splash>(AudienceNormalization::default().sorted());
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let dead = world.spawn_empty().id();
+        world.despawn(dead);
+
+        world.post_to(Splash, vec![b, a, dead, b]);
+        world.enqueue_to(Splash, vec![a, a]);
+        world.flush_event_queue(None);
+        assert_eq!(world.resource::<Hit>().0, [a, b, a]);
+    }
+
+    #[test]
+    fn handler_before_after() {
+        fn first(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(0);
+        }
+
+        fn second(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(1);
+        }
+
+        fn third(_event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(2);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(third.after(second));
+        world.add_handler(second);
+        world.add_handler(first.before(second));
+        assert!(world
+            .resource::<HandlerRegistry<Bar>>()
+            .validate_order()
+            .is_ok());
+        world.post(Bar);
+        assert_eq!(world.resource::<Counter>().0, 3);
+
+        world.add_handler(second.before(first));
+        let cycle = world
+            .resource::<HandlerRegistry<Bar>>()
+            .validate_order()
+            .unwrap_err();
+        assert_eq!(cycle.handlers.len(), 4);
+    }
+
+    #[test]
+    fn post_outcome_ext() {
+        fn cancel(mut event: Receive<Bar>) {
+            event.cancel();
+        }
+
+        let mut world = World::new();
+        world.post(Baz).assert_not_cancelled();
+        assert_eq!(world.post(Baz).into_result(), Ok(()));
+        ().assert_not_cancelled();
+        assert_eq!(().reason(), None);
+
+        world.add_handler(cancel);
+        let outcome = world.post(Bar);
+        assert!(outcome.was_cancelled());
+        assert_eq!(outcome.reason(), Some(&()));
+        assert_eq!(outcome.into_result(), Err(()));
+        assert_eq!(Some(3).into_result(), Err(3));
+    }
+
+    #[test]
+    fn per_target_cancellation() {
+        struct Invite;
+
+        impl Event for Invite {
+            type Mutability = Immutable;
+            type Cancellation = PerTargetCancellation;
+            type Audience = Vec<Entity>;
+        }
+
+        #[derive(Resource)]
+        struct Busy(Entity);
+
+        fn reject_busy(mut event: Receive<Invite>, busy: Res<Busy>) {
+            if event.audience().includes(busy.0) {
+                event.cancel_with(busy.0);
+            }
+        }
+
+        fn count_accepted(event: Receive<Invite>, mut counter: ResMut<Counter>) {
+            counter.0 += event
+                .targets()
+                .filter(|&target| !event.cancellation().is_blocked(target))
+                .count() as i32;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.insert_resource(Busy(b));
+        world.add_handler(reject_busy);
+        world.add_handler(count_accepted);
+
+        let outcome = world.post_to(Invite, vec![a, b]);
+        assert_eq!(outcome.blocked().collect::<Vec<_>>(), [(b, &())]);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert!(outcome.was_cancelled());
+        world.post_to(Invite, vec![a]).assert_not_cancelled();
+    }
+
+    #[test]
+    fn receive_cancelled_handler() {
+        fn cancel(mut event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            counter.assert_order(0);
+            event.cancel();
+        }
+
+        fn observe(event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            assert!(event.cancelled());
+            counter.assert_order(1);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(cancel.priority(First));
+        world.add_handler(|_: Receive<Bar>| panic!("ran after the post was cancelled"));
+        world.add_handler(observe.priority(Last).receive_cancelled(true));
+
+        assert!(world.post(Bar));
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn uncancel_event() {
+        fn plugin_rule(mut event: Receive<Bar>) {
+            event.cancel();
+        }
+
+        fn gameplay_rule(mut event: Receive<Bar>, mut counter: ResMut<Counter>) {
+            assert!(event.cancelled());
+            counter.assert_order(0);
+            event.uncancel();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(plugin_rule.priority(First));
+        world.add_handler(gameplay_rule.receive_cancelled(true));
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.assert_order(1));
+
+        assert!(!world.post(Bar));
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn cancellation_provenance() {
+        #[derive(Clone, PartialEq)]
+        struct Loot(u32);
+
+        impl Event for Loot {
+            type Mutability = Mutable;
+            type Cancellation = Option<&'static str>;
+            type Audience = ();
+        }
+
+        fn veto(mut event: Receive<Loot>) {
+            assert!(event.cancelled_by().is_none());
+            event.cancel_with("inventory full");
+        }
+
+        fn audit(event: Receive<Loot>) {
+            let cancelled_by = event.cancelled_by().unwrap();
+            assert!(cancelled_by.name.ends_with("veto"));
+            assert_eq!(cancelled_by.priority, Early.priority());
+        }
+
+        let mut world = World::new();
+        world.add_handler(veto.priority(Early));
+        world.add_handler(audit.receive_cancelled(true));
+
+        let (_, report) = world.build_post(Loot(3)).tracked();
+        assert_eq!(report.cancellation, Some("inventory full"));
+        let ids = world.handler_ids::<Loot>();
+        assert_eq!(report.cancelled_by.map(|by| by.id), Some(ids[0]));
+    }
+
+    #[test]
+    fn receive_conversions() {
+        struct Damage(i32);
+
+        impl Event for Damage {
+            type Mutability = Mutable;
+            type Cancellation = bool;
+            type Audience = ();
+        }
+
+        fn amount(damage: impl AsRef<Damage>) -> i32 {
+            damage.as_ref().0
+        }
+
+        fn halve(mut damage: impl AsMut<Damage>) {
+            damage.as_mut().0 /= 2;
+        }
+
+        fn armor(mut event: Receive<Damage>, mut counter: ResMut<Counter>) {
+            assert_eq!(event.map(|damage| damage.0), 10);
+            halve(&mut event);
+            assert_eq!(amount(&event), 5);
+            let event = event.inspect(|damage| counter.0 = damage.0);
+            let (damage, cancelled, _) = event.into_parts();
+            damage.0 -= 1;
+            *cancelled = true;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(armor);
+
+        let mut damage = Damage(10);
+        assert!(world.post_mut(&mut damage));
+        assert_eq!(damage.0, 4);
+        assert_eq!(world.resource::<Counter>().0, 5);
+    }
+
+    #[test]
+    fn world_setup() {
+        use crate::tick::Tick;
+
+        let mut world = World::new();
+        EventBusWorldSetup::init(&mut world);
+        world.init_resource::<Counter>();
+        world.add_handler(
+            |_: Receive<Tick>, time: Res<Time>, mut counter: ResMut<Counter>| {
+                assert_eq!(time.delta(), Duration::from_millis(16));
+                counter.0 += 1;
+            },
+        );
+        world.add_handler(|_: Receive<Bar>, mut counter: ResMut<Counter>| counter.0 += 10);
+        world.enqueue(Bar);
+
+        EventBusWorldSetup::update(&mut world, Duration::from_millis(16));
+        EventBusWorldSetup::update(&mut world, Duration::from_millis(16));
+        assert_eq!(world.resource::<Counter>().0, 12);
+        assert_eq!(
+            world.resource::<Time>().elapsed(),
+            Duration::from_millis(32)
+        );
+        assert_eq!(world.resource::<FrameCount>().0, 2);
+    }
+
+    #[test]
+    fn always_run_handler() {
+        struct Vote;
+
+        impl Event for Vote {
+            type Mutability = Immutable;
+            type Cancellation = Option<&'static str>;
+            type Audience = ();
+        }
+
+        fn monitor(mut event: Receive<Vote>, mut counter: ResMut<Counter>) {
+            assert_eq!(event.cancellation(), &Some("veto"));
+            counter.assert_order(1);
+            // Handlers that always run only see a copy of the cancellation state.
+            event.cancel_with("overruled");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.add_handler(monitor.priority(First).always_run());
+        world.add_handler(|mut event: Receive<Vote>, mut counter: ResMut<Counter>| {
+            counter.assert_order(0);
+            event.cancel_with("veto");
+        });
+        world.add_handler(|_: Receive<Vote>| panic!("ran after the post was cancelled"));
+
+        assert_eq!(world.post(Vote), Some("veto"));
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        #[derive(Clone)]
+        struct Tally(u32);
+
+        impl Event for Tally {
+            type Mutability = Mutable;
+            type Cancellation = bool;
+            type Audience = ();
+        }
+
+        // Handlers that always run only see a copy of mutable events.
+        world.add_handler((|mut event: Receive<Tally>| event.0 = 0).always_run());
+        world.add_handler(|mut event: Receive<Tally>| event.0 += 1);
+        let mut tally = Tally(1);
+        world.post_mut(&mut tally);
+        assert_eq!(tally.0, 2);
+    }
+
+    #[cfg(feature = "bevy_app")]
+    #[test]
+    fn notify_sink() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::notify::{Notify, NotifyLevel, NotifyPlugin, NotifySink, NotifyStage};
+
+        fn mute_infos(mut event: Receive<Notify>) {
+            if event.level == NotifyLevel::Info {
+                event.cancel();
+            }
+        }
+
+        fn localize(mut event: Receive<Notify>) {
+            if event.title == "saved" {
+                event.title = "Gespeichert".into();
+            }
+        }
+
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.add_plugins(NotifyPlugin { log: false })
+            .add_handler(localize.priority(NotifyStage::Enrich))
+            .add_handler(mute_infos.priority(NotifyStage::Filter));
+        let presented = shown.clone();
+        app.world_mut()
+            .resource_mut::<NotifySink>()
+            .add_presenter(move |notification: &Notify| {
+                presented.lock().unwrap().push(notification.clone());
+            });
+
+        assert!(app.world_mut().post(Notify::info("autosaved")));
+        assert!(!app
+            .world_mut()
+            .post(Notify::success("saved").with_body("slot 1")));
+        assert_eq!(
+            *shown.lock().unwrap(),
+            [Notify::success("Gespeichert").with_body("slot 1")]
+        );
+    }
+
+    #[test]
+    fn progress_emitter() {
+        #[derive(Resource, Default)]
+        struct Reports(Vec<String>);
+
+        let mut world = World::new();
+        world.init_resource::<Reports>();
+        world.add_handler(|event: Receive<Progress>, mut reports: ResMut<Reports>| {
+            reports
+                .0
+                .push(format!("{} at {}", event.id, event.fraction));
+        });
+        world.add_handler(
+            |event: Receive<ProgressCompleted>, mut reports: ResMut<Reports>| {
+                reports.0.push(format!("{} completed", event.id));
+            },
+        );
+        world.add_handler(
+            |event: Receive<ProgressAborted>, mut reports: ResMut<Reports>| {
+                reports.0.push(format!("{} aborted", event.id));
+            },
+        );
+
+        let loading = world.start_progress();
+        let aborted = world.start_progress();
+        std::thread::spawn(move || {
+            loading.report(0.5);
+            loading.complete();
+            drop(aborted);
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(ProgressTracker::flush(&mut world), 5);
+        assert_eq!(
+            world.resource::<Reports>().0,
+            ["0 at 0", "1 at 0", "0 at 0.5", "0 completed", "1 aborted"]
+        );
+        assert_eq!(world.resource::<ProgressTracker>().active().count(), 0);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn watch_field() {
+        use bevy_reflect::Reflect;
+
+        #[derive(Reflect)]
+        struct Attack {
+            damage: u32,
+            label: String,
+        }
+
+        impl Event for Attack {
+            type Cancellation = ();
+            type Audience = ();
+            type Mutability = Mutable;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        world.add_handler(
+            (|mut event: Receive<Attack>| {
+                if event.label == "critical" {
+                    event.damage *= 2;
+                }
+            })
+            .priority(First),
+        );
+        world.add_handler(
+            (|_event: Receive<Attack>, mut counter: ResMut<Counter>| {
+                counter.0 += 1;
+            })
+            .watch_field("damage"),
+        );
+
+        world.post_mut(&mut Attack {
+            damage: 5,
+            label: "normal".into(),
+        });
+        assert_eq!(world.resource::<Counter>().0, 0);
+        world.post_mut(&mut Attack {
+            damage: 5,
+            label: "critical".into(),
+        });
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn ffi_vtable() {
+        use std::{
+            ffi::c_void,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        use crate::{bevy_eventbus_vtable, DynamicEvent, EVENTBUS_ABI_VERSION};
+
+        unsafe extern "C" fn count(
+            user_data: *mut c_void,
+            _name: *const u8,
+            _name_len: usize,
+            _payload: *const u8,
+            payload_len: usize,
+        ) -> bool {
+            unsafe { &*user_data.cast::<AtomicUsize>() }.fetch_add(payload_len, Ordering::SeqCst);
+            true
+        }
+
+        assert!(bevy_eventbus_vtable(EVENTBUS_ABI_VERSION + 1).is_null());
+        let vtable = unsafe { &*bevy_eventbus_vtable(EVENTBUS_ABI_VERSION) };
+
+        let mut world = World::new();
+        let handle = (&mut world as *mut World).cast::<c_void>();
+        let counter = AtomicUsize::new(0);
+        let user_data = (&counter as *const AtomicUsize).cast_mut().cast::<c_void>();
+        let id = unsafe { (vtable.subscribe)(handle, b"hit".as_ptr(), 3, count, user_data) };
+
+        assert!(unsafe { (vtable.post)(handle, b"hit".as_ptr(), 3, b"abcd".as_ptr(), 4) });
+        assert!(!world.post_keyed(DynamicEvent::new("miss", vec![0]), &"miss".to_string()));
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+
+        let handle = (&mut world as *mut World).cast::<c_void>();
+        assert!(unsafe { (vtable.unsubscribe)(handle, b"hit".as_ptr(), 3, id) });
+        assert!(!unsafe { (vtable.post)(handle, b"hit".as_ptr(), 3, std::ptr::null(), 0) });
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn ffi_reentrant_calls() {
+        use std::{
+            ffi::c_void,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        use crate::{
+            bevy_eventbus_vtable, EventBusVTable, EVENTBUS_ABI_VERSION, EVENTBUS_INVALID_ID,
+        };
+
+        struct Plugin {
+            vtable: &'static EventBusVTable,
+            world: *mut c_void,
+            id: AtomicUsize,
+            inner: AtomicUsize,
+        }
+
+        unsafe extern "C" fn outer(
+            user_data: *mut c_void,
+            _name: *const u8,
+            _name_len: usize,
+            _payload: *const u8,
+            _payload_len: usize,
+        ) -> bool {
+            let plugin = unsafe { &*user_data.cast::<Plugin>() };
+            let (vtable, world) = (plugin.vtable, plugin.world);
+            unsafe {
+                assert!(!(vtable.post)(
+                    world,
+                    b"inner".as_ptr(),
+                    5,
+                    std::ptr::null(),
+                    0
+                ));
+                let id = (vtable.subscribe)(world, b"inner".as_ptr(), 5, inner, user_data);
+                assert_eq!(id, EVENTBUS_INVALID_ID);
+                let id = plugin.id.load(Ordering::SeqCst) as u64;
+                assert!((vtable.unsubscribe)(world, b"outer".as_ptr(), 5, id));
+            }
+            false
+        }
+
+        unsafe extern "C" fn inner(
+            user_data: *mut c_void,
+            _name: *const u8,
+            _name_len: usize,
+            _payload: *const u8,
+            _payload_len: usize,
+        ) -> bool {
+            let plugin = unsafe { &*user_data.cast::<Plugin>() };
+            plugin.inner.fetch_add(1, Ordering::SeqCst);
+            false
+        }
+
+        let mut world = World::new();
+        let handle = (&mut world as *mut World).cast::<c_void>();
+        let plugin = Plugin {
+            vtable: unsafe { &*bevy_eventbus_vtable(EVENTBUS_ABI_VERSION) },
+            world: handle,
+            id: AtomicUsize::new(0),
+            inner: AtomicUsize::new(0),
+        };
+        let user_data = (&plugin as *const Plugin).cast_mut().cast::<c_void>();
+        let vtable = plugin.vtable;
+        unsafe {
+            (vtable.subscribe)(handle, b"inner".as_ptr(), 5, inner, user_data);
+            let id = (vtable.subscribe)(handle, b"outer".as_ptr(), 5, outer, user_data);
+            plugin.id.store(id as usize, Ordering::SeqCst);
+            (vtable.post)(handle, b"outer".as_ptr(), 5, std::ptr::null(), 0);
+            (vtable.post)(handle, b"outer".as_ptr(), 5, std::ptr::null(), 0);
+        }
+        assert_eq!(plugin.inner.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn prometheus_exporter() {
+        use std::{
+            io::{Read, Write},
+            net::{SocketAddr, TcpStream},
+        };
+
+        use crate::{PrometheusExporter, PrometheusExporterPlugin};
+
+        let mut app = App::new();
+        app.add_plugins(PrometheusExporterPlugin {
+            address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            interval: Duration::ZERO,
+        });
+        app.world_mut().post(Bar);
+        app.world_mut().post(Bar);
+        app.update();
+
+        let address = app.world().resource::<PrometheusExporter>().address();
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(&format!(
+            "eventbus_posts_total{{event=\"{}\"}} 2",
+            std::any::type_name::<Bar>()
+        )));
+    }
+
+    #[test]
+    fn queue_backlog() {
+        use std::hash::Hasher;
+
+        use crate::{BacklogError, CorrelationId, PersistentEvent, StableHasher};
+
+        struct Score(i32);
+
+        impl Event for Score {
+            type Mutability = Immutable;
+            type Cancellation = ();
+            type Audience = Entity;
+        }
+
+        impl PersistentEvent for Score {
+            fn encode(&self, audience: &Entity) -> Vec<u8> {
+                let mut bytes = self.0.to_le_bytes().to_vec();
+                bytes.extend_from_slice(&audience.to_bits().to_le_bytes());
+                bytes
+            }
+
+            fn decode(bytes: &[u8]) -> Option<(Self, Entity)> {
+                let (score, target) = bytes.split_first_chunk::<4>()?;
+                let target = Entity::from_bits(u64::from_le_bytes(target.try_into().ok()?));
+                Some((Score(i32::from_le_bytes(*score)), target))
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct Scores(Vec<(i32, Entity, CorrelationId)>);
+
+        fn score(event: Receive<Score>, context: Res<EventContext>, mut scores: ResMut<Scores>) {
+            let correlation = context.current().unwrap().correlation;
+            scores.0.push((event.0, event.target(), correlation));
+        }
+
+        let mut world = World::new();
+        let saved = world.spawn_empty().id();
+        world.persist_queued::<Score>("score");
+        world.enqueue_from(saved, Score(1), saved);
+        world.enqueue(Bar);
+        let parent = EventMeta {
+            id: PostId(0),
+            parent: None,
+            correlation: CorrelationId(42),
+            event: "parent",
+            event_type: TypeId::of::<Bar>(),
+            depth: 0,
+            seed: 0,
+            elapsed: Duration::ZERO,
+            origin: None,
+        };
+        EventContext::resume(&mut world, Some(parent), |world| {
+            world.enqueue_to(Score(2), saved);
+        });
+        let backlog = world.resource::<EventQueue>().save_backlog().unwrap();
+
+        let mut world = World::new();
+        world.spawn_empty();
+        let local = world.spawn_empty().id();
+        world.init_resource::<Scores>();
+        world.add_handler(score);
+        world.sequence_by_target::<Score>();
+        world.persist_queued::<Score>("score");
+        world.remap_entity(saved, local);
+        assert_eq!(world.restore_queue_backlog(&backlog), Ok(2));
+        assert_eq!(world.resource::<EntitySequencer<Score>>().pending(local), 2);
+        world.flush_event_queue(None);
+        let scores = &world.resource::<Scores>().0;
+        assert_eq!(
+            scores[..2],
+            [(1, local, scores[0].2), (2, local, CorrelationId(42))]
+        );
+        assert_ne!(scores[0].2, CorrelationId(42));
+
+        let mut corrupted = backlog.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            world.restore_queue_backlog(&corrupted),
+            Err(BacklogError::Corrupted)
+        );
+        let mut newer = backlog.clone();
+        newer[4] = 3;
+        assert_eq!(
+            world.restore_queue_backlog(&newer),
+            Err(BacklogError::UnsupportedVersion(3))
+        );
+        assert_eq!(
+            world.restore_queue_backlog(b"{}"),
+            Err(BacklogError::NotABacklog)
+        );
+        assert!(world.resource::<EventQueue>().is_empty());
+
+        // Version 1 backlogs didn't save the source entity and correlation ID of posts.
+        let payload = Score(3).encode(&saved);
+        let mut body = Vec::new();
+        body.extend_from_slice(&5u64.to_le_bytes());
+        body.extend_from_slice(b"score");
+        body.extend_from_slice(&u64::MAX.to_le_bytes());
+        body.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        body.extend_from_slice(&payload);
+        let mut hasher = StableHasher::default();
+        hasher.write(&body);
+        let mut old = b"EBQB".to_vec();
+        old.extend_from_slice(&1u32.to_le_bytes());
+        old.extend_from_slice(&hasher.finish().to_le_bytes());
+        old.extend_from_slice(&body);
+        assert_eq!(world.restore_queue_backlog(&old), Ok(1));
+        world.flush_event_queue(None);
+        assert_eq!(world.resource::<Scores>().0[2].0, 3);
+        assert_eq!(world.resource::<Scores>().0[2].1, local);
+    }
 }
@@ -0,0 +1,220 @@
+use std::borrow::Cow;
+
+#[cfg(feature = "bevy_app")]
+use bevy_app::{App, Plugin};
+#[cfg(feature = "bevy_app")]
+use bevy_ecs::system::ResMut;
+use bevy_ecs::system::Resource;
+use bevy_utils::tracing::{error, info, warn};
+
+#[cfg(feature = "bevy_app")]
+use crate::{AppEventBus, IntoHandlerConfig, Receive};
+use crate::{Early, Event, HandlerPriority, Late, Mutable, Normal};
+
+/// How important a [`Notify`] is, from least to most important.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NotifyLevel {
+    /// Something happened, e.g. an autosave.
+    #[default]
+    Info,
+    /// Something the user asked for succeeded, e.g. an export.
+    Success,
+    /// Something went wrong, but the app can carry on as usual, e.g. a missing optional asset.
+    Warning,
+    /// Something failed and the user needs to act, e.g. a lost connection.
+    Error,
+}
+
+/// [`Event`] for notifying the user, shared by all tools and plugins so that they don't each
+/// invent their own, see the [`NotifyPlugin`].
+///
+/// Handlers react to notifications in the [`NotifyStage`]s: filters can cancel a notification to
+/// suppress it, enrichers can edit it, e.g. to localize its text, and the sink of the
+/// [`NotifyPlugin`] presents whatever is left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notify {
+    /// Short summary of the notification, e.g. `"Saved"`.
+    pub title: Cow<'static, str>,
+    /// Details of the notification, which may be empty.
+    pub body: Cow<'static, str>,
+    /// How important the notification is.
+    pub level: NotifyLevel,
+}
+
+impl Event for Notify {
+    type Mutability = Mutable;
+    type Cancellation = bool;
+    type Audience = ();
+}
+
+impl Notify {
+    /// Creates a notification without a body.
+    pub fn new(level: NotifyLevel, title: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            title: title.into(),
+            body: Cow::Borrowed(""),
+            level,
+        }
+    }
+
+    /// Creates a notification with [`NotifyLevel::Info`].
+    pub fn info(title: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(NotifyLevel::Info, title)
+    }
+
+    /// Creates a notification with [`NotifyLevel::Success`].
+    pub fn success(title: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(NotifyLevel::Success, title)
+    }
+
+    /// Creates a notification with [`NotifyLevel::Warning`].
+    pub fn warning(title: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(NotifyLevel::Warning, title)
+    }
+
+    /// Creates a notification with [`NotifyLevel::Error`].
+    pub fn error(title: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(NotifyLevel::Error, title)
+    }
+
+    /// Sets the body of the notification.
+    pub fn with_body(mut self, body: impl Into<Cow<'static, str>>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+/// Conventional [`HandlerPriority`]s of the handlers of [`Notify`], in the order they run. They
+/// match [`Early`], [`Normal`], and [`Late`], so that handlers can still run before or after them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotifyStage {
+    /// Decides whether the notification is shown at all, cancelling it to suppress it, e.g. to
+    /// mute a category or drop duplicates.
+    Filter,
+    /// Edits the notification, e.g. to localize its text or add details.
+    Enrich,
+    /// Presents the notification, like the sink of the [`NotifyPlugin`] does. Handlers at this
+    /// stage shouldn't change or cancel the notification anymore.
+    Present,
+}
+
+impl HandlerPriority for NotifyStage {
+    fn priority(&self) -> i32 {
+        match self {
+            Self::Filter => Early.priority(),
+            Self::Enrich => Normal.priority(),
+            Self::Present => Late.priority(),
+        }
+    }
+}
+
+/// Shows a [`Notify`] to the user, e.g. as a toast, in a console, or as a log message, see
+/// [`NotifySink::add_presenter`].
+///
+/// Implemented for closures taking a `&Notify`.
+pub trait NotifyPresenter: Send + Sync + 'static {
+    /// Shows the notification.
+    fn present(&mut self, notification: &Notify);
+}
+
+impl<F: FnMut(&Notify) + Send + Sync + 'static> NotifyPresenter for F {
+    fn present(&mut self, notification: &Notify) {
+        self(notification)
+    }
+}
+
+/// [`NotifyPresenter`] which logs notifications, as errors, warnings, or infos depending on their
+/// level.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogPresenter;
+
+impl NotifyPresenter for LogPresenter {
+    fn present(&mut self, notification: &Notify) {
+        let Notify { title, body, level } = notification;
+        let separator = if body.is_empty() { "" } else { ": " };
+        match level {
+            NotifyLevel::Info | NotifyLevel::Success => info!("{title}{separator}{body}"),
+            NotifyLevel::Warning => warn!("{title}{separator}{body}"),
+            NotifyLevel::Error => error!("{title}{separator}{body}"),
+        }
+    }
+}
+
+/// [`Resource`] holding the [`NotifyPresenter`]s that the sink of the [`NotifyPlugin`] shows
+/// each [`Notify`] with, in the order they were added.
+#[derive(Resource, Default)]
+pub struct NotifySink {
+    presenters: Vec<Box<dyn NotifyPresenter>>,
+}
+
+impl NotifySink {
+    /// Adds a presenter, which is shown every notification that isn't cancelled.
+    pub fn add_presenter(&mut self, presenter: impl NotifyPresenter) -> &mut Self {
+        self.presenters.push(Box::new(presenter));
+        self
+    }
+
+    /// Returns the number of presenters.
+    pub fn len(&self) -> usize {
+        self.presenters.len()
+    }
+
+    /// Returns `true` if there are no presenters, in which case notifications are dropped.
+    pub fn is_empty(&self) -> bool {
+        self.presenters.is_empty()
+    }
+}
+
+/// [`Plugin`] which adds the [`NotifySink`] and its handler, which shows every [`Notify`] that
+/// isn't cancelled with its presenters, at [`NotifyStage::Present`].
+///
+/// ```rust
+/// # use bevy_app::App;
+/// # use bevy_eventbus::{notify::*, prelude::*};
+/// fn mute_infos(mut event: Receive<Notify>) {
+///     if event.level == NotifyLevel::Info {
+///         event.cancel();
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(NotifyPlugin::default())
+///     .add_handler(mute_infos.priority(NotifyStage::Filter));
+/// app.world_mut()
+///     .resource_mut::<NotifySink>()
+///     .add_presenter(|notification: &Notify| println!("[toast] {}", notification.title));
+///
+/// app.world_mut().post(Notify::info("Autosaved"));
+/// app.world_mut().post(Notify::error("Connection lost").with_body("Retrying in 5s"));
+/// ```
+#[cfg(feature = "bevy_app")]
+pub struct NotifyPlugin {
+    /// Whether the [`LogPresenter`] is added to the sink.
+    pub log: bool,
+}
+
+#[cfg(feature = "bevy_app")]
+impl Default for NotifyPlugin {
+    fn default() -> Self {
+        Self { log: true }
+    }
+}
+
+#[cfg(feature = "bevy_app")]
+impl Plugin for NotifyPlugin {
+    fn build(&self, app: &mut App) {
+        let mut sink = NotifySink::default();
+        if self.log {
+            sink.add_presenter(LogPresenter);
+        }
+        app.insert_resource(sink)
+            .add_handler(present.priority(NotifyStage::Present));
+    }
+}
+
+#[cfg(feature = "bevy_app")]
+fn present(event: Receive<Notify>, mut sink: ResMut<NotifySink>) {
+    for presenter in &mut sink.presenters {
+        presenter.present(&event);
+    }
+}
@@ -0,0 +1,85 @@
+use std::{any::TypeId, collections::HashMap};
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{Event, HandlerId, WorldEventBus};
+
+/// [`Resource`] which tracks the handlers registered on behalf of an owner type (usually a
+/// [`Plugin`](bevy_app::Plugin)), across all [`Event`] types.
+#[derive(Resource, Default)]
+pub(crate) struct HandlerOwners {
+    owners: HashMap<TypeId, Vec<OwnedHandler>>,
+    /// Stack of owners whose registration context is currently active.
+    scope: Vec<TypeId>,
+}
+
+/// A type-erased [`HandlerId`] that knows how to remove itself from the world.
+struct OwnedHandler {
+    event: TypeId,
+    index: u64,
+    remove: fn(&mut World, u64) -> bool,
+}
+
+impl HandlerOwners {
+    /// Records that the handler is owned by the owner type with the given [`TypeId`].
+    pub(crate) fn insert<E: Event>(&mut self, owner: TypeId, id: HandlerId<E>) {
+        self.owners.entry(owner).or_default().push(OwnedHandler {
+            event: TypeId::of::<E>(),
+            index: id.to_raw(),
+            remove: remove_erased::<E>,
+        });
+    }
+
+    /// Forgets the owner of the handler, once it was removed.
+    pub(crate) fn forget<E: Event>(&mut self, id: HandlerId<E>) {
+        let (event, index) = (TypeId::of::<E>(), id.to_raw());
+        for handlers in self.owners.values_mut() {
+            handlers.retain(|handler| handler.event != event || handler.index != index);
+        }
+    }
+
+    /// Returns the owner whose registration context is currently active, if any.
+    pub(crate) fn current_scope(&self) -> Option<TypeId> {
+        self.scope.last().copied()
+    }
+
+    /// Makes `O` the active registration context, so that all handlers added to the world until
+    /// the matching [`HandlerOwners::exit_scope`] are owned by `O`.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn enter_scope<O: 'static>(world: &mut World) {
+        if !world.contains_resource::<Self>() {
+            crate::dispatch::RegistryIndex::track::<Self>(world);
+        }
+        world
+            .get_resource_or_insert_with(Self::default)
+            .scope
+            .push(TypeId::of::<O>());
+    }
+
+    /// Leaves the registration context entered last with [`HandlerOwners::enter_scope`].
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn exit_scope(world: &mut World) {
+        if let Some(mut owners) = world.get_resource_mut::<Self>() {
+            owners.scope.pop();
+        }
+    }
+
+    /// Removes all handlers owned by `O` from the world, returning how many were removed.
+    pub(crate) fn remove_all<O: 'static>(world: &mut World) -> usize {
+        let Some(owned) = world
+            .get_resource_mut::<Self>()
+            .and_then(|mut owners| owners.owners.remove(&TypeId::of::<O>()))
+        else {
+            return 0;
+        };
+
+        owned
+            .into_iter()
+            .filter(|handler| (handler.remove)(world, handler.index))
+            .count()
+    }
+}
+
+fn remove_erased<E: Event>(world: &mut World, index: u64) -> bool {
+    world.remove_handler(HandlerId::<E>::from_raw(index))
+}
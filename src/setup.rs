@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use bevy_ecs::{system::Resource, world::World};
+
+use crate::{
+    advance_replays, finish_stream_hash, post_progress, resume_parked_events, tick::Tick,
+    EventQueue, EventReplayer, FrameCount, MainThread, ParkedEvents, ProgressTracker, Resimulating,
+    Time, WorldEventBus,
+};
+
+/// Sets up the event bus' per-frame maintenance for a [`World`] that isn't driven by an
+/// [`App`](https://docs.rs/bevy/latest/bevy/app/struct.App.html), e.g. in a headless simulation
+/// without the `bevy_app` feature. Does the same as the `EventBusPlugin`, see
+/// [`EventBusWorldSetup::update`].
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use bevy_ecs::world::World;
+/// # use bevy_eventbus::EventBusWorldSetup;
+/// let mut world = World::new();
+/// EventBusWorldSetup::init(&mut world);
+/// for _ in 0..3 {
+///     EventBusWorldSetup::update(&mut world, Duration::from_millis(16));
+/// }
+/// ```
+pub struct EventBusWorldSetup;
+
+impl EventBusWorldSetup {
+    /// Inserts the resources of the event bus' maintenance, keeping those that already exist. The
+    /// [`MainThread`] is always replaced, pinning main-thread-only handlers to the current thread.
+    pub fn init(world: &mut World) {
+        world.insert_resource(MainThread::current());
+        world.init_resource::<EventQueue>();
+        world.init_resource::<EventBusSettings>();
+        world.init_resource::<EventReplayer>();
+        world.init_resource::<ProgressTracker>();
+        world.init_resource::<ParkedEvents>();
+        world.init_resource::<Resimulating>();
+    }
+
+    /// Runs one frame of maintenance, after advancing the [`Time`] by the delta:
+    /// 1. Advances the [`EventReplayer`].
+    /// 2. Posts the progress reports buffered in the [`ProgressTracker`].
+    /// 3. Resumes the [`ParkedEvents`] whose conditions return `true`.
+    /// 4. Posts [`Tick`], see [`post_tick`].
+    /// 5. Flushes the [`EventQueue`], see [`EventBusSettings::flush_budget`].
+    /// 6. Finishes the frame of the [`StreamHasher`](crate::StreamHasher), if any.
+    ///
+    /// Advances the [`FrameCount`] at the end of the frame.
+    pub fn update(world: &mut World, delta: Duration) {
+        world
+            .get_resource_or_insert_with::<Time>(Default::default)
+            .advance_by(delta);
+        advance_replays(world);
+        post_progress(world);
+        resume_parked_events(world);
+        post_tick(world);
+        flush_event_queue(world);
+        finish_stream_hash(world);
+        let mut frame = world.get_resource_or_insert_with(FrameCount::default);
+        frame.0 = frame.0.wrapping_add(1);
+    }
+}
+
+/// [`Resource`] which configures the event bus' per-frame maintenance.
+#[derive(Resource, Default)]
+pub struct EventBusSettings {
+    /// The maximum number of queued events posted per frame, or `None` to post all of them.
+    pub flush_budget: Option<usize>,
+    /// Whether adding a handler within a priority band reserved by another crate panics, rather
+    /// than logging a warning. See [`Event::reserved_priorities`](crate::Event::reserved_priorities).
+    pub deny_reserved_priorities: bool,
+    /// Whether [`Tick`] handlers are compiled into the [`TickSchedule`](crate::tick::TickSchedule)
+    /// and run by bevy's executor, rather than dispatched like other events. See
+    /// [`run_tick_schedule`](crate::tick::run_tick_schedule).
+    ///
+    /// Compiled handlers don't support everything that dispatched handlers do:
+    /// - [`Receive::defer_until`](crate::Receive::defer_until) logs a warning and does nothing.
+    /// - [`PostBuilder::excluding`](crate::PostBuilder::excluding) and
+    ///   [`PostBuilder::min_priority`](crate::PostBuilder::min_priority) only apply to
+    ///   posts of [`Tick`], not to the schedule.
+    ///
+    /// Pausing the bus and [event budgets](crate::WorldEventBus::assert_event_budget) apply to
+    /// the schedule like to any post of [`Tick`].
+    pub compile_tick: bool,
+    /// The seed with which handlers of the same priority are shuffled for every post, or `None`
+    /// to run them in the order they were added. See [`EventBusSettings::shuffle_same_priority`].
+    pub shuffle_seed: Option<u64>,
+}
+
+impl EventBusSettings {
+    /// Shuffles the order in which handlers of the same priority run, differently for every post,
+    /// to smoke out handlers that accidentally depend on the order they were added in. Handlers
+    /// ordered by [`before`](crate::HandlerConfig::before) and
+    /// [`after`](crate::HandlerConfig::after), directly or through their sets, keep that order.
+    ///
+    /// Meant for development and tests only. The order of a post is derived from the seed and its
+    /// [`PostId`](crate::PostId), so a failing run can be reproduced with the same seed.
+    /// [`Tick`] handlers compiled into the [`TickSchedule`](crate::tick::TickSchedule) run in
+    /// the order chosen by bevy's executor instead.
+    pub fn shuffle_same_priority(mut self, seed: u64) -> Self {
+        self.shuffle_seed = Some(seed);
+        self
+    }
+}
+
+/// Exclusive system that posts [`Tick`], or runs the [`TickSchedule`](crate::tick::TickSchedule) if
+/// [`EventBusSettings::compile_tick`] is set.
+pub fn post_tick(world: &mut World) {
+    if world
+        .get_resource::<EventBusSettings>()
+        .is_some_and(|settings| settings.compile_tick)
+    {
+        crate::tick::run_tick_schedule(world);
+    } else {
+        world.post(Tick);
+    }
+}
+
+/// Exclusive system that flushes the [`EventQueue`] according to the [`EventBusSettings`].
+pub fn flush_event_queue(world: &mut World) {
+    let budget = world
+        .get_resource::<EventBusSettings>()
+        .and_then(|settings| settings.flush_budget);
+    world.flush_event_queue(budget);
+}